@@ -49,6 +49,9 @@ pub struct Stream {
 
     pub epg_channel_id: Option<String>,
     pub added: Option<String>,
+    /// Series: bumped by the provider when episodes are added
+    #[serde(default)]
+    pub last_modified: Option<FlexId>,
     pub category_id: Option<String>,
     pub container_extension: Option<String>,
     #[serde(default, deserialize_with = "deserialize_flex_option_f32")]
@@ -127,7 +130,7 @@ impl Stream {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct XtreamClient {
     pub base_url: String,
     pub username: String,
@@ -136,6 +139,23 @@ pub struct XtreamClient {
     pending_requests: Arc<Mutex<HashMap<String, Arc<Notify>>>>,
 }
 
+// Passwords never reach debug output
+impl std::fmt::Debug for XtreamClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("XtreamClient")
+            .field("base_url", &self.base_url)
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for XtreamClient {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.password.zeroize();
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct UserInfo {
     pub auth: i32,
@@ -325,6 +345,15 @@ impl IptvClient {
         }
     }
 
+    /// The account's HTTP client (User-Agent, TLS and DoH settings included),
+    /// for pulling stream data directly
+    pub fn http_client(&self) -> reqwest::Client {
+        match self {
+            IptvClient::Xtream(c) => c.client.clone(),
+            IptvClient::M3u(c) => c.client.clone(),
+        }
+    }
+
     pub fn get_vod_url(&self, stream_id: &str, extension: &str) -> String {
         match self {
             IptvClient::Xtream(c) => c.get_vod_url(stream_id, extension),
@@ -338,6 +367,18 @@ impl IptvClient {
             IptvClient::M3u(c) => c.get_stream_url(stream_id),
         }
     }
+
+    /// URL the player opens for `stream`: movies and series in their container
+    /// format, live channels forced to TS
+    pub fn get_playback_url(&self, stream: &Stream) -> String {
+        let id = get_id_str(&stream.stream_id);
+        let extension = stream.container_extension.as_deref().unwrap_or("ts");
+        match stream.stream_type.as_str() {
+            "movie" => self.get_vod_url(&id, extension),
+            "series" => self.get_series_url(&id, extension),
+            _ => self.get_stream_url(&id, "ts"),
+        }
+    }
 }
 
 pub fn get_id_str(id: &FlexId) -> String {
@@ -1713,6 +1754,7 @@ impl M3uClient {
                 stream_icon: entry.logo.clone(),
                 epg_channel_id: None,
                 added: None,
+                last_modified: None,
                 category_id: cat_id,
                 container_extension: None,
                 rating: None,
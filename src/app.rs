@@ -12,18 +12,16 @@ use ratatui::layout::Rect;
 use ratatui::widgets::ListState;
 use tui_input::Input;
 
-// Casting types - conditionally use real or stub implementation
-#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
-pub use crate::cast::CastDevice;
+pub use crate::cast::{CastDevice, CastSession, CastStatus, PlaybackTarget};
 
-#[cfg(not(all(not(target_arch = "wasm32"), feature = "chromecast")))]
-#[derive(Debug, Clone, PartialEq)]
-pub struct CastDevice {
-    pub name: String,
-    pub ip: String,
-    pub port: u16,
-    pub model: Option<String>,
-}
+/// Toasts shown at once; older ones make room
+const MAX_TOASTS: usize = 3;
+
+/// How long a toast stays on screen (~5s of main-loop ticks)
+const TOAST_TICKS: u64 = 150;
+
+/// Failed status polls in a row before a cast device counts as gone
+const MAX_CAST_POLL_FAILURES: u32 = 3;
 
 #[derive(Debug, Clone)]
 pub enum AsyncAction {
@@ -36,8 +34,10 @@ pub enum AsyncAction {
     SeriesCategoriesLoaded(Vec<Category>),
     SeriesStreamsLoaded(Vec<Stream>, String),
     SeriesInfoLoaded(crate::api::SeriesInfo),
+    CatalogDiffed(Vec<crate::whats_new::CatalogChange>),
+    SearchIndexBuilt(Arc<crate::search_index::SearchIndex>),
     VodInfoLoaded(crate::api::VodInfo),
-    PlayerStarted,
+    PlayerStarted(PlayedStream),
     PlayerFailed(String),
     LoadingMessage(String),
     TotalChannelsLoaded(Vec<Stream>),
@@ -65,16 +65,37 @@ pub enum AsyncAction {
     NoUpdateFound,
     SportsMatchesLoaded(Vec<crate::sports::StreamedMatch>),
     SportsStreamsLoaded(Vec<crate::sports::StreamedStream>),
+    EmbedOpenedInBrowser(String), // Extraction error that triggered the fallback
     ScoresLoaded(Vec<crate::scores::ScoreGame>),
+    PlayerStatus(Option<crate::player_control::PlaybackStatus>), // None = player gone
+    TransportSent(String),                                       // Action label
+    PlayerStats(crate::player_control::PlaybackStats),
+    Watchdog(crate::watchdog::WatchdogEvent),
+    ChannelZapRequested(i32), // Channel step pressed inside the mpv window
+    ChannelZapped {
+        stream_id: String,
+        name: String,
+    },
+    MultiviewStarted(Vec<crate::config::MultiviewTile>),
+    RecordingPlaybackStarted(String), // Recording title
+    ScheduleGuideLoaded(String, Vec<crate::api::EpgListing>), // stream_id, listings
+    MultiviewFocused(usize),          // Tile now carrying audio
     ScanProgress {
         current: usize,
         total: usize,
         eta_secs: u64,
     },
-    // Chromecast casting
-    CastDevicesDiscovered(Vec<CastDevice>),
-    CastStarted(String), // Device name
-    CastFailed(String),  // Error message
+    // Casting (Chromecast, DLNA and Kodi)
+    CastDevicesDiscovered(Vec<PlaybackTarget>),
+    CastStarted(CastSession),
+    CastFailed(String), // Error message
+    CastStatus(Result<CastStatus, String>),
+    CastCommandSent(String),                // Action label
+    KodiPinged(String, Result<(), String>), // Host name, connection result
+    #[cfg(not(target_arch = "wasm32"))]
+    ImageLoaded(String, Option<Arc<image::RgbaImage>>), // Url, decoded logo/poster
+    // Offline mode: the provider answered again
+    Reconnected(IptvClient, Option<UserInfo>, Option<ServerInfo>),
     Error(String),
 
     // Lazy Category Loading (Phase 4)
@@ -102,6 +123,66 @@ pub enum CurrentScreen {
     GroupPicker,          // Pick a group to add stream to
     UpdatePrompt,         // Prompt for app update
     SportsDashboard,      // Integrated Live Sports from Streamed.pk
+    Recordings,           // DVR: active recordings and recorded files
+    WatchStats,           // Viewing history statistics and export
+    WhatsNew,             // Catalog additions since the last look
+}
+
+/// One entry of Settings > Player Engine
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerChoice {
+    Engine(crate::config::PlayerEngine),
+    /// A configured profile, or a detected player not yet saved to the config
+    Profile(crate::player_profile::PlayerProfile),
+}
+
+impl PlayerChoice {
+    pub fn label(&self) -> String {
+        match self {
+            PlayerChoice::Engine(engine) => engine.display_name().to_string(),
+            PlayerChoice::Profile(profile) => format!("{} ({})", profile.name, profile.executable),
+        }
+    }
+}
+
+/// Something asked for while browsing offline, done once the provider is back
+#[derive(Debug, Clone)]
+pub enum OfflineAction {
+    /// Open the play popup for this stream
+    Play(Arc<Stream>),
+    /// Full playlist refresh
+    Refresh,
+}
+
+/// What a player launch is playing, captured when it is launched and handed
+/// back with `AsyncAction::PlayerStarted` (the cursor may have moved by then)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayedStream {
+    pub stream_id: String,
+    pub name: String,
+    pub category_id: Option<String>,
+    pub account: String,
+    pub audio_only: bool,
+}
+
+/// One row of the recordings screen
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub enum RecordingRow<'a> {
+    Active(&'a crate::recorder::Recording),
+    Scheduled(ScheduledRow),
+    File(&'a crate::recorder::RecordingFile),
+}
+
+/// An upcoming scheduled airing, or a series rule with nothing in the guide yet
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledRow {
+    pub schedule_id: u64,
+    pub title: String,
+    pub channel_name: String,
+    pub start: Option<chrono::DateTime<chrono::Utc>>,
+    /// Overlaps more recordings than the account's connection limit
+    pub conflict: bool,
 }
 
 #[derive(PartialEq, Debug)]
@@ -226,6 +307,24 @@ pub struct App {
 
     // Player Engine selection
     pub player_engine_list_state: ListState,
+    /// Known players found on this machine (refreshed when the picker opens)
+    pub detected_players: Vec<crate::player_profile::PlayerProfile>,
+
+    // Player profiles
+    pub player_profile_list_state: ListState,
+    /// Text being typed for a new profile (`name | executable | arguments`) or a rename
+    pub player_profile_input: Option<tui_input::Input>,
+    /// Profile being renamed by `player_profile_input`; None when adding
+    pub player_profile_renaming: Option<usize>,
+
+    // Kodi hosts
+    pub kodi_host_list_state: ListState,
+    /// Address being typed for a new Kodi host
+    pub kodi_host_input: Option<tui_input::Input>,
+    /// Folder being typed in Settings › Recordings Folder
+    pub recordings_dir_input: tui_input::Input,
+    /// Port being typed in Settings › Relay Port
+    pub relay_port_input: tui_input::Input,
 
     // Auto-Refresh selection
     pub auto_refresh_list_state: ListState,
@@ -296,6 +395,10 @@ pub struct App {
 
     // Global Search
     pub global_search_results: Vec<Arc<Stream>>,
+    /// Trigram index over the global lists, rebuilt whenever they are reloaded
+    pub search_index: Option<Arc<crate::search_index::SearchIndex>>,
+    /// Bumped by `catalog_changed` whenever a global list is replaced or grown
+    pub catalog_generation: u64,
     pub global_search_list_state: ListState,
 
     // Group Management
@@ -305,6 +408,8 @@ pub struct App {
     pub group_name_input: String,                 // For creating/renaming groups
     pub pending_play_url: Option<String>,
     pub pending_play_title: Option<String>,
+    /// What the play popup's URL plays, for the watch history
+    pub pending_play_stream: Option<PlayedStream>,
     pub show_play_details: bool,
     pub new_version_available: Option<String>,
 
@@ -319,15 +424,104 @@ pub struct App {
 
     // Live Scores (ESPN)
     pub live_scores: Vec<crate::scores::ScoreGame>,
+    /// Game id -> loading_tick at which its score last changed (ticker highlight)
+    pub ticker_highlights: std::collections::HashMap<String, u64>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub last_ticker_refresh: Option<std::time::Instant>,
+    pub ticker_league_list_state: ListState,
 
-    // Chromecast Casting
+    // Now Playing (mpv IPC transport controls)
+    pub now_playing: Option<crate::player_control::NowPlaying>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub last_player_poll: Option<std::time::Instant>,
+    /// Channel list that channel up/down steps through (the list playback started from)
+    pub zap_playlist: Vec<Arc<Stream>>,
+    pub zap_position: usize,
+    /// Channel step requested from the mpv window, applied by the main loop
+    pub pending_zap: Option<i32>,
+    /// Play channels without video (radio categories always do)
+    pub audio_only: bool,
+
+    // Watch history
+    pub watch_history: crate::history::WatchHistory,
+    /// Session of what is playing now, recorded when playback ends or changes
+    pub viewing: Option<crate::history::ViewingSession>,
+
+    // What's New
+    pub whats_new: crate::whats_new::WhatsNew,
+    /// Changes listed on the What's New screen (captured when it opens)
+    pub whats_new_items: Vec<crate::whats_new::CatalogChange>,
+    /// When the account last looked before the screen was opened
+    pub whats_new_since: Option<chrono::DateTime<chrono::Utc>>,
+    pub whats_new_list_state: ListState,
+
+    // Logos and posters
+    #[cfg(not(target_arch = "wasm32"))]
+    pub images: crate::images::ImageCache,
+    /// Drawing method in use (`config.image_protocol` with Auto resolved)
+    pub image_protocol: crate::config::ImageProtocol,
+
+    // Offline mode
+    /// Actions waiting for the provider (latest play only)
+    pub offline_queue: Vec<OfflineAction>,
+    /// Last time the main loop tried to reach the provider
+    pub last_reconnect_attempt: Option<std::time::Instant>,
+    /// Recovery plan for the live channel that just started, picked up by the
+    /// main loop to start the stall watchdog
+    pub pending_watchdog: Option<crate::watchdog::RecoveryPlan>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub watchdog_task: Option<tokio::task::AbortHandle>,
+    /// Short notices (message, loading_tick) shown in the corner for a few seconds
+    pub toasts: Vec<(String, u64)>,
+
+    // Multiview
+    /// Streams marked for the next multiview, in tile order
+    pub multiview_tiles: Vec<crate::config::MultiviewTile>,
+    pub show_multiview: bool,
+    /// Rows: marked tiles first, then saved presets
+    pub multiview_list_state: ListState,
+    /// Preset name being typed (`Some` while the save prompt is open)
+    pub multiview_preset_name: Option<String>,
+    pub multiview_session: Option<crate::multiview::MultiviewSession>,
+
+    // DVR
+    /// Recordings started this session (finished ones stay until the app exits)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub recordings: Vec<crate::recorder::Recording>,
+    /// Files in the recordings folder, refreshed when the screen opens
+    #[cfg(not(target_arch = "wasm32"))]
+    pub recording_files: Vec<crate::recorder::RecordingFile>,
+    pub recordings_list_state: ListState,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub recording_scheduler: crate::scheduler::RecordingScheduler,
+    /// Guide entries for channels with schedules (stream_id -> programmes)
+    pub schedule_guide: std::collections::HashMap<String, Vec<crate::scheduler::GuideEntry>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub last_schedule_guide_refresh: Option<std::time::Instant>,
+    /// Local restreaming relay, running while enabled in Settings
+    #[cfg(not(target_arch = "wasm32"))]
+    pub relay: Option<crate::relay::Relay>,
+    /// Channel (stream_id, name) whose guide the schedule picker shows
+    pub schedule_picker_channel: Option<(String, String)>,
+    pub schedule_picker_state: ListState,
+
+    // Casting
     #[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
     pub cast_manager: crate::cast::CastManager,
-    pub cast_devices: Vec<CastDevice>,
+    /// Targets listed in the cast picker
+    pub cast_devices: Vec<PlaybackTarget>,
     pub cast_device_list_state: ListState,
     pub show_cast_picker: bool,
     pub cast_discovering: bool,
     pub selected_cast_device_index: usize,
+    /// Stream playing on a cast device, controlled by the alt+key transport keys
+    pub cast_session: Option<CastSession>,
+    pub cast_status: Option<CastStatus>,
+    /// A status poll is on its way (see `main.rs` 1.20)
+    pub cast_polling: bool,
+    pub cast_poll_failures: u32,
+    /// Last remote command and the tick it was confirmed
+    pub cast_last_action: Option<(String, u64)>,
 
     // UX improvements
     #[cfg(not(target_arch = "wasm32"))]
@@ -376,12 +570,100 @@ pub enum SettingsState {
     DnsSelection,
     VideoModeSelection,
     PlayerEngineSelection,
+    PlayerProfiles,
+    KodiHosts,
+    RecordingsFolder,
+    RelayPort,
     PlaylistModeSelection,
     AutoRefreshSelection,
+    TickerLeagueSelection,
     CategoryManagement,
     About,
 }
 
+/// A row of the main Settings list. Rendering and Enter both go through
+/// `SettingsItem::ALL`, so adding or moving a row is a change in one place.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum SettingsItem {
+    ManageAccounts,
+    Timezone,
+    PlaylistFilters,
+    DnsProvider,
+    VideoMode,
+    PlayerEngine,
+    PlayerProfiles,
+    KodiHosts,
+    SmoothMotion,
+    AutoRefresh,
+    ScoreTicker,
+    LocalRelay,
+    RelayAccess,
+    RelayPort,
+    StallWatchdog,
+    Artwork,
+    MultiviewScreen,
+    RecordingsFolder,
+    MatrixRain,
+    CheckForUpdates,
+    CategoryVisibility,
+    About,
+}
+
+impl SettingsItem {
+    /// On-screen order
+    pub const ALL: &'static [SettingsItem] = &[
+        SettingsItem::ManageAccounts,
+        SettingsItem::Timezone,
+        SettingsItem::PlaylistFilters,
+        SettingsItem::DnsProvider,
+        SettingsItem::VideoMode,
+        SettingsItem::PlayerEngine,
+        SettingsItem::PlayerProfiles,
+        SettingsItem::KodiHosts,
+        SettingsItem::SmoothMotion,
+        SettingsItem::AutoRefresh,
+        SettingsItem::ScoreTicker,
+        SettingsItem::LocalRelay,
+        SettingsItem::RelayAccess,
+        SettingsItem::RelayPort,
+        SettingsItem::StallWatchdog,
+        SettingsItem::Artwork,
+        SettingsItem::MultiviewScreen,
+        SettingsItem::RecordingsFolder,
+        SettingsItem::MatrixRain,
+        SettingsItem::CheckForUpdates,
+        SettingsItem::CategoryVisibility,
+        SettingsItem::About,
+    ];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            SettingsItem::ManageAccounts => "Add, edit, or remove IPTV playlist connections.",
+            SettingsItem::Timezone => "Set your local timezone for accurate program scheduling.",
+            SettingsItem::PlaylistFilters => "Playlist Mode: Change how playlists are processed and displayed. e.g. 'merica mode for US sports, Sports mode for global athletics, etc.",
+            SettingsItem::DnsProvider => "Choose DNS provider for network requests. Quad9 recommended for privacy.",
+            SettingsItem::VideoMode => "Enhanced = Interpolation/Upscaling (MPV only). MPV Default = No enhancements.",
+            SettingsItem::PlayerEngine => "Switch between MPV (High Performance) and VLC (High Stability) playback engines.",
+            SettingsItem::PlayerProfiles => "Add, rename or delete external players (IINA, ffplay, scripts, ...) offered under Player Engine.",
+            SettingsItem::KodiHosts => "Kodi boxes to offer in the cast picker (C), reached over Kodi's JSON-RPC web interface.",
+            SettingsItem::SmoothMotion => "Enables motion interpolation to double the perceived frame-rate (works on MPV and VLC).",
+            SettingsItem::AutoRefresh => "How often to automatically refresh playlist data when logging in. Set to 0 to disable.",
+            SettingsItem::ScoreTicker => "Scroll in-progress games from your followed leagues along the bottom of every screen.",
+            SettingsItem::LocalRelay => "Restream live channels at http://HOST:PORT/live/{id}, sharing one provider connection per channel. Press L on a channel to copy its relay URL.",
+            SettingsItem::RelayAccess => "This computer only, or every device on your network (TVs, phones). On the network the relay has no password: anyone who can reach it watches through your provider account.",
+            SettingsItem::RelayPort => "Port the relay listens on (1-65535).",
+            SettingsItem::StallWatchdog => "When a live channel freezes or keeps buffering this long, reload it, try other stream formats, then switch to another feed of the same channel (MPV only).",
+            SettingsItem::Artwork => "How channel logos and posters are drawn: kitty graphics, iTerm2 inline images, sixel, or colored half blocks for any terminal. Auto picks from your terminal.",
+            SettingsItem::MultiviewScreen => "Screen size VLC multiview windows are tiled over. Auto asks the desktop and falls back to 1920x1080; MPV multiview uses one window and ignores this.",
+            SettingsItem::RecordingsFolder => "Where recordings are saved and listed from. Leave empty for the Videos folder.",
+            SettingsItem::MatrixRain => "Launch the iconic Matrix digital rain animation.",
+            SettingsItem::CheckForUpdates => "Check if a newer version of Matrix IPTV is available for download.",
+            SettingsItem::CategoryVisibility => "Hide or show specific playlist categories for a cleaner experience.",
+            SettingsItem::About => "View application info, version, and credits.",
+        }
+    }
+}
+
 impl Default for App {
     fn default() -> Self {
         Self::new()
@@ -390,7 +672,25 @@ impl Default for App {
 
 impl App {
     pub fn new() -> App {
-        let config = AppConfig::load().unwrap_or_default();
+        Self::with_storage(crate::storage::ConfigStorage::platform())
+    }
+
+    /// App whose config is loaded from and saved to `storage`
+    pub fn with_storage(storage: crate::storage::ConfigStorage) -> App {
+        match AppConfig::load_from(storage.clone()) {
+            Ok(config) => Self::with_config(config),
+            Err(e) => {
+                // Don't let the next save overwrite the unusable file
+                let (config, notice) = AppConfig::recover(storage, &e);
+                let mut app = Self::with_config(config);
+                app.login_error = Some(notice);
+                app
+            }
+        }
+    }
+
+    /// App around an already loaded config
+    pub fn with_config(config: AppConfig) -> App {
         let mut account_list_state = ListState::default();
         if !config.accounts.is_empty() {
             account_list_state.select(Some(0));
@@ -411,6 +711,11 @@ impl App {
 
         let cached_tz = config.get_user_timezone();
 
+        #[cfg(not(target_arch = "wasm32"))]
+        let image_protocol = crate::images::resolve(config.image_protocol);
+        #[cfg(target_arch = "wasm32")]
+        let image_protocol = crate::config::ImageProtocol::Off;
+
         let mut app = App {
             config,
             current_screen: CurrentScreen::Home,
@@ -488,6 +793,8 @@ impl App {
             global_series_streams_by_cat: std::collections::HashMap::new(),
 
             global_search_results: vec![],
+            search_index: None,
+            catalog_generation: 0,
             global_search_list_state: ListState::default(),
 
             settings_options: vec![],
@@ -518,6 +825,14 @@ impl App {
             dns_list_state: ListState::default(),
             video_mode_list_state: ListState::default(),
             player_engine_list_state: ListState::default(),
+            detected_players: Vec::new(),
+            player_profile_list_state: ListState::default(),
+            player_profile_input: None,
+            player_profile_renaming: None,
+            kodi_host_list_state: ListState::default(),
+            kodi_host_input: None,
+            recordings_dir_input: tui_input::Input::default(),
+            relay_port_input: tui_input::Input::default(),
             auto_refresh_list_state: ListState::default(),
 
             active_pane: Pane::Categories,
@@ -566,6 +881,7 @@ impl App {
             group_name_input: String::new(),
             pending_play_url: None,
             pending_play_title: None,
+            pending_play_stream: None,
             show_play_details: false,
             new_version_available: None,
 
@@ -590,8 +906,62 @@ impl App {
 
             // Live Scores
             live_scores: Vec::new(),
+            ticker_highlights: std::collections::HashMap::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            last_ticker_refresh: None,
+            ticker_league_list_state: ListState::default(),
 
-            // Chromecast Casting
+            // Now Playing
+            now_playing: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            last_player_poll: None,
+            zap_playlist: Vec::new(),
+            zap_position: 0,
+            pending_zap: None,
+            audio_only: false,
+            watch_history: crate::history::WatchHistory::load(),
+            viewing: None,
+            whats_new: crate::whats_new::WhatsNew::load(),
+            whats_new_items: Vec::new(),
+            whats_new_since: None,
+            whats_new_list_state: ListState::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            images: crate::images::ImageCache::default(),
+            image_protocol,
+            offline_queue: Vec::new(),
+            last_reconnect_attempt: None,
+            pending_watchdog: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            watchdog_task: None,
+            toasts: Vec::new(),
+
+            // Multiview
+            multiview_tiles: Vec::new(),
+            show_multiview: false,
+            multiview_list_state: ListState::default(),
+            multiview_preset_name: None,
+            multiview_session: None,
+
+            // DVR
+            #[cfg(not(target_arch = "wasm32"))]
+            recordings: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            recording_files: Vec::new(),
+            recordings_list_state: ListState::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            recording_scheduler: crate::scheduler::RecordingScheduler::new(
+                std::sync::Arc::new(crate::scheduler::SystemClock),
+                crate::scheduler::Padding::from_minutes(2, 5),
+            ),
+            schedule_guide: std::collections::HashMap::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            last_schedule_guide_refresh: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            relay: None,
+            schedule_picker_channel: None,
+            schedule_picker_state: ListState::default(),
+
+            // Casting
             #[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
             cast_manager: crate::cast::CastManager::new(),
             cast_devices: Vec::new(),
@@ -599,6 +969,11 @@ impl App {
             show_cast_picker: false,
             cast_discovering: false,
             selected_cast_device_index: 0,
+            cast_session: None,
+            cast_status: None,
+            cast_polling: false,
+            cast_poll_failures: 0,
+            cast_last_action: None,
 
             // UX improvements
             #[cfg(not(target_arch = "wasm32"))]
@@ -624,6 +999,10 @@ impl App {
 
         app.refresh_settings_options();
         app.apply_category_filters(); // Apply initial filters
+        for notice in std::mem::take(&mut app.config.notices) {
+            app.push_toast(notice);
+        }
+        app.warn_unsealed_passwords();
         app
     }
 
@@ -745,8 +1124,15 @@ impl App {
         self.series_categories = process(&self.all_series_categories, hidden, sort_order, true);
     }
 
+    /// Note that a global list was replaced or grown, so the search index built
+    /// for the old lists is no longer used
+    pub fn catalog_changed(&mut self) {
+        self.catalog_generation += 1;
+    }
+
     pub fn on_channels_loaded(&mut self, streams: Vec<Stream>, reset_selection: bool) {
         let wrapped: Vec<Arc<Stream>> = streams.into_iter().map(Arc::new).collect();
+        self.catalog_changed();
 
         if reset_selection {
             self.global_all_streams = wrapped.clone();
@@ -854,14 +1240,85 @@ impl App {
         })
     }
 
-    pub fn refresh_settings_options(&mut self) {
-        self.settings_options = vec![
-            "Manage Playlists".to_string(),
-            format!(
+    /// In-progress games from followed leagues, in the order shown by the score ticker
+    pub fn ticker_games(&self) -> Vec<&crate::scores::ScoreGame> {
+        self.live_scores
+            .iter()
+            .filter(|g| g.is_in_progress() && self.config.follows_league(&g.league))
+            .collect()
+    }
+
+    /// Whether a game's score changed recently enough to be highlighted in the ticker
+    pub fn is_ticker_highlighted(&self, game_id: &str) -> bool {
+        const TICKER_HIGHLIGHT_TICKS: u64 = 2700; // ~90s at the 33ms frame cadence
+        self.ticker_highlights
+            .get(game_id)
+            .is_some_and(|t| self.session.loading_tick.wrapping_sub(*t) < TICKER_HIGHLIGHT_TICKS)
+    }
+
+    /// The ticker game the jump key targets: the most recent score change, else the first game
+    pub fn highlighted_ticker_game(&self) -> Option<&crate::scores::ScoreGame> {
+        let games = self.ticker_games();
+        games
+            .iter()
+            .filter(|g| self.is_ticker_highlighted(&g.id))
+            .max_by_key(|g| self.ticker_highlights.get(&g.id).copied().unwrap_or(0))
+            .or_else(|| games.first())
+            .copied()
+    }
+
+    /// Open the live channel carrying the highlighted ticker game in Global Search.
+    /// Returns false if no loaded channel matches the game.
+    pub fn jump_to_ticker_game(&mut self) -> bool {
+        let Some(game) = self.highlighted_ticker_game().cloned() else {
+            return false;
+        };
+
+        let Some(stream) = self
+            .global_all_streams
+            .iter()
+            .find(|s| {
+                let name = s
+                    .cached_parsed
+                    .as_ref()
+                    .map(|p| p.display_name.as_str())
+                    .unwrap_or(&s.name);
+                self.get_score_for_stream(name)
+                    .is_some_and(|g| g.id == game.id)
+            })
+            .cloned()
+        else {
+            return false;
+        };
+
+        if self.current_screen != CurrentScreen::GlobalSearch {
+            self.previous_screen = Some(self.current_screen.clone());
+        }
+        self.current_screen = CurrentScreen::GlobalSearch;
+        self.search_mode = false;
+        self.search_state.query = stream.name.clone();
+        self.last_search_query.clear();
+        self.update_search();
+
+        if let Some(idx) = self
+            .global_search_results
+            .iter()
+            .position(|s| s.stream_id == stream.stream_id)
+        {
+            self.jump_to_global_search_result(idx);
+        }
+        true
+    }
+
+    /// The Settings row for `item`, with its current value
+    pub fn settings_label(&self, item: SettingsItem) -> String {
+        match item {
+            SettingsItem::ManageAccounts => "Manage Playlists".to_string(),
+            SettingsItem::Timezone => format!(
                 "Set Timezone (Current: {})",
                 self.config.get_user_timezone()
             ),
-            format!(
+            SettingsItem::PlaylistFilters => format!(
                 "Playlist Filters: {}",
                 if self.config.processing_modes.is_empty() {
                     "None".to_string()
@@ -878,8 +1335,10 @@ impl App {
                         .join(" + ")
                 }
             ),
-            format!("DNS Provider: {}", self.config.dns_provider.display_name()),
-            format!(
+            SettingsItem::DnsProvider => {
+                format!("DNS Provider: {}", self.config.dns_provider.display_name())
+            }
+            SettingsItem::VideoMode => format!(
                 "Video Mode: {}",
                 if self.config.use_default_mpv {
                     "MPV Default"
@@ -887,11 +1346,24 @@ impl App {
                     "Enhanced"
                 }
             ),
-            format!(
-                "Player Engine: {}",
-                self.config.preferred_player.display_name()
+            SettingsItem::PlayerEngine => format!("Player Engine: {}", self.player_label()),
+            SettingsItem::PlayerProfiles => format!(
+                "Player Profiles: {}",
+                match self.config.player_profiles.len() {
+                    0 => "None".to_string(),
+                    1 => self.config.player_profiles[0].name.clone(),
+                    n => format!("{} configured", n),
+                }
+            ),
+            SettingsItem::KodiHosts => format!(
+                "Kodi Hosts: {}",
+                match self.config.kodi_hosts.len() {
+                    0 => "None".to_string(),
+                    1 => self.config.kodi_hosts[0].name.clone(),
+                    n => format!("{} configured", n),
+                }
             ),
-            format!(
+            SettingsItem::SmoothMotion => format!(
                 "Smooth Motion: {}",
                 if self.config.smooth_motion {
                     "ON"
@@ -899,7 +1371,7 @@ impl App {
                     "OFF"
                 }
             ),
-            format!(
+            SettingsItem::AutoRefresh => format!(
                 "Auto-Refresh: {}",
                 if self.config.auto_refresh_hours == 0 {
                     "Disabled".to_string()
@@ -907,33 +1379,145 @@ impl App {
                     format!("Every {}h", self.config.auto_refresh_hours)
                 }
             ),
-            "Matrix Rain Screensaver".to_string(),
-            "Check for Updates".to_string(),
-            "Manage Category Visibility".to_string(),
-            "About".to_string(),
-        ];
+            SettingsItem::ScoreTicker => format!(
+                "Score Ticker: {}",
+                if !self.config.score_ticker {
+                    "OFF".to_string()
+                } else if self.config.ticker_leagues.is_empty() {
+                    "All Leagues".to_string()
+                } else {
+                    self.config.ticker_leagues.join(" + ")
+                }
+            ),
+            SettingsItem::LocalRelay => format!(
+                "Local Relay: {}",
+                if self.config.relay_enabled {
+                    format!("ON ({}:{})", self.config.relay_bind, self.config.relay_port)
+                } else {
+                    "OFF".to_string()
+                }
+            ),
+            SettingsItem::RelayAccess => format!(
+                "Relay Access: {}",
+                if self.config.relay_bind == crate::config::RELAY_LOCAL_BIND {
+                    "This computer only".to_string()
+                } else if self.config.relay_bind == crate::config::RELAY_LAN_BIND {
+                    "Local network".to_string()
+                } else {
+                    self.config.relay_bind.clone()
+                }
+            ),
+            SettingsItem::RelayPort => format!("Relay Port: {}", self.config.relay_port),
+            SettingsItem::StallWatchdog => format!(
+                "Stall Watchdog: {}",
+                if self.config.watchdog_stall_secs == 0 {
+                    "OFF".to_string()
+                } else {
+                    format!("Recover after {}s", self.config.watchdog_stall_secs)
+                }
+            ),
+            SettingsItem::Artwork => format!(
+                "Artwork: {}",
+                if self.config.image_protocol == crate::config::ImageProtocol::Auto {
+                    format!("Auto ({})", self.image_protocol.display_name())
+                } else {
+                    self.image_protocol.display_name().to_string()
+                }
+            ),
+            SettingsItem::MultiviewScreen => format!(
+                "Multiview Screen: {}",
+                match self.config.multiview_screen {
+                    Some((w, h)) => format!("{}x{}", w, h),
+                    None => "Auto".to_string(),
+                }
+            ),
+            SettingsItem::RecordingsFolder => format!(
+                "Recordings Folder: {}",
+                match self.config.recordings_dir.as_deref().map(str::trim) {
+                    Some(dir) if !dir.is_empty() => dir.to_string(),
+                    _ => "Default".to_string(),
+                }
+            ),
+            SettingsItem::MatrixRain => "Matrix Rain Screensaver".to_string(),
+            SettingsItem::CheckForUpdates => "Check for Updates".to_string(),
+            SettingsItem::CategoryVisibility => "Manage Category Visibility".to_string(),
+            SettingsItem::About => "About".to_string(),
+        }
+    }
 
-        // Descriptions for each setting (same order as settings_options)
-        self.settings_descriptions = vec![
-            "Add, edit, or remove IPTV playlist connections.".to_string(),
-            "Set your local timezone for accurate program scheduling.".to_string(),
-            "Playlist Mode: Change how playlists are processed and displayed. e.g. 'merica mode for US sports, Sports mode for global athletics, etc.".to_string(),
-            "Choose DNS provider for network requests. Quad9 recommended for privacy.".to_string(),
-            "Enhanced = Interpolation/Upscaling (MPV only). MPV Default = No enhancements.".to_string(),
-            "Switch between MPV (High Performance) and VLC (High Stability) playback engines.".to_string(),
-            "Enables motion interpolation to double the perceived frame-rate (works on MPV and VLC).".to_string(),
-            "How often to automatically refresh playlist data when logging in. Set to 0 to disable.".to_string(),
-            "Launch the iconic Matrix digital rain animation.".to_string(),
-            "Check if a newer version of Matrix IPTV is available for download.".to_string(),
-            "Hide or show specific playlist categories for a cleaner experience.".to_string(),
-            "View application info, version, and credits.".to_string(),
-        ];
+    pub fn refresh_settings_options(&mut self) {
+        self.settings_options = SettingsItem::ALL
+            .iter()
+            .map(|&item| self.settings_label(item))
+            .collect();
+        self.settings_descriptions = SettingsItem::ALL
+            .iter()
+            .map(|item| item.description().to_string())
+            .collect();
 
         if self.settings_list_state.selected().is_none() {
             self.settings_list_state.select(Some(0));
         }
     }
 
+    /// The Settings row under the cursor
+    pub fn selected_setting(&self) -> Option<SettingsItem> {
+        SettingsItem::ALL.get(self.selected_settings_index).copied()
+    }
+
+    /// Built-in engines, configured profiles, then detected players not yet configured
+    pub fn player_choices(&self) -> Vec<PlayerChoice> {
+        let mut choices: Vec<PlayerChoice> = crate::config::PlayerEngine::all()
+            .iter()
+            .map(|e| PlayerChoice::Engine(*e))
+            .collect();
+        choices.extend(
+            self.config
+                .player_profiles
+                .iter()
+                .cloned()
+                .map(PlayerChoice::Profile),
+        );
+        choices.extend(
+            self.detected_players
+                .iter()
+                .filter(|d| !self.config.player_profiles.iter().any(|p| p.name == d.name))
+                .cloned()
+                .map(PlayerChoice::Profile),
+        );
+        choices
+    }
+
+    pub fn is_current_player(&self, choice: &PlayerChoice) -> bool {
+        match choice {
+            PlayerChoice::Engine(engine) => *engine == self.config.preferred_player,
+            PlayerChoice::Profile(profile) => {
+                self.config.preferred_player == crate::config::PlayerEngine::Custom
+                    && self.config.active_player_profile.as_deref() == Some(profile.name.as_str())
+            }
+        }
+    }
+
+    /// Make `choice` the player for new playback, saving a detected player as a profile
+    pub fn select_player(&mut self, choice: PlayerChoice) {
+        match choice {
+            PlayerChoice::Engine(engine) => self.config.preferred_player = engine,
+            PlayerChoice::Profile(profile) => {
+                self.config.preferred_player = crate::config::PlayerEngine::Custom;
+                self.config.active_player_profile = Some(profile.name.clone());
+                if !self
+                    .config
+                    .player_profiles
+                    .iter()
+                    .any(|p| p.name == profile.name)
+                {
+                    self.config.player_profiles.push(profile);
+                }
+            }
+        }
+        let _ = self.config.save();
+    }
+
     pub fn get_selected_account(&self) -> Option<&crate::config::Account> {
         self.config
             .accounts
@@ -948,6 +1532,768 @@ impl App {
         self.streams.get(self.selected_stream_index)
     }
 
+    /// Simultaneous connections the provider allows, if it reports a limit
+    pub fn connection_limit(&self) -> Option<usize> {
+        self.session
+            .account_info
+            .as_ref()?
+            .max_connections
+            .as_ref()?
+            .to_string_value()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Connections the provider reported in use when the account info was fetched
+    pub fn active_connections(&self) -> usize {
+        self.session
+            .account_info
+            .as_ref()
+            .and_then(|info| info.active_cons.as_ref())
+            .and_then(|cons| cons.to_string_value())
+            .and_then(|cons| cons.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Tiles a multiview can hold without going over the account's connection limit
+    pub fn multiview_tile_limit(&self) -> usize {
+        crate::multiview::tile_limit(self.connection_limit(), self.active_connections())
+    }
+
+    /// Provider connections the player holds: one per multiview tile, else one
+    /// while something is playing
+    pub fn player_connections(&self) -> usize {
+        match (&self.multiview_session, &self.now_playing) {
+            (Some(session), _) => session.tiles.len(),
+            (None, Some(_)) => 1,
+            (None, None) => 0,
+        }
+    }
+
+    /// Mark or unmark a stream for multiview. Returns whether it is now marked,
+    /// or an error when the grid (or the account's connection limit) is full.
+    pub fn toggle_multiview_tile(&mut self, stream: &Stream) -> Result<bool, String> {
+        let stream_id = crate::api::get_id_str(&stream.stream_id);
+        if let Some(pos) = self
+            .multiview_tiles
+            .iter()
+            .position(|t| t.stream_id == stream_id)
+        {
+            self.multiview_tiles.remove(pos);
+            return Ok(false);
+        }
+
+        let limit = self.multiview_tile_limit();
+        if self.multiview_tiles.len() >= limit {
+            let active = self.active_connections();
+            return Err(match self.connection_limit() {
+                Some(max) if max > 0 && active > 0 && limit < crate::multiview::MAX_TILES => {
+                    format!(
+                        "Your account allows {} simultaneous connections and {} are in use",
+                        max, active
+                    )
+                }
+                _ if limit < crate::multiview::MAX_TILES => {
+                    format!("Your account allows {} simultaneous connections", limit)
+                }
+                _ => format!("Multiview holds at most {} channels", limit),
+            });
+        }
+        self.multiview_tiles.push(crate::config::MultiviewTile {
+            stream_id,
+            name: stream.name.clone(),
+        });
+        Ok(true)
+    }
+
+    /// Replace the marked tiles with a saved preset, trimmed to the connection limit
+    pub fn load_multiview_preset(&mut self, index: usize) {
+        if let Some(preset) = self.config.multiview_presets.get(index) {
+            let limit = self.multiview_tile_limit();
+            self.multiview_tiles = preset.tiles.iter().take(limit).cloned().collect();
+        }
+    }
+
+    /// The in-progress recording of `stream_id`, if any
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn active_recording(&self, stream_id: &str) -> Option<&crate::recorder::Recording> {
+        self.recordings
+            .iter()
+            .find(|r| r.stream_id == stream_id && r.is_active())
+    }
+
+    /// Start or stop the relay to match Settings and point it at the active account
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn sync_relay(&mut self) {
+        if !self.config.relay_enabled {
+            if let Some(relay) = self.relay.take() {
+                relay.stop();
+            }
+            return;
+        }
+        if self.relay.is_none() {
+            match crate::relay::Relay::start(&self.config.relay_bind, self.config.relay_port) {
+                Ok(relay) => self.relay = Some(relay),
+                Err(e) => {
+                    self.config.relay_enabled = false;
+                    let _ = self.config.save();
+                    self.refresh_settings_options();
+                    self.login_error = Some(e.to_string());
+                    return;
+                }
+            }
+        }
+        if let Some(relay) = &self.relay {
+            relay.set_client(self.session.current_client.clone());
+        }
+    }
+
+    /// Stop the relay after its address changed; the main loop's next
+    /// `sync_relay` starts it again on the new one
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn restart_relay(&mut self) {
+        if let Some(relay) = self.relay.take() {
+            relay.stop();
+        }
+    }
+
+    /// Start recording `stream` to the recordings folder, named after the
+    /// programme airing now when the guide knows it
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start_recording(&mut self, stream: &Stream) -> Result<String, String> {
+        let client = self
+            .session
+            .current_client
+            .as_ref()
+            .ok_or_else(|| "Not connected to a playlist".to_string())?;
+        let stream_id = crate::api::get_id_str(&stream.stream_id);
+
+        let active = self.recordings.iter().filter(|r| r.is_active()).count();
+        if let Some(limit) = self.connection_limit().filter(|l| *l > 0) {
+            let playing = self.player_connections();
+            if active + playing >= limit {
+                return Err(if playing > 0 {
+                    format!(
+                        "Your account allows {} simultaneous connections and playback is using {}",
+                        limit, playing
+                    )
+                } else {
+                    format!("Your account allows {} simultaneous connections", limit)
+                });
+            }
+        }
+
+        let programme = self.epg_cache.get(&stream_id).map(|s| s.as_str());
+        let file_name =
+            crate::recorder::recording_file_name(&stream.name, programme, chrono::Local::now());
+        let path = self.config.recordings_path().join(&file_name);
+        let url = client.get_stream_url(&stream_id, crate::recorder::RECORDING_EXTENSION);
+
+        self.recordings.push(crate::recorder::Recording::start(
+            client.http_client(),
+            url,
+            path,
+            stream_id,
+            stream.name.clone(),
+        ));
+        Ok(file_name)
+    }
+
+    /// Rows of the recordings screen: active recordings, upcoming scheduled
+    /// airings, then files on disk
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn recording_rows(&self) -> Vec<RecordingRow<'_>> {
+        let active: Vec<&crate::recorder::Recording> =
+            self.recordings.iter().filter(|r| r.is_active()).collect();
+        let files = self
+            .recording_files
+            .iter()
+            .filter(|f| !active.iter().any(|r| r.path == f.path))
+            .map(RecordingRow::File);
+        active
+            .iter()
+            .map(|r| RecordingRow::Active(r))
+            .chain(
+                self.scheduled_rows()
+                    .into_iter()
+                    .map(RecordingRow::Scheduled),
+            )
+            .chain(files)
+            .collect()
+    }
+
+    pub fn schedule_padding(&self) -> crate::scheduler::Padding {
+        crate::scheduler::Padding::from_minutes(
+            self.config.recording_padding_before_mins,
+            self.config.recording_padding_after_mins,
+        )
+    }
+
+    /// Every airing the saved schedules currently cover, sorted by start.
+    /// Schedules saved without an account are attributed to the active one.
+    pub fn scheduled_airings(&self) -> Vec<crate::scheduler::Airing> {
+        let tz: chrono_tz::Tz = self
+            .config
+            .get_user_timezone()
+            .parse()
+            .unwrap_or(chrono_tz::UTC);
+        let active = self.active_account_name();
+        let mut airings = crate::scheduler::expand_airings(
+            &self.config.recording_schedules,
+            &self.schedule_guide,
+            &tz,
+        );
+        for airing in airings.iter_mut().filter(|a| a.account.is_empty()) {
+            airing.account = active.clone();
+        }
+        airings
+    }
+
+    /// Airings on the active account that overlap beyond its connection limit
+    /// (none if the provider doesn't report one; other accounts' limits aren't known)
+    pub fn schedule_conflicts(
+        &self,
+        airings: &[crate::scheduler::Airing],
+    ) -> Vec<crate::scheduler::Conflict> {
+        let Some(limit) = self.connection_limit().filter(|l| *l > 0) else {
+            return Vec::new();
+        };
+        let active = self.active_account_name();
+        let airings: Vec<_> = airings
+            .iter()
+            .filter(|a| a.account == active)
+            .cloned()
+            .collect();
+        crate::scheduler::find_conflicts(&airings, self.schedule_padding(), limit)
+    }
+
+    /// Upcoming airings not yet recording, plus series rules the guide has no match for
+    pub fn scheduled_rows(&self) -> Vec<ScheduledRow> {
+        let airings = self.scheduled_airings();
+        let conflicts = self.schedule_conflicts(&airings);
+        let padding = self.schedule_padding();
+        let now = chrono::Utc::now();
+
+        let mut rows: Vec<ScheduledRow> = airings
+            .iter()
+            .filter(|a| a.window(padding).0 > now)
+            .map(|a| ScheduledRow {
+                schedule_id: a.schedule_id,
+                title: a.title.clone(),
+                channel_name: a.channel_name.clone(),
+                start: Some(a.start),
+                conflict: conflicts.iter().any(|c| c.airings.contains(&a.key())),
+            })
+            .collect();
+        for schedule in &self.config.recording_schedules {
+            if !airings.iter().any(|a| a.schedule_id == schedule.id) {
+                rows.push(ScheduledRow {
+                    schedule_id: schedule.id,
+                    title: schedule.rule.title().to_string(),
+                    channel_name: schedule.channel_name.clone(),
+                    start: None,
+                    conflict: false,
+                });
+            }
+        }
+        rows
+    }
+
+    /// Programmes the schedule picker offers: the picked channel's guide, minus
+    /// anything already over
+    pub fn schedule_picker_entries(&self) -> Vec<crate::scheduler::GuideEntry> {
+        let now = chrono::Utc::now();
+        self.schedule_picker_channel
+            .as_ref()
+            .and_then(|(stream_id, _)| self.schedule_guide.get(stream_id))
+            .map(|entries| entries.iter().filter(|e| e.end > now).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Save a schedule for `stream`. Returns a warning if it creates an overlap
+    /// the account's connection limit can't cover.
+    pub fn add_recording_schedule(
+        &mut self,
+        stream_id: String,
+        channel_name: String,
+        rule: crate::config::ScheduleRule,
+    ) -> Option<String> {
+        let title = rule.title().to_string();
+        let account = self.active_account_name();
+        let id = self
+            .config
+            .add_recording_schedule(account, stream_id, channel_name, rule);
+
+        let airings = self.scheduled_airings();
+        let conflict = self
+            .schedule_conflicts(&airings)
+            .into_iter()
+            .find(|c| c.airings.iter().any(|(schedule_id, _)| *schedule_id == id))?;
+        let tz: chrono_tz::Tz = self
+            .config
+            .get_user_timezone()
+            .parse()
+            .unwrap_or(chrono_tz::UTC);
+        Some(format!(
+            "Scheduled \"{}\", but {} recordings overlap at {} ({}) and your account allows {} connections",
+            title,
+            conflict.titles.len(),
+            conflict.at.with_timezone(&tz).format("%a %H:%M"),
+            conflict.titles.join(", "),
+            self.connection_limit().unwrap_or_default()
+        ))
+    }
+
+    /// Start/stop scheduled recordings due at this moment and drop one-off
+    /// schedules that have finished
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tick_recording_scheduler(&mut self) {
+        use crate::scheduler::SchedulerEvent;
+
+        self.recording_scheduler.padding = self.schedule_padding();
+        let airings = self.scheduled_airings();
+        let dir = self.config.recordings_path();
+        let client = self.session.current_client.clone();
+        let active = self.active_account_name();
+        let accounts = &self.config.accounts;
+        let events = self.recording_scheduler.tick(&airings, &dir, |airing| {
+            let ext = crate::recorder::RECORDING_EXTENSION;
+            if airing.account == active {
+                let client = client.as_ref()?;
+                return Some((
+                    client.http_client(),
+                    client.get_stream_url(&airing.stream_id, ext),
+                ));
+            }
+            // Another account's schedule: Xtream URLs can be built from its
+            // credentials, M3U ones need that playlist loaded
+            let account = accounts.iter().find(|a| a.name == airing.account)?;
+            if account.account_type != crate::config::AccountType::Xtream {
+                return None;
+            }
+            let client = crate::api::IptvClient::Xtream(crate::api::XtreamClient::new(
+                account.base_url.clone(),
+                account.username.clone(),
+                account.secret().to_string(),
+            ));
+            Some((
+                client.http_client(),
+                client.get_stream_url(&airing.stream_id, ext),
+            ))
+        });
+        for event in events {
+            match event {
+                SchedulerEvent::Started(recording) => self.recordings.push(recording),
+                SchedulerEvent::Stopped(_) => {}
+                SchedulerEvent::Failed(title, reason) => {
+                    self.login_error = Some(format!(
+                        "Scheduled recording of {} failed: {}",
+                        title, reason
+                    ));
+                }
+            }
+        }
+
+        let now = self.recording_scheduler.now();
+        let padding = self.schedule_padding();
+        let expired: Vec<u64> = self
+            .config
+            .recording_schedules
+            .iter()
+            .filter(|s| match s.rule {
+                crate::config::ScheduleRule::Once { end, .. } => {
+                    end + padding.after.num_seconds() < now.timestamp()
+                }
+                crate::config::ScheduleRule::Series { .. } => false,
+            })
+            .map(|s| s.id)
+            .collect();
+        if !expired.is_empty() {
+            self.config.remove_recording_schedules(&expired);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn refresh_recording_files(&mut self) {
+        self.recording_files = crate::recorder::list_recordings(&self.config.recordings_path());
+        let rows = self.recording_rows().len();
+        match self.recordings_list_state.selected() {
+            _ if rows == 0 => self.recordings_list_state.select(None),
+            Some(i) if i < rows => {}
+            _ => self.recordings_list_state.select(Some(0)),
+        }
+    }
+
+    /// Remember the list `played` was started from (a category, Favorites, search
+    /// results...), so channel up/down can step through its live channels.
+    /// Playing something that isn't a live channel of `list` keeps the old zap list.
+    pub fn capture_zap_playlist(&mut self, list: &[Arc<Stream>], played: &Stream) {
+        let live: Vec<Arc<Stream>> = list
+            .iter()
+            .filter(|s| s.stream_type != "movie" && s.stream_type != "series")
+            .cloned()
+            .collect();
+        if let Some(position) = live.iter().position(|s| s.stream_id == played.stream_id) {
+            self.zap_playlist = live;
+            self.zap_position = position;
+        }
+    }
+
+    /// Step `delta` channels through the zap list (wrapping) and return the new channel.
+    /// The stream list selection follows along while it shows that channel.
+    pub fn zap_target(&mut self, delta: i32) -> Option<Arc<Stream>> {
+        if self.zap_playlist.is_empty() {
+            return None;
+        }
+        let len = self.zap_playlist.len() as i64;
+        self.zap_position = (self.zap_position as i64 + delta as i64).rem_euclid(len) as usize;
+        let target = self.zap_playlist[self.zap_position].clone();
+
+        if matches!(
+            self.current_screen,
+            CurrentScreen::Categories | CurrentScreen::Streams
+        ) {
+            if let Some(index) = self
+                .streams
+                .iter()
+                .position(|s| s.stream_id == target.stream_id)
+            {
+                self.selected_stream_index = index;
+                self.stream_list_state.select(Some(index));
+            }
+        }
+        Some(target)
+    }
+
+    /// Whether a live category is radio: the account's override for it, else
+    /// a guess from its name
+    pub fn is_radio_category(&self, category: &Category) -> bool {
+        self.config
+            .accounts
+            .get(self.session.selected_account_index)
+            .and_then(|acc| acc.radio_overrides.get(&category.category_id).copied())
+            .unwrap_or_else(|| {
+                crate::parser::parse_category(&category.category_name).content_type
+                    == Some(crate::parser::ContentType::Radio)
+            })
+    }
+
+    /// Cycle a live category between the name-based guess, always radio and
+    /// never radio
+    pub fn cycle_radio_override(&mut self, category_id: String) {
+        if let Some(acc) = self
+            .config
+            .accounts
+            .get_mut(self.session.selected_account_index)
+        {
+            match acc.radio_overrides.get(&category_id) {
+                None => {
+                    acc.radio_overrides.insert(category_id, true);
+                }
+                Some(true) => {
+                    acc.radio_overrides.insert(category_id, false);
+                }
+                Some(false) => {
+                    acc.radio_overrides.remove(&category_id);
+                }
+            }
+            let _ = self.config.save();
+        }
+    }
+
+    /// The live category under the cursor in Settings › Manage Category
+    /// Visibility, in the order that screen lists them
+    pub fn category_mgmt_selected_live(&self) -> Option<Arc<Category>> {
+        if self.category_mgmt.content_type != crate::state::ContentType::Live {
+            return None;
+        }
+        let query = self.category_mgmt.search_query.to_lowercase();
+        let mut cats: Vec<&Arc<Category>> = self
+            .all_categories
+            .iter()
+            .filter(|c| c.category_name.to_lowercase().contains(&query))
+            .collect();
+        match self.get_selected_account()?.category_sort_order {
+            crate::config::CategorySortOrder::Alphabetical => {
+                cats.sort_by(|a, b| a.category_name.cmp(&b.category_name))
+            }
+            crate::config::CategorySortOrder::ZtoA => {
+                cats.sort_by(|a, b| b.category_name.cmp(&a.category_name))
+            }
+            _ => {}
+        }
+        cats.get(self.category_mgmt.list_state.selected()?)
+            .map(|c| Arc::clone(c))
+    }
+
+    /// Whether the stream is a radio station: the provider lists it as one, or
+    /// its category is radio
+    pub fn is_radio_stream(&self, stream: &Stream) -> bool {
+        if stream.stream_type.eq_ignore_ascii_case("radio_streams") {
+            return true;
+        }
+        let Some(category_id) = stream.category_id.as_deref() else {
+            return false;
+        };
+        self.all_categories
+            .iter()
+            .find(|c| c.category_id == category_id)
+            .is_some_and(|c| self.is_radio_category(c))
+    }
+
+    /// Whether a channel should play without video
+    pub fn wants_audio_only(&self, stream: &Stream) -> bool {
+        self.audio_only || self.is_radio_stream(stream)
+    }
+
+    /// Engine or custom profile name of the configured player
+    pub fn player_label(&self) -> &str {
+        match (
+            self.config.preferred_player,
+            self.config.active_player_profile(),
+        ) {
+            (crate::config::PlayerEngine::Custom, Some(profile)) => profile.name.as_str(),
+            (engine, _) => engine.display_name(),
+        }
+    }
+
+    /// Live channel by id, from the loaded lists
+    pub fn find_live_stream(&self, stream_id: &str) -> Option<&Arc<Stream>> {
+        self.global_all_streams
+            .iter()
+            .chain(&self.all_streams)
+            .chain(&self.zap_playlist)
+            .find(|s| crate::api::get_id_str(&s.stream_id) == stream_id)
+    }
+
+    /// Name of the active account, or empty when none is selected
+    pub fn active_account_name(&self) -> String {
+        self.config
+            .accounts
+            .get(self.session.selected_account_index)
+            .map(|a| a.name.clone())
+            .unwrap_or_default()
+    }
+
+    /// `stream` on the active account, as about to be played
+    pub fn played_stream(&self, stream: &Stream) -> PlayedStream {
+        PlayedStream {
+            stream_id: crate::api::get_id_str(&stream.stream_id),
+            name: stream.name.clone(),
+            category_id: stream.category_id.clone(),
+            account: self.active_account_name(),
+            audio_only: self.wants_audio_only(stream),
+        }
+    }
+
+    /// An episode of the focused series, as about to be played
+    pub fn played_episode(&self, episode: &crate::api::SeriesEpisode) -> PlayedStream {
+        let series = self.series_streams.get(self.selected_series_stream_index);
+        PlayedStream {
+            stream_id: episode
+                .id
+                .as_ref()
+                .map(crate::api::get_id_str)
+                .unwrap_or_default(),
+            name: episode
+                .title
+                .clone()
+                .or_else(|| series.map(|s| s.name.clone()))
+                .unwrap_or_default(),
+            category_id: series.and_then(|s| s.category_id.clone()),
+            account: self.active_account_name(),
+            audio_only: self.audio_only,
+        }
+    }
+
+    /// Start recording a viewing session for what just started playing,
+    /// closing the previous one
+    pub fn begin_viewing(&mut self, stream: &PlayedStream) {
+        self.end_viewing();
+        let category = stream.category_id.as_deref().and_then(|id| {
+            self.all_categories
+                .iter()
+                .chain(&self.all_vod_categories)
+                .chain(&self.all_series_categories)
+                .find(|c| c.category_id == id)
+                .map(|c| c.category_name.clone())
+        });
+        let league =
+            crate::history::detect_league(&[category.as_deref().unwrap_or_default(), &stream.name]);
+        let mut engine = self.player_label().to_string();
+        if stream.audio_only {
+            engine.push_str(" (audio only)");
+        }
+        let now = chrono::Utc::now();
+        self.viewing = Some(crate::history::ViewingSession {
+            stream_id: stream.stream_id.clone(),
+            channel: stream.name.clone(),
+            account: stream.account.clone(),
+            category,
+            league,
+            engine,
+            started_at: now,
+            ended_at: now,
+        });
+    }
+
+    /// Close the current viewing session and add it to the history
+    pub fn end_viewing(&mut self) {
+        if let Some(mut session) = self.viewing.take() {
+            session.ended_at = chrono::Utc::now();
+            if self.watch_history.record(session) {
+                #[cfg(not(target_arch = "wasm32"))]
+                let _ = self.watch_history.save();
+            }
+        }
+    }
+
+    /// Tell the user when saved passwords could not be moved out of the config file
+    pub fn warn_unsealed_passwords(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(warning) = self.config.unsealed_passwords_warning() {
+            self.push_toast(warning);
+        }
+    }
+
+    /// Show a short notice in the corner of the screen
+    pub fn push_toast(&mut self, message: String) {
+        self.toasts.push((message, self.session.loading_tick));
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// Hold an action until the provider is reachable again. A new play replaces
+    /// an earlier one; a refresh is only queued once.
+    pub fn queue_offline(&mut self, action: OfflineAction) {
+        let message = match &action {
+            OfflineAction::Play(stream) => {
+                self.offline_queue
+                    .retain(|a| !matches!(a, OfflineAction::Play(_)));
+                format!("Offline · {} opens when the provider is back", stream.name)
+            }
+            OfflineAction::Refresh => {
+                self.offline_queue
+                    .retain(|a| !matches!(a, OfflineAction::Refresh));
+                "Offline · the playlist refreshes when the provider is back".to_string()
+            }
+        };
+        self.offline_queue.push(action);
+        self.push_toast(message);
+    }
+
+    /// Leave offline mode without reconnecting (account switched or removed)
+    pub fn leave_offline(&mut self) {
+        self.session.offline = false;
+        self.offline_queue.clear();
+    }
+
+    /// Take a cast status poll: keep it for the cast bar, or end the session when
+    /// the device was switched off, taken over or stopped responding
+    pub fn apply_cast_status(&mut self, result: Result<CastStatus, String>) {
+        self.cast_polling = false;
+        let Some(device) = self
+            .cast_session
+            .as_ref()
+            .map(|s| s.device_name().to_string())
+        else {
+            return;
+        };
+        match result {
+            Ok(status) => {
+                self.cast_poll_failures = 0;
+                match status.end_reason(self.cast_status.as_ref(), &device) {
+                    Some(reason) => self.end_cast(reason),
+                    None => self.cast_status = Some(status),
+                }
+            }
+            Err(e) => {
+                self.cast_poll_failures += 1;
+                if self.cast_poll_failures >= MAX_CAST_POLL_FAILURES {
+                    self.end_cast(format!("Lost connection to {}: {}", device, e));
+                }
+            }
+        }
+    }
+
+    /// Forget the cast session and say why
+    pub fn end_cast(&mut self, reason: String) {
+        self.cast_session = None;
+        self.cast_status = None;
+        self.cast_poll_failures = 0;
+        self.cast_last_action = None;
+        self.push_toast(reason);
+    }
+
+    /// Drop toasts that have been on screen long enough
+    pub fn expire_toasts(&mut self) {
+        let now = self.session.loading_tick;
+        self.toasts
+            .retain(|(_, shown_at)| now.wrapping_sub(*shown_at) < TOAST_TICKS);
+    }
+
+    /// Arm the stall watchdog for the live channel that just started playing.
+    /// `None`, a VOD item or a disabled watchdog disarms it.
+    pub fn arm_watchdog(&mut self, stream_id: Option<&str>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(task) = self.watchdog_task.take() {
+            task.abort();
+        }
+        self.pending_watchdog = stream_id.and_then(|id| self.watchdog_plan(id));
+    }
+
+    fn watchdog_plan(&self, stream_id: &str) -> Option<crate::watchdog::RecoveryPlan> {
+        if self.config.watchdog_stall_secs == 0 {
+            return None;
+        }
+        let client = self.session.current_client.as_ref()?;
+        let live = if self.global_all_streams.is_empty() {
+            &self.all_streams
+        } else {
+            &self.global_all_streams
+        };
+        let stream = self.find_live_stream(stream_id)?;
+        if stream.stream_type == "movie" || stream.stream_type == "series" {
+            return None;
+        }
+
+        let formats = ["m3u8", "mp4"]
+            .iter()
+            .map(|ext| (ext.to_string(), client.get_stream_url(stream_id, ext)))
+            .collect();
+        let alternates = crate::watchdog::alternate_feeds(live, stream)
+            .iter()
+            .map(|s| {
+                let id = crate::api::get_id_str(&s.stream_id);
+                let url = client.get_stream_url(&id, "ts");
+                (id, s.name.clone(), url)
+            })
+            .collect();
+        Some(crate::watchdog::RecoveryPlan::new(
+            stream.name.clone(),
+            client.get_stream_url(stream_id, "ts"),
+            formats,
+            alternates,
+        ))
+    }
+
+    /// The focused item on the current screen
+    pub fn focused_playable_stream(&self) -> Option<&Arc<Stream>> {
+        match self.current_screen {
+            CurrentScreen::Categories | CurrentScreen::Streams => self.get_selected_stream(),
+            CurrentScreen::VodStreams => self.vod_streams.get(self.selected_vod_stream_index),
+            CurrentScreen::SeriesStreams => {
+                self.series_streams.get(self.selected_series_stream_index)
+            }
+            CurrentScreen::GlobalSearch => {
+                self.global_search_results.get(self.selected_stream_index)
+            }
+            _ => None,
+        }
+    }
+
     pub fn next_timezone(&mut self) {
         let len = self.timezone_list.len();
         if len > 0 {
@@ -1065,6 +2411,7 @@ impl App {
             // Switched to a different account — invalidate cached session
             // so re-entry will authenticate with the new account's credentials.
             self.session.current_client = None;
+            self.leave_offline();
         }
     }
 
@@ -1078,6 +2425,7 @@ impl App {
         );
         if self.session.selected_account_index != prev {
             self.session.current_client = None;
+            self.leave_offline();
         }
     }
 
@@ -2171,33 +3519,14 @@ impl App {
                 }
             }
             CurrentScreen::GlobalSearch => {
-                let results = if query.is_empty() {
-                    Vec::new()
-                } else {
-                    // Multi-pass prioritized search
-                    let use_fuzzy = query.len() >= 3;
-                    let mut hits: Vec<Arc<Stream>> = self
-                        .global_all_streams
-                        .par_iter()
-                        .filter(|s| {
-                            s.search_name.contains(&query)
-                                || (use_fuzzy && s.fuzzy_match(&query, 70))
-                        })
-                        .chain(self.global_all_vod_streams.par_iter().filter(|s| {
-                            s.search_name.contains(&query)
-                                || (use_fuzzy && s.fuzzy_match(&query, 70))
-                        }))
-                        .chain(self.global_all_series_streams.par_iter().filter(|s| {
-                            s.search_name.contains(&query)
-                                || (use_fuzzy && s.fuzzy_match(&query, 70))
-                        }))
-                        .cloned()
-                        .collect();
-
-                    // Prioritize exact substring matches
-                    hits.sort_by_cached_key(|s| !s.search_name.contains(&query));
-                    hits.into_iter().take(100).collect()
-                };
+                let results = crate::search_index::search(
+                    self.search_index.as_deref(),
+                    self.catalog_generation,
+                    &self.global_all_streams,
+                    &self.global_all_vod_streams,
+                    &self.global_all_series_streams,
+                    &query,
+                );
 
                 self.global_search_results = results;
                 App::pre_cache_parsed(
@@ -2367,11 +3696,13 @@ impl App {
         );
     }
 
-    pub fn save_account(&mut self) {
+    /// Save the account form as a new account, or over the one being edited.
+    /// A rejected account leaves the form filled in and returns the reason.
+    pub fn save_account(&mut self) -> Result<(), String> {
         use crate::config::Account;
 
-        let name = self.input_name.value().to_string();
-        let url = self.input_url.value().to_string();
+        let name = self.input_name.value().trim().to_string();
+        let url = self.input_url.value().trim().to_string();
         let user = self.input_username.value().to_string();
         let pass = self.input_password.value().to_string();
         let epg = self.input_epg_url.value().to_string();
@@ -2384,41 +3715,44 @@ impl App {
             Some(tz_str)
         };
 
-        if !name.is_empty() && !url.is_empty() {
-            // Sanitize URL
-            let mut final_url = url.trim().to_string();
-            if !final_url.starts_with("http://") && !final_url.starts_with("https://") {
-                final_url = format!("http://{}", final_url);
-            }
-            if final_url.ends_with('/') {
-                final_url.pop();
-            }
+        if name.is_empty() || url.is_empty() {
+            return Err("Name and URL required".to_string());
+        }
 
-            let acc = Account {
-                name,
-                base_url: final_url.clone(),
-                username: user.clone(),
-                password: pass.clone(),
-                account_type: if Self::is_m3u_url(&final_url, &user, &pass) {
-                    crate::config::AccountType::M3uUrl
-                } else {
-                    crate::config::AccountType::Xtream
-                },
-                epg_url: epg_opt,
-                last_refreshed: None,
-                total_channels: None,
-                total_movies: None,
-                total_series: None,
-                server_timezone: tz_opt,
-                hidden_categories: std::collections::HashSet::new(),
-                category_sort_order: crate::config::CategorySortOrder::Default,
-            };
+        // Sanitize URL
+        let mut final_url = url;
+        if !final_url.starts_with("http://") && !final_url.starts_with("https://") {
+            final_url = format!("http://{}", final_url);
+        }
+        if final_url.ends_with('/') {
+            final_url.pop();
+        }
 
-            if let Some(idx) = self.editing_account_index {
-                self.config.update_account(idx, acc);
+        let acc = Account {
+            name,
+            base_url: final_url.clone(),
+            username: user.clone(),
+            password: pass.clone(),
+            credential: None,
+            account_type: if Self::is_m3u_url(&final_url, &user, &pass) {
+                crate::config::AccountType::M3uUrl
             } else {
-                self.config.add_account(acc);
-            }
+                crate::config::AccountType::Xtream
+            },
+            epg_url: epg_opt,
+            last_refreshed: None,
+            total_channels: None,
+            total_movies: None,
+            total_series: None,
+            server_timezone: tz_opt,
+            hidden_categories: std::collections::HashSet::new(),
+            radio_overrides: std::collections::HashMap::new(),
+            category_sort_order: crate::config::CategorySortOrder::Default,
+        };
+
+        match self.editing_account_index {
+            Some(idx) => self.config.update_account(idx, acc)?,
+            None => self.config.add_account(acc)?,
         }
 
         // Reset inputs
@@ -2430,6 +3764,8 @@ impl App {
         self.input_server_timezone = Input::default();
         self.editing_account_index = None;
         self.login_error = None;
+        self.warn_unsealed_passwords();
+        Ok(())
     }
 
     /// Detect if a URL is an M3U playlist URL rather than an Xtream Codes server
@@ -2786,7 +4122,7 @@ mod tests {
 
     #[test]
     fn test_series_navigation_flow() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
 
         // 1. Initial State
         assert_eq!(app.current_screen, CurrentScreen::Home);
@@ -2799,7 +4135,7 @@ mod tests {
 
         // If accounts are empty (default), nothing happens or it tries to init.
         // We need to inject a dummy account to test the "if accounts not empty" branch.
-        // However, App::new() loads from config.
+        // App::with_storage starts from an empty in-memory config.
         // Let's assume for this test we manually inject an account.
         use crate::config::Account;
         app.config.accounts.push(Account {
@@ -2807,6 +4143,7 @@ mod tests {
             base_url: "http://test".to_string(),
             username: "u".to_string(),
             password: "p".to_string(),
+            credential: None,
             epg_url: None,
             last_refreshed: None,
             total_channels: None,
@@ -2815,6 +4152,7 @@ mod tests {
             server_timezone: None,
             account_type: Default::default(),
             hidden_categories: std::collections::HashSet::new(),
+            radio_overrides: std::collections::HashMap::new(),
             category_sort_order: crate::config::CategorySortOrder::Default,
         });
 
@@ -2864,7 +4202,7 @@ mod tests {
     /// and the search_name field is used for matching.
     #[test]
     fn test_all_channels_msnbc_search() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
 
         // Simulate TotalChannelsLoaded: populate global_all_streams with test data
         let msnbc = Stream {
@@ -2943,7 +4281,7 @@ mod tests {
     /// Test that merica mode filters out non-American streams from search results
     #[test]
     fn test_merica_mode_filters_search() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         app.config.playlist_mode = crate::config::PlaylistMode::Merica;
 
         let msnbc = Stream {
@@ -2991,7 +4329,7 @@ mod tests {
 
     #[test]
     fn test_background_load_view_consistency() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
 
         // 1. Setup Categories
         app.categories = vec![
@@ -3062,7 +4400,7 @@ mod tests {
 
     #[test]
     fn test_vod_non_all_category_queues_specific_lazy_load() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
 
         app.current_screen = CurrentScreen::VodStreams;
         app.active_pane = Pane::Streams;
@@ -3112,7 +4450,7 @@ mod tests {
 
     #[test]
     fn test_categories_grid_navigation() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         // Mock some categories
         for i in 0..10 {
             app.categories.push(Arc::new(Category {
@@ -3162,7 +4500,7 @@ mod tests {
 
     #[test]
     fn test_page_navigation_live_streams() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         app.area_streams = Rect::new(0, 0, 50, 22);
 
         for i in 0..100 {
@@ -3236,7 +4574,7 @@ mod tests {
 
     #[test]
     fn test_page_navigation_vod_streams() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         app.area_streams = Rect::new(0, 0, 50, 22);
 
         for i in 0..50 {
@@ -3267,7 +4605,7 @@ mod tests {
 
     #[test]
     fn test_page_navigation_series_streams() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         app.area_streams = Rect::new(0, 0, 50, 22);
 
         for i in 0..50 {
@@ -3295,7 +4633,7 @@ mod tests {
 
     #[test]
     fn test_page_navigation_series_episodes() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         app.area_episodes = Rect::new(0, 0, 30, 22);
 
         for i in 0..30 {
@@ -3324,7 +4662,7 @@ mod tests {
 
     #[test]
     fn test_page_navigation_categories() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         app.area_categories = Rect::new(0, 0, 30, 22);
         app.grid_cols = 1;
 
@@ -3358,7 +4696,7 @@ mod tests {
 
     #[test]
     fn test_page_navigation_global_search() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         app.area_streams = Rect::new(0, 0, 50, 22);
 
         for i in 0..80 {
@@ -3385,7 +4723,7 @@ mod tests {
 
     #[test]
     fn test_page_size_dynamic_viewport() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
 
         app.area_categories = Rect::new(0, 0, 30, 10);
         app.area_streams = Rect::new(0, 0, 50, 22);
@@ -3398,7 +4736,7 @@ mod tests {
 
     #[test]
     fn test_jump_list_clamps_at_boundaries() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
 
         app.streams.push(Arc::new(Stream {
             name: "Only".to_string(),
@@ -3430,7 +4768,7 @@ mod tests {
 
     #[test]
     fn test_jump_list_empty_lists() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         app.area_streams = Rect::new(0, 0, 50, 22);
 
         app.page_down_stream();
@@ -3448,7 +4786,7 @@ mod tests {
 
     #[test]
     fn test_category_page_navigation_multi_screen() {
-        let mut app = App::new();
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
         app.area_categories = Rect::new(0, 0, 30, 22);
         app.grid_cols = 1;
 
@@ -3494,4 +4832,29 @@ mod tests {
         app.jump_to_category_top();
         assert_eq!(app.selected_series_category_index, 0);
     }
+
+    #[test]
+    fn offline_queue_keeps_latest_play_and_one_refresh() {
+        let mut app = App::with_storage(crate::storage::ConfigStorage::memory(None));
+        let stream = |name: &str| {
+            Arc::new(Stream {
+                name: name.to_string(),
+                ..Default::default()
+            })
+        };
+        app.queue_offline(OfflineAction::Play(stream("CNN")));
+        app.queue_offline(OfflineAction::Refresh);
+        app.queue_offline(OfflineAction::Play(stream("BBC One")));
+        app.queue_offline(OfflineAction::Refresh);
+        assert_eq!(app.offline_queue.len(), 2);
+        assert!(app
+            .offline_queue
+            .iter()
+            .any(|a| matches!(a, OfflineAction::Play(s) if s.name == "BBC One")));
+
+        app.session.offline = true;
+        app.leave_offline();
+        assert!(!app.session.offline);
+        assert!(app.offline_queue.is_empty());
+    }
 }
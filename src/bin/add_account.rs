@@ -1,21 +1,8 @@
-use directories::ProjectDirs;
 use matrix_iptv_lib::config::{Account, AccountType, AppConfig, DnsProvider};
-use std::fs;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let proj_dirs =
-        ProjectDirs::from("com", "vibecoding", "vibe-iptv").ok_or("Could not find project dirs")?;
-    let config_dir = proj_dirs.config_dir();
-    let config_path = config_dir.join("config.json");
-
-    fs::create_dir_all(config_dir)?;
-
-    let mut config = if config_path.exists() {
-        let content = fs::read_to_string(&config_path)?;
-        serde_json::from_str(&content)?
-    } else {
-        AppConfig::default()
-    };
+    // Loading moves any plaintext passwords into the credential vault
+    let mut config = AppConfig::load().map_err(|e| e.to_string())?;
 
     // Add new account
     let new_account = Account {
@@ -23,6 +10,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         base_url: "http://zfruvync.rmtil.com:8080".to_string(),
         username: "PE1S9S8U".to_string(),
         password: "11EZZUMW".to_string(),
+        credential: None,
         account_type: AccountType::Xtream,
         epg_url: None,
         last_refreshed: None,
@@ -31,19 +19,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         total_series: None,
         server_timezone: None,
         hidden_categories: std::collections::HashSet::new(),
+        radio_overrides: std::collections::HashMap::new(),
         category_sort_order: matrix_iptv_lib::config::CategorySortOrder::Default,
     };
 
-    // Remove if exists
-    config.accounts.retain(|a| a.name != new_account.name);
-    config.accounts.push(new_account);
-
     // Set DNS to system (for best compatibility with 8080 port bypass)
     config.dns_provider = DnsProvider::System;
 
-    let json = serde_json::to_string_pretty(&config)?;
-    fs::write(&config_path, json)?;
-
-    println!("Successfully added account to {:?}", config_path);
+    // Replace if exists. Adding stores the password in the vault and saves;
+    // config.json only gets a reference
+    if let Some(index) = config
+        .accounts
+        .iter()
+        .position(|a| a.name == new_account.name)
+    {
+        config.remove_account(index);
+    }
+    config.add_account(new_account)?;
+
+    println!(
+        "Successfully added account to {}",
+        config.storage().describe()
+    );
     Ok(())
 }
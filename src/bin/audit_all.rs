@@ -31,7 +31,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         match XtreamClient::new_with_doh(
             acc.base_url.clone(),
             acc.username.clone(),
-            acc.password.clone(),
+            acc.secret().to_string(),
             DnsProvider::System,
         )
         .await
@@ -1,12 +1,10 @@
-use directories::ProjectDirs;
-
 fn main() {
-    if let Some(proj_dirs) = ProjectDirs::from("com", "vibecoding", "vibe-iptv") {
-        println!("Config dir: {:?}", proj_dirs.config_dir());
-        println!(
-            "Config file: {:?}",
-            proj_dirs.config_dir().join("config.json")
-        );
+    // Honors MATRIX_IPTV_CONFIG, like the app
+    if let Some(paths) = matrix_iptv_lib::storage::paths() {
+        println!("Config dir: {:?}", paths.config_dir);
+        println!("Config file: {:?}", paths.config_file);
+        println!("Data dir: {:?}", paths.data_dir);
+        println!("Cache dir: {:?}", paths.cache_dir);
     } else {
         println!("Could not determine project paths");
     }
@@ -17,7 +17,7 @@ async fn main() -> Result<(), anyhow::Error> {
             (
                 acc.base_url.clone(),
                 acc.username.clone(),
-                acc.password.clone(),
+                acc.secret().to_string(),
                 acc.name.clone(),
             )
         } else {
@@ -9,7 +9,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = XtreamClient::new_with_doh(
         account.base_url.clone(),
         account.username.clone(),
-        account.password.clone(),
+        account.secret().to_string(),
         config.dns_provider,
     )
     .await?;
@@ -57,7 +57,7 @@ async fn run_qa() {
         let client_res = matrix_iptv_lib::api::XtreamClient::new_with_doh(
             base_url.clone(),
             acc.username.clone(),
-            acc.password.clone(),
+            acc.secret().to_string(),
             app.config.dns_provider,
         )
         .await;
@@ -193,7 +193,11 @@ async fn run_qa() {
 
                             let _expected_url = format!(
                                 "{}/series/{}/{}/{}.{}",
-                                base_clean, acc.username, acc.password, stream_id, ext
+                                base_clean,
+                                acc.username,
+                                *acc.secret(),
+                                stream_id,
+                                ext
                             );
                             // series usually uses /series/ but client has get_stream_url (live) and get_vod_url (movie)
                             // We should check if we have a get_series_url.
@@ -201,7 +205,11 @@ async fn run_qa() {
                             let gen_url = client.get_vod_url(stream_id, ext);
                             let expected_vod = format!(
                                 "{}/movie/{}/{}/{}.{}",
-                                base_clean, acc.username, acc.password, stream_id, ext
+                                base_clean,
+                                acc.username,
+                                *acc.secret(),
+                                stream_id,
+                                ext
                             );
 
                             if gen_url == expected_vod {
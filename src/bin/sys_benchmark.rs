@@ -2,8 +2,10 @@ use matrix_iptv_lib::api::{Category, Stream};
 use matrix_iptv_lib::config::ProcessingMode;
 use matrix_iptv_lib::flex_id::FlexId;
 use matrix_iptv_lib::preprocessing::{preprocess_categories, preprocess_streams};
+use matrix_iptv_lib::search_index::{search, SearchIndex};
 use std::collections::HashSet;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[tokio::main]
 async fn main() {
@@ -98,6 +100,92 @@ async fn main() {
     } else {
         println!("⚠️ PERFORMANCE WARNING: Preprocessing exceeded 800ms threshold.");
     }
+
+    search_benchmark();
+}
+
+/// 4. Global search keystroke latency on a 200k-item fixture, full scan vs prebuilt index
+fn search_benchmark() {
+    const WORDS: [&str; 16] = [
+        "breaking", "news", "sports", "night", "world", "cup", "house", "dragon", "star", "wars",
+        "family", "kitchen", "doctor", "ocean", "crime", "bad",
+    ];
+    println!("\n=== Global Search Benchmark ===");
+    println!("Target Scale: 200,000 Items (100k live, 60k movies, 40k series)\n");
+
+    let fixture = |count: usize, first_id: usize, kind: &str| -> Vec<Arc<Stream>> {
+        (0..count)
+            .map(|i| {
+                let n = first_id + i;
+                let name = format!(
+                    "{} {} {} {}",
+                    WORDS[n % 16],
+                    WORDS[(n / 16) % 16],
+                    WORDS[(n / 256) % 16],
+                    n
+                );
+                Arc::new(Stream {
+                    search_name: name.to_lowercase(),
+                    name,
+                    stream_id: FlexId::from_number(n as i64),
+                    stream_type: kind.to_string(),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    };
+    let live = fixture(100_000, 0, "live");
+    let vod = fixture(60_000, 100_000, "movie");
+    let series = fixture(40_000, 160_000, "series");
+
+    println!("Building trigram index...");
+    let start_build = Instant::now();
+    let index = SearchIndex::build(&live, &vod, &series);
+    println!(
+        "  >> Indexed 200,000 items in: {:?}\n",
+        start_build.elapsed()
+    );
+
+    let query = "breaking bad";
+    println!("Typing {:?} one keystroke at a time...", query);
+    println!(
+        "  {:<14} {:>12} {:>12} {:>8}",
+        "query", "full scan", "indexed", "hits"
+    );
+    let mut worst_scan = Duration::ZERO;
+    let mut worst_indexed = Duration::ZERO;
+    for end in 1..=query.len() {
+        let typed = &query[..end];
+
+        let start_scan = Instant::now();
+        let scanned = search(None, 0, &live, &vod, &series, typed);
+        let scan = start_scan.elapsed();
+
+        let start_indexed = Instant::now();
+        let indexed = search(Some(&index), 0, &live, &vod, &series, typed);
+        let indexed_time = start_indexed.elapsed();
+
+        worst_scan = worst_scan.max(scan);
+        worst_indexed = worst_indexed.max(indexed_time);
+        println!(
+            "  {:<14} {:>12?} {:>12?} {:>8}",
+            format!("{:?}", typed),
+            scan,
+            indexed_time,
+            indexed.len().max(scanned.len())
+        );
+    }
+    println!(
+        "\n  >> Worst keystroke: full scan {:?}, indexed {:?}",
+        worst_scan, worst_indexed
+    );
+
+    // Benchmark target: every keystroke answered within one 60Hz frame
+    if worst_indexed.as_millis() < 16 {
+        println!("✅ PERFORMANCE TARGET MET: Indexed search sub-16ms per keystroke at 200k items.");
+    } else {
+        println!("⚠️ PERFORMANCE WARNING: Indexed search exceeded 16ms per keystroke.");
+    }
 }
 
 fn num_cpus_count() -> usize {
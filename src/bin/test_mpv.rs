@@ -13,7 +13,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let client = XtreamClient::new(
         account.base_url.clone(),
         account.username.clone(),
-        account.password.clone(),
+        account.secret().to_string(),
     );
     let _ = client.authenticate().await?;
 
@@ -54,7 +54,7 @@ async fn main() {
     let client = XtreamClient::new(
         account.base_url.clone(),
         account.username.clone(),
-        account.password.clone(),
+        account.secret().to_string(),
     );
 
     match client.authenticate().await {
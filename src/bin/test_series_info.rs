@@ -9,7 +9,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = XtreamClient::new_with_doh(
         account.base_url.clone(),
         account.username.clone(),
-        account.password.clone(),
+        account.secret().to_string(),
         config.dns_provider,
     )
     .await?;
@@ -40,7 +40,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             // Make direct API call to get_series_info
             let url = format!(
                 "{}/player_api.php?username={}&password={}&action=get_series_info&series_id={}",
-                account.base_url, account.username, account.password, series_id
+                account.base_url,
+                account.username,
+                *account.secret(),
+                series_id
             );
 
             println!("URL: {}", url);
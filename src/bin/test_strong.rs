@@ -22,7 +22,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let client = XtreamClient::new_with_doh(
         strong_account.base_url.clone(),
         strong_account.username.clone(),
-        strong_account.password.clone(),
+        strong_account.secret().to_string(),
         config.dns_provider,
     )
     .await?;
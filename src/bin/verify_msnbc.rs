@@ -18,7 +18,7 @@ async fn main() -> Result<(), anyhow::Error> {
     let client = XtreamClient::new(
         base_url_8080.clone(),
         acc.username.clone(),
-        acc.password.clone(),
+        acc.secret().to_string(),
     );
 
     let streams = client.get_live_streams("0", None).await?;
@@ -43,7 +43,10 @@ async fn main() -> Result<(), anyhow::Error> {
     let target = msnbc_streams[0];
     let stream_url = format!(
         "{}/live/{}/{}/{}.ts",
-        base_url_8080, acc.username, acc.password, target.stream_id
+        base_url_8080,
+        acc.username,
+        *acc.secret(),
+        target.stream_id
     );
 
     let client_http = reqwest::Client::builder()
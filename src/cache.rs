@@ -1,26 +1,142 @@
 //! Local catalog cache for instant cold starts.
 //!
-//! This module provides binary serialization of catalog data using bincode,
-//! enabling sub-200ms cold starts from cached data while refreshing in background.
+//! Each account gets a directory of separately loadable sections (live, VOD,
+//! series, EPG, parsed metadata and the search index). Every section is
+//! gzip-compressed JSON (bincode for the search index) with its CRC32 recorded
+//! in `manifest.json`, so a corrupt section is dropped on its own and a refresh
+//! of one section rewrites only that file and the manifest. Older on-disk
+//! formats are migrated on load rather than wiped.
 
-use crate::api::{Category, Stream};
+use crate::api::{get_id_str, Category, Stream};
 use crate::config::ProcessingMode;
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-/// Cache version — increment when CachedCatalog struct changes to auto-invalidate old caches
-const CACHE_VERSION: u32 = 1;
+/// Manifest format version — add a step to `MIGRATIONS` when bumping it
+pub const CACHE_VERSION: u32 = 3;
 
-/// On-disk catalog cache for a single account
-#[derive(Serialize, Deserialize)]
-pub struct CachedCatalog {
+/// EPG titles are "now playing" lines, so they are not restored once this old
+const EPG_MAX_AGE_SECS: u64 = 3600;
+
+/// A separately stored part of the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheSection {
+    Live,
+    Vod,
+    Series,
+    Epg,
+    Parsed,
+    Search,
+}
+
+impl CacheSection {
+    fn file_name(&self) -> &'static str {
+        match self {
+            CacheSection::Live => "live.json.gz",
+            CacheSection::Vod => "vod.json.gz",
+            CacheSection::Series => "series.json.gz",
+            CacheSection::Epg => "epg.json.gz",
+            CacheSection::Parsed => "parsed.json.gz",
+            CacheSection::Search => "search.bin.gz",
+        }
+    }
+
+    /// Derived sections are stored as bincode and rebuilt rather than migrated
+    fn is_binary(&self) -> bool {
+        *self == CacheSection::Search
+    }
+
+    /// Payload format version — add a step to `SECTION_MIGRATIONS` when bumping it
+    fn version(&self) -> u32 {
+        match self {
+            // v2: one map per catalog section, since live, VOD and series ids overlap
+            CacheSection::Parsed => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// Categories and streams of one content type
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CatalogSection {
+    pub categories: Vec<Category>,
+    pub streams: Vec<Stream>,
+}
+
+/// Fields preprocessing derives for a stream that are not part of its JSON
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParsedMeta {
+    pub clean_name: String,
+    pub search_name: String,
+    #[serde(default)]
+    pub is_american: bool,
+    #[serde(default)]
+    pub is_english: bool,
+}
+
+/// Payload of the Parsed section: stream id -> metadata, per catalog section
+pub type ParsedSections = BTreeMap<CacheSection, HashMap<String, ParsedMeta>>;
+
+impl ParsedMeta {
+    pub fn of(stream: &Stream) -> Self {
+        Self {
+            clean_name: stream.clean_name.clone(),
+            search_name: stream.search_name.clone(),
+            is_american: stream.is_american,
+            is_english: stream.is_english,
+        }
+    }
+
+    fn apply(&self, stream: &mut Stream) {
+        stream.clean_name = self.clean_name.clone();
+        stream.search_name = self.search_name.clone();
+        stream.is_american = self.is_american;
+        stream.is_english = self.is_english;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionEntry {
+    /// Payload format version
     pub version: u32,
-    pub cached_at: u64, // Unix timestamp (seconds)
+    pub cached_at: u64,
+    /// CRC32 of the compressed file
+    pub checksum: u32,
+    pub bytes: u64,
+}
+
+/// Account-level details written with every section
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheHeader {
     pub account_name: String,
     pub account_url: String, // To detect if provider changed
+    // Sections are pre-preprocessed (already filtered by these modes)
+    pub processing_modes: Vec<ProcessingMode>,
+    pub total_channels: usize,
+    pub total_movies: usize,
+    pub total_series: usize,
+    // Category channel counts (category_id -> count)
+    pub category_counts: Vec<(String, usize)>,
+}
 
-    // Pre-preprocessed data (already filtered by active modes at cache time)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CacheManifest {
+    pub version: u32,
+    #[serde(flatten)]
+    pub header: CacheHeader,
+    #[serde(default)]
+    pub sections: BTreeMap<CacheSection, SectionEntry>,
+}
+
+/// On-disk catalog cache for a single account, as loaded
+#[derive(Default)]
+pub struct CachedCatalog {
+    pub cached_at: u64, // Oldest catalog section (Unix seconds)
+    pub account_name: String,
+    pub account_url: String,
     pub processing_modes: Vec<ProcessingMode>,
 
     // Live
@@ -39,50 +155,187 @@ pub struct CachedCatalog {
     pub total_channels: usize,
     pub total_movies: usize,
     pub total_series: usize,
-
-    // Category channel counts (category_id -> count)
     pub category_counts: Vec<(String, usize)>,
+
+    /// Current programme titles, when the EPG section is recent enough
+    pub epg_cache: HashMap<String, String>,
+    /// Global search index; check `SearchIndex::matches` before using it
+    pub search_index: Option<crate::search_index::SearchIndex>,
+    /// Sections that were present and intact
+    pub sections: Vec<CacheSection>,
+}
+
+/// Upgrades a cache directory written by an older manifest version to the next one
+type Migration = fn(&Path, &str) -> Result<(), anyhow::Error>;
+
+/// Manifest migrations, keyed by the version they upgrade from. Versions 1 and
+/// 2 never wrote a manifest (see `migrate_single_file`, run by `load_manifest`
+/// when there is none), so the first step will upgrade from 3.
+const MIGRATIONS: &[(u32, Migration)] = &[];
+
+/// Upgrades a section payload from one version to the next
+type SectionMigration = fn(&mut serde_json::Value);
+
+/// Section payload migrations, keyed by section and the version they upgrade from
+const SECTION_MIGRATIONS: &[(CacheSection, u32, SectionMigration)] =
+    &[(CacheSection::Parsed, 1, split_parsed_by_section)];
+
+/// Parsed v1 was one map for all three catalog sections. Which section an id
+/// came from is not recorded, so every section gets the whole map; each is
+/// replaced by exact data the next time that section is saved.
+fn split_parsed_by_section(value: &mut serde_json::Value) {
+    let all = value.take();
+    *value = serde_json::json!({ "live": all, "vod": all, "series": all });
+}
+
+/// Versions 1 and 2 kept the whole catalog in one bincode file next to the
+/// account directories. Bincode cannot decode the self-describing provider IDs,
+/// so those files never loaded; they are removed and the sections start empty.
+fn migrate_single_file(dir: &Path, account_name: &str) -> Result<(), anyhow::Error> {
+    if let Some(parent) = dir.parent() {
+        let legacy = parent.join(format!("{}.bin", simple_hash(account_name)));
+        if legacy.exists() {
+            std::fs::remove_file(legacy)?;
+        }
+    }
+    Ok(())
+}
+
+/// Run the payload migrations from `from` up to `to`
+fn migrate_section(
+    migrations: &[(CacheSection, u32, SectionMigration)],
+    section: CacheSection,
+    from: u32,
+    to: u32,
+    value: &mut serde_json::Value,
+) -> Result<(), anyhow::Error> {
+    for version in from..to {
+        let step = migrations
+            .iter()
+            .find(|(s, v, _)| *s == section && *v == version)
+            .ok_or_else(|| anyhow::anyhow!("No migration for {:?} v{}", section, version))?;
+        (step.2)(value);
+    }
+    Ok(())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn compress(json: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    use std::io::Write;
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    encoder.write_all(json)?;
+    Ok(encoder.finish()?)
 }
 
+fn decompress(data: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    use std::io::Read;
+    let mut json = Vec::new();
+    flate2::read::GzDecoder::new(data).read_to_end(&mut json)?;
+    Ok(json)
+}
+
+/// Serialises manifest read-modify-write cycles from concurrent section saves
+#[cfg(not(target_arch = "wasm32"))]
+static MANIFEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Serialises Parsed section updates, which each catalog section save makes
+#[cfg(not(target_arch = "wasm32"))]
+static PARSED_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
 impl CachedCatalog {
-    /// Returns the cache file path for a given account.
-    /// Path: <config_dir>/cache/<account_name_hash>.bin
+    /// Returns the cache directory for a given account.
+    /// Path: <cache_dir>/<account_name_hash>/
     #[cfg(not(target_arch = "wasm32"))]
     pub fn cache_path(account_name: &str) -> Option<PathBuf> {
-        use directories::ProjectDirs;
-        let proj = ProjectDirs::from("com", "vibecoding", "vibe-iptv")?;
-        let cache_dir = proj.cache_dir().to_path_buf();
-        std::fs::create_dir_all(&cache_dir).ok()?;
-
+        let paths = crate::storage::paths()?;
         // Hash the account name to avoid filesystem issues with special characters
-        let hash = simple_hash(account_name);
-        Some(cache_dir.join(format!("{}.bin", hash)))
+        let dir = paths.cache_dir.join(simple_hash(account_name).to_string());
+        std::fs::create_dir_all(&dir).ok()?;
+        Some(dir)
     }
 
-    /// Save catalog to disk. Non-blocking — call from a background task.
+    /// Write one section and update the manifest. Blocking — call from a background task.
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn save(&self) -> Result<(), anyhow::Error> {
-        let path = Self::cache_path(&self.account_name)
+    pub fn save_section<T: Serialize>(
+        header: &CacheHeader,
+        section: CacheSection,
+        payload: &T,
+    ) -> Result<(), anyhow::Error> {
+        let dir = Self::cache_path(&header.account_name)
             .ok_or_else(|| anyhow::anyhow!("Cannot determine cache directory"))?;
-        let encoded = bincode::serialize(self)?;
-        std::fs::write(&path, encoded)?;
-        Ok(())
+        write_section(&dir, header, section, payload, now_secs())
+    }
+
+    /// Write the Live, VOD or Series section and the parsed metadata of its
+    /// streams, replacing that section's metadata so removed streams drop out
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_catalog_section(
+        header: &CacheHeader,
+        section: CacheSection,
+        categories: Vec<Category>,
+        streams: Vec<Stream>,
+    ) -> Result<(), anyhow::Error> {
+        let dir = Self::cache_path(&header.account_name)
+            .ok_or_else(|| anyhow::anyhow!("Cannot determine cache directory"))?;
+        let now = now_secs();
+        let _guard = PARSED_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut parsed: ParsedSections =
+            read_section(&dir, CacheSection::Parsed).unwrap_or_default();
+        parsed.insert(
+            section,
+            streams
+                .iter()
+                .map(|s| (get_id_str(&s.stream_id), ParsedMeta::of(s)))
+                .collect(),
+        );
+        write_section(
+            &dir,
+            header,
+            section,
+            &CatalogSection {
+                categories,
+                streams,
+            },
+            now,
+        )?;
+        write_section(&dir, header, CacheSection::Parsed, &parsed, now)
     }
 
-    /// Load catalog from disk. Returns None if cache doesn't exist, is corrupt, or version mismatches.
+    /// Load every intact section. Returns None if there is no cache or none of
+    /// the catalog sections survived.
     #[cfg(not(target_arch = "wasm32"))]
     pub fn load(account_name: &str) -> Option<CachedCatalog> {
-        let path = Self::cache_path(account_name)?;
-        let data = std::fs::read(&path).ok()?;
-        let catalog: CachedCatalog = bincode::deserialize(&data).ok()?;
-
-        // Version check — reject outdated cache format
-        if catalog.version != CACHE_VERSION {
-            let _ = std::fs::remove_file(&path); // Clean up stale cache
-            return None;
-        }
+        Self::load_with(account_name, EPG_MAX_AGE_SECS)
+    }
+
+    /// Like `load`, but keeps the EPG section however old it is: when the
+    /// provider is unreachable, stale programme titles beat none.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_offline(account_name: &str) -> Option<CachedCatalog> {
+        Self::load_with(account_name, u64::MAX)
+    }
 
-        Some(catalog)
+    /// Whether anything has been cached for the account, without reading it
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn exists(account_name: &str) -> bool {
+        Self::cache_path(account_name).is_some_and(|dir| dir.join("manifest.json").exists())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_with(account_name: &str, epg_max_age: u64) -> Option<CachedCatalog> {
+        let dir = Self::cache_path(account_name)?;
+        let manifest = load_manifest(&dir, account_name)?;
+        let catalog = assemble(&dir, manifest, now_secs(), epg_max_age);
+        (!catalog.live_streams.is_empty()
+            || !catalog.vod_streams.is_empty()
+            || !catalog.series_streams.is_empty())
+        .then_some(catalog)
     }
 
     /// Check if cache is stale based on auto_refresh_hours setting.
@@ -91,11 +344,7 @@ impl CachedCatalog {
         if auto_refresh_hours == 0 {
             return false; // Auto-refresh disabled
         }
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        let age_hours = (now.saturating_sub(self.cached_at)) / 3600;
+        let age_hours = (now_secs().saturating_sub(self.cached_at)) / 3600;
         age_hours >= auto_refresh_hours as u64
     }
 
@@ -107,8 +356,9 @@ impl CachedCatalog {
     /// Delete cache for account
     #[cfg(not(target_arch = "wasm32"))]
     pub fn invalidate(account_name: &str) {
-        if let Some(path) = Self::cache_path(account_name) {
-            let _ = std::fs::remove_file(path);
+        if let Some(dir) = Self::cache_path(account_name) {
+            let _ = migrate_single_file(&dir, account_name);
+            let _ = std::fs::remove_dir_all(dir);
         }
     }
 
@@ -120,7 +370,22 @@ impl CachedCatalog {
 
     /// WASM stub - cache not supported in browser
     #[cfg(target_arch = "wasm32")]
-    pub fn save(&self) -> Result<(), anyhow::Error> {
+    pub fn save_section<T: Serialize>(
+        _header: &CacheHeader,
+        _section: CacheSection,
+        _payload: &T,
+    ) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    /// WASM stub - cache not supported in browser
+    #[cfg(target_arch = "wasm32")]
+    pub fn save_catalog_section(
+        _header: &CacheHeader,
+        _section: CacheSection,
+        _categories: Vec<Category>,
+        _streams: Vec<Stream>,
+    ) -> Result<(), anyhow::Error> {
         Ok(())
     }
 
@@ -130,14 +395,427 @@ impl CachedCatalog {
         None
     }
 
+    /// WASM stub - cache not supported in browser
+    #[cfg(target_arch = "wasm32")]
+    pub fn load_offline(_account_name: &str) -> Option<CachedCatalog> {
+        None
+    }
+
+    /// WASM stub - cache not supported in browser
+    #[cfg(target_arch = "wasm32")]
+    pub fn exists(_account_name: &str) -> bool {
+        false
+    }
+
     /// WASM stub - cache not supported in browser
     #[cfg(target_arch = "wasm32")]
     pub fn invalidate(_account_name: &str) {}
 }
 
+/// Read the manifest, migrating older versions. Returns None for a missing,
+/// unreadable or newer-than-supported manifest.
+fn load_manifest(dir: &Path, account_name: &str) -> Option<CacheManifest> {
+    let path = dir.join("manifest.json");
+    let Ok(data) = std::fs::read(&path) else {
+        // Nothing written in this format yet; clear out single-file caches
+        let _ = migrate_single_file(dir, account_name);
+        return None;
+    };
+    let mut manifest: CacheManifest = serde_json::from_slice(&data).ok()?;
+    if manifest.version > CACHE_VERSION {
+        return None;
+    }
+    while manifest.version < CACHE_VERSION {
+        let (_, step) = MIGRATIONS.iter().find(|(v, _)| *v == manifest.version)?;
+        step(dir, account_name).ok()?;
+        manifest.version += 1;
+    }
+    Some(manifest)
+}
+
+/// Read one section, verifying its checksum and migrating its payload.
+/// Returns None when the section is missing, corrupt or from a newer version.
+/// Only a corrupt file is deleted: a newer build's section is valid data.
+fn read_section<T: DeserializeOwned>(dir: &Path, section: CacheSection) -> Option<T> {
+    let data = std::fs::read(dir.join("manifest.json")).ok()?;
+    let manifest: CacheManifest = serde_json::from_slice(&data).ok()?;
+    read_section_with(dir, &manifest, section)
+}
+
+fn read_section_with<T: DeserializeOwned>(
+    dir: &Path,
+    manifest: &CacheManifest,
+    section: CacheSection,
+) -> Option<T> {
+    let entry = manifest.sections.get(&section)?;
+    let path = dir.join(section.file_name());
+    let data = std::fs::read(&path).ok()?;
+    if crc32fast::hash(&data) != entry.checksum {
+        let _ = std::fs::remove_file(path);
+        return None;
+    }
+    if entry.version > section.version() {
+        return None;
+    }
+    let json = decompress(&data).ok()?;
+    if section.is_binary() {
+        return (entry.version == section.version())
+            .then(|| bincode::deserialize(&json).ok())
+            .flatten();
+    }
+    if entry.version == section.version() {
+        return serde_json::from_slice(&json).ok();
+    }
+    let mut value: serde_json::Value = serde_json::from_slice(&json).ok()?;
+    migrate_section(
+        SECTION_MIGRATIONS,
+        section,
+        entry.version,
+        section.version(),
+        &mut value,
+    )
+    .ok()?;
+    serde_json::from_value(value).ok()
+}
+
+fn write_section<T: Serialize>(
+    dir: &Path,
+    header: &CacheHeader,
+    section: CacheSection,
+    payload: &T,
+    cached_at: u64,
+) -> Result<(), anyhow::Error> {
+    let data = if section.is_binary() {
+        compress(&bincode::serialize(payload)?)?
+    } else {
+        compress(&serde_json::to_vec(payload)?)?
+    };
+    let entry = SectionEntry {
+        version: section.version(),
+        cached_at,
+        checksum: crc32fast::hash(&data),
+        bytes: data.len() as u64,
+    };
+    // Write to a temp file first so a crash never leaves a half-written section
+    let path = dir.join(section.file_name());
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, &data)?;
+    std::fs::rename(&tmp, &path)?;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let _guard = MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let manifest_path = dir.join("manifest.json");
+    let mut sections = std::fs::read(&manifest_path)
+        .ok()
+        .and_then(|data| serde_json::from_slice::<CacheManifest>(&data).ok())
+        // A different account URL or filter set invalidates the other sections
+        .filter(|m| {
+            m.version == CACHE_VERSION
+                && m.header.account_url == header.account_url
+                && m.header.processing_modes == header.processing_modes
+        })
+        .map(|m| m.sections)
+        .unwrap_or_default();
+    sections.insert(section, entry);
+    let manifest = CacheManifest {
+        version: CACHE_VERSION,
+        header: header.clone(),
+        sections,
+    };
+    std::fs::write(manifest_path, serde_json::to_vec_pretty(&manifest)?)?;
+    Ok(())
+}
+
+/// Build the in-memory catalog from the intact sections of a manifest. The EPG
+/// section is skipped once it is older than `epg_max_age` seconds.
+fn assemble(dir: &Path, manifest: CacheManifest, now: u64, epg_max_age: u64) -> CachedCatalog {
+    let header = &manifest.header;
+    let mut catalog = CachedCatalog {
+        account_name: header.account_name.clone(),
+        account_url: header.account_url.clone(),
+        processing_modes: header.processing_modes.clone(),
+        total_channels: header.total_channels,
+        total_movies: header.total_movies,
+        total_series: header.total_series,
+        category_counts: header.category_counts.clone(),
+        cached_at: now,
+        ..Default::default()
+    };
+
+    for section in [CacheSection::Live, CacheSection::Vod, CacheSection::Series] {
+        let Some(data) = read_section_with::<CatalogSection>(dir, &manifest, section) else {
+            continue;
+        };
+        let (categories, streams) = match section {
+            CacheSection::Live => (&mut catalog.live_categories, &mut catalog.live_streams),
+            CacheSection::Vod => (&mut catalog.vod_categories, &mut catalog.vod_streams),
+            _ => (&mut catalog.series_categories, &mut catalog.series_streams),
+        };
+        *categories = data.categories;
+        *streams = data.streams;
+        catalog.cached_at = catalog.cached_at.min(manifest.sections[&section].cached_at);
+        catalog.sections.push(section);
+    }
+
+    if let Some(parsed) = read_section_with::<ParsedSections>(dir, &manifest, CacheSection::Parsed)
+    {
+        let account = Some(catalog.account_name.clone());
+        for (section, streams) in [
+            (CacheSection::Live, &mut catalog.live_streams),
+            (CacheSection::Vod, &mut catalog.vod_streams),
+            (CacheSection::Series, &mut catalog.series_streams),
+        ] {
+            let metas = parsed.get(&section);
+            for stream in streams.iter_mut() {
+                if let Some(meta) = metas.and_then(|m| m.get(&get_id_str(&stream.stream_id))) {
+                    meta.apply(stream);
+                }
+                stream.account_name = account.clone();
+            }
+        }
+        catalog.sections.push(CacheSection::Parsed);
+    }
+
+    let epg_fresh = manifest
+        .sections
+        .get(&CacheSection::Epg)
+        .is_some_and(|e| now.saturating_sub(e.cached_at) < epg_max_age);
+    if epg_fresh {
+        if let Some(epg) = read_section_with(dir, &manifest, CacheSection::Epg) {
+            catalog.epg_cache = epg;
+            catalog.sections.push(CacheSection::Epg);
+        }
+    }
+
+    catalog.search_index = read_section_with(dir, &manifest, CacheSection::Search);
+    if catalog.search_index.is_some() {
+        catalog.sections.push(CacheSection::Search);
+    }
+    catalog
+}
+
 fn simple_hash(s: &str) -> u64 {
     use std::hash::{Hash, Hasher};
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     s.hash(&mut hasher);
     hasher.finish()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flex_id::FlexId;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("matrix-iptv-cache-{}-{}", name, now_secs()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn header() -> CacheHeader {
+        CacheHeader {
+            account_name: "Provider".to_string(),
+            account_url: "http://provider.example".to_string(),
+            total_channels: 1,
+            ..Default::default()
+        }
+    }
+
+    fn stream(id: i64, name: &str) -> Stream {
+        Stream {
+            name: name.to_string(),
+            stream_id: FlexId::Number(id),
+            clean_name: name.to_string(),
+            search_name: name.to_lowercase(),
+            ..Default::default()
+        }
+    }
+
+    fn manifest(dir: &Path) -> CacheManifest {
+        serde_json::from_slice(&std::fs::read(dir.join("manifest.json")).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn sections_round_trip_and_refresh_independently() {
+        let dir = temp_dir("sections");
+        let live = CatalogSection {
+            categories: vec![Category {
+                category_id: "1".to_string(),
+                category_name: "News".to_string(),
+                ..Default::default()
+            }],
+            streams: vec![stream(7, "CNN HD")],
+        };
+        let mut parsed = ParsedSections::new();
+        parsed
+            .entry(CacheSection::Live)
+            .or_default()
+            .insert("7".to_string(), ParsedMeta::of(&live.streams[0]));
+        write_section(&dir, &header(), CacheSection::Live, &live, 100).unwrap();
+        write_section(&dir, &header(), CacheSection::Parsed, &parsed, 100).unwrap();
+        let movies = CatalogSection {
+            categories: Vec::new(),
+            streams: vec![stream(8, "Heat")],
+        };
+        write_section(&dir, &header(), CacheSection::Vod, &movies, 200).unwrap();
+
+        // Refreshing VOD left the live section's file and entry untouched
+        let m = manifest(&dir);
+        assert_eq!(m.sections[&CacheSection::Live].cached_at, 100);
+        assert_eq!(m.sections[&CacheSection::Vod].cached_at, 200);
+
+        let catalog = assemble(&dir, m, 300, EPG_MAX_AGE_SECS);
+        assert_eq!(catalog.cached_at, 100);
+        assert_eq!(catalog.live_categories[0].category_name, "News");
+        assert_eq!(catalog.live_streams[0].search_name, "cnn hd");
+        assert_eq!(catalog.vod_streams[0].name, "Heat");
+        assert_eq!(
+            catalog.sections,
+            vec![CacheSection::Live, CacheSection::Vod, CacheSection::Parsed]
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn corrupt_section_is_dropped_alone() {
+        let dir = temp_dir("corrupt");
+        let section = |id, name| CatalogSection {
+            categories: Vec::new(),
+            streams: vec![stream(id, name)],
+        };
+        write_section(&dir, &header(), CacheSection::Live, &section(1, "BBC"), 100).unwrap();
+        write_section(&dir, &header(), CacheSection::Vod, &section(2, "Heat"), 100).unwrap();
+        let vod = dir.join(CacheSection::Vod.file_name());
+        let mut data = std::fs::read(&vod).unwrap();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+        std::fs::write(&vod, data).unwrap();
+
+        let catalog = assemble(&dir, manifest(&dir), 200, EPG_MAX_AGE_SECS);
+        assert_eq!(catalog.live_streams.len(), 1);
+        assert!(catalog.vod_streams.is_empty());
+        assert!(!vod.exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn stale_epg_is_not_restored() {
+        let dir = temp_dir("epg");
+        let epg: HashMap<String, String> = [("7".to_string(), "Newsroom".to_string())].into();
+        write_section(&dir, &header(), CacheSection::Epg, &epg, 1_000).unwrap();
+        let recent = assemble(&dir, manifest(&dir), 1_000 + 60, EPG_MAX_AGE_SECS);
+        assert_eq!(
+            recent.epg_cache.get("7").map(String::as_str),
+            Some("Newsroom")
+        );
+        let old_at = 1_000 + EPG_MAX_AGE_SECS;
+        let old = assemble(&dir, manifest(&dir), old_at, EPG_MAX_AGE_SECS);
+        assert!(old.epg_cache.is_empty());
+        // Offline browsing keeps whatever guide data there is
+        let offline = assemble(&dir, manifest(&dir), old_at, u64::MAX);
+        assert_eq!(offline.epg_cache.len(), 1);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn migrations_run_in_order() {
+        fn rename_streams(value: &mut serde_json::Value) {
+            if let Some(streams) = value.get_mut("channels").map(|v| v.take()) {
+                value["streams"] = streams;
+            }
+        }
+        fn add_categories(value: &mut serde_json::Value) {
+            value["categories"] = serde_json::json!([]);
+        }
+        let steps: &[(CacheSection, u32, SectionMigration)] = &[
+            (CacheSection::Live, 1, rename_streams),
+            (CacheSection::Live, 2, add_categories),
+        ];
+        let mut value = serde_json::json!({ "channels": [{ "name": "CNN", "stream_id": 1 }] });
+        migrate_section(steps, CacheSection::Live, 1, 3, &mut value).unwrap();
+        let section: CatalogSection = serde_json::from_value(value).unwrap();
+        assert_eq!(section.streams[0].name, "CNN");
+        assert!(
+            migrate_section(steps, CacheSection::Vod, 1, 2, &mut serde_json::json!({})).is_err()
+        );
+
+        // Single-file caches from before manifests are cleared; a manifest
+        // that is too new is ignored
+        let dir = temp_dir("manifest");
+        let legacy = dir
+            .parent()
+            .unwrap()
+            .join(format!("{}.bin", simple_hash("Provider")));
+        std::fs::write(&legacy, b"old").unwrap();
+        assert!(load_manifest(&dir, "Provider").is_none());
+        assert!(!legacy.exists());
+        let newer = CacheManifest {
+            version: CACHE_VERSION + 1,
+            header: header(),
+            sections: BTreeMap::new(),
+        };
+        std::fs::write(
+            dir.join("manifest.json"),
+            serde_json::to_vec(&newer).unwrap(),
+        )
+        .unwrap();
+        assert!(load_manifest(&dir, "Provider").is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn parsed_metadata_is_kept_per_section() {
+        let dir = temp_dir("parsed");
+        // A channel and a series that share an id
+        let live = CatalogSection {
+            categories: Vec::new(),
+            streams: vec![stream(5, "CNN HD")],
+        };
+        let series = CatalogSection {
+            categories: Vec::new(),
+            streams: vec![stream(5, "The Wire")],
+        };
+        let mut parsed = ParsedSections::new();
+        for (section, data) in [(CacheSection::Live, &live), (CacheSection::Series, &series)] {
+            write_section(&dir, &header(), section, data, 100).unwrap();
+            parsed.insert(
+                section,
+                data.streams
+                    .iter()
+                    .map(|s| (get_id_str(&s.stream_id), ParsedMeta::of(s)))
+                    .collect(),
+            );
+        }
+        write_section(&dir, &header(), CacheSection::Parsed, &parsed, 100).unwrap();
+
+        let catalog = assemble(&dir, manifest(&dir), 200, EPG_MAX_AGE_SECS);
+        assert_eq!(catalog.live_streams[0].search_name, "cnn hd");
+        assert_eq!(catalog.series_streams[0].search_name, "the wire");
+
+        // A v1 payload (one shared map) still loads
+        let flat: HashMap<String, ParsedMeta> =
+            [("5".to_string(), ParsedMeta::of(&live.streams[0]))].into();
+        let mut value = serde_json::to_value(&flat).unwrap();
+        migrate_section(SECTION_MIGRATIONS, CacheSection::Parsed, 1, 2, &mut value).unwrap();
+        let migrated: ParsedSections = serde_json::from_value(value).unwrap();
+        assert_eq!(migrated[&CacheSection::Vod]["5"].clean_name, "CNN HD");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn newer_sections_are_left_for_the_build_that_wrote_them() {
+        let dir = temp_dir("newer");
+        let live = CatalogSection {
+            categories: Vec::new(),
+            streams: vec![stream(1, "BBC")],
+        };
+        write_section(&dir, &header(), CacheSection::Live, &live, 100).unwrap();
+        let mut m = manifest(&dir);
+        m.sections.get_mut(&CacheSection::Live).unwrap().version += 1;
+
+        assert!(read_section_with::<CatalogSection>(&dir, &m, CacheSection::Live).is_none());
+        assert!(dir.join(CacheSection::Live.file_name()).exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
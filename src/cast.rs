@@ -1,22 +1,27 @@
-//! Chromecast casting support for Matrix IPTV
+//! Casting support for Matrix IPTV
 //!
-//! This module provides functionality for discovering and streaming to Chromecast devices.
+//! This module provides functionality for discovering and streaming to cast targets:
+//! Chromecast devices (behind the `chromecast` feature) and DLNA media renderers.
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
 use std::net::IpAddr;
 #[cfg(not(target_arch = "wasm32"))]
-use std::sync::Arc;
-#[cfg(not(target_arch = "wasm32"))]
 use std::time::Duration;
 
+use crate::dlna::DlnaRenderer;
+use crate::kodi::KodiHost;
 #[cfg(not(target_arch = "wasm32"))]
+use crate::player_control::TransportCommand;
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
 use mdns_sd::{ServiceDaemon, ServiceEvent};
-#[cfg(not(target_arch = "wasm32"))]
-use rust_cast::channels::media::{Media, StreamType};
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
+use rust_cast::channels::media::{
+    GenericMediaMetadata, Media, Metadata, PlayerState, StatusEntry, StreamType,
+};
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
 use rust_cast::channels::receiver::CastDeviceApp;
-#[cfg(not(target_arch = "wasm32"))]
-use rust_cast::{CastDevice as RustCastDevice, ChannelMessage};
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
+use rust_cast::CastDevice as RustCastDevice;
 
 /// Represents a discovered Chromecast device
 #[derive(Debug, Clone)]
@@ -44,40 +49,124 @@ impl CastDevice {
 }
 
 /// Playback target for streams
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum PlaybackTarget {
     /// Play locally via mpv
+    #[default]
     Local,
     /// Cast to a Chromecast device
     Chromecast(CastDevice),
+    /// Cast to a DLNA/UPnP media renderer
+    Dlna(DlnaRenderer),
+    /// Play on a Kodi box over JSON-RPC
+    Kodi(KodiHost),
 }
 
-impl PartialEq for CastDevice {
-    fn eq(&self, other: &Self) -> bool {
-        self.ip == other.ip && self.port == other.port
+impl PlaybackTarget {
+    /// Name shown in the cast picker
+    pub fn name(&self) -> &str {
+        match self {
+            PlaybackTarget::Local => "This computer",
+            PlaybackTarget::Chromecast(device) => &device.name,
+            PlaybackTarget::Dlna(renderer) => &renderer.name,
+            PlaybackTarget::Kodi(host) => &host.name,
+        }
+    }
+
+    /// Kind and model shown next to the name
+    pub fn describe(&self) -> String {
+        match self {
+            PlaybackTarget::Local => "local player".to_string(),
+            PlaybackTarget::Chromecast(device) => device
+                .model
+                .clone()
+                .unwrap_or_else(|| "Chromecast".to_string()),
+            PlaybackTarget::Dlna(renderer) => match &renderer.model {
+                Some(model) => format!("DLNA · {}", model),
+                None => "DLNA".to_string(),
+            },
+            PlaybackTarget::Kodi(host) => format!("Kodi · {}", host.address()),
+        }
     }
 }
 
-impl Default for PlaybackTarget {
-    fn default() -> Self {
-        PlaybackTarget::Local
+/// Player state reported by a cast device
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CastState {
+    Playing,
+    Paused,
+    Buffering,
+    #[default]
+    Idle,
+}
+
+impl CastState {
+    pub fn label(self) -> &'static str {
+        match self {
+            CastState::Playing => "playing",
+            CastState::Paused => "paused",
+            CastState::Buffering => "buffering",
+            CastState::Idle => "idle",
+        }
+    }
+}
+
+/// Snapshot of an active cast session, polled for the cast bar
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CastStatus {
+    pub state: CastState,
+    pub position_secs: Option<f64>,
+    /// Device volume in percent
+    pub volume: Option<f64>,
+    pub muted: bool,
+    /// Why the player went idle, when the device says
+    pub idle_reason: Option<String>,
+    /// Another app or sender now owns the device
+    pub taken_over: bool,
+    /// The device went to standby (TV switched off)
+    pub standby: bool,
+}
+
+impl CastStatus {
+    /// Why the session is over, if it is. An idle player only counts once it
+    /// has played, since devices report idle while a stream is still loading.
+    pub fn end_reason(&self, previous: Option<&CastStatus>, device: &str) -> Option<String> {
+        if self.taken_over {
+            return Some(format!("Another app took over {}", device));
+        }
+        if self.standby {
+            return Some(format!("{} was switched off", device));
+        }
+        let was_active = previous.is_some_and(|p| p.state != CastState::Idle);
+        match (&self.idle_reason, self.state) {
+            (Some(reason), CastState::Idle) if was_active => {
+                Some(format!("Casting to {} ended ({})", device, reason))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for CastDevice {
+    fn eq(&self, other: &Self) -> bool {
+        self.ip == other.ip && self.port == other.port
     }
 }
 
 /// Manager for Chromecast casting operations
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
 pub struct CastManager {
     /// Currently active cast connection
     connection: Option<ActiveCast>,
 }
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
 struct ActiveCast {
     device: CastDevice,
-    // Future: Could hold the CastDevice connection for control
+    session: ChromecastSession,
 }
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
 impl CastManager {
     /// Create a new CastManager
     pub fn new() -> Self {
@@ -154,48 +243,12 @@ impl CastManager {
         url: &str,
         title: Option<&str>,
     ) -> Result<(), anyhow::Error> {
-        // Connect to the Chromecast
-        let cast_device =
-            RustCastDevice::connect_without_host_verification(&device.ip, device.port).map_err(
-                |e| anyhow::anyhow!("Failed to connect to Chromecast '{}': {}", device.name, e),
-            )?;
-
-        // Connect to receiver
-        cast_device
-            .connection
-            .connect("receiver-0")
-            .map_err(|e| anyhow::anyhow!("Failed to connect to receiver: {}", e))?;
-
-        // Launch the default media receiver app
-        let app = cast_device
-            .receiver
-            .launch_app(&CastDeviceApp::DefaultMediaReceiver)
-            .map_err(|e| anyhow::anyhow!("Failed to launch media receiver: {}", e))?;
-
-        // Connect to the media receiver
-        cast_device
-            .connection
-            .connect(&app.transport_id)
-            .map_err(|e| anyhow::anyhow!("Failed to connect to media receiver: {}", e))?;
-
-        // Create media info
-        let media = Media {
-            content_id: url.to_string(),
-            content_type: "video/mp4".to_string(), // Generic, Chromecast will handle it
-            stream_type: StreamType::Live,
-            duration: None,
-            metadata: None,
-        };
-
-        // Load and play the media
-        cast_device
-            .media
-            .load(&app.transport_id, &app.session_id, &media)
-            .map_err(|e| anyhow::anyhow!("Failed to load media: {}", e))?;
+        let session = ChromecastSession::start(device, url, title.unwrap_or("Matrix IPTV"))?;
 
         // Store the active connection info
         self.connection = Some(ActiveCast {
             device: device.clone(),
+            session,
         });
 
         Ok(())
@@ -204,14 +257,7 @@ impl CastManager {
     /// Stop the current cast session
     pub fn stop_cast(&mut self) -> Result<(), anyhow::Error> {
         if let Some(active) = self.connection.take() {
-            // Reconnect to stop
-            if let Ok(cast_device) = RustCastDevice::connect_without_host_verification(
-                &active.device.ip,
-                active.device.port,
-            ) {
-                let _ = cast_device.connection.connect("receiver-0");
-                let _ = cast_device.receiver.stop_app("receiver-0");
-            }
+            let _ = active.session.stop();
         }
         Ok(())
     }
@@ -227,25 +273,27 @@ impl CastManager {
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
 impl Default for CastManager {
     fn default() -> Self {
         Self::new()
     }
 }
 
-// WASM stub implementation
-#[cfg(target_arch = "wasm32")]
+// Stub implementation for the browser and builds without the `chromecast` feature
+#[cfg(not(all(not(target_arch = "wasm32"), feature = "chromecast")))]
 pub struct CastManager;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(not(all(not(target_arch = "wasm32"), feature = "chromecast")))]
 impl CastManager {
     pub fn new() -> Self {
         Self
     }
 
     pub async fn discover_devices(_timeout_secs: u64) -> Result<Vec<CastDevice>, anyhow::Error> {
-        Err(anyhow::anyhow!("Casting not supported in browser"))
+        Err(anyhow::anyhow!(
+            "Chromecast support not enabled. Rebuild with --features chromecast"
+        ))
     }
 
     pub fn cast_to_device(
@@ -254,7 +302,9 @@ impl CastManager {
         _url: &str,
         _title: Option<&str>,
     ) -> Result<(), anyhow::Error> {
-        Err(anyhow::anyhow!("Casting not supported in browser"))
+        Err(anyhow::anyhow!(
+            "Chromecast support not enabled. Rebuild with --features chromecast"
+        ))
     }
 
     pub fn stop_cast(&mut self) -> Result<(), anyhow::Error> {
@@ -270,13 +320,451 @@ impl CastManager {
     }
 }
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(not(all(not(target_arch = "wasm32"), feature = "chromecast")))]
 impl Default for CastManager {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// HTTP client for cast targets on the local network
+#[cfg(not(target_arch = "wasm32"))]
+pub fn lan_client() -> Result<reqwest::Client, anyhow::Error> {
+    Ok(reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(5))
+        .build()?)
+}
+
+/// Every cast target: the configured Kodi hosts, then what answers on the local
+/// network, Chromecast devices (when built with the `chromecast` feature) and
+/// DLNA media renderers
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn discover_targets(
+    timeout_secs: u64,
+    kodi_hosts: Vec<KodiHost>,
+) -> Result<Vec<PlaybackTarget>, anyhow::Error> {
+    #[cfg(feature = "chromecast")]
+    let (chromecasts, renderers) = tokio::join!(
+        CastManager::discover_devices(timeout_secs),
+        crate::dlna::discover(Duration::from_secs(timeout_secs))
+    );
+    #[cfg(not(feature = "chromecast"))]
+    let (chromecasts, renderers) = (
+        Ok::<Vec<CastDevice>, anyhow::Error>(Vec::new()),
+        crate::dlna::discover(Duration::from_secs(timeout_secs)).await,
+    );
+
+    match (chromecasts, renderers) {
+        (Err(cast_err), Err(dlna_err)) if kodi_hosts.is_empty() => {
+            Err(anyhow::anyhow!("{}; {}", cast_err, dlna_err))
+        }
+        (chromecasts, renderers) => Ok(kodi_hosts
+            .into_iter()
+            .map(PlaybackTarget::Kodi)
+            .chain(
+                chromecasts
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(PlaybackTarget::Chromecast),
+            )
+            .chain(
+                renderers
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(PlaybackTarget::Dlna),
+            )
+            .collect()),
+    }
+}
+
+/// A Default Media Receiver session on a Chromecast. Every call opens a fresh
+/// connection (as `CastManager::stop_cast` does), so the handle is plain data
+/// that can move between tasks, and a device that was switched off simply
+/// fails to connect.
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
+#[derive(Debug, Clone)]
+pub struct ChromecastSession {
+    device: CastDevice,
+    session_id: String,
+    transport_id: String,
+}
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
+impl ChromecastSession {
+    fn connect(device: &CastDevice) -> Result<RustCastDevice<'static>, anyhow::Error> {
+        let cast_device =
+            RustCastDevice::connect_without_host_verification(device.ip.clone(), device.port)
+                .map_err(|e| {
+                    anyhow::anyhow!("Failed to connect to Chromecast '{}': {}", device.name, e)
+                })?;
+        cast_device
+            .connection
+            .connect("receiver-0")
+            .map_err(|e| anyhow::anyhow!("Failed to connect to receiver: {}", e))?;
+        Ok(cast_device)
+    }
+
+    fn media(url: &str, title: &str) -> Media {
+        Media {
+            content_id: url.to_string(),
+            content_type: "video/mp4".to_string(), // Generic, Chromecast will handle it
+            stream_type: StreamType::Live,
+            duration: None,
+            metadata: Some(Metadata::Generic(GenericMediaMetadata {
+                title: Some(title.to_string()),
+                subtitle: None,
+                images: Vec::new(),
+                release_date: None,
+            })),
+        }
+    }
+
+    /// Launch the Default Media Receiver and load the stream
+    pub fn start(device: &CastDevice, url: &str, title: &str) -> Result<Self, anyhow::Error> {
+        let cast_device = Self::connect(device)?;
+        let app = cast_device
+            .receiver
+            .launch_app(&CastDeviceApp::DefaultMediaReceiver)
+            .map_err(|e| anyhow::anyhow!("Failed to launch media receiver: {}", e))?;
+        let session = Self {
+            device: device.clone(),
+            session_id: app.session_id,
+            transport_id: app.transport_id,
+        };
+        session.load_on(&cast_device, url, title)?;
+        Ok(session)
+    }
+
+    /// Connection joined to our receiver app
+    fn open(&self) -> Result<RustCastDevice<'static>, anyhow::Error> {
+        let cast_device = Self::connect(&self.device)?;
+        cast_device
+            .connection
+            .connect(self.transport_id.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to connect to media receiver: {}", e))?;
+        Ok(cast_device)
+    }
+
+    fn load_on(
+        &self,
+        cast_device: &RustCastDevice<'static>,
+        url: &str,
+        title: &str,
+    ) -> Result<(), anyhow::Error> {
+        cast_device
+            .connection
+            .connect(self.transport_id.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to connect to media receiver: {}", e))?;
+        cast_device
+            .media
+            .load(
+                self.transport_id.clone(),
+                self.session_id.clone(),
+                &Self::media(url, title),
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to load media: {}", e))?;
+        Ok(())
+    }
+
+    /// Load another stream in the running receiver session
+    pub fn load(&self, url: &str, title: &str) -> Result<(), anyhow::Error> {
+        self.load_on(&Self::connect(&self.device)?, url, title)
+    }
+
+    fn media_entry(
+        &self,
+        cast_device: &RustCastDevice<'static>,
+    ) -> Result<StatusEntry, anyhow::Error> {
+        cast_device
+            .media
+            .get_status(self.transport_id.clone(), None)
+            .map_err(|e| anyhow::anyhow!("Failed to read media status: {}", e))?
+            .entries
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Nothing is playing on '{}'", self.device.name))
+    }
+
+    pub fn status(&self) -> Result<CastStatus, anyhow::Error> {
+        let cast_device = Self::connect(&self.device)?;
+        let receiver = cast_device
+            .receiver
+            .get_status()
+            .map_err(|e| anyhow::anyhow!("Failed to read receiver status: {}", e))?;
+        let mut status = CastStatus {
+            volume: receiver.volume.level.map(|v| f64::from(v) * 100.0),
+            muted: receiver.volume.muted.unwrap_or(false),
+            taken_over: !receiver
+                .applications
+                .iter()
+                .any(|app| app.session_id == self.session_id),
+            standby: receiver.is_stand_by,
+            ..CastStatus::default()
+        };
+        if status.taken_over {
+            return Ok(status);
+        }
+
+        cast_device
+            .connection
+            .connect(self.transport_id.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to connect to media receiver: {}", e))?;
+        if let Ok(entry) = self.media_entry(&cast_device) {
+            status.state = match entry.player_state {
+                PlayerState::Playing => CastState::Playing,
+                PlayerState::Paused => CastState::Paused,
+                PlayerState::Buffering => CastState::Buffering,
+                PlayerState::Idle => CastState::Idle,
+            };
+            status.position_secs = entry.current_time.map(f64::from);
+            status.idle_reason = entry
+                .idle_reason
+                .map(|reason| format!("{:?}", reason).to_lowercase());
+        }
+        Ok(status)
+    }
+
+    pub fn send(&self, cmd: TransportCommand) -> Result<(), anyhow::Error> {
+        let cast_device = self.open()?;
+        let failed = |e: rust_cast::errors::Error| anyhow::anyhow!("{} failed: {}", cmd.label(), e);
+        match cmd {
+            TransportCommand::TogglePause => {
+                let entry = self.media_entry(&cast_device)?;
+                let id = entry.media_session_id;
+                if matches!(entry.player_state, PlayerState::Paused) {
+                    cast_device.media.play(self.transport_id.clone(), id)
+                } else {
+                    cast_device.media.pause(self.transport_id.clone(), id)
+                }
+                .map_err(failed)?;
+            }
+            TransportCommand::Seek(secs) => {
+                let entry = self.media_entry(&cast_device)?;
+                let target = (entry.current_time.unwrap_or(0.0) + secs as f32).max(0.0);
+                cast_device
+                    .media
+                    .seek(
+                        self.transport_id.clone(),
+                        entry.media_session_id,
+                        Some(target),
+                        None,
+                    )
+                    .map_err(failed)?;
+            }
+            TransportCommand::Volume(delta) => {
+                let current = cast_device
+                    .receiver
+                    .get_status()
+                    .map_err(failed)?
+                    .volume
+                    .level
+                    .unwrap_or(0.5);
+                let level = (current + delta as f32 / 100.0).clamp(0.0, 1.0);
+                cast_device.receiver.set_volume(level).map_err(failed)?;
+            }
+            TransportCommand::ToggleMute => {
+                let muted = cast_device
+                    .receiver
+                    .get_status()
+                    .map_err(failed)?
+                    .volume
+                    .muted
+                    .unwrap_or(false);
+                cast_device.receiver.set_volume(!muted).map_err(failed)?;
+            }
+            other => {
+                return Err(anyhow::anyhow!(
+                    "{} is not available while casting",
+                    other.label()
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    pub fn stop(&self) -> Result<(), anyhow::Error> {
+        let cast_device = Self::connect(&self.device)?;
+        cast_device
+            .receiver
+            .stop_app(self.session_id.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to stop casting: {}", e))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CastLink {
+    #[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
+    Chromecast(ChromecastSession),
+    Dlna(DlnaRenderer),
+    Kodi(KodiHost),
+}
+
+/// A stream playing on a cast target, with remote control and status polling
+#[derive(Debug, Clone)]
+pub struct CastSession {
+    /// Title of what is playing
+    pub title: String,
+    /// Stream URL handed to the device
+    pub url: String,
+    target: PlaybackTarget,
+    link: CastLink,
+}
+
+impl CastSession {
+    pub fn device_name(&self) -> &str {
+        self.target.name()
+    }
+
+    pub fn target(&self) -> &PlaybackTarget {
+        &self.target
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CastSession {
+    /// Start playing a media URL on a cast target
+    pub async fn start(
+        target: &PlaybackTarget,
+        url: &str,
+        title: &str,
+    ) -> Result<Self, anyhow::Error> {
+        let link = match target {
+            PlaybackTarget::Local => {
+                return Err(anyhow::anyhow!("Local playback is not a cast target"))
+            }
+            #[cfg(feature = "chromecast")]
+            PlaybackTarget::Chromecast(device) => {
+                let (device, url, title) = (device.clone(), url.to_string(), title.to_string());
+                let session = tokio::task::spawn_blocking(move || {
+                    ChromecastSession::start(&device, &url, &title)
+                })
+                .await??;
+                CastLink::Chromecast(session)
+            }
+            #[cfg(not(feature = "chromecast"))]
+            PlaybackTarget::Chromecast(_) => {
+                return Err(anyhow::anyhow!(
+                    "Chromecast support not enabled. Rebuild with --features chromecast"
+                ))
+            }
+            PlaybackTarget::Dlna(renderer) => {
+                renderer.cast(&lan_client()?, url, Some(title)).await?;
+                CastLink::Dlna(renderer.clone())
+            }
+            PlaybackTarget::Kodi(host) => {
+                let host = host.with_password();
+                host.open(&lan_client()?, url).await?;
+                CastLink::Kodi(host)
+            }
+        };
+        Ok(Self {
+            title: title.to_string(),
+            url: url.to_string(),
+            target: target.clone(),
+            link,
+        })
+    }
+
+    /// Switch the running session to another stream
+    pub async fn load(&mut self, url: &str, title: &str) -> Result<(), anyhow::Error> {
+        match &self.link {
+            #[cfg(feature = "chromecast")]
+            CastLink::Chromecast(session) => {
+                let (session, url, title) = (session.clone(), url.to_string(), title.to_string());
+                tokio::task::spawn_blocking(move || session.load(&url, &title)).await??;
+            }
+            CastLink::Dlna(renderer) => {
+                renderer.cast(&lan_client()?, url, Some(title)).await?;
+            }
+            CastLink::Kodi(host) => host.open(&lan_client()?, url).await?,
+        }
+        self.url = url.to_string();
+        self.title = title.to_string();
+        Ok(())
+    }
+
+    pub async fn status(&self) -> Result<CastStatus, anyhow::Error> {
+        match &self.link {
+            #[cfg(feature = "chromecast")]
+            CastLink::Chromecast(session) => {
+                let session = session.clone();
+                tokio::task::spawn_blocking(move || session.status()).await?
+            }
+            CastLink::Dlna(renderer) => renderer.status(&lan_client()?, &self.url).await,
+            CastLink::Kodi(host) => host.status(&lan_client()?, &self.url).await,
+        }
+    }
+
+    /// Pause/resume, seek, volume and mute on the device
+    pub async fn send(&self, cmd: TransportCommand) -> Result<(), anyhow::Error> {
+        match &self.link {
+            #[cfg(feature = "chromecast")]
+            CastLink::Chromecast(session) => {
+                let session = session.clone();
+                tokio::task::spawn_blocking(move || session.send(cmd)).await?
+            }
+            CastLink::Dlna(renderer) => {
+                let client = lan_client()?;
+                match cmd {
+                    TransportCommand::TogglePause => {
+                        match renderer.status(&client, &self.url).await?.state {
+                            CastState::Paused => renderer.play(&client).await,
+                            _ => renderer.pause(&client).await,
+                        }
+                    }
+                    TransportCommand::Seek(secs) => {
+                        let status = renderer.status(&client, &self.url).await?;
+                        let position = status.position_secs.ok_or_else(|| {
+                            anyhow::anyhow!("'{}' does not report a position", renderer.name)
+                        })?;
+                        renderer.seek(&client, position + secs as f64).await
+                    }
+                    TransportCommand::Volume(delta) => {
+                        let current = renderer.volume(&client).await?.unwrap_or(50) as i32;
+                        renderer
+                            .set_volume(&client, (current + delta).clamp(0, 100) as u32)
+                            .await
+                    }
+                    TransportCommand::ToggleMute => {
+                        let muted = renderer.muted(&client).await?;
+                        renderer.set_mute(&client, !muted).await
+                    }
+                    other => Err(anyhow::anyhow!(
+                        "{} is not available while casting",
+                        other.label()
+                    )),
+                }
+            }
+            CastLink::Kodi(host) => {
+                let client = lan_client()?;
+                match cmd {
+                    TransportCommand::TogglePause => host.play_pause(&client).await,
+                    TransportCommand::Seek(secs) => host.seek(&client, secs).await,
+                    TransportCommand::Volume(delta) => host.change_volume(&client, delta).await,
+                    TransportCommand::ToggleMute => host.toggle_mute(&client).await,
+                    other => Err(anyhow::anyhow!(
+                        "{} is not available while casting",
+                        other.label()
+                    )),
+                }
+            }
+        }
+    }
+
+    pub async fn stop(&self) -> Result<(), anyhow::Error> {
+        match &self.link {
+            #[cfg(feature = "chromecast")]
+            CastLink::Chromecast(session) => {
+                let session = session.clone();
+                tokio::task::spawn_blocking(move || session.stop()).await?
+            }
+            CastLink::Dlna(renderer) => renderer.stop(&lan_client()?).await,
+            CastLink::Kodi(host) => host.stop(&lan_client()?).await,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -296,4 +784,69 @@ mod tests {
         let target = PlaybackTarget::default();
         assert_eq!(target, PlaybackTarget::Local);
     }
+
+    #[test]
+    fn test_playback_target_labels() {
+        let mut device = CastDevice::new("Living Room".into(), "192.168.1.100".into(), 8009);
+        assert_eq!(
+            PlaybackTarget::Chromecast(device.clone()).describe(),
+            "Chromecast"
+        );
+        device.model = Some("Chromecast Ultra".into());
+        assert_eq!(
+            PlaybackTarget::Chromecast(device).describe(),
+            "Chromecast Ultra"
+        );
+
+        let renderer = DlnaRenderer {
+            name: "Bedroom TV".into(),
+            location: "http://192.168.1.50:49152/desc.xml".into(),
+            control_url: "http://192.168.1.50:49152/avt".into(),
+            rendering_control_url: None,
+            model: Some("Bravia".into()),
+        };
+        let target = PlaybackTarget::Dlna(renderer);
+        assert_eq!(target.name(), "Bedroom TV");
+        assert_eq!(target.describe(), "DLNA · Bravia");
+
+        let kodi = PlaybackTarget::Kodi(crate::kodi::KodiHost::parse("192.168.1.20").unwrap());
+        assert_eq!(kodi.name(), "192.168.1.20");
+        assert_eq!(kodi.describe(), "Kodi · 192.168.1.20:8080");
+    }
+
+    #[test]
+    fn test_cast_status_end_reason() {
+        let playing = CastStatus {
+            state: CastState::Playing,
+            ..CastStatus::default()
+        };
+        let stopped = CastStatus {
+            idle_reason: Some("finished".into()),
+            ..CastStatus::default()
+        };
+        assert_eq!(playing.end_reason(None, "TV"), None);
+        // Idle while the stream is still loading is not the end
+        assert_eq!(stopped.end_reason(None, "TV"), None);
+        assert_eq!(
+            stopped.end_reason(Some(&playing), "TV").as_deref(),
+            Some("Casting to TV ended (finished)")
+        );
+
+        let taken = CastStatus {
+            taken_over: true,
+            ..playing.clone()
+        };
+        assert_eq!(
+            taken.end_reason(None, "TV").as_deref(),
+            Some("Another app took over TV")
+        );
+        let off = CastStatus {
+            standby: true,
+            ..playing
+        };
+        assert_eq!(
+            off.end_reason(None, "TV").as_deref(),
+            Some("TV was switched off")
+        );
+    }
 }
@@ -1,8 +1,6 @@
-#[cfg(not(target_arch = "wasm32"))]
-use directories::ProjectDirs;
+pub use crate::config_schema::ConfigError;
+use crate::storage::ConfigStorage;
 use serde::{Deserialize, Serialize};
-#[cfg(not(target_arch = "wasm32"))]
-use std::fs;
 
 /// Player engine options
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
@@ -10,6 +8,8 @@ pub enum PlayerEngine {
     #[default]
     Mpv,
     Vlc,
+    /// The user-defined profile named by `AppConfig::active_player_profile`
+    Custom,
 }
 
 impl PlayerEngine {
@@ -17,6 +17,7 @@ impl PlayerEngine {
         match self {
             PlayerEngine::Mpv => "MPV (High Performance)",
             PlayerEngine::Vlc => "VLC (High Stability)",
+            PlayerEngine::Custom => "Custom Player",
         }
     }
 
@@ -58,6 +59,44 @@ impl DnsProvider {
     }
 }
 
+/// How channel logos and posters are drawn in the terminal
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ImageProtocol {
+    /// Pick from the terminal's capabilities at startup
+    #[default]
+    Auto,
+    Kitty,
+    Iterm2,
+    Sixel,
+    /// Unicode half blocks, two pixels per cell; works everywhere with true colour
+    HalfBlocks,
+    Off,
+}
+
+impl ImageProtocol {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ImageProtocol::Auto => "Auto",
+            ImageProtocol::Kitty => "Kitty Graphics",
+            ImageProtocol::Iterm2 => "iTerm2 Inline",
+            ImageProtocol::Sixel => "Sixel",
+            ImageProtocol::HalfBlocks => "Half Blocks",
+            ImageProtocol::Off => "Off",
+        }
+    }
+
+    pub fn all() -> &'static [ImageProtocol] {
+        &[
+            ImageProtocol::Auto,
+            ImageProtocol::Kitty,
+            ImageProtocol::Iterm2,
+            ImageProtocol::Sixel,
+            ImageProtocol::HalfBlocks,
+            ImageProtocol::Off,
+        ]
+    }
+}
+
 /// Playlist processing mode options (Legacy - specific combinations)
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
 pub enum PlaylistMode {
@@ -154,12 +193,18 @@ impl CategorySortOrder {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Account {
     pub name: String,
     pub base_url: String, // For M3U, this is the M3U URL
     pub username: String, // Empty for M3U
+    /// A password not in a credential store yet: just typed, or read from a
+    /// config saved without one. Cleared once sealed; log in with `secret()`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub password: String, // Empty for M3U
+    /// Where the password is kept (`keyring:<id>` or `vault:<id>`, see `crate::vault`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential: Option<String>,
     #[serde(default)]
     pub account_type: AccountType,
     pub epg_url: Option<String>,
@@ -170,10 +215,129 @@ pub struct Account {
     pub server_timezone: Option<String>,
     #[serde(default)]
     pub hidden_categories: std::collections::HashSet<String>,
+    /// Live category id -> whether it is radio, where the name-based guess is wrong
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub radio_overrides: std::collections::HashMap<String, bool>,
     #[serde(default)]
     pub category_sort_order: CategorySortOrder,
 }
 
+// Passwords never reach debug output
+impl std::fmt::Debug for Account {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Account")
+            .field("name", &self.name)
+            .field("base_url", &self.base_url)
+            .field("username", &self.username)
+            .field("credential", &self.credential)
+            .field("account_type", &self.account_type)
+            .field("epg_url", &self.epg_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Account {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.password.zeroize();
+    }
+}
+
+impl Account {
+    /// The password to log in with, read from its credential store unless it
+    /// is still waiting to be sealed
+    pub fn secret(&self) -> zeroize::Zeroizing<String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.password.is_empty() {
+            if let Some(secret) = self.credential.as_deref().and_then(crate::vault::fetch) {
+                return secret;
+            }
+        }
+        zeroize::Zeroizing::new(self.password.clone())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Account {
+    /// Stable vault entry id for an account that has no reference yet. The
+    /// config file is part of it so separate profiles never share an entry.
+    fn credential_id(&self) -> String {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (&self.name, &self.base_url, &self.username).hash(&mut hasher);
+        crate::storage::paths()
+            .map(|p| p.config_file)
+            .hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Move the password into the vault, keeping only the reference. Returns
+    /// false when no store is available and the password stays in plaintext.
+    fn seal_password(&mut self) -> bool {
+        let id = self.credential_id();
+        seal_secret(&mut self.password, &mut self.credential, &id)
+    }
+}
+
+/// Store `password` under `credential` (or a new entry `id`) and clear it.
+/// Returns false when no store is available and the password stays in plaintext.
+#[cfg(not(target_arch = "wasm32"))]
+fn seal_secret(password: &mut String, credential: &mut Option<String>, id: &str) -> bool {
+    use zeroize::Zeroize;
+    if password.is_empty() {
+        return true;
+    }
+    match crate::vault::store(credential.as_deref(), id, password) {
+        Some(reference) => {
+            *credential = Some(reference);
+            password.zeroize();
+            password.clear();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl crate::kodi::KodiHost {
+    /// Vault entry id, as for [`Account`]
+    fn credential_id(&self) -> String {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        ("kodi", &self.host, self.port, &self.username).hash(&mut hasher);
+        crate::storage::paths()
+            .map(|p| p.config_file)
+            .hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    /// Copy for a cast session, with the password read from its credential store
+    pub fn with_password(&self) -> Self {
+        let mut host = self.clone();
+        if host.password.is_none() {
+            host.password = host
+                .credential
+                .as_deref()
+                .and_then(crate::vault::fetch)
+                .map(|s| s.to_string());
+        }
+        host
+    }
+
+    /// Move the password into the vault; see [`Account::seal_password`]
+    fn seal_password(&mut self) -> bool {
+        let id = self.credential_id();
+        let Some(password) = self.password.as_mut() else {
+            return true;
+        };
+        let sealed = seal_secret(password, &mut self.credential, &id);
+        if sealed {
+            self.password = None;
+        }
+        sealed
+    }
+}
+
 /// A user-defined channel group
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChannelGroup {
@@ -184,6 +348,54 @@ pub struct ChannelGroup {
     pub stream_ids: Vec<String>, // Ordered list of stream IDs
 }
 
+/// One channel in a multiview grid
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MultiviewTile {
+    pub stream_id: String,
+    pub name: String,
+}
+
+/// A saved multiview set, e.g. "Sunday Ticket"
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MultiviewPreset {
+    pub name: String,
+    pub tiles: Vec<MultiviewTile>,
+}
+
+/// A recording the scheduler should make on `stream_id`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RecordingSchedule {
+    pub id: u64,
+    /// Name of the account `stream_id` belongs to. Empty for schedules saved
+    /// before this was recorded; those follow whichever account is active.
+    #[serde(default)]
+    pub account: String,
+    pub stream_id: String,
+    pub channel_name: String,
+    pub rule: ScheduleRule,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ScheduleRule {
+    /// A single programme (unix timestamps)
+    Once { title: String, start: i64, end: i64 },
+    /// Every guide entry whose title contains `title`, optionally only on one
+    /// weekday (in the user's timezone), e.g. "Monday Night Football" on Mondays
+    Series {
+        title: String,
+        weekday: Option<chrono::Weekday>,
+    },
+}
+
+impl ScheduleRule {
+    pub fn title(&self) -> &str {
+        match self {
+            ScheduleRule::Once { title, .. } | ScheduleRule::Series { title, .. } => title,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct Favorites {
     pub categories: std::collections::HashSet<String>, // Category IDs
@@ -194,8 +406,12 @@ pub struct Favorites {
     pub groups: Vec<ChannelGroup>, // Custom user groups
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
+    /// Layout version of config.json; older files are migrated on load (see
+    /// `config_schema`)
+    #[serde(default)]
+    pub schema_version: u32,
     pub accounts: Vec<Account>,
     pub last_used_account_index: Option<usize>,
     #[serde(default)]
@@ -229,6 +445,96 @@ pub struct AppConfig {
     /// Recently watched channels (stream_id, stream_name), most recent first, max 20
     #[serde(default)]
     pub recently_watched: Vec<(String, String)>,
+
+    /// Show the scrolling live score ticker in the footer
+    #[serde(default)]
+    pub score_ticker: bool,
+
+    /// Leagues shown in the score ticker (e.g. "NFL"). Empty = all leagues.
+    #[serde(default)]
+    pub ticker_leagues: Vec<String>,
+
+    /// Saved multiview channel sets
+    #[serde(default)]
+    pub multiview_presets: Vec<MultiviewPreset>,
+    /// Screen (width, height) to tile VLC multiview windows over; None detects it
+    #[serde(default)]
+    pub multiview_screen: Option<(u32, u32)>,
+
+    /// Where live recordings are written. None = the platform's Videos folder.
+    #[serde(default)]
+    pub recordings_dir: Option<String>,
+
+    /// Scheduled recordings (single programmes and series rules)
+    #[serde(default)]
+    pub recording_schedules: Vec<RecordingSchedule>,
+
+    /// Minutes a scheduled recording starts early / runs over
+    #[serde(default = "default_padding_before_mins")]
+    pub recording_padding_before_mins: u32,
+    #[serde(default = "default_padding_after_mins")]
+    pub recording_padding_after_mins: u32,
+
+    /// Serve live channels to other devices through the local relay
+    #[serde(default)]
+    pub relay_enabled: bool,
+    #[serde(default = "default_relay_port")]
+    pub relay_port: u16,
+    /// Address the relay listens on; "0.0.0.0" exposes it to the LAN
+    #[serde(default = "default_relay_bind")]
+    pub relay_bind: String,
+
+    /// External players beyond MPV and VLC (see `player_profile`)
+    #[serde(default)]
+    pub player_profiles: Vec<crate::player_profile::PlayerProfile>,
+    /// Profile used when `preferred_player` is `Custom`
+    #[serde(default)]
+    pub active_player_profile: Option<String>,
+    /// Kodi boxes offered in the cast picker
+    #[serde(default)]
+    pub kodi_hosts: Vec<crate::kodi::KodiHost>,
+
+    /// Seconds without playback progress before the watchdog recovers a live
+    /// stream (0 = watchdog off)
+    #[serde(default = "default_watchdog_stall_secs")]
+    pub watchdog_stall_secs: u64,
+
+    /// How logos and posters are drawn (Auto = detect the terminal)
+    #[serde(default)]
+    pub image_protocol: ImageProtocol,
+
+    /// Where `save` writes to (not part of the JSON)
+    #[serde(skip)]
+    storage: ConfigStorage,
+
+    /// What the last load's migrations changed, for the user (not part of the JSON)
+    #[serde(skip)]
+    pub notices: Vec<String>,
+}
+
+fn default_relay_port() -> u16 {
+    8089
+}
+
+/// Relay address reachable from this computer only
+pub const RELAY_LOCAL_BIND: &str = "127.0.0.1";
+/// Relay address reachable from every device on the network
+pub const RELAY_LAN_BIND: &str = "0.0.0.0";
+
+fn default_relay_bind() -> String {
+    RELAY_LOCAL_BIND.to_string()
+}
+
+fn default_watchdog_stall_secs() -> u64 {
+    20
+}
+
+fn default_padding_before_mins() -> u32 {
+    2
+}
+
+fn default_padding_after_mins() -> u32 {
+    5
 }
 
 fn default_auto_refresh_hours() -> u32 {
@@ -238,6 +544,7 @@ fn default_auto_refresh_hours() -> u32 {
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            schema_version: crate::config_schema::CURRENT_SCHEMA_VERSION,
             accounts: Vec::new(),
             last_used_account_index: None,
             favorites: Favorites::default(),
@@ -250,120 +557,265 @@ impl Default for AppConfig {
             smooth_motion: true, // Default to smoothing ON for better UX
             auto_refresh_hours: 12,
             recently_watched: Vec::new(),
+            score_ticker: false,
+            ticker_leagues: Vec::new(),
+            multiview_presets: Vec::new(),
+            multiview_screen: None,
+            recordings_dir: None,
+            recording_schedules: Vec::new(),
+            recording_padding_before_mins: 2,
+            recording_padding_after_mins: 5,
+            relay_enabled: false,
+            relay_port: default_relay_port(),
+            relay_bind: default_relay_bind(),
+            player_profiles: Vec::new(),
+            active_player_profile: None,
+            kodi_hosts: Vec::new(),
+            watchdog_stall_secs: default_watchdog_stall_secs(),
+            image_protocol: ImageProtocol::default(),
+            storage: ConfigStorage::default(),
+            notices: Vec::new(),
         }
     }
 }
 
 impl AppConfig {
-    #[cfg(not(target_arch = "wasm32"))]
-    pub fn load() -> Result<Self, anyhow::Error> {
-        let new_proj = ProjectDirs::from("com", "vibecoding", "vibe-iptv");
-        let old_proj = ProjectDirs::from("com", "vibecoding", "iptv-cli");
-
-        if let Some(proj_dirs) = new_proj {
-            let config_path = proj_dirs.config_dir().join("config.json");
-            if config_path.exists() {
-                let content = fs::read_to_string(config_path)?;
-                let mut config: AppConfig = serde_json::from_str(&content)?;
-
-                // MIGRATION: V3.0.4 - Convert legacy playlist_mode to processing_modes
-                if config.processing_modes.is_empty()
-                    && config.playlist_mode != PlaylistMode::Default
-                {
-                    match config.playlist_mode {
-                        PlaylistMode::Merica => {
-                            config.processing_modes.push(ProcessingMode::Merica)
-                        }
-                        PlaylistMode::Sports => {
-                            config.processing_modes.push(ProcessingMode::Sports)
-                        }
-                        PlaylistMode::AllEnglish => {
-                            config.processing_modes.push(ProcessingMode::AllEnglish)
-                        }
-                        PlaylistMode::SportsMerica => {
-                            config.processing_modes.push(ProcessingMode::Merica);
-                            config.processing_modes.push(ProcessingMode::Sports);
-                        }
-                        _ => {}
-                    }
-                }
-
-                // MIGRATION: V4.0.9 - Force robust MPV backend settings
-                // Previously, use_default_mpv was true by default which bypasses all IPTV stability fixes.
-                config.use_default_mpv = false;
-
-                return Ok(config);
-            }
+    /// Load from the platform config file (or the `--config` override)
+    pub fn load() -> Result<Self, ConfigError> {
+        Self::load_from(ConfigStorage::platform())
+    }
 
-            // If new doesn't exist, check old
-            if let Some(old_dirs) = old_proj {
-                let old_path = old_dirs.config_dir().join("config.json");
-                if old_path.exists() {
-                    // MIGRATION: Copy old to new
-                    let content = fs::read_to_string(&old_path)?;
-                    let config: AppConfig = serde_json::from_str(&content)?;
+    /// Empty config that saves to `storage`
+    pub fn with_storage(storage: ConfigStorage) -> Self {
+        Self {
+            storage,
+            ..Self::default()
+        }
+    }
 
-                    // Save to new location
-                    let _ = fs::create_dir_all(proj_dirs.config_dir());
-                    let _ = fs::write(config_path, &content);
+    /// Load from `storage`, which later saves go back to. Older files are
+    /// migrated (after a backup copy is kept) and everything is validated; an
+    /// error names the offending field.
+    pub fn load_from(storage: ConfigStorage) -> Result<Self, ConfigError> {
+        let content = storage
+            .read()
+            .map_err(|e| ConfigError::Read(e.to_string()))?;
+        let Some(content) = content else {
+            return Ok(Self::with_storage(storage));
+        };
+        let (mut config, stored_version) = crate::config_schema::parse(&content)?;
+        config.storage = storage;
 
-                    return Ok(config);
-                }
-            }
+        let migrated = stored_version < crate::config_schema::CURRENT_SCHEMA_VERSION;
+        if migrated {
+            // Keep the file as it was before any migration touched it
+            config
+                .storage
+                .keep_copy(&format!("v{}.bak", stored_version), &content)
+                .map_err(|e| ConfigError::Read(format!("could not back up the config: {}", e)))?;
+        }
+
+        // Move plaintext passwords into the credential vault
+        #[cfg(not(target_arch = "wasm32"))]
+        let migrated = if config.storage.persistent() {
+            crate::vault::unlock();
+            config.seal_credentials() || migrated
+        } else {
+            migrated
+        };
+
+        if migrated {
+            let _ = config.save();
         }
-        Ok(AppConfig::default())
+        Ok(config)
     }
 
-    #[cfg(target_arch = "wasm32")]
-    pub fn load() -> Result<Self, anyhow::Error> {
-        if let Some(window) = web_sys::window() {
-            if let Ok(Some(storage)) = window.local_storage() {
-                if let Ok(Some(json)) = storage.get_item("app_config") {
-                    return serde_json::from_str(&json).map_err(|e| anyhow::anyhow!(e));
-                }
+    /// Start over with defaults after `error`, keeping the unusable file next
+    /// to the config. Returns the fresh config and a message for the user.
+    pub fn recover(storage: ConfigStorage, error: &ConfigError) -> (Self, String) {
+        let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+        let kept = storage.read().ok().flatten().and_then(|content| {
+            storage
+                .keep_copy(&format!("broken-{}", stamp), &content)
+                .ok()
+        });
+        let config = Self::with_storage(storage);
+        let message = match kept {
+            Some(place) => {
+                let _ = config.save();
+                format!(
+                    "Settings could not be loaded ({}). Started with defaults; the old file was kept as {}.",
+                    error, place
+                )
             }
+            // Leave the original in place if it could not be copied
+            None => format!(
+                "Settings could not be loaded ({}). Started with defaults for this session.",
+                error
+            ),
+        };
+        (config, message)
+    }
+
+    /// Where this config is saved
+    pub fn storage(&self) -> &ConfigStorage {
+        &self.storage
+    }
+
+    /// Write the config. Sealed passwords are only referenced; one no store
+    /// took is written in plaintext rather than lost, and `unsealed_passwords`
+    /// is how the user hears about it.
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        self.storage.write(&serde_json::to_string_pretty(self)?)
+    }
+
+    /// Move passwords that are not in a credential store yet (new, edited or
+    /// read from plaintext) into one. Only those touch the store, so saving
+    /// settings never does. Returns true when one was moved and the config
+    /// should be saved again without it.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn seal_credentials(&mut self) -> bool {
+        if !self.storage.persistent() {
+            return false;
         }
-        Ok(AppConfig::default())
+        let mut sealed = false;
+        for account in self.accounts.iter_mut().filter(|a| !a.password.is_empty()) {
+            sealed |= account.seal_password();
+        }
+        for host in self.kodi_hosts.iter_mut().filter(|h| h.password.is_some()) {
+            sealed |= host.seal_password();
+        }
+        sealed
     }
 
+    /// Names of the accounts and Kodi hosts whose passwords would be saved in
+    /// plaintext, because no keyring answers and the file vault is locked
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn save(&self) -> Result<(), anyhow::Error> {
-        if let Some(proj_dirs) = ProjectDirs::from("com", "vibecoding", "vibe-iptv") {
-            let config_dir = proj_dirs.config_dir();
-            fs::create_dir_all(config_dir)?;
-            let config_path = config_dir.join("config.json");
-            let content = serde_json::to_string_pretty(self)?;
-            fs::write(config_path, content)?;
+    pub fn unsealed_passwords(&self) -> Vec<String> {
+        if !self.storage.persistent() {
+            return Vec::new();
         }
-        Ok(())
+        let accounts = self
+            .accounts
+            .iter()
+            .filter(|a| !a.password.is_empty())
+            .map(|a| a.name.clone());
+        let hosts = self
+            .kodi_hosts
+            .iter()
+            .filter(|h| h.password.as_deref().is_some_and(|p| !p.is_empty()))
+            .map(|h| h.name.clone());
+        accounts.chain(hosts).collect()
     }
 
-    #[cfg(target_arch = "wasm32")]
-    pub fn save(&self) -> Result<(), anyhow::Error> {
-        if let Some(window) = web_sys::window() {
-            if let Ok(Some(storage)) = window.local_storage() {
-                let json = serde_json::to_string(self)?;
-                let _ = storage.set_item("app_config", &json);
-            }
+    /// Warning for `unsealed_passwords`, if there are any
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn unsealed_passwords_warning(&self) -> Option<String> {
+        let names = self.unsealed_passwords();
+        (!names.is_empty()).then(|| {
+            format!(
+                "No keyring or unlocked vault: passwords for {} are kept in plaintext in the config file. Restart and unlock the vault (or set {}) to seal them.",
+                names.join(", "),
+                crate::vault::PASSPHRASE_ENV
+            )
+        })
+    }
+
+    /// Whether the relay accepts devices other than this computer
+    pub fn relay_on_lan(&self) -> bool {
+        self.relay_bind
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| !ip.is_loopback())
+    }
+
+    /// Reject an account the next load would have to repair (see
+    /// `config_schema`): a blank name or URL, or a name another account already uses. `index` is
+    /// the account being edited, if any.
+    pub fn check_account(&self, account: &Account, index: Option<usize>) -> Result<(), String> {
+        if account.name.trim().is_empty() {
+            return Err("Name is required".to_string());
+        }
+        if account.base_url.trim().is_empty() {
+            return Err("URL is required".to_string());
+        }
+        let taken = self
+            .accounts
+            .iter()
+            .enumerate()
+            .any(|(i, a)| Some(i) != index && a.name == account.name);
+        if taken {
+            return Err(format!(
+                "An account named \"{}\" already exists",
+                account.name
+            ));
         }
         Ok(())
     }
 
-    pub fn add_account(&mut self, account: Account) {
+    pub fn add_account(&mut self, account: Account) -> Result<(), String> {
+        self.check_account(&account, None)?;
         self.accounts.push(account);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.seal_credentials();
         let _ = self.save();
+        Ok(())
     }
 
-    pub fn update_account(&mut self, index: usize, account: Account) {
-        if index < self.accounts.len() {
-            self.accounts[index] = account;
+    pub fn update_account(&mut self, index: usize, mut account: Account) -> Result<(), String> {
+        if index >= self.accounts.len() {
+            return Err("That account no longer exists".to_string());
+        }
+        self.check_account(&account, Some(index))?;
+        // Keep the same vault entry for the edited password
+        if account.credential.is_none() {
+            account.credential = self.accounts[index].credential.clone();
+        }
+        self.accounts[index] = account;
+        #[cfg(not(target_arch = "wasm32"))]
+        self.seal_credentials();
+        let _ = self.save();
+        Ok(())
+    }
+
+    /// Add a Kodi host, replacing one at the same address
+    pub fn add_kodi_host(&mut self, host: crate::kodi::KodiHost) {
+        let (kept, replaced): (Vec<_>, Vec<_>) = std::mem::take(&mut self.kodi_hosts)
+            .into_iter()
+            .partition(|h| h.host != host.host || h.port != host.port);
+        self.kodi_hosts = kept;
+        for old in replaced {
+            self.forget_kodi_credential(&old);
+        }
+        self.kodi_hosts.push(host);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.seal_credentials();
+        let _ = self.save();
+    }
+
+    pub fn remove_kodi_host(&mut self, index: usize) {
+        if index < self.kodi_hosts.len() {
+            let host = self.kodi_hosts.remove(index);
+            self.forget_kodi_credential(&host);
             let _ = self.save();
         }
     }
 
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    fn forget_kodi_credential(&self, host: &crate::kodi::KodiHost) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(reference) = &host.credential {
+            crate::vault::forget(reference);
+        }
+    }
+
     pub fn remove_account(&mut self, index: usize) {
         if index < self.accounts.len() {
-            self.accounts.remove(index);
+            #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+            let account = self.accounts.remove(index);
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(reference) = &account.credential {
+                crate::vault::forget(reference);
+            }
             let _ = self.save();
         }
     }
@@ -426,6 +878,11 @@ impl AppConfig {
         let _ = self.save();
     }
 
+    /// Whether games from `league` should appear in the score ticker
+    pub fn follows_league(&self, league: &str) -> bool {
+        self.ticker_leagues.is_empty() || self.ticker_leagues.iter().any(|l| l == league)
+    }
+
     pub fn set_dns_provider(&mut self, provider: DnsProvider) {
         self.dns_provider = provider;
         let _ = self.save();
@@ -466,6 +923,142 @@ impl AppConfig {
         }
     }
 
+    /// Directory recordings are saved to (and listed from)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn recordings_path(&self) -> std::path::PathBuf {
+        if let Some(dir) = self
+            .recordings_dir
+            .as_deref()
+            .filter(|d| !d.trim().is_empty())
+        {
+            return std::path::PathBuf::from(dir.trim());
+        }
+        Self::default_recordings_path()
+    }
+
+    /// Recordings folder when none is set: Videos/Matrix IPTV
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn default_recordings_path() -> std::path::PathBuf {
+        directories::UserDirs::new()
+            .and_then(|u| u.video_dir().map(|v| v.join("Matrix IPTV")))
+            .or_else(|| crate::storage::paths().map(|p| p.data_dir.join("recordings")))
+            .unwrap_or_else(|| std::path::PathBuf::from("recordings"))
+    }
+
+    /// Add a player profile; names must be unique
+    pub fn add_player_profile(
+        &mut self,
+        profile: crate::player_profile::PlayerProfile,
+    ) -> Result<(), String> {
+        if self.player_profiles.iter().any(|p| p.name == profile.name) {
+            return Err(format!("A profile named '{}' already exists", profile.name));
+        }
+        self.player_profiles.push(profile);
+        let _ = self.save();
+        Ok(())
+    }
+
+    /// Rename a player profile, keeping it active if it was
+    pub fn rename_player_profile(&mut self, index: usize, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Name is required".to_string());
+        }
+        if self
+            .player_profiles
+            .iter()
+            .enumerate()
+            .any(|(i, p)| i != index && p.name == name)
+        {
+            return Err(format!("A profile named '{}' already exists", name));
+        }
+        let Some(profile) = self.player_profiles.get_mut(index) else {
+            return Ok(());
+        };
+        if self.active_player_profile.as_deref() == Some(profile.name.as_str()) {
+            self.active_player_profile = Some(name.to_string());
+        }
+        profile.name = name.to_string();
+        let _ = self.save();
+        Ok(())
+    }
+
+    /// Delete a player profile; deleting the active one falls back to MPV
+    pub fn remove_player_profile(&mut self, index: usize) {
+        if index >= self.player_profiles.len() {
+            return;
+        }
+        let profile = self.player_profiles.remove(index);
+        if self.active_player_profile.as_deref() == Some(profile.name.as_str()) {
+            self.active_player_profile = None;
+            if self.preferred_player == PlayerEngine::Custom {
+                self.preferred_player = PlayerEngine::default();
+            }
+        }
+        let _ = self.save();
+    }
+
+    /// The profile the Custom engine launches
+    pub fn active_player_profile(&self) -> Option<&crate::player_profile::PlayerProfile> {
+        let name = self.active_player_profile.as_deref()?;
+        self.player_profiles.iter().find(|p| p.name == name)
+    }
+
+    /// Add a scheduled recording, returning its id
+    pub fn add_recording_schedule(
+        &mut self,
+        account: String,
+        stream_id: String,
+        channel_name: String,
+        rule: ScheduleRule,
+    ) -> u64 {
+        let id = self
+            .recording_schedules
+            .iter()
+            .map(|s| s.id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        self.recording_schedules.push(RecordingSchedule {
+            id,
+            account,
+            stream_id,
+            channel_name,
+            rule,
+        });
+        let _ = self.save();
+        id
+    }
+
+    pub fn remove_recording_schedules(&mut self, ids: &[u64]) {
+        let before = self.recording_schedules.len();
+        self.recording_schedules.retain(|s| !ids.contains(&s.id));
+        if self.recording_schedules.len() != before {
+            let _ = self.save();
+        }
+    }
+
+    /// Save a multiview preset, replacing any existing preset with the same name
+    pub fn save_multiview_preset(&mut self, name: String, tiles: Vec<MultiviewTile>) {
+        let preset = MultiviewPreset { name, tiles };
+        match self
+            .multiview_presets
+            .iter_mut()
+            .find(|p| p.name == preset.name)
+        {
+            Some(existing) => *existing = preset,
+            None => self.multiview_presets.push(preset),
+        }
+        let _ = self.save();
+    }
+
+    pub fn delete_multiview_preset(&mut self, index: usize) {
+        if index < self.multiview_presets.len() {
+            self.multiview_presets.remove(index);
+            let _ = self.save();
+        }
+    }
+
     pub fn rename_group(&mut self, group_index: usize, new_name: String) {
         if let Some(group) = self.favorites.groups.get_mut(group_index) {
             group.name = new_name;
@@ -0,0 +1,314 @@
+//! config.json schema versions, the migrations between them, and validation.
+//!
+//! Each migration upgrades the raw JSON by one version, so a file written by
+//! any older release is brought forward step by step before it is parsed. The
+//! caller keeps a backup of the file before the first step runs. A step that
+//! has to change what the user wrote (rather than just its shape) leaves a note
+//! saying so, which is shown once the app starts.
+
+use crate::config::AppConfig;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+/// Version written by this build
+pub const CURRENT_SCHEMA_VERSION: u32 = 3;
+
+/// Migration from version N-1 to N, in order. `apply` pushes a note for
+/// each change the user should hear about.
+struct Migration {
+    to: u32,
+    description: &'static str,
+    apply: fn(&mut Value, &mut Vec<String>),
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        to: 1,
+        description: "convert legacy playlist_mode to processing_modes",
+        apply: playlist_mode_to_processing_modes,
+    },
+    Migration {
+        to: 2,
+        description: "turn off use_default_mpv, which skipped the IPTV stability fixes",
+        apply: disable_default_mpv,
+    },
+    Migration {
+        to: 3,
+        description: "give accounts with blank or duplicate names a unique name",
+        apply: repair_account_names,
+    },
+];
+
+/// Why a config cannot be used, pointing at the offending field
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("could not read the config: {0}")]
+    Read(String),
+    #[error("not valid JSON at line {line}, column {column}: {message}")]
+    Syntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("{field}: {message}")]
+    Field { field: String, message: String },
+    #[error("written by a newer version (schema {found}; this build reads up to {supported})")]
+    TooNew { found: u32, supported: u32 },
+}
+
+impl ConfigError {
+    fn field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        ConfigError::Field {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Parse `content`, bringing it up to the current schema. Returns the config
+/// (with the migrations' notes in `notices`) and the version it was stored as.
+pub fn parse(content: &str) -> Result<(AppConfig, u32), ConfigError> {
+    let mut value: Value = serde_json::from_str(content).map_err(|e| ConfigError::Syntax {
+        line: e.line(),
+        column: e.column(),
+        message: e.to_string(),
+    })?;
+    if !value.is_object() {
+        return Err(ConfigError::field("(top level)", "expected an object"));
+    }
+
+    let stored = match value.get("schema_version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| ConfigError::field("schema_version", "expected a whole number"))?,
+    };
+    if stored > CURRENT_SCHEMA_VERSION {
+        return Err(ConfigError::TooNew {
+            found: stored,
+            supported: CURRENT_SCHEMA_VERSION,
+        });
+    }
+    let mut notes = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.to > stored) {
+        (migration.apply)(&mut value, &mut notes);
+        value["schema_version"] = json!(migration.to);
+    }
+
+    let mut config: AppConfig = serde_path_to_error::deserialize(value).map_err(|e| {
+        let field = e.path().to_string();
+        ConfigError::field(field, e.into_inner().to_string())
+    })?;
+    validate(&config)?;
+    config.notices = notes;
+    Ok((config, stored))
+}
+
+/// Migrations that run for a file stored at `version`
+pub fn pending(version: u32) -> Vec<&'static str> {
+    MIGRATIONS
+        .iter()
+        .filter(|m| m.to > version)
+        .map(|m| m.description)
+        .collect()
+}
+
+/// Values that parse but would break the app. Account names and URLs are
+/// repaired by migration instead, so one bad account never costs the others.
+pub fn validate(config: &AppConfig) -> Result<(), ConfigError> {
+    if let Some(tz) = config.timezone.as_deref() {
+        if tz.parse::<chrono_tz::Tz>().is_err() {
+            return Err(ConfigError::field(
+                "timezone",
+                format!(
+                    "\"{}\" is not a known time zone (e.g. \"America/New_York\")",
+                    tz
+                ),
+            ));
+        }
+    }
+    if config.relay_port == 0 {
+        return Err(ConfigError::field(
+            "relay_port",
+            "must be between 1 and 65535",
+        ));
+    }
+    if config.relay_bind.parse::<std::net::IpAddr>().is_err() {
+        return Err(ConfigError::field(
+            "relay_bind",
+            format!("\"{}\" is not an IP address", config.relay_bind),
+        ));
+    }
+    Ok(())
+}
+
+fn playlist_mode_to_processing_modes(config: &mut Value, _notes: &mut Vec<String>) {
+    let has_modes = config
+        .get("processing_modes")
+        .and_then(Value::as_array)
+        .is_some_and(|m| !m.is_empty());
+    if has_modes {
+        return;
+    }
+    let modes = match config.get("playlist_mode").and_then(Value::as_str) {
+        Some("Merica") => json!(["Merica"]),
+        Some("Sports") => json!(["Sports"]),
+        Some("AllEnglish") => json!(["AllEnglish"]),
+        Some("SportsMerica") => json!(["Merica", "Sports"]),
+        _ => return,
+    };
+    config["processing_modes"] = modes;
+}
+
+fn disable_default_mpv(config: &mut Value, _notes: &mut Vec<String>) {
+    config["use_default_mpv"] = json!(false);
+}
+
+/// Caches and history are keyed by the account name, so every account needs
+/// its own. The first account with a name keeps it; later ones and blank ones
+/// get "Name (2)", "Account (3)" and so on. A missing server URL is left for
+/// the user to fill in.
+fn repair_account_names(config: &mut Value, notes: &mut Vec<String>) {
+    let Some(accounts) = config.get_mut("accounts").and_then(Value::as_array_mut) else {
+        return;
+    };
+    let name_of = |a: &Value| {
+        a.get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .trim()
+            .to_string()
+    };
+    let mut taken: std::collections::HashSet<String> = accounts
+        .iter()
+        .map(name_of)
+        .filter(|n| !n.is_empty())
+        .collect();
+    let mut kept = std::collections::HashSet::new();
+    for account in accounts.iter_mut() {
+        let name = name_of(account);
+        if !name.is_empty() && kept.insert(name.clone()) {
+            continue;
+        }
+        let base = if name.is_empty() {
+            "Account"
+        } else {
+            name.as_str()
+        };
+        let new_name = (1..)
+            .map(|n| match n {
+                1 => base.to_string(),
+                n => format!("{} ({})", base, n),
+            })
+            .find(|candidate| !taken.contains(candidate))
+            .unwrap_or_default();
+        notes.push(if name.is_empty() {
+            format!("An account had no name; it is now \"{}\".", new_name)
+        } else {
+            format!(
+                "Two accounts were named \"{}\"; the second is now \"{}\".",
+                name, new_name
+            )
+        });
+        taken.insert(new_name.clone());
+        kept.insert(new_name.clone());
+        account["name"] = json!(new_name);
+    }
+    for account in accounts.iter() {
+        let url = account
+            .get("base_url")
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        if url.trim().is_empty() {
+            notes.push(format!(
+                "Account \"{}\" has no server URL; add one in Settings > Accounts.",
+                name_of(account)
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_files_are_migrated_in_order() {
+        let (config, stored) =
+            parse(r#"{"accounts": [], "playlist_mode": "SportsMerica", "use_default_mpv": true}"#)
+                .unwrap();
+        assert_eq!(stored, 0);
+        assert_eq!(config.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(config.processing_modes.len(), 2);
+        assert!(!config.use_default_mpv);
+        assert_eq!(pending(0).len(), MIGRATIONS.len());
+
+        // A current file keeps the user's choice
+        let (config, _) = parse(&format!(
+            r#"{{"schema_version": {}, "accounts": [], "use_default_mpv": true}}"#,
+            CURRENT_SCHEMA_VERSION
+        ))
+        .unwrap();
+        assert!(config.use_default_mpv);
+        assert!(pending(CURRENT_SCHEMA_VERSION).is_empty());
+    }
+
+    #[test]
+    fn blank_and_duplicate_account_names_are_repaired() {
+        let account = |name: &str, url: &str| {
+            format!(
+                r#"{{"name": "{}", "base_url": "{}", "username": "", "epg_url": null,
+                    "last_refreshed": null, "total_channels": null, "total_movies": null,
+                    "total_series": null, "server_timezone": null}}"#,
+                name, url
+            )
+        };
+        let (config, stored) = parse(&format!(
+            r#"{{"schema_version": 2, "accounts": [{}, {}, {}, {}]}}"#,
+            account("Home", "http://a"),
+            account("Home", "http://b"),
+            account("Home (2)", "http://c"),
+            account(" ", ""),
+        ))
+        .unwrap();
+        assert_eq!(stored, 2);
+        let names: Vec<&str> = config.accounts.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["Home", "Home (3)", "Home (2)", "Account"]);
+        // Two renames and the missing URL
+        assert_eq!(config.notices.len(), 3, "{:?}", config.notices);
+        assert!(config.notices[2].contains("no server URL"));
+    }
+
+    #[test]
+    fn errors_name_the_offending_field() {
+        let err = parse(r#"{"accounts": [], "relay_port": "abc"}"#).unwrap_err();
+        assert!(err.to_string().starts_with("relay_port: "), "{}", err);
+
+        let err = parse(
+            r#"{"accounts": [{"name": "A", "base_url": "http://a", "username": "",
+                "epg_url": null, "last_refreshed": null, "total_channels": null,
+                "total_movies": null, "total_series": null, "server_timezone": null,
+                "account_type": "Nope"}]}"#,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().starts_with("accounts[0].account_type: "),
+            "{}",
+            err
+        );
+
+        let err = parse(r#"{"accounts": [], "timezone": "Mars/Olympus"}"#).unwrap_err();
+        assert!(err.to_string().starts_with("timezone: "), "{}", err);
+
+        let err = parse("{\"accounts\": [,]}").unwrap_err();
+        assert!(
+            matches!(err, ConfigError::Syntax { line: 1, .. }),
+            "{}",
+            err
+        );
+
+        let err = parse(r#"{"schema_version": 99, "accounts": []}"#).unwrap_err();
+        assert!(matches!(err, ConfigError::TooNew { found: 99, .. }));
+    }
+}
@@ -0,0 +1,670 @@
+//! DLNA/UPnP MediaRenderer casting
+//!
+//! Renderers are found with an SSDP M-SEARCH, described by the device XML at
+//! the advertised LOCATION and driven through their AVTransport service with
+//! SOAP requests. Unlike Chromecast this needs nothing beyond HTTP and UDP.
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::cast::{CastState, CastStatus};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Duration;
+
+/// SSDP multicast group and port
+pub const SSDP_ADDR: &str = "239.255.255.250:1900";
+
+/// Device type searched for
+pub const MEDIA_RENDERER: &str = "urn:schemas-upnp-org:device:MediaRenderer:1";
+
+/// Service that takes the stream URL and the play/pause/stop commands
+pub const AV_TRANSPORT: &str = "urn:schemas-upnp-org:service:AVTransport:1";
+
+/// Service for volume and mute
+pub const RENDERING_CONTROL: &str = "urn:schemas-upnp-org:service:RenderingControl:1";
+
+/// A discovered DLNA media renderer
+#[derive(Debug, Clone)]
+pub struct DlnaRenderer {
+    /// Friendly name of the device (e.g., "Bedroom TV")
+    pub name: String,
+    /// Device description URL from the SSDP response
+    pub location: String,
+    /// Absolute AVTransport control URL
+    pub control_url: String,
+    /// Absolute RenderingControl control URL, when the device has volume control
+    pub rendering_control_url: Option<String>,
+    /// Model name if available
+    pub model: Option<String>,
+}
+
+impl PartialEq for DlnaRenderer {
+    fn eq(&self, other: &Self) -> bool {
+        self.control_url == other.control_url
+    }
+}
+
+/// M-SEARCH request for media renderers; `mx` is the response window in seconds
+pub fn search_request(mx: u64) -> String {
+    format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {}\r\nMAN: \"ssdp:discover\"\r\nMX: {}\r\nST: {}\r\n\r\n",
+        SSDP_ADDR, mx, MEDIA_RENDERER
+    )
+}
+
+/// LOCATION of a successful SSDP search response
+pub fn parse_search_response(response: &str) -> Option<String> {
+    let mut lines = response.lines();
+    if !lines.next()?.contains(" 200") {
+        return None;
+    }
+    lines.find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case("location")
+            .then(|| value.trim().to_string())
+    })
+}
+
+/// Build a renderer from its device description. Returns None when the device
+/// has no AVTransport service.
+pub fn parse_description(xml: &str, location: &str) -> Option<DlnaRenderer> {
+    let base = reqwest::Url::parse(tag_text(xml, "URLBase").unwrap_or(location)).ok()?;
+    let control_url = |service: &str| {
+        let block = xml
+            .split("<service>")
+            .skip(1)
+            .find(|block| tag_text(block, "serviceType").is_some_and(|t| t.contains(service)))?;
+        Some(base.join(tag_text(block, "controlURL")?).ok()?.to_string())
+    };
+
+    let host = reqwest::Url::parse(location)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()));
+    let name = tag_text(xml, "friendlyName")
+        .map(xml_unescape)
+        .or(host)
+        .unwrap_or_else(|| "DLNA renderer".to_string());
+
+    Some(DlnaRenderer {
+        name,
+        location: location.to_string(),
+        control_url: control_url("AVTransport")?,
+        rendering_control_url: control_url("RenderingControl"),
+        model: tag_text(xml, "modelName").map(xml_unescape),
+    })
+}
+
+/// Trimmed text of the first `<tag>` element
+fn tag_text<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = xml[start..].find(&format!("</{}>", tag))? + start;
+    Some(xml[start..end].trim()).filter(|t| !t.is_empty())
+}
+
+pub fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// DIDL-Lite item describing the stream; many renderers refuse a URI without it
+pub fn didl_metadata(url: &str, title: &str) -> String {
+    format!(
+        "<DIDL-Lite xmlns=\"urn:schemas-upnp-org:metadata-1-0/DIDL-Lite/\" \
+         xmlns:dc=\"http://purl.org/dc/elements/1.1/\" \
+         xmlns:upnp=\"urn:schemas-upnp-org:metadata-1-0/upnp/\">\
+         <item id=\"0\" parentID=\"-1\" restricted=\"1\">\
+         <dc:title>{}</dc:title>\
+         <upnp:class>object.item.videoItem</upnp:class>\
+         <res protocolInfo=\"http-get:*:video/mp2t:*\">{}</res>\
+         </item></DIDL-Lite>",
+        xml_escape(title),
+        xml_escape(url)
+    )
+}
+
+/// Seconds from a UPnP `H+:MM:SS[.F]` time; None for `NOT_IMPLEMENTED` and the like
+pub fn parse_duration(value: &str) -> Option<f64> {
+    let mut parts = value.trim().split(':');
+    let hours: f64 = parts.next()?.parse().ok()?;
+    let minutes: f64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// UPnP `H:MM:SS` time for a seek target
+pub fn format_duration(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    format!(
+        "{}:{:02}:{:02}",
+        total / 3600,
+        (total / 60) % 60,
+        total % 60
+    )
+}
+
+/// SOAP envelope for an action of `service` on instance 0
+pub fn soap_envelope(service: &str, action: &str, args: &[(&str, String)]) -> String {
+    let mut body = String::from("<InstanceID>0</InstanceID>");
+    for (name, value) in args {
+        body.push_str(&format!("<{0}>{1}</{0}>", name, xml_escape(value)));
+    }
+    format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+         <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+         s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+         <s:Body><u:{0} xmlns:u=\"{1}\">{2}</u:{0}></s:Body></s:Envelope>",
+        action, service, body
+    )
+}
+
+/// Readable reason from a UPnP SOAP fault body
+fn soap_fault(body: &str) -> Option<String> {
+    match (
+        tag_text(body, "errorCode"),
+        tag_text(body, "errorDescription"),
+    ) {
+        (Some(code), Some(desc)) => Some(format!("{} (UPnP error {})", desc, code)),
+        (None, Some(desc)) => Some(desc.to_string()),
+        (Some(code), None) => Some(format!("UPnP error {}", code)),
+        (None, None) => None,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl DlnaRenderer {
+    /// Run one SOAP action and return the response body
+    async fn call(
+        &self,
+        client: &reqwest::Client,
+        service: &str,
+        action: &str,
+        args: &[(&str, String)],
+    ) -> Result<String, anyhow::Error> {
+        let control_url = if service == RENDERING_CONTROL {
+            self.rendering_control_url
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("'{}' has no volume control", self.name))?
+        } else {
+            &self.control_url
+        };
+        let resp = client
+            .post(control_url)
+            .header("Content-Type", "text/xml; charset=\"utf-8\"")
+            .header("SOAPAction", format!("\"{}#{}\"", service, action))
+            .body(soap_envelope(service, action, args))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to reach '{}': {}", self.name, e))?;
+        let status = resp.status();
+        let body = resp.text().await.unwrap_or_default();
+        if status.is_success() {
+            return Ok(body);
+        }
+        Err(anyhow::anyhow!(
+            "'{}' rejected {}: {}",
+            self.name,
+            action,
+            soap_fault(&body).unwrap_or_else(|| status.to_string())
+        ))
+    }
+
+    async fn invoke(
+        &self,
+        client: &reqwest::Client,
+        action: &str,
+        args: &[(&str, String)],
+    ) -> Result<(), anyhow::Error> {
+        self.call(client, AV_TRANSPORT, action, args).await?;
+        Ok(())
+    }
+
+    /// Hand the renderer a stream URL (AVTransport SetAVTransportURI)
+    pub async fn set_uri(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        title: &str,
+    ) -> Result<(), anyhow::Error> {
+        self.invoke(
+            client,
+            "SetAVTransportURI",
+            &[
+                ("CurrentURI", url.to_string()),
+                ("CurrentURIMetaData", didl_metadata(url, title)),
+            ],
+        )
+        .await
+    }
+
+    pub async fn play(&self, client: &reqwest::Client) -> Result<(), anyhow::Error> {
+        self.invoke(client, "Play", &[("Speed", "1".to_string())])
+            .await
+    }
+
+    pub async fn pause(&self, client: &reqwest::Client) -> Result<(), anyhow::Error> {
+        self.invoke(client, "Pause", &[]).await
+    }
+
+    pub async fn stop(&self, client: &reqwest::Client) -> Result<(), anyhow::Error> {
+        self.invoke(client, "Stop", &[]).await
+    }
+
+    /// Jump to an absolute position in seconds
+    pub async fn seek(&self, client: &reqwest::Client, secs: f64) -> Result<(), anyhow::Error> {
+        self.invoke(
+            client,
+            "Seek",
+            &[
+                ("Unit", "REL_TIME".to_string()),
+                ("Target", format_duration(secs)),
+            ],
+        )
+        .await
+    }
+
+    /// Volume in percent
+    pub async fn volume(&self, client: &reqwest::Client) -> Result<Option<u32>, anyhow::Error> {
+        let body = self
+            .call(
+                client,
+                RENDERING_CONTROL,
+                "GetVolume",
+                &[("Channel", "Master".to_string())],
+            )
+            .await?;
+        Ok(tag_text(&body, "CurrentVolume").and_then(|v| v.parse().ok()))
+    }
+
+    pub async fn set_volume(
+        &self,
+        client: &reqwest::Client,
+        percent: u32,
+    ) -> Result<(), anyhow::Error> {
+        self.call(
+            client,
+            RENDERING_CONTROL,
+            "SetVolume",
+            &[
+                ("Channel", "Master".to_string()),
+                ("DesiredVolume", percent.min(100).to_string()),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn muted(&self, client: &reqwest::Client) -> Result<bool, anyhow::Error> {
+        let body = self
+            .call(
+                client,
+                RENDERING_CONTROL,
+                "GetMute",
+                &[("Channel", "Master".to_string())],
+            )
+            .await?;
+        Ok(matches!(tag_text(&body, "CurrentMute"), Some("1" | "true")))
+    }
+
+    pub async fn set_mute(
+        &self,
+        client: &reqwest::Client,
+        muted: bool,
+    ) -> Result<(), anyhow::Error> {
+        self.call(
+            client,
+            RENDERING_CONTROL,
+            "SetMute",
+            &[
+                ("Channel", "Master".to_string()),
+                ("DesiredMute", (muted as u8).to_string()),
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Poll transport state, position, the loaded URI and volume. `url` is what
+    /// we cast; any other URI means another controller has taken the device.
+    pub async fn status(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+    ) -> Result<CastStatus, anyhow::Error> {
+        let info = self
+            .call(client, AV_TRANSPORT, "GetTransportInfo", &[])
+            .await?;
+        let state = match tag_text(&info, "CurrentTransportState").unwrap_or("") {
+            "PLAYING" => CastState::Playing,
+            "PAUSED_PLAYBACK" | "PAUSED_RECORDING" => CastState::Paused,
+            "TRANSITIONING" => CastState::Buffering,
+            _ => CastState::Idle,
+        };
+        let media = self.call(client, AV_TRANSPORT, "GetMediaInfo", &[]).await?;
+        let taken_over = tag_text(&media, "CurrentURI")
+            .map(xml_unescape)
+            .is_some_and(|current| current != url);
+        let position = self
+            .call(client, AV_TRANSPORT, "GetPositionInfo", &[])
+            .await
+            .ok()
+            .and_then(|body| tag_text(&body, "RelTime").and_then(parse_duration));
+
+        let (volume, muted) = if self.rendering_control_url.is_some() {
+            (
+                self.volume(client).await.ok().flatten(),
+                self.muted(client).await.unwrap_or(false),
+            )
+        } else {
+            (None, false)
+        };
+
+        Ok(CastStatus {
+            state,
+            position_secs: position,
+            volume: volume.map(f64::from),
+            muted,
+            idle_reason: (state == CastState::Idle).then(|| "stopped".to_string()),
+            taken_over,
+            standby: false,
+        })
+    }
+
+    /// Load a stream and start playing it
+    pub async fn cast(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        title: Option<&str>,
+    ) -> Result<(), anyhow::Error> {
+        self.set_uri(client, url, title.unwrap_or("Matrix IPTV"))
+            .await?;
+        self.play(client).await
+    }
+}
+
+/// Discover media renderers on the local network
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn discover(timeout: Duration) -> Result<Vec<DlnaRenderer>, anyhow::Error> {
+    search(SSDP_ADDR, timeout).await
+}
+
+/// Send an M-SEARCH to `addr`, collect responses until `timeout` and fetch
+/// each advertised device description
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn search(addr: &str, timeout: Duration) -> Result<Vec<DlnaRenderer>, anyhow::Error> {
+    let socket = tokio::net::UdpSocket::bind("0.0.0.0:0")
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to open SSDP socket: {}", e))?;
+    socket
+        .send_to(
+            search_request(timeout.as_secs().clamp(1, 5)).as_bytes(),
+            addr,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to send SSDP search: {}", e))?;
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut locations: Vec<String> = Vec::new();
+    let mut buf = [0u8; 2048];
+    while let Ok(Ok((n, _))) = tokio::time::timeout_at(deadline, socket.recv_from(&mut buf)).await {
+        if let Some(location) = parse_search_response(&String::from_utf8_lossy(&buf[..n])) {
+            if !locations.contains(&location) {
+                locations.push(location);
+            }
+        }
+    }
+
+    let client = crate::cast::lan_client()?;
+    let mut renderers: Vec<DlnaRenderer> = Vec::new();
+    for location in locations {
+        let Ok(resp) = client.get(&location).send().await else {
+            continue;
+        };
+        let Ok(xml) = resp.text().await else {
+            continue;
+        };
+        if let Some(renderer) = parse_description(&xml, &location) {
+            if !renderers.contains(&renderer) {
+                renderers.push(renderer);
+            }
+        }
+    }
+    Ok(renderers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const DESCRIPTION: &str = "<?xml version=\"1.0\"?>\
+        <root xmlns=\"urn:schemas-upnp-org:device-1-0\"><device>\
+        <deviceType>urn:schemas-upnp-org:device:MediaRenderer:1</deviceType>\
+        <friendlyName>Bedroom TV</friendlyName><modelName>Bravia</modelName>\
+        <serviceList>\
+        <service><serviceType>urn:schemas-upnp-org:service:RenderingControl:1</serviceType>\
+        <controlURL>/rc</controlURL></service>\
+        <service><serviceType>urn:schemas-upnp-org:service:AVTransport:1</serviceType>\
+        <controlURL>/avt</controlURL></service>\
+        </serviceList></device></root>";
+
+    /// Fake renderer: serves the description, records SOAP actions and
+    /// rejects Pause with a UPnP fault
+    async fn fake_renderer() -> (String, Arc<Mutex<Vec<(String, String)>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let actions = Arc::new(Mutex::new(Vec::new()));
+        let seen = actions.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let seen = seen.clone();
+                tokio::spawn(async move {
+                    let mut data = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    let (head, body) = loop {
+                        let n = socket.read(&mut buf).await.unwrap();
+                        data.extend_from_slice(&buf[..n]);
+                        let text = String::from_utf8_lossy(&data).to_string();
+                        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                            let length = head
+                                .lines()
+                                .find_map(|l| {
+                                    let (k, v) = l.split_once(':')?;
+                                    k.eq_ignore_ascii_case("content-length")
+                                        .then(|| v.trim().parse::<usize>().unwrap())
+                                })
+                                .unwrap_or(0);
+                            if body.len() >= length || n == 0 {
+                                break (head.to_string(), body.to_string());
+                            }
+                        }
+                    };
+                    let action = head
+                        .lines()
+                        .find_map(|l| {
+                            let (k, v) = l.split_once(':')?;
+                            k.eq_ignore_ascii_case("soapaction")
+                                .then(|| v.trim().to_string())
+                        })
+                        .map(|a| a.trim_matches('"').rsplit('#').next().unwrap().to_string());
+                    let (status, reply) = match action {
+                        None => ("200 OK", DESCRIPTION.to_string()),
+                        Some(action) if action.starts_with("Get") => {
+                            ("200 OK", query_reply(&action, &seen.lock().unwrap()))
+                        }
+                        Some(action) => {
+                            let failed = action == "Pause";
+                            seen.lock().unwrap().push((action, body));
+                            if failed {
+                                (
+                                    "500 Internal Server Error",
+                                    "<s:Envelope><s:Body><s:Fault><detail><UPnPError>\
+                                     <errorCode>701</errorCode>\
+                                     <errorDescription>Transition not available</errorDescription>\
+                                     </UPnPError></detail></s:Fault></s:Body></s:Envelope>"
+                                        .to_string(),
+                                )
+                            } else {
+                                ("200 OK", "<s:Envelope/>".to_string())
+                            }
+                        }
+                    };
+                    let resp = format!(
+                        "HTTP/1.1 {}\r\nContent-Type: text/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        reply.len(),
+                        reply
+                    );
+                    let _ = socket.write_all(resp.as_bytes()).await;
+                });
+            }
+        });
+        (base, actions)
+    }
+
+    /// Answers to the status queries; the URI is whatever was last set
+    fn query_reply(action: &str, seen: &[(String, String)]) -> String {
+        let uri = seen
+            .iter()
+            .rev()
+            .find(|(a, _)| a == "SetAVTransportURI")
+            .and_then(|(_, body)| tag_text(body, "CurrentURI").map(|u| u.to_string()))
+            .unwrap_or_default();
+        match action {
+            "GetTransportInfo" => "<CurrentTransportState>PLAYING</CurrentTransportState>".into(),
+            "GetMediaInfo" => format!("<CurrentURI>{}</CurrentURI>", uri),
+            "GetPositionInfo" => "<RelTime>0:01:05</RelTime>".into(),
+            "GetVolume" => "<CurrentVolume>30</CurrentVolume>".into(),
+            "GetMute" => "<CurrentMute>0</CurrentMute>".into(),
+            _ => String::new(),
+        }
+    }
+
+    /// Fake SSDP responder pointing at `location`
+    async fn fake_ssdp(location: String) -> String {
+        let socket = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            while let Ok((n, from)) = socket.recv_from(&mut buf).await {
+                let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                if request.starts_with("M-SEARCH") && request.contains(MEDIA_RENDERER) {
+                    let reply = format!(
+                        "HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age=1800\r\nLocation: {}\r\nST: {}\r\n\r\n",
+                        location, MEDIA_RENDERER
+                    );
+                    let _ = socket.send_to(reply.as_bytes(), from).await;
+                }
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn parses_search_response_and_description() {
+        let response =
+            "HTTP/1.1 200 OK\r\nST: x\r\nLOCATION: http://10.0.0.5:49152/desc.xml\r\n\r\n";
+        assert_eq!(
+            parse_search_response(response).as_deref(),
+            Some("http://10.0.0.5:49152/desc.xml")
+        );
+        assert_eq!(parse_search_response("NOTIFY * HTTP/1.1\r\n\r\n"), None);
+
+        let renderer = parse_description(DESCRIPTION, "http://10.0.0.5:49152/desc.xml").unwrap();
+        assert_eq!(renderer.name, "Bedroom TV");
+        assert_eq!(renderer.model.as_deref(), Some("Bravia"));
+        assert_eq!(renderer.control_url, "http://10.0.0.5:49152/avt");
+        assert_eq!(
+            renderer.rendering_control_url.as_deref(),
+            Some("http://10.0.0.5:49152/rc")
+        );
+
+        let no_transport = DESCRIPTION.replace("AVTransport", "ConnectionManager");
+        assert!(parse_description(&no_transport, "http://10.0.0.5/").is_none());
+    }
+
+    #[test]
+    fn envelope_escapes_metadata() {
+        let url = "http://host/live/u/p/1.ts?a=1&b=2";
+        let envelope = soap_envelope(
+            AV_TRANSPORT,
+            "SetAVTransportURI",
+            &[
+                ("CurrentURI", url.to_string()),
+                ("CurrentURIMetaData", didl_metadata(url, "Sky <Sports>")),
+            ],
+        );
+        assert!(envelope.contains("<CurrentURI>http://host/live/u/p/1.ts?a=1&amp;b=2</CurrentURI>"));
+        assert!(envelope.contains("&lt;dc:title&gt;Sky &amp;lt;Sports&amp;gt;&lt;/dc:title&gt;"));
+        assert!(envelope.contains("<InstanceID>0</InstanceID>"));
+    }
+
+    #[test]
+    fn converts_upnp_times() {
+        assert_eq!(parse_duration("0:01:05"), Some(65.0));
+        assert_eq!(parse_duration("01:00:00.500"), Some(3600.5));
+        assert_eq!(parse_duration("NOT_IMPLEMENTED"), None);
+        assert_eq!(format_duration(3725.9), "1:02:05");
+        assert_eq!(format_duration(-4.0), "0:00:00");
+    }
+
+    #[tokio::test]
+    async fn discovers_and_drives_a_fake_renderer() {
+        let (base, actions) = fake_renderer().await;
+        let ssdp = fake_ssdp(format!("{}/desc.xml", base)).await;
+
+        let renderers = search(&ssdp, Duration::from_millis(500)).await.unwrap();
+        assert_eq!(renderers.len(), 1);
+        let renderer = &renderers[0];
+        assert_eq!(renderer.name, "Bedroom TV");
+        assert_eq!(renderer.control_url, format!("{}/avt", base));
+
+        let client = crate::cast::lan_client().unwrap();
+        renderer
+            .cast(&client, "http://host/1.ts", Some("News"))
+            .await
+            .unwrap();
+        let status = renderer.status(&client, "http://host/1.ts").await.unwrap();
+        assert_eq!(status.state, CastState::Playing);
+        assert_eq!(status.position_secs, Some(65.0));
+        assert_eq!(status.volume, Some(30.0));
+        assert!(!status.taken_over);
+        let status = renderer.status(&client, "http://host/2.ts").await.unwrap();
+        assert!(status.taken_over);
+
+        renderer.seek(&client, 125.0).await.unwrap();
+        renderer.stop(&client).await.unwrap();
+        let err = renderer.pause(&client).await.unwrap_err().to_string();
+        assert!(
+            err.contains("Transition not available (UPnP error 701)"),
+            "{}",
+            err
+        );
+
+        let actions = actions.lock().unwrap();
+        let names: Vec<&str> = actions.iter().map(|(a, _)| a.as_str()).collect();
+        assert_eq!(
+            names,
+            ["SetAVTransportURI", "Play", "Seek", "Stop", "Pause"]
+        );
+        assert!(actions[2].1.contains("<Target>0:02:05</Target>"));
+        assert!(actions[0]
+            .1
+            .contains("<CurrentURI>http://host/1.ts</CurrentURI>"));
+        assert!(actions[1].1.contains("<Speed>1</Speed>"));
+    }
+}
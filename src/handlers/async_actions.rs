@@ -1,10 +1,9 @@
 use crate::api::{Category, SeriesEpisode, Stream};
-use crate::app::{App, AsyncAction, CurrentScreen, Pane};
-use crate::cache::CachedCatalog;
+use crate::app::{App, AsyncAction, CurrentScreen, OfflineAction, Pane};
+use crate::cache::{CacheHeader, CacheSection, CachedCatalog};
 use crate::{parser, preprocessing};
 use futures::join;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 
 pub async fn handle_async_action(
@@ -68,106 +67,8 @@ pub async fn handle_async_action(
                 .filter(|cache| !cache.modes_changed(&current_modes));
 
             if let Some(cache) = cache_hit {
-                // Cache hit - populate UI instantly
-                app.all_categories = cache.live_categories.into_iter().map(Arc::new).collect();
-                app.categories = app.all_categories.clone();
-                app.global_all_streams = cache.live_streams.into_iter().map(Arc::new).collect();
-
-                app.all_vod_categories = cache.vod_categories.into_iter().map(Arc::new).collect();
-                app.vod_categories = app.all_vod_categories.clone();
-                app.global_all_vod_streams = cache.vod_streams.into_iter().map(Arc::new).collect();
-
-                app.all_series_categories =
-                    cache.series_categories.into_iter().map(Arc::new).collect();
-                app.series_categories = app.all_series_categories.clone();
-                app.global_all_series_streams =
-                    cache.series_streams.into_iter().map(Arc::new).collect();
-
-                app.session.total_channels = cache.total_channels;
-                app.session.total_movies = cache.total_movies;
-                app.session.total_series = cache.total_series;
-
-                // Restore category counts
-                app.category_channel_counts = cache.category_counts.into_iter().collect();
-
-                // Navigate to content selection immediately
-                app.current_screen = CurrentScreen::ContentTypeSelection;
-                app.session.state_loading = false;
-                app.session.cache_loaded = true;
-
-                // Select first items and populate streams from cache
-                if !app.categories.is_empty() {
-                    app.select_category(0);
-                }
-                if !app.vod_categories.is_empty() {
-                    app.selected_vod_category_index = 0;
-                    app.vod_category_list_state.select(Some(0));
-                }
-                if !app.series_categories.is_empty() {
-                    app.selected_series_category_index = 0;
-                    app.series_category_list_state.select(Some(0));
-                }
-
-                // Spawn background refresh to update data silently
-                app.session.background_refresh_active = true;
-
-                if let Some(client) = &app.session.current_client {
-                    let client = client.clone();
-                    let tx = tx.clone();
-                    let pms = current_modes.clone();
-                    let account_name_bg = account_name.clone();
-                    let cat_favs = app.config.favorites.categories.clone();
-                    let vod_cat_favs = app.config.favorites.vod_categories.clone();
-
-                    // Spawn a single task that fetches all categories in parallel using join!
-                    tokio::spawn(async move {
-                        // Execute all category fetches in parallel
-                        let (live_result, vod_result, series_result) = join!(
-                            client.get_live_categories(),
-                            client.get_vod_categories(),
-                            client.get_series_categories()
-                        );
-
-                        // Process and send Live Categories (silent fail on background refresh)
-                        if let Ok(mut cats) = live_result {
-                            preprocessing::preprocess_categories(
-                                &mut cats,
-                                &cat_favs,
-                                &pms,
-                                true,
-                                false,
-                                &account_name_bg,
-                            );
-                            let _ = tx.send(AsyncAction::CategoriesLoaded(cats)).await;
-                        }
-
-                        // Process and send VOD Categories
-                        if let Ok(mut cats) = vod_result {
-                            preprocessing::preprocess_categories(
-                                &mut cats,
-                                &vod_cat_favs,
-                                &pms,
-                                false,
-                                true,
-                                &account_name_bg,
-                            );
-                            let _ = tx.send(AsyncAction::VodCategoriesLoaded(cats)).await;
-                        }
-
-                        // Process and send Series Categories
-                        if let Ok(mut cats) = series_result {
-                            preprocessing::preprocess_categories(
-                                &mut cats,
-                                &cat_favs,
-                                &pms,
-                                false,
-                                false,
-                                &account_name_bg,
-                            );
-                            let _ = tx.send(AsyncAction::SeriesCategoriesLoaded(cats)).await;
-                        }
-                    });
-                }
+                apply_cached_catalog(app, cache, tx);
+                spawn_background_refresh(app, tx);
 
                 // Update last_refreshed timestamp
                 let ts_now = chrono::Utc::now().timestamp();
@@ -308,7 +209,19 @@ pub async fn handle_async_action(
             app.on_channels_loaded(streams, false);
         }
         AsyncAction::LoginFailed(e) => {
-            app.login_error = Some(e);
+            let cached = app
+                .config
+                .accounts
+                .get(app.session.selected_account_index)
+                .is_some_and(|a| CachedCatalog::exists(&a.name));
+            app.login_error = Some(if cached {
+                format!(
+                    "{}\n\nHint: press o to browse the cached catalog offline",
+                    e
+                )
+            } else {
+                e
+            });
             app.session.state_loading = false;
             app.session.loading_message = None;
         }
@@ -363,6 +276,8 @@ pub async fn handle_async_action(
             let wrapped: Vec<Arc<Stream>> = streams.into_iter().map(Arc::new).collect();
             if cat_id == "ALL" {
                 app.global_all_streams = wrapped.clone();
+                app.catalog_changed();
+                spawn_search_index_build(app, None, tx);
             } else {
                 app.global_streams_by_cat
                     .insert(cat_id.clone(), wrapped.clone());
@@ -423,6 +338,8 @@ pub async fn handle_async_action(
             let wrapped: Vec<Arc<Stream>> = streams.into_iter().map(Arc::new).collect();
             if cat_id == "ALL" {
                 app.global_all_vod_streams = wrapped.clone();
+                app.catalog_changed();
+                spawn_search_index_build(app, None, tx);
             } else {
                 app.global_vod_streams_by_cat
                     .insert(cat_id.clone(), wrapped.clone());
@@ -483,6 +400,8 @@ pub async fn handle_async_action(
             let wrapped: Vec<Arc<Stream>> = streams.into_iter().map(Arc::new).collect();
             if cat_id == "ALL" {
                 app.global_all_series_streams = wrapped.clone();
+                app.catalog_changed();
+                spawn_search_index_build(app, None, tx);
             } else {
                 app.global_series_streams_by_cat
                     .insert(cat_id.clone(), wrapped.clone());
@@ -501,14 +420,127 @@ pub async fn handle_async_action(
             app.series_stream_list_state.select(Some(0));
             app.update_search();
         }
-        AsyncAction::PlayerStarted => {
+        AsyncAction::PlayerStarted(played) => {
             app.session.state_loading = false;
             app.session.loading_message = None;
+            app.now_playing = Some(crate::player_control::NowPlaying::new(played.name.clone()));
+            app.multiview_session = None;
+            app.arm_watchdog(Some(&played.stream_id));
+            app.begin_viewing(&played);
             // Track recently watched
-            if let Some(stream) = app.get_selected_stream() {
-                let id = crate::api::get_id_str(&stream.stream_id);
-                let name = stream.name.clone();
-                app.record_recently_watched(id, name);
+            app.record_recently_watched(played.stream_id, played.name);
+        }
+        AsyncAction::PlayerStatus(status) => match status {
+            Some(status) => {
+                if let Some(np) = app.now_playing.as_mut() {
+                    np.status = Some(status);
+                }
+            }
+            None => {
+                app.now_playing = None;
+                app.arm_watchdog(None);
+                app.end_viewing();
+            }
+        },
+        AsyncAction::ChannelZapRequested(delta) => {
+            app.pending_zap = Some(app.pending_zap.unwrap_or(0) + delta);
+        }
+        AsyncAction::ChannelZapped { stream_id, name } => {
+            app.session.state_loading = false;
+            app.session.loading_message = None;
+            match app.now_playing.as_mut() {
+                Some(np) => {
+                    np.title = name.clone();
+                    np.stats = None;
+                    np.cache_history.clear();
+                }
+                None => {
+                    app.now_playing = Some(crate::player_control::NowPlaying::new(name.clone()))
+                }
+            }
+            app.arm_watchdog(Some(&stream_id));
+            if let Some(stream) = app.find_live_stream(&stream_id).cloned() {
+                let played = app.played_stream(&stream);
+                app.begin_viewing(&played);
+            }
+            app.record_recently_watched(stream_id, name);
+        }
+        AsyncAction::ScheduleGuideLoaded(stream_id, listings) => {
+            app.schedule_guide
+                .insert(stream_id, crate::scheduler::guide_entries(&listings));
+        }
+        AsyncAction::RecordingPlaybackStarted(title) => {
+            app.session.state_loading = false;
+            app.session.loading_message = None;
+            app.now_playing = Some(crate::player_control::NowPlaying::new(title.clone()));
+            app.multiview_session = None;
+            app.zap_playlist.clear();
+            app.arm_watchdog(None);
+            app.begin_viewing(&crate::app::PlayedStream {
+                name: title,
+                account: "Recordings".to_string(),
+                audio_only: app.audio_only,
+                ..Default::default()
+            });
+        }
+        AsyncAction::MultiviewStarted(tiles) => {
+            app.session.state_loading = false;
+            app.session.loading_message = None;
+            let names: Vec<&str> = tiles.iter().map(|t| t.name.as_str()).collect();
+            app.now_playing = Some(crate::player_control::NowPlaying::new(format!(
+                "Multiview: {}",
+                names.join(" | ")
+            )));
+            // Channel up/down (and watchdog reloads) would replace the whole grid with one stream
+            app.zap_playlist.clear();
+            app.arm_watchdog(None);
+            app.end_viewing();
+            app.show_multiview = false;
+            app.multiview_session = Some(crate::multiview::MultiviewSession {
+                tiles,
+                audio_focus: 0,
+            });
+        }
+        AsyncAction::MultiviewFocused(focus) => {
+            if let Some(session) = app.multiview_session.as_mut() {
+                session.audio_focus = focus;
+                if let Some(name) = session.tiles.get(focus).map(|t| t.name.clone()) {
+                    if let Some(np) = app.now_playing.as_mut() {
+                        np.last_action =
+                            Some((format!("Audio: {}", name), app.session.loading_tick));
+                    }
+                }
+            }
+        }
+        AsyncAction::PlayerStats(stats) => {
+            if let Some(np) = app.now_playing.as_mut() {
+                np.record_stats(stats);
+            }
+        }
+        AsyncAction::Watchdog(event) => match event {
+            crate::watchdog::WatchdogEvent::Recovered {
+                message,
+                switched_to,
+            } => {
+                if let Some((stream_id, name)) = switched_to {
+                    if let Some(np) = app.now_playing.as_mut() {
+                        np.title = name.clone();
+                        np.stats = None;
+                        np.cache_history.clear();
+                    }
+                    if let Some(stream) = app.find_live_stream(&stream_id).cloned() {
+                        let played = app.played_stream(&stream);
+                        app.begin_viewing(&played);
+                    }
+                    app.record_recently_watched(stream_id, name);
+                }
+                app.push_toast(message);
+            }
+            crate::watchdog::WatchdogEvent::GaveUp(message) => app.push_toast(message),
+        },
+        AsyncAction::TransportSent(label) => {
+            if let Some(np) = app.now_playing.as_mut() {
+                np.last_action = Some((label, app.session.loading_tick));
             }
         }
         AsyncAction::PlayerFailed(e) => {
@@ -571,6 +603,8 @@ pub async fn handle_async_action(
             let count = streams.len();
             app.session.total_channels = count;
             app.global_all_streams = streams;
+            app.catalog_changed();
+            spawn_catalog_diff(app, crate::whats_new::Section::Live, tx);
 
             // Fast O(1) map injections on the main thread
             app.category_channel_counts.retain(|k, _| {
@@ -592,81 +626,7 @@ pub async fn handle_async_action(
             }
 
             // Save to cache for instant startup on next launch
-            let account_name = app
-                .config
-                .accounts
-                .get(app.session.selected_account_index)
-                .map(|a| a.name.clone())
-                .unwrap_or_default();
-            let account_url = app
-                .config
-                .accounts
-                .get(app.session.selected_account_index)
-                .map(|a| a.base_url.clone())
-                .unwrap_or_default();
-
-            let arc_live_categories = app.all_categories.clone();
-            let arc_live_streams = app.global_all_streams.clone();
-            let arc_vod_categories = app.all_vod_categories.clone();
-            let arc_vod_streams = app.global_all_vod_streams.clone();
-            let arc_series_categories = app.all_series_categories.clone();
-            let arc_series_streams = app.global_all_series_streams.clone();
-
-            let category_counts = app.category_channel_counts.clone().into_iter().collect();
-            let processing_modes = app.config.processing_modes.clone();
-            let total_channels = app.session.total_channels;
-            let total_movies = app.session.total_movies;
-            let total_series = app.session.total_series;
-
-            let tx_clone = tx.clone();
-            tokio::task::spawn_blocking(move || {
-                let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
-                    "Extracting background reference state...".to_string(),
-                ));
-
-                let live_categories: Vec<_> =
-                    arc_live_categories.iter().map(|c| (**c).clone()).collect();
-                let live_streams: Vec<_> = arc_live_streams.iter().map(|s| (**s).clone()).collect();
-                let vod_categories: Vec<_> =
-                    arc_vod_categories.iter().map(|c| (**c).clone()).collect();
-                let vod_streams: Vec<_> = arc_vod_streams.iter().map(|s| (**s).clone()).collect();
-                let series_categories: Vec<_> = arc_series_categories
-                    .iter()
-                    .map(|c| (**c).clone())
-                    .collect();
-                let series_streams: Vec<_> =
-                    arc_series_streams.iter().map(|s| (**s).clone()).collect();
-
-                let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
-                    "Updating Matrix Database Cache...".to_string(),
-                ));
-
-                let cache = CachedCatalog {
-                    version: 1,
-                    cached_at: SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs(),
-                    account_name,
-                    account_url,
-                    live_categories,
-                    live_streams,
-                    vod_categories,
-                    vod_streams,
-                    series_categories,
-                    series_streams,
-                    total_channels,
-                    total_movies,
-                    total_series,
-                    category_counts,
-                    processing_modes,
-                };
-                let _ = cache.save();
-
-                let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
-                    "Cache Saved Successfully.".to_string(),
-                ));
-            });
+            spawn_cache_save(app, CacheSection::Live, tx);
 
             let current_idx = app.selected_category_index;
             app.select_category(current_idx);
@@ -711,6 +671,8 @@ pub async fn handle_async_action(
             let count = streams.len();
             app.session.total_movies = count;
             app.global_all_vod_streams = streams;
+            app.catalog_changed();
+            spawn_catalog_diff(app, crate::whats_new::Section::Movies, tx);
             app.global_vod_streams_by_cat = by_cat;
 
             if let Some(account) = app
@@ -723,81 +685,7 @@ pub async fn handle_async_action(
             }
 
             // Save to cache for instant startup on next launch
-            let account_name = app
-                .config
-                .accounts
-                .get(app.session.selected_account_index)
-                .map(|a| a.name.clone())
-                .unwrap_or_default();
-            let account_url = app
-                .config
-                .accounts
-                .get(app.session.selected_account_index)
-                .map(|a| a.base_url.clone())
-                .unwrap_or_default();
-
-            let arc_live_categories = app.all_categories.clone();
-            let arc_live_streams = app.global_all_streams.clone();
-            let arc_vod_categories = app.all_vod_categories.clone();
-            let arc_vod_streams = app.global_all_vod_streams.clone();
-            let arc_series_categories = app.all_series_categories.clone();
-            let arc_series_streams = app.global_all_series_streams.clone();
-
-            let category_counts = app.category_channel_counts.clone().into_iter().collect();
-            let processing_modes = app.config.processing_modes.clone();
-            let total_channels = app.session.total_channels;
-            let total_movies = app.session.total_movies;
-            let total_series = app.session.total_series;
-
-            let tx_clone = tx.clone();
-            tokio::task::spawn_blocking(move || {
-                let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
-                    "Extracting background reference state...".to_string(),
-                ));
-
-                let live_categories: Vec<_> =
-                    arc_live_categories.iter().map(|c| (**c).clone()).collect();
-                let live_streams: Vec<_> = arc_live_streams.iter().map(|s| (**s).clone()).collect();
-                let vod_categories: Vec<_> =
-                    arc_vod_categories.iter().map(|c| (**c).clone()).collect();
-                let vod_streams: Vec<_> = arc_vod_streams.iter().map(|s| (**s).clone()).collect();
-                let series_categories: Vec<_> = arc_series_categories
-                    .iter()
-                    .map(|c| (**c).clone())
-                    .collect();
-                let series_streams: Vec<_> =
-                    arc_series_streams.iter().map(|s| (**s).clone()).collect();
-
-                let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
-                    "Updating Matrix Database Cache...".to_string(),
-                ));
-
-                let cache = CachedCatalog {
-                    version: 1,
-                    cached_at: SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs(),
-                    account_name,
-                    account_url,
-                    live_categories,
-                    live_streams,
-                    vod_categories,
-                    vod_streams,
-                    series_categories,
-                    series_streams,
-                    total_channels,
-                    total_movies,
-                    total_series,
-                    category_counts,
-                    processing_modes,
-                };
-                let _ = cache.save();
-
-                let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
-                    "Cache Saved Successfully.".to_string(),
-                ));
-            });
+            spawn_cache_save(app, CacheSection::Vod, tx);
 
             if app.search_mode {
                 app.update_search();
@@ -841,6 +729,8 @@ pub async fn handle_async_action(
             let count = streams.len();
             app.session.total_series = count;
             app.global_all_series_streams = streams;
+            app.catalog_changed();
+            spawn_catalog_diff(app, crate::whats_new::Section::Series, tx);
             app.global_series_streams_by_cat = by_cat;
 
             if let Some(account) = app
@@ -853,81 +743,7 @@ pub async fn handle_async_action(
             }
 
             // Save to cache for instant startup on next launch
-            let account_name = app
-                .config
-                .accounts
-                .get(app.session.selected_account_index)
-                .map(|a| a.name.clone())
-                .unwrap_or_default();
-            let account_url = app
-                .config
-                .accounts
-                .get(app.session.selected_account_index)
-                .map(|a| a.base_url.clone())
-                .unwrap_or_default();
-
-            let arc_live_categories = app.all_categories.clone();
-            let arc_live_streams = app.global_all_streams.clone();
-            let arc_vod_categories = app.all_vod_categories.clone();
-            let arc_vod_streams = app.global_all_vod_streams.clone();
-            let arc_series_categories = app.all_series_categories.clone();
-            let arc_series_streams = app.global_all_series_streams.clone();
-
-            let category_counts = app.category_channel_counts.clone().into_iter().collect();
-            let processing_modes = app.config.processing_modes.clone();
-            let total_channels = app.session.total_channels;
-            let total_movies = app.session.total_movies;
-            let total_series = app.session.total_series;
-
-            let tx_clone = tx.clone();
-            tokio::task::spawn_blocking(move || {
-                let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
-                    "Extracting background reference state...".to_string(),
-                ));
-
-                let live_categories: Vec<_> =
-                    arc_live_categories.iter().map(|c| (**c).clone()).collect();
-                let live_streams: Vec<_> = arc_live_streams.iter().map(|s| (**s).clone()).collect();
-                let vod_categories: Vec<_> =
-                    arc_vod_categories.iter().map(|c| (**c).clone()).collect();
-                let vod_streams: Vec<_> = arc_vod_streams.iter().map(|s| (**s).clone()).collect();
-                let series_categories: Vec<_> = arc_series_categories
-                    .iter()
-                    .map(|c| (**c).clone())
-                    .collect();
-                let series_streams: Vec<_> =
-                    arc_series_streams.iter().map(|s| (**s).clone()).collect();
-
-                let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
-                    "Updating Matrix Database Cache...".to_string(),
-                ));
-
-                let cache = CachedCatalog {
-                    version: 1,
-                    cached_at: SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap_or_default()
-                        .as_secs(),
-                    account_name,
-                    account_url,
-                    live_categories,
-                    live_streams,
-                    vod_categories,
-                    vod_streams,
-                    series_categories,
-                    series_streams,
-                    total_channels,
-                    total_movies,
-                    total_series,
-                    category_counts,
-                    processing_modes,
-                };
-                let _ = cache.save();
-
-                let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
-                    "Cache Saved Successfully.".to_string(),
-                ));
-            });
+            spawn_cache_save(app, CacheSection::Series, tx);
 
             if app.search_mode {
                 app.update_search();
@@ -1032,6 +848,23 @@ pub async fn handle_async_action(
                 }
             });
         }
+        // A build for lists that have since been replaced is dropped
+        AsyncAction::SearchIndexBuilt(index) => {
+            if index.generation == app.catalog_generation {
+                app.search_index = Some(index);
+            }
+        }
+        AsyncAction::CatalogDiffed(changes) => {
+            let added = changes.iter().filter(|c| c.is_new()).count();
+            app.whats_new.record(changes, chrono::Utc::now());
+            let _ = app.whats_new.save();
+            if added > 0 {
+                app.push_toast(format!(
+                    "{} new in the catalog · w on Home to see what's new",
+                    added
+                ));
+            }
+        }
         AsyncAction::SeriesInfoLoaded(info) => {
             app.current_series_info = Some(info.clone());
             app.session.state_loading = false;
@@ -1104,10 +937,21 @@ pub async fn handle_async_action(
             app.current_sports_streams = streams;
             app.sports_details_loading = false;
         }
+        AsyncAction::EmbedOpenedInBrowser(reason) => {
+            app.session.state_loading = false;
+            app.session.loading_message = None;
+            app.push_toast(format!("Opened in browser: {}", reason));
+        }
         AsyncAction::ScoresLoaded(scores) => {
+            let tick = app.session.loading_tick;
+            for id in crate::scores::changed_game_ids(&app.live_scores, &scores) {
+                app.ticker_highlights.insert(id, tick);
+            }
+            app.ticker_highlights
+                .retain(|id, _| scores.iter().any(|g| &g.id == id));
             app.live_scores = scores;
         }
-        // Chromecast Casting
+        // Casting
         AsyncAction::CastDevicesDiscovered(devices) => {
             app.cast_devices = devices;
             app.cast_discovering = false;
@@ -1116,12 +960,74 @@ pub async fn handle_async_action(
                 app.cast_device_list_state.select(Some(0));
             }
         }
-        AsyncAction::CastStarted(device_name) => {
+        AsyncAction::CastStarted(session) => {
             app.session.state_loading = false;
-            app.session.loading_message = Some(format!("▶ Casting to {}", device_name));
+            app.session.loading_message = Some(format!("▶ Casting to {}", session.device_name()));
             app.show_cast_picker = false;
+            // A new device starts with a fresh status; a channel change keeps it
+            if app
+                .cast_session
+                .as_ref()
+                .is_none_or(|s| s.target() != session.target())
+            {
+                app.cast_status = None;
+                app.cast_poll_failures = 0;
+            }
+            app.cast_session = Some(session);
+        }
+        AsyncAction::CastStatus(result) => app.apply_cast_status(result),
+        AsyncAction::CastCommandSent(label) => {
+            app.cast_last_action = Some((label, app.session.loading_tick));
         }
+        AsyncAction::Reconnected(client, ui, si) => {
+            // Offline mode was left (account switched) while the probe ran
+            if !app.session.offline {
+                return;
+            }
+            app.session.offline = false;
+            app.session.current_client = Some(client.clone());
+            app.session.account_info = ui.clone();
+            app.session.server_info = si.clone();
+            app.session.provider_timezone = si.as_ref().and_then(|s| s.timezone.clone());
+            let queue = std::mem::take(&mut app.offline_queue);
+            let account_name = app
+                .config
+                .accounts
+                .get(app.session.selected_account_index)
+                .map(|a| a.name.clone())
+                .unwrap_or_default();
+            app.push_toast(format!("Back online · {}", account_name));
+
+            // The refresh overwrites each cached section as it lands, so the
+            // cache stays usable if it fails
+            if queue.iter().any(|a| matches!(a, OfflineAction::Refresh)) {
+                let _ = tx
+                    .send(AsyncAction::PlaylistRefreshed(client, ui, si))
+                    .await;
+            } else {
+                spawn_background_refresh(app, tx);
+            }
+
+            let play = queue.into_iter().find_map(|a| match a {
+                OfflineAction::Play(stream) => Some(stream),
+                OfflineAction::Refresh => None,
+            });
+            if let (Some(stream), Some(client)) = (play, &app.session.current_client) {
+                app.pending_play_url = Some(client.get_playback_url(&stream));
+                app.pending_play_stream = Some(app.played_stream(&stream));
+                app.pending_play_title = Some(stream.name.clone());
+                app.show_play_details = true;
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        AsyncAction::ImageLoaded(url, image) => app.images.insert(url, image),
+        AsyncAction::KodiPinged(name, result) => app.push_toast(match result {
+            Ok(()) => format!("✓ {} is reachable", name),
+            Err(e) => format!("✗ {}: {}", name, e),
+        }),
         AsyncAction::CastFailed(e) => {
+            app.session.state_loading = false;
+            app.session.loading_message = None;
             app.cast_discovering = false;
             app.show_cast_picker = false;
             app.ui.player_error = Some(format!("Cast failed: {}", e));
@@ -1216,6 +1122,280 @@ pub async fn handle_async_action(
     }
 }
 
+/// Populate the UI from a cached catalog and open content selection
+fn apply_cached_catalog(app: &mut App, cache: CachedCatalog, tx: &mpsc::Sender<AsyncAction>) {
+    app.all_categories = cache.live_categories.into_iter().map(Arc::new).collect();
+    app.categories = app.all_categories.clone();
+    app.global_all_streams = cache.live_streams.into_iter().map(Arc::new).collect();
+
+    app.all_vod_categories = cache.vod_categories.into_iter().map(Arc::new).collect();
+    app.vod_categories = app.all_vod_categories.clone();
+    app.global_all_vod_streams = cache.vod_streams.into_iter().map(Arc::new).collect();
+
+    app.all_series_categories = cache.series_categories.into_iter().map(Arc::new).collect();
+    app.series_categories = app.all_series_categories.clone();
+    app.global_all_series_streams = cache.series_streams.into_iter().map(Arc::new).collect();
+    app.catalog_changed();
+
+    app.session.total_channels = cache.total_channels;
+    app.session.total_movies = cache.total_movies;
+    app.session.total_series = cache.total_series;
+
+    // Restore category counts and recent programme titles
+    app.category_channel_counts = cache.category_counts.into_iter().collect();
+    app.epg_cache.extend(cache.epg_cache);
+
+    // Reuse the stored search index if it still describes these lists
+    match cache.search_index.filter(|index| {
+        index.matches(
+            &app.global_all_streams,
+            &app.global_all_vod_streams,
+            &app.global_all_series_streams,
+        )
+    }) {
+        Some(mut index) => {
+            index.generation = app.catalog_generation;
+            app.search_index = Some(Arc::new(index));
+        }
+        None => spawn_search_index_build(app, None, tx),
+    }
+
+    // Navigate to content selection immediately
+    app.current_screen = CurrentScreen::ContentTypeSelection;
+    app.session.state_loading = false;
+    app.session.cache_loaded = true;
+
+    // Select first items and populate streams from cache
+    if !app.categories.is_empty() {
+        app.select_category(0);
+    }
+    if !app.vod_categories.is_empty() {
+        app.selected_vod_category_index = 0;
+        app.vod_category_list_state.select(Some(0));
+    }
+    if !app.series_categories.is_empty() {
+        app.selected_series_category_index = 0;
+        app.series_category_list_state.select(Some(0));
+    }
+}
+
+/// Re-fetch the categories in the background and merge them in silently
+fn spawn_background_refresh(app: &mut App, tx: &mpsc::Sender<AsyncAction>) {
+    app.session.background_refresh_active = true;
+
+    if let Some(client) = &app.session.current_client {
+        let client = client.clone();
+        let tx = tx.clone();
+        let pms = app.config.processing_modes.clone();
+        let account_name_bg = app
+            .config
+            .accounts
+            .get(app.session.selected_account_index)
+            .map(|a| a.name.clone())
+            .unwrap_or_default();
+        let cat_favs = app.config.favorites.categories.clone();
+        let vod_cat_favs = app.config.favorites.vod_categories.clone();
+
+        // Spawn a single task that fetches all categories in parallel using join!
+        tokio::spawn(async move {
+            // Execute all category fetches in parallel
+            let (live_result, vod_result, series_result) = join!(
+                client.get_live_categories(),
+                client.get_vod_categories(),
+                client.get_series_categories()
+            );
+
+            // Process and send Live Categories (silent fail on background refresh)
+            if let Ok(mut cats) = live_result {
+                preprocessing::preprocess_categories(
+                    &mut cats,
+                    &cat_favs,
+                    &pms,
+                    true,
+                    false,
+                    &account_name_bg,
+                );
+                let _ = tx.send(AsyncAction::CategoriesLoaded(cats)).await;
+            }
+
+            // Process and send VOD Categories
+            if let Ok(mut cats) = vod_result {
+                preprocessing::preprocess_categories(
+                    &mut cats,
+                    &vod_cat_favs,
+                    &pms,
+                    false,
+                    true,
+                    &account_name_bg,
+                );
+                let _ = tx.send(AsyncAction::VodCategoriesLoaded(cats)).await;
+            }
+
+            // Process and send Series Categories
+            if let Ok(mut cats) = series_result {
+                preprocessing::preprocess_categories(
+                    &mut cats,
+                    &cat_favs,
+                    &pms,
+                    false,
+                    false,
+                    &account_name_bg,
+                );
+                let _ = tx.send(AsyncAction::SeriesCategoriesLoaded(cats)).await;
+            }
+        });
+    }
+}
+
+/// Browse the selected account's cached catalog (and guide, however old)
+/// without authenticating. Returns false when nothing is cached for it.
+pub fn open_offline(app: &mut App, tx: &mpsc::Sender<AsyncAction>) -> bool {
+    let Some(account_name) = app
+        .config
+        .accounts
+        .get(app.session.selected_account_index)
+        .map(|a| a.name.clone())
+    else {
+        return false;
+    };
+    let Some(cache) = CachedCatalog::load_offline(&account_name) else {
+        return false;
+    };
+    if cache.modes_changed(&app.config.processing_modes) {
+        app.push_toast("Offline · catalog was cached with other filter modes".to_string());
+    }
+    app.session.current_client = None;
+    app.session.account_info = None;
+    app.session.server_info = None;
+    app.session.offline = true;
+    app.login_error = None;
+    app.search_mode = false;
+    app.search_state.query.clear();
+    app.last_search_query.clear();
+    apply_cached_catalog(app, cache, tx);
+    // Let the main loop try the provider straight away
+    app.last_reconnect_attempt = None;
+    true
+}
+
+/// Try to log in to the selected account again; sends `Reconnected` on success
+/// and stays quiet otherwise, since the main loop retries on a timer
+pub fn spawn_reconnect(app: &App, tx: &mpsc::Sender<AsyncAction>) {
+    let Some(acc) = app.config.accounts.get(app.session.selected_account_index) else {
+        return;
+    };
+    let base_url = acc.base_url.clone();
+    let username = acc.username.clone();
+    let password = acc.secret();
+    let account_type = acc.account_type;
+    let dns_provider = app.config.dns_provider;
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let connected = match account_type {
+            crate::config::AccountType::M3uUrl => {
+                match crate::api::M3uClient::new_with_doh(base_url, dns_provider).await {
+                    Ok(client) => match client.authenticate().await {
+                        Ok((true, ui, si)) => Some((crate::api::IptvClient::M3u(client), ui, si)),
+                        _ => None,
+                    },
+                    Err(_) => None,
+                }
+            }
+            _ => {
+                match crate::api::XtreamClient::new_with_doh(
+                    base_url,
+                    username,
+                    password.to_string(),
+                    dns_provider,
+                )
+                .await
+                {
+                    Ok(client) => match client.authenticate().await {
+                        Ok((true, ui, si)) => {
+                            Some((crate::api::IptvClient::Xtream(client), ui, si))
+                        }
+                        _ => None,
+                    },
+                    Err(_) => None,
+                }
+            }
+        };
+        if let Some((client, ui, si)) = connected {
+            let _ = tx.send(AsyncAction::Reconnected(client, ui, si)).await;
+        }
+    });
+}
+
+/// Write one catalog section (plus parsed metadata, the EPG titles and a fresh
+/// search index) to the cache in the background, leaving the other sections alone
+fn spawn_cache_save(app: &App, section: CacheSection, tx: &mpsc::Sender<AsyncAction>) {
+    let Some(account) = app.config.accounts.get(app.session.selected_account_index) else {
+        return;
+    };
+    let header = CacheHeader {
+        account_name: account.name.clone(),
+        account_url: account.base_url.clone(),
+        processing_modes: app.config.processing_modes.clone(),
+        total_channels: app.session.total_channels,
+        total_movies: app.session.total_movies,
+        total_series: app.session.total_series,
+        category_counts: app.category_channel_counts.clone().into_iter().collect(),
+    };
+    let (categories, streams) = match section {
+        CacheSection::Live => (app.all_categories.clone(), app.global_all_streams.clone()),
+        CacheSection::Vod => (
+            app.all_vod_categories.clone(),
+            app.global_all_vod_streams.clone(),
+        ),
+        _ => (
+            app.all_series_categories.clone(),
+            app.global_all_series_streams.clone(),
+        ),
+    };
+    let epg = app.epg_cache.clone();
+    spawn_search_index_build(app, Some(header.clone()), tx);
+
+    let tx_clone = tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
+            "Updating Matrix Database Cache...".to_string(),
+        ));
+        let categories = categories.iter().map(|c| (**c).clone()).collect();
+        let streams = streams.iter().map(|s| (**s).clone()).collect();
+        let saved = CachedCatalog::save_catalog_section(&header, section, categories, streams);
+        if !epg.is_empty() {
+            let _ = CachedCatalog::save_section(&header, CacheSection::Epg, &epg);
+        }
+        if saved.is_ok() {
+            let _ = tx_clone.blocking_send(crate::app::AsyncAction::LoadingMessage(
+                "Cache Saved Successfully.".to_string(),
+            ));
+        }
+    });
+}
+
+/// Rebuild the global search index in the background, storing it in the cache
+/// when a header is given
+fn spawn_search_index_build(
+    app: &App,
+    header: Option<CacheHeader>,
+    tx: &mpsc::Sender<AsyncAction>,
+) {
+    let live = app.global_all_streams.clone();
+    let vod = app.global_all_vod_streams.clone();
+    let series = app.global_all_series_streams.clone();
+    let generation = app.catalog_generation;
+    let tx = tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut index = crate::search_index::SearchIndex::build(&live, &vod, &series);
+        index.generation = generation;
+        if let Some(header) = header {
+            let _ = CachedCatalog::save_section(&header, CacheSection::Search, &index);
+        }
+        let _ = tx.blocking_send(AsyncAction::SearchIndexBuilt(Arc::new(index)));
+    });
+}
+
 /// Spawn background Live channel parallel scan (called lazily when user navigates to Live Channels)
 ///
 /// Strategy:
@@ -1468,6 +1648,46 @@ pub fn spawn_live_scan(app: &App, tx: &mpsc::Sender<AsyncAction>) {
     });
 }
 
+/// Diff a freshly loaded catalog section against the last snapshot of it
+/// and replace the snapshot
+fn spawn_catalog_diff(
+    app: &App,
+    section: crate::whats_new::Section,
+    tx: &mpsc::Sender<AsyncAction>,
+) {
+    use crate::whats_new::{diff, Section, Snapshot};
+    let Some(account) = app
+        .config
+        .accounts
+        .get(app.session.selected_account_index)
+        .map(|a| a.name.clone())
+    else {
+        return;
+    };
+    let streams = match section {
+        Section::Live => app.global_all_streams.clone(),
+        Section::Movies => app.global_all_vod_streams.clone(),
+        Section::Series => app.global_all_series_streams.clone(),
+    };
+    let modes = app.config.processing_modes.clone();
+    let tx = tx.clone();
+    tokio::task::spawn_blocking(move || {
+        let current = Snapshot::of(&streams, &modes);
+        let previous = Snapshot::load(&account, section);
+        let changes = diff(
+            previous.as_ref(),
+            &current,
+            &account,
+            section,
+            chrono::Utc::now(),
+        );
+        let _ = current.save(&account, section);
+        if !changes.is_empty() {
+            let _ = tx.blocking_send(AsyncAction::CatalogDiffed(changes));
+        }
+    });
+}
+
 /// Spawn background VOD parallel scan (called lazily when user navigates to Movies)
 /// Uses semaphore-limited concurrency (15 max) with progress reporting
 pub fn spawn_vod_scan(app: &App, tx: &mpsc::Sender<AsyncAction>) {
@@ -1,11 +1,12 @@
 use crate::api::get_id_str;
 use crate::app::{
-    App, AsyncAction, CurrentScreen, Guide, InputMode, LoginField, Pane, SettingsState,
+    App, AsyncAction, CurrentScreen, Guide, InputMode, LoginField, OfflineAction, Pane,
+    SettingsItem, SettingsState,
 };
 use crate::cache::CachedCatalog;
-#[cfg(feature = "chromecast")]
 use crate::cast;
 use crate::config::Account;
+use crate::player_control::TransportCommand;
 use crate::state::ContentType;
 use crate::{player, preprocessing};
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
@@ -66,7 +67,7 @@ pub async fn handle_key_event(
                 let tx = tx.clone();
                 let base_url = acc.base_url.clone();
                 let username = acc.username.clone();
-                let password = acc.password.clone();
+                let password = acc.secret();
                 let account_type = acc.account_type;
 
                 tokio::spawn(async move {
@@ -81,8 +82,11 @@ pub async fn handle_key_event(
                             }
                         }
                         _ => {
-                            let client =
-                                crate::api::XtreamClient::new(base_url, username, password);
+                            let client = crate::api::XtreamClient::new(
+                                base_url,
+                                username,
+                                password.to_string(),
+                            );
                             if let Ok((true, _, _)) = client.authenticate().await {
                                 let iptv_client = crate::api::IptvClient::Xtream(client.clone());
                                 let _ = tx
@@ -104,6 +108,7 @@ pub async fn handle_key_event(
     let is_ctrl_r = key.code == KeyCode::Char('r') && key.modifiers.contains(KeyModifiers::CONTROL);
     let is_ctrl_g = key.code == KeyCode::Char('g') && key.modifiers.contains(KeyModifiers::CONTROL);
     let is_ctrl_b = key.code == KeyCode::Char('b') && key.modifiers.contains(KeyModifiers::CONTROL);
+    let is_ctrl_t = key.code == KeyCode::Char('t') && key.modifiers.contains(KeyModifiers::CONTROL);
 
     // Ctrl+C: Cancel loading or quit confirmation
     if is_ctrl_c {
@@ -141,7 +146,7 @@ pub async fn handle_key_event(
                 let tx = tx.clone();
                 let base_url = acc.base_url.clone();
                 let username = acc.username.clone();
-                let password = acc.password.clone();
+                let password = acc.secret();
                 let account_type = acc.account_type;
                 tokio::spawn(async move {
                     match account_type {
@@ -155,8 +160,11 @@ pub async fn handle_key_event(
                             }
                         }
                         _ => {
-                            let client =
-                                crate::api::XtreamClient::new(base_url, username, password);
+                            let client = crate::api::XtreamClient::new(
+                                base_url,
+                                username,
+                                password.to_string(),
+                            );
                             if let Ok((true, _, _)) = client.authenticate().await {
                                 let iptv_client = crate::api::IptvClient::Xtream(client.clone());
                                 let _ = tx
@@ -182,6 +190,56 @@ pub async fn handle_key_event(
         return Ok(InputResult::Continue);
     }
 
+    // Ctrl+T: Jump to the channel carrying the highlighted score ticker game
+    if is_ctrl_t && app.config.score_ticker {
+        if !app.jump_to_ticker_game() {
+            if let Some(game) = app.highlighted_ticker_game() {
+                app.login_error = Some(format!(
+                    "No channel found for {} @ {}",
+                    game.away_team, game.home_team
+                ));
+            }
+        }
+        return Ok(InputResult::Continue);
+    }
+
+    // Alt+Key: Transport controls for the stream in the now-playing bar, or on
+    // the cast device while casting
+    let casting = app.cast_session.is_some();
+    if (app.now_playing.is_some() || casting) && key.modifiers.contains(KeyModifiers::ALT) {
+        if let Some(cmd) = transport_command_for_key(key.code) {
+            if casting {
+                send_cast_command(app, tx, cmd);
+            } else {
+                send_transport_command(app, tx, player, cmd);
+            }
+            return Ok(InputResult::Continue);
+        }
+        match key.code {
+            KeyCode::Char('x') if casting => {
+                stop_casting(app);
+                return Ok(InputResult::Continue);
+            }
+            KeyCode::Char(c @ '1'..='4') if app.multiview_session.is_some() => {
+                focus_multiview_audio(app, tx, player, c as usize - '1' as usize);
+                return Ok(InputResult::Continue);
+            }
+            KeyCode::PageUp => {
+                zap_channel(app, tx, player, -1);
+                return Ok(InputResult::Continue);
+            }
+            KeyCode::PageDown => {
+                zap_channel(app, tx, player, 1);
+                return Ok(InputResult::Continue);
+            }
+            KeyCode::Char('v') if !casting => {
+                toggle_audio_only(app, tx, player);
+                return Ok(InputResult::Continue);
+            }
+            _ => {}
+        }
+    }
+
     // Ctrl+B: Background operation (stub - shows a subtle hint)
     if is_ctrl_b {
         // Stub: could be used for background loading in the future
@@ -270,12 +328,27 @@ pub async fn handle_key_event(
     if app.show_play_details {
         match key.code {
             KeyCode::Enter => {
-                if let Some(url) = app.pending_play_url.take() {
+                if app.current_screen == CurrentScreen::SportsDashboard {
+                    if let Some(url) = app.pending_play_url.take() {
+                        let title = app.pending_play_title.take().unwrap_or_default();
+                        spawn_embed_playback(app, tx, player, url, title);
+                    }
+                } else if let Some(url) = app.pending_play_url.take() {
                     let title = app.pending_play_title.take().unwrap_or_default();
+                    let played = app.pending_play_stream.take().unwrap_or_else(|| {
+                        crate::app::PlayedStream {
+                            name: title.clone(),
+                            account: app.active_account_name(),
+                            audio_only: app.audio_only,
+                            ..Default::default()
+                        }
+                    });
                     app.session.state_loading = true;
                     app.ui.player_error = None;
                     app.session.loading_message = Some(format!("Preparing: {}...", title));
                     let tx = tx.clone();
+                    player.set_media_title(&title);
+                    player.set_audio_only(played.audio_only);
                     let player = player.clone();
                     let engine = app.config.preferred_player;
                     let smooth = app.config.smooth_motion;
@@ -287,7 +360,7 @@ pub async fn handle_key_event(
                         match player.play(&url, engine, use_default, smooth).await {
                             Ok(_) => match player.wait_for_playback(10000).await {
                                 Ok(true) => {
-                                    let _ = tx.send(AsyncAction::PlayerStarted).await;
+                                    let _ = tx.send(AsyncAction::PlayerStarted(played)).await;
                                 }
                                 _ => {
                                     let log_err = player
@@ -308,6 +381,7 @@ pub async fn handle_key_event(
                 app.show_play_details = false;
                 app.pending_play_url = None;
                 app.pending_play_title = None;
+                app.pending_play_stream = None;
             }
             _ => {}
         }
@@ -320,7 +394,19 @@ pub async fn handle_key_event(
         return Ok(InputResult::Continue);
     }
 
-    // Priority 7: Cast Device Picker
+    // Priority 7: Multiview Builder
+    if app.show_multiview {
+        handle_multiview_popup_key(app, key, tx, player);
+        return Ok(InputResult::Continue);
+    }
+
+    // Priority 8: Schedule Recording Picker
+    if app.schedule_picker_channel.is_some() {
+        handle_schedule_picker_key(app, key);
+        return Ok(InputResult::Continue);
+    }
+
+    // Priority 9: Cast Device Picker
     if app.show_cast_picker {
         match key.code {
             KeyCode::Esc => {
@@ -347,64 +433,61 @@ pub async fn handle_key_event(
                         .select(Some(app.selected_cast_device_index));
                 }
             }
-            KeyCode::Char('r') => {
+            KeyCode::Char('r') if !app.cast_discovering => {
                 // Refresh/rescan for devices
-                #[cfg(feature = "chromecast")]
-                if !app.cast_discovering {
-                    app.cast_discovering = true;
-                    app.cast_devices.clear();
+                app.cast_discovering = true;
+                app.cast_devices.clear();
+                let tx = tx.clone();
+                let kodi_hosts = app.config.kodi_hosts.clone();
+                tokio::spawn(async move {
+                    match cast::discover_targets(5, kodi_hosts).await {
+                        Ok(devices) => {
+                            let _ = tx.send(AsyncAction::CastDevicesDiscovered(devices)).await;
+                        }
+                        Err(e) => {
+                            let _ = tx
+                                .send(AsyncAction::CastFailed(format!("Discovery failed: {}", e)))
+                                .await;
+                        }
+                    }
+                });
+            }
+            KeyCode::Enter
+                if !app.cast_devices.is_empty()
+                    && app.selected_cast_device_index < app.cast_devices.len() =>
+            {
+                let target = app.cast_devices[app.selected_cast_device_index].clone();
+                let title = app.pending_play_title.take().unwrap_or_default();
+
+                // Get the pending URL to cast
+                if let Some(url) = app.pending_play_url.take() {
+                    // The device already casting just switches stream; another
+                    // device takes over from it
+                    let current = app.cast_session.clone();
                     let tx = tx.clone();
                     tokio::spawn(async move {
-                        match cast::CastManager::discover_devices(5).await {
-                            Ok(devices) => {
-                                let _ = tx.send(AsyncAction::CastDevicesDiscovered(devices)).await;
+                        let result = match current {
+                            Some(mut session) if session.target() == &target => {
+                                session.load(&url, &title).await.map(|_| session)
+                            }
+                            current => {
+                                if let Some(old) = current {
+                                    let _ = old.stop().await;
+                                }
+                                cast::CastSession::start(&target, &url, &title).await
+                            }
+                        };
+                        match result {
+                            Ok(session) => {
+                                let _ = tx.send(AsyncAction::CastStarted(session)).await;
                             }
                             Err(e) => {
-                                let _ = tx
-                                    .send(AsyncAction::CastFailed(format!(
-                                        "Discovery failed: {}",
-                                        e
-                                    )))
-                                    .await;
+                                let _ = tx.send(AsyncAction::CastFailed(e.to_string())).await;
                             }
                         }
                     });
                 }
-                #[cfg(not(feature = "chromecast"))]
-                {
-                    let _ = tx
-                        .send(AsyncAction::CastFailed(
-                            "Chromecast support not enabled. Rebuild with --features chromecast"
-                                .to_string(),
-                        ))
-                        .await;
-                }
-            }
-            KeyCode::Enter => {
-                #[cfg(feature = "chromecast")]
-                if !app.cast_devices.is_empty()
-                    && app.selected_cast_device_index < app.cast_devices.len()
-                {
-                    let device = app.cast_devices[app.selected_cast_device_index].clone();
-                    let device_name = device.name.clone();
-
-                    // Get the pending URL to cast
-                    if let Some(url) = app.pending_play_url.take() {
-                        let tx = tx.clone();
-                        tokio::spawn(async move {
-                            let mut manager = cast::CastManager::new();
-                            match manager.cast_to_device(&device, &url, None) {
-                                Ok(_) => {
-                                    let _ = tx.send(AsyncAction::CastStarted(device_name)).await;
-                                }
-                                Err(e) => {
-                                    let _ = tx.send(AsyncAction::CastFailed(e.to_string())).await;
-                                }
-                            }
-                        });
-                    }
-                    app.show_cast_picker = false;
-                }
+                app.show_cast_picker = false;
             }
             _ => {}
         }
@@ -428,7 +511,9 @@ pub async fn handle_key_event(
         }
         // Refresh Playlist
         if matches!(key.code, KeyCode::Char('r') | KeyCode::Char('R')) {
-            if let Some(client) = app.session.current_client.clone() {
+            if app.session.offline {
+                app.queue_offline(OfflineAction::Refresh);
+            } else if let Some(client) = app.session.current_client.clone() {
                 let tx = tx.clone();
                 app.session.state_loading = true;
                 app.session.loading_message = Some("Refreshing playlist...".to_string());
@@ -541,7 +626,7 @@ pub async fn handle_key_event(
                         app.input_name = tui_input::Input::new(acc.name.clone());
                         app.input_url = tui_input::Input::new(acc.base_url.clone());
                         app.input_username = tui_input::Input::new(acc.username.clone());
-                        app.input_password = tui_input::Input::new(acc.password.clone());
+                        app.input_password = tui_input::Input::new(acc.secret().to_string());
                         app.input_epg_url =
                             tui_input::Input::new(acc.epg_url.clone().unwrap_or_default());
 
@@ -557,6 +642,9 @@ pub async fn handle_key_event(
                             app.config.accounts.get(app.session.selected_account_index)
                         {
                             CachedCatalog::invalidate(&account.name);
+                            crate::whats_new::Snapshot::invalidate(&account.name);
+                            #[cfg(not(target_arch = "wasm32"))]
+                            crate::images::invalidate(&account.name);
                         }
                         app.config
                             .remove_account(app.session.selected_account_index);
@@ -573,6 +661,21 @@ pub async fn handle_key_event(
                     }
                 }
                 KeyCode::Char('x') => app.current_screen = CurrentScreen::Settings,
+                KeyCode::Char('D') => open_recordings(app),
+                KeyCode::Char('H') => {
+                    app.previous_screen = Some(app.current_screen.clone());
+                    app.current_screen = CurrentScreen::WatchStats;
+                }
+                KeyCode::Char('w') => open_whats_new(app),
+                KeyCode::Char('o')
+                    if !app.config.accounts.is_empty()
+                        && !crate::handlers::async_actions::open_offline(app, tx) =>
+                {
+                    app.login_error = Some(
+                        "Nothing cached for this playlist yet. Load it once while online to browse it offline."
+                            .to_string(),
+                    );
+                }
                 KeyCode::Char('s') | KeyCode::Char('S') => {
                     app.previous_screen = Some(CurrentScreen::Home);
                     app.current_screen = CurrentScreen::SportsDashboard;
@@ -598,14 +701,16 @@ pub async fn handle_key_event(
                     if !app.config.accounts.is_empty() {
                         // If we already have an active session for this account, reuse it
                         // instead of re-authenticating (avoids provider rate-limiting / 503s)
-                        if app.session.current_client.is_some() && !app.all_categories.is_empty() {
+                        if (app.session.current_client.is_some() || app.session.offline)
+                            && !app.all_categories.is_empty()
+                        {
                             app.current_screen = CurrentScreen::ContentTypeSelection;
                             app.login_error = None;
                         } else {
                             let acc = &app.config.accounts[app.session.selected_account_index];
                             let base_url = acc.base_url.clone();
                             let username = acc.username.clone();
-                            let password = acc.password.clone();
+                            let password = acc.secret();
                             let account_type = acc.account_type;
                             let now = chrono::Utc::now().timestamp();
                             let needs_refresh = acc
@@ -691,7 +796,7 @@ pub async fn handle_key_event(
                                         match crate::api::XtreamClient::new_with_doh(
                                             base_url,
                                             username,
-                                            password,
+                                            password.to_string(),
                                             dns_provider,
                                         )
                                         .await
@@ -853,10 +958,14 @@ pub async fn handle_key_event(
             if app.show_save_confirmation {
                 match key.code {
                     KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
-                        app.save_account();
                         app.show_save_confirmation = false;
-                        app.current_screen =
-                            app.previous_screen.take().unwrap_or(CurrentScreen::Home);
+                        match app.save_account() {
+                            Ok(()) => {
+                                app.current_screen =
+                                    app.previous_screen.take().unwrap_or(CurrentScreen::Home);
+                            }
+                            Err(e) => app.login_error = Some(e),
+                        }
                     }
                     KeyCode::Char('n') | KeyCode::Char('N') => {
                         app.show_save_confirmation = false;
@@ -887,7 +996,7 @@ pub async fn handle_key_event(
                                                 acc.name.clone(),
                                                 acc.base_url.clone(),
                                                 acc.username.clone(),
-                                                acc.password.clone(),
+                                                acc.secret().to_string(),
                                                 acc.epg_url.clone().unwrap_or_default(),
                                             )
                                         } else {
@@ -1016,8 +1125,8 @@ pub async fn handle_key_event(
                             KeyCode::Enter => {
                                 // On Enter from last field, submit the form
                                 if app.login_field_focus == LoginField::EpgUrl {
-                                    let name = app.input_name.value().to_string();
-                                    let url = app.input_url.value().to_string();
+                                    let name = app.input_name.value().trim().to_string();
+                                    let url = app.input_url.value().trim().to_string();
                                     let user = app.input_username.value().to_string();
                                     let pass = app.input_password.value().to_string();
                                     let epg = app.input_epg_url.value().to_string();
@@ -1035,6 +1144,7 @@ pub async fn handle_key_event(
                                             base_url: url,
                                             username: user,
                                             password: pass,
+                                            credential: None,
                                             account_type: detected_type,
                                             epg_url: epg_opt,
                                             last_refreshed: None,
@@ -1043,20 +1153,36 @@ pub async fn handle_key_event(
                                             total_series: None,
                                             server_timezone: None,
                                             hidden_categories: std::collections::HashSet::new(),
+                                            radio_overrides: std::collections::HashMap::new(),
                                             category_sort_order:
                                                 crate::config::CategorySortOrder::Default,
                                         };
-                                        if let Some(idx) = app.editing_account_index {
+                                        let saved = if let Some(idx) = app.editing_account_index {
+                                            let old_name = app
+                                                .config
+                                                .accounts
+                                                .get(idx)
+                                                .map(|a| a.name.clone());
+                                            let new_name = acc.name.clone();
+                                            let saved = app.config.update_account(idx, acc);
                                             // Invalidate cache for the old account name if name changed
-                                            if let Some(old_account) = app.config.accounts.get(idx)
-                                            {
-                                                if old_account.name != acc.name {
-                                                    CachedCatalog::invalidate(&old_account.name);
+                                            if let Some(old_name) = old_name {
+                                                if saved.is_ok() && old_name != new_name {
+                                                    CachedCatalog::invalidate(&old_name);
+                                                    crate::whats_new::Snapshot::invalidate(
+                                                        &old_name,
+                                                    );
+                                                    #[cfg(not(target_arch = "wasm32"))]
+                                                    crate::images::invalidate(&old_name);
                                                 }
                                             }
-                                            app.config.update_account(idx, acc);
+                                            saved
                                         } else {
-                                            app.config.add_account(acc);
+                                            app.config.add_account(acc)
+                                        };
+                                        if let Err(e) = saved {
+                                            app.login_error = Some(e);
+                                            return Ok(InputResult::Continue);
                                         }
 
                                         app.current_screen = CurrentScreen::Home;
@@ -1538,7 +1664,9 @@ pub async fn handle_key_event(
                             }
                             Pane::Streams => {
                                 if !app.streams.is_empty() {
-                                    let stream = &app.streams[app.selected_stream_index];
+                                    let list = app.streams.clone();
+                                    let stream = &list[app.selected_stream_index];
+                                    app.capture_zap_playlist(&list, stream);
                                     if let Some(client) = &app.session.current_client {
                                         let id = get_id_str(&stream.stream_id);
                                         let url = client.get_stream_url(&id, "ts");
@@ -1549,6 +1677,9 @@ pub async fn handle_key_event(
                                             stream.name
                                         ));
                                         let tx = tx.clone();
+                                        let played = app.played_stream(stream);
+                                        player.set_media_title(&stream.name);
+                                        player.set_audio_only(played.audio_only);
                                         let player = player.clone();
                                         let stream_url = url.clone();
                                         let use_default = app.config.use_default_mpv;
@@ -1587,7 +1718,9 @@ pub async fn handle_key_event(
                                                     {
                                                         Ok(true) => {
                                                             let _ = tx
-                                                                .send(AsyncAction::PlayerStarted)
+                                                                .send(AsyncAction::PlayerStarted(
+                                                                    played,
+                                                                ))
                                                                 .await;
                                                         }
                                                         Ok(false) => {
@@ -1617,10 +1750,8 @@ pub async fn handle_key_event(
                                                         }
                                                         Err(e) => {
                                                             // Use diagnostic for error message
-                                                            let diagnosis = player
-                                                                .diagnose_playback_failure(
-                                                                    &e.to_string(),
-                                                                );
+                                                            let diagnosis =
+                                                                player.diagnose_error(&e);
                                                             let hint_msg = match diagnosis.hint {
                                                                 Some(hint) => format!(
                                                                     "{}\n\nHint: {}",
@@ -1637,8 +1768,7 @@ pub async fn handle_key_event(
                                                     }
                                                 }
                                                 Err(e) => {
-                                                    let diagnosis = player
-                                                        .diagnose_playback_failure(&e.to_string());
+                                                    let diagnosis = player.diagnose_error(&e);
                                                     let hint_msg = match diagnosis.hint {
                                                         Some(hint) => {
                                                             format!("{}\n\nHint: {}", e, hint)
@@ -1651,12 +1781,50 @@ pub async fn handle_key_event(
                                                 }
                                             }
                                         });
+                                    } else if app.session.offline {
+                                        app.queue_offline(OfflineAction::Play(stream.clone()));
                                     }
                                 }
                             }
                             _ => {}
                         }
                     }
+                    KeyCode::Char('w') if app.active_pane == Pane::Streams => {
+                        // Mark/unmark the focused channel as a multiview tile
+                        if let Some(stream) = app.streams.get(app.selected_stream_index).cloned() {
+                            if let Err(e) = app.toggle_multiview_tile(&stream) {
+                                app.login_error = Some(e);
+                            }
+                        }
+                    }
+                    KeyCode::Char('o') if app.active_pane == Pane::Streams => {
+                        // Start/stop recording the focused channel
+                        if let Some(stream) = app.streams.get(app.selected_stream_index).cloned() {
+                            toggle_recording(app, &stream);
+                        }
+                    }
+                    KeyCode::Char('O') if app.active_pane == Pane::Streams => {
+                        // Schedule a recording from the channel's guide
+                        if let Some(stream) = app.streams.get(app.selected_stream_index).cloned() {
+                            open_schedule_picker(app, tx, &stream);
+                        }
+                    }
+                    KeyCode::Char('L') if app.active_pane == Pane::Streams => {
+                        // Copy the focused channel's relay URL for another device
+                        if let Some(stream) = app.streams.get(app.selected_stream_index) {
+                            let stream_id = get_id_str(&stream.stream_id);
+                            copy_relay_url(app, &stream_id);
+                        }
+                    }
+                    KeyCode::Char('A') => toggle_audio_only(app, tx, player),
+                    KeyCode::Char('D') => open_recordings(app),
+                    KeyCode::Char('W') => {
+                        app.show_multiview = true;
+                        app.multiview_preset_name = None;
+                        let rows = app.multiview_tiles.len() + app.config.multiview_presets.len();
+                        app.multiview_list_state
+                            .select(if rows == 0 { None } else { Some(0) });
+                    }
                     KeyCode::Char('x') => app.current_screen = CurrentScreen::Settings,
 
                     KeyCode::Char('G') => {
@@ -1671,41 +1839,43 @@ pub async fn handle_key_event(
                             });
                         app.current_screen = CurrentScreen::GroupManagement;
                     }
-                    #[cfg(feature = "chromecast")]
-                    KeyCode::Char('C') => {
-                        // Cast to Chromecast - open device picker
-                        if app.active_pane == Pane::Streams && !app.streams.is_empty() {
-                            let stream = &app.streams[app.selected_stream_index];
-                            if let Some(client) = &app.session.current_client {
-                                let id = get_id_str(&stream.stream_id);
-                                let url = client.get_stream_url(&id, "ts");
-                                app.pending_play_url = Some(url);
-                                app.pending_play_title = Some(stream.name.clone());
-                                app.show_cast_picker = true;
-                                app.cast_discovering = true;
-                                app.selected_cast_device_index = 0;
-                                app.cast_device_list_state.select(None);
+                    KeyCode::Char('C')
+                        if app.active_pane == Pane::Streams && !app.streams.is_empty() =>
+                    {
+                        // Cast to Chromecast / DLNA - open device picker
+                        let stream = app.streams[app.selected_stream_index].clone();
+                        if let Some(client) = &app.session.current_client {
+                            let id = get_id_str(&stream.stream_id);
+                            let url = client.get_stream_url(&id, "ts");
+                            app.pending_play_url = Some(url);
+                            app.pending_play_title = Some(stream.name.clone());
+                            let list = app.streams.clone();
+                            app.capture_zap_playlist(&list, &stream);
+                            app.show_cast_picker = true;
+                            app.cast_discovering = true;
+                            app.selected_cast_device_index = 0;
+                            app.cast_device_list_state.select(None);
 
-                                // Start device discovery
-                                let tx = tx.clone();
-                                tokio::spawn(async move {
-                                    match cast::CastManager::discover_devices(5).await {
-                                        Ok(devices) => {
-                                            let _ = tx
-                                                .send(AsyncAction::CastDevicesDiscovered(devices))
-                                                .await;
-                                        }
-                                        Err(e) => {
-                                            let _ = tx
-                                                .send(AsyncAction::CastFailed(format!(
-                                                    "Discovery failed: {}",
-                                                    e
-                                                )))
-                                                .await;
-                                        }
+                            // Start device discovery
+                            let tx = tx.clone();
+                            let kodi_hosts = app.config.kodi_hosts.clone();
+                            tokio::spawn(async move {
+                                match cast::discover_targets(5, kodi_hosts).await {
+                                    Ok(devices) => {
+                                        let _ = tx
+                                            .send(AsyncAction::CastDevicesDiscovered(devices))
+                                            .await;
                                     }
-                                });
-                            }
+                                    Err(e) => {
+                                        let _ = tx
+                                            .send(AsyncAction::CastFailed(format!(
+                                                "Discovery failed: {}",
+                                                e
+                                            )))
+                                            .await;
+                                    }
+                                }
+                            });
                         }
                     }
                     _ => {}
@@ -2013,7 +2183,10 @@ pub async fn handle_key_event(
                                 let url = client.get_vod_url(&id, extension);
                                 app.pending_play_url = Some(url);
                                 app.pending_play_title = Some(stream.name.clone());
+                                app.pending_play_stream = Some(app.played_stream(stream));
                                 app.show_play_details = true;
+                            } else if app.session.offline {
+                                app.queue_offline(OfflineAction::Play(stream.clone()));
                             }
                         }
                     }
@@ -2090,7 +2263,10 @@ pub async fn handle_key_event(
                                 let url = client.get_vod_url(&id, extension);
                                 app.pending_play_url = Some(url);
                                 app.pending_play_title = Some(stream.name.clone());
+                                app.pending_play_stream = Some(app.played_stream(stream));
                                 app.show_play_details = true;
+                            } else if app.session.offline {
+                                app.queue_offline(OfflineAction::Play(stream.clone()));
                             }
                         }
                     }
@@ -2522,6 +2698,7 @@ pub async fn handle_key_event(
                                         let url = client.get_series_url(&id, ext);
                                         app.pending_play_url = Some(url);
                                         app.pending_play_title = episode.title.clone();
+                                        app.pending_play_stream = Some(app.played_episode(episode));
                                         app.show_play_details = true;
                                     }
                                 }
@@ -2614,6 +2791,7 @@ pub async fn handle_key_event(
                                         let url = client.get_series_url(&id, ext);
                                         app.pending_play_url = Some(url);
                                         app.pending_play_title = episode.title.clone();
+                                        app.pending_play_stream = Some(app.played_episode(episode));
                                         app.show_play_details = true;
                                     }
                                 }
@@ -2672,21 +2850,17 @@ pub async fn handle_key_event(
                     KeyCode::End => app.jump_to_global_search_bottom(),
                     KeyCode::Enter => {
                         if !app.global_search_results.is_empty() {
-                            let stream = &app.global_search_results[app.selected_stream_index];
+                            let results = app.global_search_results.clone();
+                            let stream = &results[app.selected_stream_index];
+                            app.capture_zap_playlist(&results, stream);
                             if let Some(client) = &app.session.current_client {
-                                let id = get_id_str(&stream.stream_id);
-                                let extension =
-                                    stream.container_extension.as_deref().unwrap_or("ts");
-
-                                let url = match stream.stream_type.as_str() {
-                                    "movie" => client.get_vod_url(&id, extension),
-                                    "series" => client.get_series_url(&id, extension),
-                                    _ => client.get_stream_url(&id, "ts"), // Force TS for live streams
-                                };
+                                let url = client.get_playback_url(stream);
 
+                                let played = app.played_stream(stream);
                                 if stream.stream_type == "movie" || stream.stream_type == "series" {
                                     app.pending_play_url = Some(url);
                                     app.pending_play_title = Some(stream.name.clone());
+                                    app.pending_play_stream = Some(played);
                                     app.show_play_details = true;
                                 } else {
                                     app.session.state_loading = true;
@@ -2694,6 +2868,8 @@ pub async fn handle_key_event(
                                     app.session.loading_message =
                                         Some(format!("Preparing: {}...", stream.name));
                                     let tx = tx.clone();
+                                    player.set_media_title(&stream.name);
+                                    player.set_audio_only(played.audio_only);
                                     let player = player.clone();
                                     let stream_url = url.clone();
                                     let use_default = app.config.use_default_mpv;
@@ -2718,7 +2894,9 @@ pub async fn handle_key_event(
                                                 match player.wait_for_playback(10000).await {
                                                     Ok(true) => {
                                                         let _ = tx
-                                                            .send(AsyncAction::PlayerStarted)
+                                                            .send(AsyncAction::PlayerStarted(
+                                                                played,
+                                                            ))
                                                             .await;
                                                     }
                                                     Ok(false) => {
@@ -2754,6 +2932,8 @@ pub async fn handle_key_event(
                                         }
                                     });
                                 }
+                            } else if app.session.offline {
+                                app.queue_offline(OfflineAction::Play(stream.clone()));
                             }
                         }
                     }
@@ -2768,8 +2948,8 @@ pub async fn handle_key_event(
                     KeyCode::Char('j') | KeyCode::Down => app.next_setting(),
                     KeyCode::Char('k') | KeyCode::Up => app.previous_setting(),
                     KeyCode::Enter => {
-                        match app.selected_settings_index {
-                            0 => {
+                        match app.selected_setting() {
+                            Some(SettingsItem::ManageAccounts) => {
                                 app.settings_state = SettingsState::ManageAccounts;
                                 // Initialize list selection so user can navigate
                                 if !app.config.accounts.is_empty() {
@@ -2777,7 +2957,7 @@ pub async fn handle_key_event(
                                     app.session.selected_account_index = 0;
                                 }
                             }
-                            1 => {
+                            Some(SettingsItem::Timezone) => {
                                 app.current_screen = CurrentScreen::TimezoneSettings;
                                 // Pre-select current timezone in list
                                 let current_tz = app
@@ -2792,7 +2972,7 @@ pub async fn handle_key_event(
                                     .unwrap_or(0);
                                 app.timezone_list_state.select(Some(idx));
                             }
-                            2 => {
+                            Some(SettingsItem::PlaylistFilters) => {
                                 // Open Playlist Mode Selection dropdown
                                 app.settings_state = SettingsState::PlaylistModeSelection;
                                 // Pre-select: 0 = None, or first active mode (offset by 1)
@@ -2809,7 +2989,7 @@ pub async fn handle_key_event(
                                 };
                                 app.playlist_mode_list_state.select(Some(idx));
                             }
-                            3 => {
+                            Some(SettingsItem::DnsProvider) => {
                                 // Open DNS selection dropdown
                                 app.settings_state = SettingsState::DnsSelection;
                                 // Pre-select current DNS provider
@@ -2820,30 +3000,43 @@ pub async fn handle_key_event(
                                     .unwrap_or(0);
                                 app.dns_list_state.select(Some(idx));
                             }
-                            4 => {
+                            Some(SettingsItem::VideoMode) => {
                                 // Open Video Mode selection dropdown
                                 app.settings_state = SettingsState::VideoModeSelection;
                                 // Pre-select current video mode (0 = Enhanced, 1 = MPV Default)
                                 let idx = if app.config.use_default_mpv { 1 } else { 0 };
                                 app.video_mode_list_state.select(Some(idx));
                             }
-                            5 => {
+                            Some(SettingsItem::PlayerEngine) => {
                                 // Open Player Engine selection dropdown
                                 app.settings_state = SettingsState::PlayerEngineSelection;
-                                let engines = crate::config::PlayerEngine::all();
-                                let idx = engines
+                                app.detected_players = crate::setup::detect_known_players();
+                                let idx = app
+                                    .player_choices()
                                     .iter()
-                                    .position(|e| *e == app.config.preferred_player)
+                                    .position(|c| app.is_current_player(c))
                                     .unwrap_or(0);
                                 app.player_engine_list_state.select(Some(idx));
                             }
-                            6 => {
+                            Some(SettingsItem::PlayerProfiles) => {
+                                // Open the player profile list
+                                app.settings_state = SettingsState::PlayerProfiles;
+                                app.player_profile_list_state
+                                    .select((!app.config.player_profiles.is_empty()).then_some(0));
+                            }
+                            Some(SettingsItem::KodiHosts) => {
+                                // Open the Kodi host list
+                                app.settings_state = SettingsState::KodiHosts;
+                                app.kodi_host_list_state
+                                    .select((!app.config.kodi_hosts.is_empty()).then_some(0));
+                            }
+                            Some(SettingsItem::SmoothMotion) => {
                                 // Toggle Smooth Motion
                                 app.config.smooth_motion = !app.config.smooth_motion;
                                 let _ = app.config.save();
                                 app.refresh_settings_options();
                             }
-                            7 => {
+                            Some(SettingsItem::AutoRefresh) => {
                                 // Open Auto-Refresh selection
                                 app.settings_state = SettingsState::AutoRefreshSelection;
                                 let idx = match app.config.auto_refresh_hours {
@@ -2856,14 +3049,104 @@ pub async fn handle_key_event(
                                 };
                                 app.auto_refresh_list_state.select(Some(idx));
                             }
-                            8 => {
+                            Some(SettingsItem::ScoreTicker) => {
+                                // Open Score Ticker league picker
+                                app.settings_state = SettingsState::TickerLeagueSelection;
+                                app.ticker_league_list_state.select(Some(0));
+                            }
+                            Some(SettingsItem::LocalRelay) => {
+                                // Toggle the local relay
+                                app.config.relay_enabled = !app.config.relay_enabled;
+                                let _ = app.config.save();
+                                app.sync_relay();
+                                app.refresh_settings_options();
+                            }
+                            Some(SettingsItem::RelayAccess) => {
+                                // Switch between this computer only and the whole network
+                                let on_lan = app.config.relay_on_lan();
+                                app.config.relay_bind = if on_lan {
+                                    crate::config::RELAY_LOCAL_BIND
+                                } else {
+                                    crate::config::RELAY_LAN_BIND
+                                }
+                                .to_string();
+                                let _ = app.config.save();
+                                if !on_lan {
+                                    app.push_toast(
+                                        "The relay is open to your whole network, without a password."
+                                            .to_string(),
+                                    );
+                                }
+                                #[cfg(not(target_arch = "wasm32"))]
+                                app.restart_relay();
+                                app.refresh_settings_options();
+                            }
+                            Some(SettingsItem::RelayPort) => {
+                                // Type the relay port
+                                app.settings_state = SettingsState::RelayPort;
+                                app.relay_port_input =
+                                    tui_input::Input::new(app.config.relay_port.to_string());
+                                app.input_mode = InputMode::Editing;
+                            }
+                            Some(SettingsItem::StallWatchdog) => {
+                                // Cycle the stall watchdog timeout
+                                app.config.watchdog_stall_secs =
+                                    match app.config.watchdog_stall_secs {
+                                        0 => 10,
+                                        1..=10 => 20,
+                                        11..=20 => 30,
+                                        21..=30 => 60,
+                                        _ => 0,
+                                    };
+                                let _ = app.config.save();
+                                app.refresh_settings_options();
+                            }
+                            Some(SettingsItem::Artwork) => {
+                                // Cycle how logos and posters are drawn
+                                let all = crate::config::ImageProtocol::all();
+                                let pos = all
+                                    .iter()
+                                    .position(|p| *p == app.config.image_protocol)
+                                    .unwrap_or(0);
+                                app.config.image_protocol = all[(pos + 1) % all.len()];
+                                let _ = app.config.save();
+                                #[cfg(not(target_arch = "wasm32"))]
+                                {
+                                    app.image_protocol =
+                                        crate::images::resolve(app.config.image_protocol);
+                                }
+                                app.refresh_settings_options();
+                            }
+                            Some(SettingsItem::MultiviewScreen) => {
+                                // Cycle the screen size VLC multiview windows are tiled over
+                                let sizes = crate::multiview::SCREEN_SIZES;
+                                app.config.multiview_screen = match app
+                                    .config
+                                    .multiview_screen
+                                    .and_then(|s| sizes.iter().position(|&z| z == s))
+                                {
+                                    None => sizes.first().copied(),
+                                    Some(i) => sizes.get(i + 1).copied(),
+                                };
+                                let _ = app.config.save();
+                                app.refresh_settings_options();
+                            }
+                            Some(SettingsItem::RecordingsFolder) => {
+                                // Type a folder for recordings
+                                app.settings_state = SettingsState::RecordingsFolder;
+                                app.recordings_dir_input = tui_input::Input::new(
+                                    app.config.recordings_dir.clone().unwrap_or_default(),
+                                );
+                                app.input_mode = InputMode::Editing;
+                            }
+                            Some(SettingsItem::MatrixRain) => {
                                 // Enable Matrix Rain Screensaver
                                 app.show_matrix_rain = true;
                                 app.matrix_rain_screensaver_mode = true;
                                 app.matrix_rain_start_time = None;
                                 app.matrix_rain_columns.clear();
                             }
-                            9 => {
+                            Some(SettingsItem::CheckForUpdates) => {
                                 app.session.state_loading = true;
                                 app.session.loading_message =
                                     Some("Checking for updates...".to_string());
@@ -2872,14 +3155,14 @@ pub async fn handle_key_event(
                                     crate::setup::check_for_updates(tx, true).await;
                                 });
                             }
-                            10 => {
+                            Some(SettingsItem::CategoryVisibility) => {
                                 app.settings_state = SettingsState::CategoryManagement;
                                 app.category_mgmt.list_state.select(Some(0));
                             }
-                            11 => {
+                            Some(SettingsItem::About) => {
                                 app.settings_state = SettingsState::About;
                             }
-                            _ => {}
+                            None => {}
                         }
                     }
                     _ => {}
@@ -2914,7 +3197,8 @@ pub async fn handle_key_event(
                             app.input_name = tui_input::Input::new(account.name.clone());
                             app.input_url = tui_input::Input::new(account.base_url.clone());
                             app.input_username = tui_input::Input::new(account.username.clone());
-                            app.input_password = tui_input::Input::new(account.password.clone());
+                            app.input_password =
+                                tui_input::Input::new(account.secret().to_string());
                             app.input_epg_url =
                                 tui_input::Input::new(account.epg_url.clone().unwrap_or_default());
                             app.input_server_timezone = tui_input::Input::new(
@@ -2936,11 +3220,12 @@ pub async fn handle_key_event(
                                 app.config.accounts.get(app.session.selected_account_index)
                             {
                                 CachedCatalog::invalidate(&account.name);
+                                crate::whats_new::Snapshot::invalidate(&account.name);
+                                #[cfg(not(target_arch = "wasm32"))]
+                                crate::images::invalidate(&account.name);
                             }
                             app.config
-                                .accounts
-                                .remove(app.session.selected_account_index);
-                            let _ = app.config.save();
+                                .remove_account(app.session.selected_account_index);
                             if app.session.selected_account_index > 0 {
                                 app.session.selected_account_index -= 1;
                             }
@@ -3036,30 +3321,298 @@ pub async fn handle_key_event(
                         app.settings_state = SettingsState::Main;
                     }
                     KeyCode::Up | KeyCode::Char('k') => {
-                        let engines = crate::config::PlayerEngine::all();
+                        let count = app.player_choices().len();
                         if let Some(idx) = app.player_engine_list_state.selected() {
-                            let new_idx = if idx == 0 { engines.len() - 1 } else { idx - 1 };
+                            let new_idx = if idx == 0 { count - 1 } else { idx - 1 };
                             app.player_engine_list_state.select(Some(new_idx));
                         }
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
-                        let engines = crate::config::PlayerEngine::all();
+                        let count = app.player_choices().len();
                         if let Some(idx) = app.player_engine_list_state.selected() {
-                            let new_idx = if idx >= engines.len() - 1 { 0 } else { idx + 1 };
+                            let new_idx = if idx >= count - 1 { 0 } else { idx + 1 };
                             app.player_engine_list_state.select(Some(new_idx));
                         }
                     }
                     KeyCode::Enter => {
-                        let engines = crate::config::PlayerEngine::all();
-                        if let Some(idx) = app.player_engine_list_state.selected() {
-                            if idx < engines.len() {
-                                app.config.preferred_player = engines[idx];
+                        let choice = app
+                            .player_engine_list_state
+                            .selected()
+                            .and_then(|idx| app.player_choices().into_iter().nth(idx));
+                        if let Some(choice) = choice {
+                            app.select_player(choice);
+                            player.set_profile(app.config.active_player_profile().cloned());
+                        }
+                        app.settings_state = SettingsState::Main;
+                        app.refresh_settings_options();
+                    }
+                    _ => {}
+                },
+                SettingsState::RecordingsFolder => match key.code {
+                    KeyCode::Esc => {
+                        app.input_mode = InputMode::Normal;
+                        app.settings_state = SettingsState::Main;
+                    }
+                    KeyCode::Enter => {
+                        let dir = app.recordings_dir_input.value().trim().to_string();
+                        app.config.recordings_dir = (!dir.is_empty()).then_some(dir);
+                        let _ = app.config.save();
+                        #[cfg(not(target_arch = "wasm32"))]
+                        app.refresh_recording_files();
+                        app.input_mode = InputMode::Normal;
+                        app.settings_state = SettingsState::Main;
+                        app.refresh_settings_options();
+                    }
+                    _ => {
+                        app.recordings_dir_input.handle_event(&Event::Key(key));
+                    }
+                },
+                SettingsState::RelayPort => match key.code {
+                    KeyCode::Esc => {
+                        app.input_mode = InputMode::Normal;
+                        app.settings_state = SettingsState::Main;
+                    }
+                    KeyCode::Enter => match app.relay_port_input.value().trim().parse::<u16>() {
+                        Ok(port) if port > 0 => {
+                            if port != app.config.relay_port {
+                                app.config.relay_port = port;
                                 let _ = app.config.save();
+                                #[cfg(not(target_arch = "wasm32"))]
+                                app.restart_relay();
+                            }
+                            app.input_mode = InputMode::Normal;
+                            app.settings_state = SettingsState::Main;
+                            app.refresh_settings_options();
+                        }
+                        _ => {
+                            app.login_error =
+                                Some("The port must be a number from 1 to 65535".to_string());
+                        }
+                    },
+                    _ => {
+                        app.relay_port_input.handle_event(&Event::Key(key));
+                    }
+                },
+                SettingsState::PlayerProfiles => {
+                    if let Some(input) = app.player_profile_input.as_mut() {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.player_profile_input = None;
+                                app.player_profile_renaming = None;
+                                app.input_mode = InputMode::Normal;
+                                app.login_error = None;
+                            }
+                            KeyCode::Enter => {
+                                let saved =
+                                    match app.player_profile_renaming {
+                                        Some(idx) => app
+                                            .config
+                                            .rename_player_profile(idx, input.value())
+                                            .map(|_| idx),
+                                        None => crate::player_profile::PlayerProfile::parse(
+                                            input.value(),
+                                        )
+                                        .and_then(|profile| app.config.add_player_profile(profile))
+                                        .map(|_| app.config.player_profiles.len() - 1),
+                                    };
+                                match saved {
+                                    Ok(idx) => {
+                                        app.player_profile_list_state.select(Some(idx));
+                                        app.player_profile_input = None;
+                                        app.player_profile_renaming = None;
+                                        app.input_mode = InputMode::Normal;
+                                        app.login_error = None;
+                                        player.set_profile(
+                                            app.config.active_player_profile().cloned(),
+                                        );
+                                    }
+                                    Err(e) => app.login_error = Some(e),
+                                }
+                            }
+                            _ => {
+                                input.handle_event(&Event::Key(key));
                             }
                         }
+                        return Ok(InputResult::Continue);
+                    }
+                    let count = app.config.player_profiles.len();
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Backspace => {
+                            app.login_error = None;
+                            app.settings_state = SettingsState::Main;
+                            app.refresh_settings_options();
+                        }
+                        KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+                            let idx = app.player_profile_list_state.selected().unwrap_or(0);
+                            let new_idx = if idx == 0 { count - 1 } else { idx - 1 };
+                            app.player_profile_list_state.select(Some(new_idx));
+                        }
+                        KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+                            let idx = app.player_profile_list_state.selected().unwrap_or(0);
+                            let new_idx = if idx + 1 >= count { 0 } else { idx + 1 };
+                            app.player_profile_list_state.select(Some(new_idx));
+                        }
+                        KeyCode::Char('a') => {
+                            app.player_profile_input = Some(tui_input::Input::default());
+                            app.player_profile_renaming = None;
+                            app.input_mode = InputMode::Editing;
+                        }
+                        // Not 'r': that refreshes the playlist from any screen
+                        KeyCode::Char('e') => {
+                            let selected = app.player_profile_list_state.selected();
+                            if let Some((idx, profile)) = selected
+                                .and_then(|idx| Some((idx, app.config.player_profiles.get(idx)?)))
+                            {
+                                app.player_profile_input =
+                                    Some(tui_input::Input::new(profile.name.clone()));
+                                app.player_profile_renaming = Some(idx);
+                                app.input_mode = InputMode::Editing;
+                            }
+                        }
+                        KeyCode::Char('d') | KeyCode::Delete => {
+                            if let Some(idx) = app.player_profile_list_state.selected() {
+                                if idx < count {
+                                    app.config.remove_player_profile(idx);
+                                    player.set_profile(app.config.active_player_profile().cloned());
+                                    let remaining = app.config.player_profiles.len();
+                                    app.player_profile_list_state.select(if remaining == 0 {
+                                        None
+                                    } else {
+                                        Some(idx.min(remaining - 1))
+                                    });
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                SettingsState::KodiHosts => {
+                    if let Some(input) = app.kodi_host_input.as_mut() {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.kodi_host_input = None;
+                                app.input_mode = InputMode::Normal;
+                            }
+                            KeyCode::Enter => match crate::kodi::KodiHost::parse(input.value()) {
+                                Ok(host) => {
+                                    app.config.add_kodi_host(host);
+                                    app.kodi_host_list_state
+                                        .select(Some(app.config.kodi_hosts.len() - 1));
+                                    app.kodi_host_input = None;
+                                    app.input_mode = InputMode::Normal;
+                                    app.login_error = None;
+                                    app.warn_unsealed_passwords();
+                                }
+                                Err(e) => app.login_error = Some(e),
+                            },
+                            _ => {
+                                input.handle_event(&Event::Key(key));
+                            }
+                        }
+                        return Ok(InputResult::Continue);
+                    }
+                    let count = app.config.kodi_hosts.len();
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Backspace => {
+                            app.login_error = None;
+                            app.settings_state = SettingsState::Main;
+                            app.refresh_settings_options();
+                        }
+                        KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+                            let idx = app.kodi_host_list_state.selected().unwrap_or(0);
+                            let new_idx = if idx == 0 { count - 1 } else { idx - 1 };
+                            app.kodi_host_list_state.select(Some(new_idx));
+                        }
+                        KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+                            let idx = app.kodi_host_list_state.selected().unwrap_or(0);
+                            let new_idx = if idx + 1 >= count { 0 } else { idx + 1 };
+                            app.kodi_host_list_state.select(Some(new_idx));
+                        }
+                        KeyCode::Char('a') => {
+                            app.kodi_host_input = Some(tui_input::Input::default());
+                            app.input_mode = InputMode::Editing;
+                        }
+                        KeyCode::Char('d') | KeyCode::Delete => {
+                            if let Some(idx) = app.kodi_host_list_state.selected() {
+                                if idx < count {
+                                    app.config.remove_kodi_host(idx);
+                                    let remaining = app.config.kodi_hosts.len();
+                                    app.kodi_host_list_state.select(if remaining == 0 {
+                                        None
+                                    } else {
+                                        Some(idx.min(remaining - 1))
+                                    });
+                                }
+                            }
+                        }
+                        KeyCode::Enter => {
+                            // Test the connection to the selected host
+                            let host = app
+                                .kodi_host_list_state
+                                .selected()
+                                .and_then(|idx| app.config.kodi_hosts.get(idx))
+                                .cloned();
+                            if let Some(host) = host {
+                                app.push_toast(format!("Contacting {}…", host.address()));
+                                let tx = tx.clone();
+                                tokio::spawn(async move {
+                                    let result = match cast::lan_client() {
+                                        Ok(client) => host.ping(&client).await,
+                                        Err(e) => Err(e),
+                                    }
+                                    .map_err(|e| e.to_string());
+                                    let _ = tx
+                                        .send(AsyncAction::KodiPinged(host.name.clone(), result))
+                                        .await;
+                                });
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                SettingsState::TickerLeagueSelection => match key.code {
+                    KeyCode::Esc | KeyCode::Backspace => {
                         app.settings_state = SettingsState::Main;
                         app.refresh_settings_options();
                     }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        // "Off" + "All Leagues" + one row per league
+                        let total_items = crate::scores::league_names().len() + 2;
+                        if let Some(idx) = app.ticker_league_list_state.selected() {
+                            let new_idx = if idx == 0 { total_items - 1 } else { idx - 1 };
+                            app.ticker_league_list_state.select(Some(new_idx));
+                        }
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        let total_items = crate::scores::league_names().len() + 2;
+                        if let Some(idx) = app.ticker_league_list_state.selected() {
+                            let new_idx = if idx >= total_items - 1 { 0 } else { idx + 1 };
+                            app.ticker_league_list_state.select(Some(new_idx));
+                        }
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter => {
+                        let leagues = crate::scores::league_names();
+                        match app.ticker_league_list_state.selected() {
+                            Some(0) => app.config.score_ticker = false,
+                            Some(1) => {
+                                app.config.score_ticker = true;
+                                app.config.ticker_leagues.clear();
+                            }
+                            Some(idx) => {
+                                if let Some(league) = leagues.get(idx - 2) {
+                                    app.config.score_ticker = true;
+                                    if app.config.ticker_leagues.iter().any(|l| l == league) {
+                                        app.config.ticker_leagues.retain(|l| l != league);
+                                    } else {
+                                        app.config.ticker_leagues.push(league.to_string());
+                                    }
+                                }
+                            }
+                            None => {}
+                        }
+                        let _ = app.config.save();
+                        app.refresh_settings_options();
+                    }
                     _ => {}
                 },
                 SettingsState::PlaylistModeSelection => match key.code {
@@ -3278,6 +3831,11 @@ pub async fn handle_key_event(
                         if app.category_mgmt.search_mode {
                             app.category_mgmt.search_query.push(c);
                             app.category_mgmt.list_state.select(Some(0));
+                        } else if c == 'a' {
+                            // Live only: auto-detect -> radio -> not radio
+                            if let Some(cat) = app.category_mgmt_selected_live() {
+                                app.cycle_radio_override(cat.category_id.clone());
+                            }
                         } else if c == ' ' {
                             // Handle space for toggle if not in search mode
                             let content_type = app.category_mgmt.content_type;
@@ -3475,6 +4033,9 @@ pub async fn handle_key_event(
                 _ => {}
             }
         }
+        CurrentScreen::Recordings => handle_recordings_key(app, key, tx, player),
+        CurrentScreen::WatchStats => handle_watch_stats_key(app, key),
+        CurrentScreen::WhatsNew => handle_whats_new_key(app, key),
         CurrentScreen::SportsDashboard => {
             match key.code {
                 KeyCode::Esc | KeyCode::Backspace => {
@@ -3568,33 +4129,7 @@ pub async fn handle_key_event(
                             [app.sports_list_state.selected().unwrap_or(0)]
                         .title
                         .clone();
-
-                        app.session.state_loading = true;
-                        app.session.loading_message = Some(format!("Preparing: {}...", title));
-
-                        let tx = tx.clone();
-                        let player = player.clone();
-                        let use_default = app.config.use_default_mpv;
-                        let engine = app.config.preferred_player;
-                        let smooth = app.config.smooth_motion;
-                        tokio::spawn(async move {
-                            match player.play(&url, engine, use_default, smooth).await {
-                                Ok(_) => match player.wait_for_playback(10000).await {
-                                    Ok(true) => {
-                                        let _ = tx.send(AsyncAction::PlayerStarted).await;
-                                    }
-                                    _ => {
-                                        let log_err = player
-                                            .get_last_error_from_log()
-                                            .unwrap_or_else(|| "Failed to start".to_string());
-                                        let _ = tx.send(AsyncAction::PlayerFailed(log_err)).await;
-                                    }
-                                },
-                                Err(e) => {
-                                    let _ = tx.send(AsyncAction::PlayerFailed(e.to_string())).await;
-                                }
-                            }
-                        });
+                        spawn_embed_playback(app, tx, player, url, title);
                     }
                 }
                 _ => {}
@@ -3604,3 +4139,691 @@ pub async fn handle_key_event(
     }
     Ok(InputResult::Continue)
 }
+
+/// Resolves a Streamed.pk embed page to its HLS manifest and plays it in the
+/// configured player. Falls back to opening the embed page in the browser.
+fn spawn_embed_playback(
+    app: &mut App,
+    tx: &mpsc::Sender<AsyncAction>,
+    player: &player::Player,
+    embed_url: String,
+    title: String,
+) {
+    app.session.state_loading = true;
+    app.ui.player_error = None;
+    app.session.loading_message = Some(format!("Preparing: {}...", title));
+
+    let played = crate::app::PlayedStream {
+        stream_id: embed_url.clone(),
+        name: title.clone(),
+        account: "Live Sports".to_string(),
+        audio_only: app.audio_only,
+        ..Default::default()
+    };
+    let tx = tx.clone();
+    player.set_media_title(&title);
+    player.set_audio_only(app.audio_only);
+    let player = player.clone();
+    let use_default = app.config.use_default_mpv;
+    let engine = app.config.preferred_player;
+    let smooth = app.config.smooth_motion;
+    tokio::spawn(async move {
+        let _ = tx
+            .send(AsyncAction::LoadingMessage(
+                "Extracting stream from embed...".to_string(),
+            ))
+            .await;
+
+        let resolved = match crate::sports::resolve_embed_stream(&embed_url).await {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                let action = match webbrowser::open(&embed_url) {
+                    Ok(_) => AsyncAction::EmbedOpenedInBrowser(e.to_string()),
+                    Err(_) => AsyncAction::PlayerFailed(format!(
+                        "Could not extract a playable stream: {}",
+                        e
+                    )),
+                };
+                let _ = tx.send(action).await;
+                return;
+            }
+        };
+
+        let headers = player::StreamHeaders {
+            referrer: Some(resolved.referrer),
+            origin: Some(resolved.origin),
+            user_agent: Some(crate::sports::EMBED_USER_AGENT.to_string()),
+        };
+
+        match player
+            .play_with_headers(&resolved.url, &headers, engine, use_default, smooth)
+            .await
+        {
+            Ok(_) => match player.wait_for_playback(10000).await {
+                Ok(true) => {
+                    let _ = tx.send(AsyncAction::PlayerStarted(played)).await;
+                }
+                _ => {
+                    let log_err = player
+                        .get_last_error_from_log()
+                        .unwrap_or_else(|| "Failed to start".to_string());
+                    let _ = tx.send(AsyncAction::PlayerFailed(log_err)).await;
+                }
+            },
+            Err(e) => {
+                let _ = tx.send(AsyncAction::PlayerFailed(e.to_string())).await;
+            }
+        }
+    });
+}
+
+/// Alt+key bindings of the now-playing bar (mirroring mpv's own defaults where possible)
+fn transport_command_for_key(code: KeyCode) -> Option<TransportCommand> {
+    match code {
+        KeyCode::Char(' ') | KeyCode::Char('p') => Some(TransportCommand::TogglePause),
+        KeyCode::Left => Some(TransportCommand::Seek(-10)),
+        KeyCode::Right => Some(TransportCommand::Seek(10)),
+        KeyCode::Down => Some(TransportCommand::Seek(-60)),
+        KeyCode::Up => Some(TransportCommand::Seek(60)),
+        KeyCode::Char('-') => Some(TransportCommand::Volume(-5)),
+        KeyCode::Char('=') | KeyCode::Char('+') => Some(TransportCommand::Volume(5)),
+        KeyCode::Char('m') => Some(TransportCommand::ToggleMute),
+        KeyCode::Char('a') => Some(TransportCommand::CycleAudioTrack),
+        KeyCode::Char('s') => Some(TransportCommand::CycleSubtitleTrack),
+        KeyCode::Char('f') => Some(TransportCommand::ToggleFullscreen),
+        _ => None,
+    }
+}
+
+/// Send a transport command over mpv IPC, then refresh the now-playing bar
+fn send_transport_command(
+    app: &mut App,
+    tx: &mpsc::Sender<AsyncAction>,
+    player: &player::Player,
+    cmd: TransportCommand,
+) {
+    let Some(controller) = player.controller() else {
+        app.login_error = Some("Transport controls require MPV (IPC not available)".to_string());
+        return;
+    };
+
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        match controller.send(cmd).await {
+            Ok(()) => {
+                let _ = tx.send(AsyncAction::TransportSent(cmd.label())).await;
+                if let Ok(status) = controller.status().await {
+                    let _ = tx.send(AsyncAction::PlayerStatus(Some(status))).await;
+                }
+            }
+            Err(e) => {
+                let _ = tx
+                    .send(AsyncAction::TransportSent(format!(
+                        "{} failed: {}",
+                        cmd.label(),
+                        e
+                    )))
+                    .await;
+            }
+        }
+    });
+}
+
+/// Send a transport command to the cast device; the next status poll refreshes the cast bar
+fn send_cast_command(app: &mut App, tx: &mpsc::Sender<AsyncAction>, cmd: TransportCommand) {
+    let Some(session) = app.cast_session.clone() else {
+        return;
+    };
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let label = match session.send(cmd).await {
+            Ok(()) => cmd.label(),
+            Err(e) => format!("{} failed: {}", cmd.label(), e),
+        };
+        let _ = tx.send(AsyncAction::CastCommandSent(label)).await;
+        if let Ok(status) = session.status().await {
+            let _ = tx.send(AsyncAction::CastStatus(Ok(status))).await;
+        }
+    });
+}
+
+/// Stop playback on the cast device and end the session
+fn stop_casting(app: &mut App) {
+    let Some(session) = app.cast_session.clone() else {
+        return;
+    };
+    app.end_cast(format!("Stopped casting to {}", session.device_name()));
+    tokio::spawn(async move {
+        let _ = session.stop().await;
+    });
+}
+
+/// Switch the running player `delta` channels through the zap list. mpv is reused
+/// via IPC `loadfile` (see `Player::play_with_headers`), so its window stays put.
+/// While casting, the new channel loads on the cast device's running session.
+pub fn zap_channel(
+    app: &mut App,
+    tx: &mpsc::Sender<AsyncAction>,
+    player: &player::Player,
+    delta: i32,
+) {
+    let Some(client) = app.session.current_client.clone() else {
+        return;
+    };
+    let Some(stream) = app.zap_target(delta) else {
+        app.login_error = Some("Start a channel from a list to use channel up/down".to_string());
+        return;
+    };
+
+    let stream_id = get_id_str(&stream.stream_id);
+    let url = client.get_playback_url(&stream);
+    let name = stream.name.clone();
+    app.session.state_loading = true;
+    app.ui.player_error = None;
+    app.session.loading_message = Some(format!("Switching to {}...", name));
+
+    let tx = tx.clone();
+    if let Some(mut session) = app.cast_session.clone() {
+        tokio::spawn(async move {
+            match session.load(&url, &name).await {
+                Ok(()) => {
+                    let _ = tx.send(AsyncAction::CastStarted(session)).await;
+                }
+                Err(e) => {
+                    let _ = tx.send(AsyncAction::CastFailed(e.to_string())).await;
+                }
+            }
+        });
+        return;
+    }
+    player.set_media_title(&name);
+    player.set_audio_only(app.wants_audio_only(&stream));
+    let player = player.clone();
+    let engine = app.config.preferred_player;
+    let use_default = app.config.use_default_mpv;
+    let smooth = app.config.smooth_motion;
+    tokio::spawn(async move {
+        match player.play(&url, engine, use_default, smooth).await {
+            Ok(_) => match player.wait_for_playback(10000).await {
+                Ok(true) => {
+                    let _ = tx
+                        .send(AsyncAction::ChannelZapped { stream_id, name })
+                        .await;
+                }
+                _ => {
+                    let log_err = player
+                        .get_last_error_from_log()
+                        .unwrap_or_else(|| "Failed to start".to_string());
+                    let _ = tx.send(AsyncAction::PlayerFailed(log_err)).await;
+                }
+            },
+            Err(e) => {
+                let _ = tx.send(AsyncAction::PlayerFailed(e.to_string())).await;
+            }
+        }
+    });
+}
+
+/// Switch audio-only mode; a live channel that is playing restarts in the new mode
+fn toggle_audio_only(app: &mut App, tx: &mpsc::Sender<AsyncAction>, player: &player::Player) {
+    app.audio_only = !app.audio_only;
+    let state = if app.audio_only { "on" } else { "off" };
+    // Only restart when the zap list's current channel is what is playing (not a VOD)
+    let playing_live = app.now_playing.as_ref().is_some_and(|np| {
+        app.zap_playlist
+            .get(app.zap_position)
+            .is_some_and(|s| s.name == np.title)
+    });
+    if playing_live && app.multiview_session.is_none() {
+        zap_channel(app, tx, player, 0);
+    }
+    app.push_toast(format!("Audio-only mode {}", state));
+}
+
+/// Keys for the multiview builder popup. Rows are the marked tiles followed by
+/// the saved presets.
+fn handle_multiview_popup_key(
+    app: &mut App,
+    key: KeyEvent,
+    tx: &mpsc::Sender<AsyncAction>,
+    player: &player::Player,
+) {
+    // Preset name prompt
+    if let Some(name) = app.multiview_preset_name.as_mut() {
+        match key.code {
+            KeyCode::Esc => app.multiview_preset_name = None,
+            KeyCode::Backspace => {
+                name.pop();
+            }
+            KeyCode::Char(c) => name.push(c),
+            KeyCode::Enter => {
+                let name = name.trim().to_string();
+                app.multiview_preset_name = None;
+                if !name.is_empty() {
+                    let tiles = app.multiview_tiles.clone();
+                    app.config.save_multiview_preset(name, tiles);
+                }
+            }
+            _ => {}
+        }
+        return;
+    }
+
+    let tile_count = app.multiview_tiles.len();
+    let rows = tile_count + app.config.multiview_presets.len();
+    let selected = app.multiview_list_state.selected();
+
+    match key.code {
+        KeyCode::Esc => app.show_multiview = false,
+        KeyCode::Char('j') | KeyCode::Down if rows > 0 => {
+            let next = selected.map(|i| (i + 1) % rows).unwrap_or(0);
+            app.multiview_list_state.select(Some(next));
+        }
+        KeyCode::Char('k') | KeyCode::Up if rows > 0 => {
+            let prev = match selected {
+                Some(0) | None => rows - 1,
+                Some(i) => i - 1,
+            };
+            app.multiview_list_state.select(Some(prev));
+        }
+        KeyCode::Char('d') | KeyCode::Delete => match selected {
+            Some(i) if i < tile_count => {
+                app.multiview_tiles.remove(i);
+            }
+            Some(i) if i < rows => {
+                app.config.delete_multiview_preset(i - tile_count);
+            }
+            _ => {}
+        },
+        KeyCode::Char('c') => app.multiview_tiles.clear(),
+        KeyCode::Char('s') if tile_count > 0 => {
+            app.multiview_preset_name = Some(String::new());
+        }
+        KeyCode::Enter => match selected {
+            Some(i) if i >= tile_count && i < rows => {
+                app.load_multiview_preset(i - tile_count);
+                app.multiview_list_state.select(Some(0));
+            }
+            _ => launch_multiview(app, tx, player),
+        },
+        _ => {}
+    }
+
+    // Keep the selection inside the (possibly shrunk) list
+    let rows = app.multiview_tiles.len() + app.config.multiview_presets.len();
+    match app.multiview_list_state.selected() {
+        _ if rows == 0 => app.multiview_list_state.select(None),
+        Some(i) if i >= rows => app.multiview_list_state.select(Some(rows - 1)),
+        _ => {}
+    }
+}
+
+/// Start the marked tiles as one multiview, audio on the first tile
+fn launch_multiview(app: &mut App, tx: &mpsc::Sender<AsyncAction>, player: &player::Player) {
+    if app.multiview_tiles.len() < 2 {
+        app.login_error = Some("Mark at least 2 channels with 'w' for multiview".to_string());
+        return;
+    }
+    let Some(client) = app.session.current_client.clone() else {
+        return;
+    };
+
+    let tiles = app.multiview_tiles.clone();
+    let urls: Vec<String> = tiles
+        .iter()
+        .map(|t| client.get_stream_url(&t.stream_id, "ts"))
+        .collect();
+    app.show_multiview = false;
+    app.session.state_loading = true;
+    app.ui.player_error = None;
+    app.session.loading_message = Some(format!("Opening {} streams...", tiles.len()));
+
+    let tx = tx.clone();
+    let player = player.clone();
+    let engine = app.config.preferred_player;
+    let use_default = app.config.use_default_mpv;
+    let smooth = app.config.smooth_motion;
+    let configured_screen = app.config.multiview_screen;
+    tokio::spawn(async move {
+        let screen = crate::multiview::screen_size(configured_screen);
+        if let Err(e) = player.play_multiview(&urls, 0, engine, use_default, smooth, screen) {
+            let _ = tx.send(AsyncAction::PlayerFailed(e.to_string())).await;
+            return;
+        }
+        match player.wait_for_playback(15000).await {
+            Ok(true) => {
+                let _ = tx.send(AsyncAction::MultiviewStarted(tiles)).await;
+            }
+            _ => {
+                let log_err = player
+                    .get_last_error_from_log()
+                    .unwrap_or_else(|| "Multiview failed to start".to_string());
+                let _ = tx.send(AsyncAction::PlayerFailed(log_err)).await;
+            }
+        }
+    });
+}
+
+/// Route the multiview's audio to tile `focus` (0-based)
+fn focus_multiview_audio(
+    app: &mut App,
+    tx: &mpsc::Sender<AsyncAction>,
+    player: &player::Player,
+    focus: usize,
+) {
+    let Some(session) = app.multiview_session.as_ref() else {
+        return;
+    };
+    if focus >= session.tiles.len() || focus == session.audio_focus {
+        return;
+    }
+    let tiles = session.tiles.len();
+    let tx = tx.clone();
+    let player = player.clone();
+    tokio::spawn(async move {
+        match player.set_multiview_audio_focus(tiles, focus).await {
+            Ok(_) => {
+                let _ = tx.send(AsyncAction::MultiviewFocused(focus)).await;
+            }
+            Err(e) => {
+                let _ = tx
+                    .send(AsyncAction::TransportSent(format!(
+                        "Audio switch failed: {}",
+                        e
+                    )))
+                    .await;
+            }
+        }
+    });
+}
+
+fn open_recordings(app: &mut App) {
+    app.previous_screen = Some(app.current_screen.clone());
+    app.current_screen = CurrentScreen::Recordings;
+    app.refresh_recording_files();
+}
+
+/// Show the selected playlist's catalog changes since it last looked, and mark them seen
+fn open_whats_new(app: &mut App) {
+    let Some(account) = app
+        .config
+        .accounts
+        .get(app.session.selected_account_index)
+        .map(|a| a.name.clone())
+    else {
+        return;
+    };
+    let now = chrono::Utc::now();
+    app.whats_new_since = app.whats_new.last_seen(&account);
+    app.whats_new_items =
+        app.whats_new
+            .since(&account, app.whats_new_since, &app.config.processing_modes);
+    app.whats_new.mark_seen(&account, now);
+    let _ = app.whats_new.save();
+    let has_new = app.whats_new_items.iter().any(|c| c.is_new());
+    app.whats_new_list_state.select(has_new.then_some(0));
+    app.previous_screen = Some(app.current_screen.clone());
+    app.current_screen = CurrentScreen::WhatsNew;
+}
+
+fn handle_whats_new_key(app: &mut App, key: KeyEvent) {
+    let count = app.whats_new_items.iter().filter(|c| c.is_new()).count();
+    match key.code {
+        KeyCode::Esc | KeyCode::Backspace => {
+            app.current_screen = app.previous_screen.take().unwrap_or(CurrentScreen::Home);
+        }
+        KeyCode::Char('j') | KeyCode::Down if count > 0 => {
+            let idx = app.whats_new_list_state.selected().unwrap_or(0);
+            app.whats_new_list_state
+                .select(Some((idx + 1).min(count - 1)));
+        }
+        KeyCode::Char('k') | KeyCode::Up if count > 0 => {
+            let idx = app.whats_new_list_state.selected().unwrap_or(0);
+            app.whats_new_list_state.select(Some(idx.saturating_sub(1)));
+        }
+        _ => {}
+    }
+}
+
+fn handle_watch_stats_key(app: &mut App, key: KeyEvent) {
+    let format = match key.code {
+        KeyCode::Esc | KeyCode::Backspace => {
+            app.current_screen = app.previous_screen.take().unwrap_or(CurrentScreen::Home);
+            return;
+        }
+        KeyCode::Char('c') => crate::history::ExportFormat::Csv,
+        KeyCode::Char('J') => crate::history::ExportFormat::Json,
+        _ => return,
+    };
+    // Include what is playing right now
+    let mut history = app.watch_history.clone();
+    if let Some(mut session) = app.viewing.clone() {
+        session.ended_at = chrono::Utc::now();
+        history.record(session);
+    }
+    match history.export(format) {
+        Ok(path) => app.push_toast(format!("Exported watch history to {}", path.display())),
+        Err(e) => app.login_error = Some(format!("Export failed: {}", e)),
+    }
+}
+
+/// Put the channel's relay URL on the clipboard (shown instead when that fails)
+fn copy_relay_url(app: &mut App, stream_id: &str) {
+    let Some(relay) = &app.relay else {
+        app.login_error = Some("The local relay is off. Enable it in Settings.".to_string());
+        return;
+    };
+    let url = relay.url_for(stream_id);
+    if arboard::Clipboard::new()
+        .and_then(|mut c| c.set_text(url.clone()))
+        .is_err()
+    {
+        app.login_error = Some(format!("Relay URL: {}", url));
+    }
+}
+
+/// Record the channel, or stop it if it is already recording
+fn toggle_recording(app: &mut App, stream: &crate::api::Stream) {
+    let stream_id = get_id_str(&stream.stream_id);
+    if let Some(recording) = app.active_recording(&stream_id) {
+        recording.stop();
+        return;
+    }
+    if let Err(e) = app.start_recording(stream) {
+        app.login_error = Some(format!("Cannot record: {}", e));
+    }
+}
+
+fn handle_recordings_key(
+    app: &mut App,
+    key: KeyEvent,
+    tx: &mpsc::Sender<AsyncAction>,
+    player: &player::Player,
+) {
+    use crate::app::RecordingRow;
+
+    let rows = app.recording_rows().len();
+    let selected = app.recordings_list_state.selected();
+    match key.code {
+        KeyCode::Esc | KeyCode::Backspace => {
+            app.current_screen = app.previous_screen.take().unwrap_or(CurrentScreen::Home);
+        }
+        KeyCode::Char('j') | KeyCode::Down if rows > 0 => {
+            let next = selected.map(|i| (i + 1) % rows).unwrap_or(0);
+            app.recordings_list_state.select(Some(next));
+        }
+        KeyCode::Char('k') | KeyCode::Up if rows > 0 => {
+            let prev = match selected {
+                Some(0) | None => rows - 1,
+                Some(i) => i - 1,
+            };
+            app.recordings_list_state.select(Some(prev));
+        }
+        KeyCode::Char('o') | KeyCode::Char('s') => {
+            if let Some(RecordingRow::Active(rec)) =
+                selected.and_then(|i| app.recording_rows().get(i).cloned())
+            {
+                rec.stop();
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            let (file, schedule) = match selected.and_then(|i| app.recording_rows().get(i).cloned())
+            {
+                Some(RecordingRow::File(file)) => (Some(file.path.clone()), None),
+                Some(RecordingRow::Scheduled(row)) => (None, Some(row.schedule_id)),
+                _ => (None, None),
+            };
+            if let Some(path) = file {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    app.login_error = Some(format!("Failed to delete recording: {}", e));
+                }
+            }
+            if let Some(id) = schedule {
+                app.config.remove_recording_schedules(&[id]);
+            }
+            app.refresh_recording_files();
+        }
+        KeyCode::Enter => {
+            let target = selected
+                .and_then(|i| app.recording_rows().get(i).cloned())
+                .and_then(|row| match row {
+                    RecordingRow::Active(rec) => Some((rec.path.clone(), rec.title.clone())),
+                    RecordingRow::Scheduled(_) => None,
+                    RecordingRow::File(file) => Some((
+                        file.path.clone(),
+                        file.path
+                            .file_stem()
+                            .map(|s| s.to_string_lossy().to_string())
+                            .unwrap_or_default(),
+                    )),
+                });
+            let Some((path, title)) = target else {
+                return;
+            };
+
+            app.session.state_loading = true;
+            app.ui.player_error = None;
+            app.session.loading_message = Some(format!("Opening {}...", title));
+            let tx = tx.clone();
+            player.set_media_title(&title);
+            player.set_audio_only(app.audio_only);
+            let player = player.clone();
+            let engine = app.config.preferred_player;
+            let use_default = app.config.use_default_mpv;
+            let smooth = app.config.smooth_motion;
+            tokio::spawn(async move {
+                let url = path.to_string_lossy().to_string();
+                match player.play(&url, engine, use_default, smooth).await {
+                    Ok(_) => match player.wait_for_playback(10000).await {
+                        Ok(true) => {
+                            let _ = tx.send(AsyncAction::RecordingPlaybackStarted(title)).await;
+                        }
+                        _ => {
+                            let log_err = player
+                                .get_last_error_from_log()
+                                .unwrap_or_else(|| "Failed to start".to_string());
+                            let _ = tx.send(AsyncAction::PlayerFailed(log_err)).await;
+                        }
+                    },
+                    Err(e) => {
+                        let _ = tx.send(AsyncAction::PlayerFailed(e.to_string())).await;
+                    }
+                }
+            });
+        }
+        _ => {}
+    }
+}
+
+fn open_schedule_picker(
+    app: &mut App,
+    tx: &mpsc::Sender<AsyncAction>,
+    stream: &crate::api::Stream,
+) {
+    let Some(client) = app.session.current_client.clone() else {
+        return;
+    };
+    let stream_id = get_id_str(&stream.stream_id);
+    app.schedule_picker_channel = Some((stream_id.clone(), stream.name.clone()));
+    app.schedule_picker_state.select(Some(0));
+
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        match client.get_short_epg(&stream_id).await {
+            Ok(epg) => {
+                let _ = tx
+                    .send(AsyncAction::ScheduleGuideLoaded(
+                        stream_id,
+                        epg.epg_listings,
+                    ))
+                    .await;
+            }
+            Err(e) => {
+                let _ = tx
+                    .send(AsyncAction::Error(format!(
+                        "Could not load the guide: {}",
+                        e
+                    )))
+                    .await;
+            }
+        }
+    });
+}
+
+/// Keys for the schedule picker: enter records one programme, `s` the show every
+/// week on that weekday, `a` every airing of the show
+fn handle_schedule_picker_key(app: &mut App, key: KeyEvent) {
+    use crate::config::ScheduleRule;
+    use chrono::Datelike;
+
+    let Some((stream_id, channel_name)) = app.schedule_picker_channel.clone() else {
+        return;
+    };
+    let entries = app.schedule_picker_entries();
+    let selected = app.schedule_picker_state.selected().unwrap_or(0);
+
+    let rule = match key.code {
+        KeyCode::Esc => {
+            app.schedule_picker_channel = None;
+            return;
+        }
+        KeyCode::Char('j') | KeyCode::Down if !entries.is_empty() => {
+            app.schedule_picker_state
+                .select(Some((selected + 1) % entries.len()));
+            return;
+        }
+        KeyCode::Char('k') | KeyCode::Up if !entries.is_empty() => {
+            app.schedule_picker_state
+                .select(Some(selected.checked_sub(1).unwrap_or(entries.len() - 1)));
+            return;
+        }
+        KeyCode::Enter => entries.get(selected).map(|e| ScheduleRule::Once {
+            title: e.title.clone(),
+            start: e.start.timestamp(),
+            end: e.end.timestamp(),
+        }),
+        KeyCode::Char('s') => entries.get(selected).map(|e| {
+            let tz: chrono_tz::Tz = app
+                .config
+                .get_user_timezone()
+                .parse()
+                .unwrap_or(chrono_tz::UTC);
+            ScheduleRule::Series {
+                title: crate::scheduler::series_title(&e.title).to_string(),
+                weekday: Some(e.start.with_timezone(&tz).weekday()),
+            }
+        }),
+        KeyCode::Char('a') => entries.get(selected).map(|e| ScheduleRule::Series {
+            title: crate::scheduler::series_title(&e.title).to_string(),
+            weekday: None,
+        }),
+        _ => None,
+    };
+
+    if let Some(rule) = rule {
+        app.schedule_picker_channel = None;
+        if let Some(warning) = app.add_recording_schedule(stream_id, channel_name, rule) {
+            app.login_error = Some(warning);
+        }
+    }
+}
@@ -251,6 +251,7 @@ pub fn handle_mouse_event(app: &mut App, mouse: MouseEvent, tx: &mpsc::Sender<As
                                         // In TUI, best if we just delegate it: create a new popup/play pending state
                                         app.pending_play_url = Some(url);
                                         app.pending_play_title = Some(stream.name.clone());
+                                        app.pending_play_stream = Some(app.played_stream(stream));
                                         // Force enter input to process playback properly if needed
                                     }
                                 }
@@ -269,6 +270,7 @@ pub fn handle_mouse_event(app: &mut App, mouse: MouseEvent, tx: &mpsc::Sender<As
                                         let url = client.get_vod_url(&id, extension);
                                         app.pending_play_url = Some(url);
                                         app.pending_play_title = Some(stream.name.clone());
+                                        app.pending_play_stream = Some(app.played_stream(stream));
                                         app.show_play_details = true;
                                     }
                                 }
@@ -309,24 +311,21 @@ pub fn handle_mouse_event(app: &mut App, mouse: MouseEvent, tx: &mpsc::Sender<As
                                     // Same pending logic
                                     if let Some(client) = &app.session.current_client {
                                         let stream = &app.global_search_results[selected_idx];
-                                        let id = get_id_str(&stream.stream_id);
-                                        let extension =
-                                            stream.container_extension.as_deref().unwrap_or("ts");
-                                        let url = match stream.stream_type.as_str() {
-                                            "movie" => client.get_vod_url(&id, extension),
-                                            "series" => client.get_series_url(&id, extension),
-                                            _ => client.get_stream_url(&id, "ts"), // Force TS for live streams
-                                        };
+                                        let url = client.get_playback_url(stream);
 
                                         if stream.stream_type == "movie"
                                             || stream.stream_type == "series"
                                         {
                                             app.pending_play_url = Some(url);
                                             app.pending_play_title = Some(stream.name.clone());
+                                            app.pending_play_stream =
+                                                Some(app.played_stream(stream));
                                             app.show_play_details = true;
                                         } else {
                                             app.pending_play_url = Some(url);
                                             app.pending_play_title = Some(stream.name.clone());
+                                            app.pending_play_stream =
+                                                Some(app.played_stream(stream));
                                         }
                                     }
                                 }
@@ -357,6 +356,7 @@ pub fn handle_mouse_event(app: &mut App, mouse: MouseEvent, tx: &mpsc::Sender<As
                                     let url = client.get_series_url(&id, ext);
                                     app.pending_play_url = Some(url);
                                     app.pending_play_title = episode.title.clone();
+                                    app.pending_play_stream = Some(app.played_episode(episode));
                                     app.show_play_details = true;
                                 }
                             }
@@ -441,6 +441,7 @@ pub fn handle_mouse_event(app: &mut App, mouse: MouseEvent, tx: &mpsc::Sender<As
 
                             app.pending_play_url = Some(stream_url.clone());
                             app.pending_play_title = Some(stream_title);
+                            app.show_play_details = true;
                         }
                     }
                 }
@@ -0,0 +1,360 @@
+//! Watch history: one record per viewing session, kept in `watch_history.json`
+//! in the data directory, plus the aggregates shown on the statistics screen
+//! and CSV/JSON export.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Sessions shorter than this (zapping past a channel) are not recorded
+pub const MIN_SESSION_SECS: i64 = 30;
+
+/// Oldest sessions are dropped beyond this many
+const MAX_SESSIONS: usize = 10_000;
+
+/// Weeks shown in the hours-per-week chart
+pub const WEEKS_SHOWN: usize = 8;
+
+/// Rows per ranking on the statistics screen
+const TOP_N: usize = 10;
+
+/// League names recognised in category and channel names (checked in order)
+const LEAGUES: &[(&str, &str)] = &[
+    ("WNBA", "WNBA"),
+    ("NBA", "NBA"),
+    ("NFL", "NFL"),
+    ("MLB", "MLB"),
+    ("NHL", "NHL"),
+    ("MLS", "MLS"),
+    ("NCAAF", "NCAAF"),
+    ("NCAAB", "NCAAB"),
+    ("UFC", "UFC"),
+    ("F1", "F1"),
+    ("FORMULA 1", "F1"),
+    ("EPL", "Premier League"),
+    ("PREMIER LEAGUE", "Premier League"),
+    ("CHAMPIONS LEAGUE", "Champions League"),
+    ("LA LIGA", "LaLiga"),
+    ("LALIGA", "LaLiga"),
+    ("BUNDESLIGA", "Bundesliga"),
+    ("SERIE A", "Serie A"),
+];
+
+/// One stretch of watching a single channel or title
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ViewingSession {
+    pub stream_id: String,
+    pub channel: String,
+    pub account: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub league: Option<String>,
+    /// Player engine or profile name
+    pub engine: String,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+impl ViewingSession {
+    pub fn duration_secs(&self) -> i64 {
+        (self.ended_at - self.started_at).num_seconds().max(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchHistory {
+    pub sessions: Vec<ViewingSession>,
+}
+
+impl WatchHistory {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn path() -> Option<std::path::PathBuf> {
+        crate::storage::paths().map(|p| p.data_dir.join("watch_history.json"))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    /// WASM stub - history is not kept in the browser
+    #[cfg(target_arch = "wasm32")]
+    pub fn load() -> Self {
+        Self::default()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let path =
+            Self::path().ok_or_else(|| anyhow::anyhow!("Cannot determine data directory"))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Add a finished session. Returns false for sessions too short to count.
+    pub fn record(&mut self, session: ViewingSession) -> bool {
+        if session.duration_secs() < MIN_SESSION_SECS {
+            return false;
+        }
+        self.sessions.push(session);
+        if self.sessions.len() > MAX_SESSIONS {
+            let excess = self.sessions.len() - MAX_SESSIONS;
+            self.sessions.drain(..excess);
+        }
+        true
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "started_at,ended_at,duration_secs,channel,stream_id,account,category,league,engine\n",
+        );
+        for s in &self.sessions {
+            let fields = [
+                s.started_at.to_rfc3339(),
+                s.ended_at.to_rfc3339(),
+                s.duration_secs().to_string(),
+                s.channel.clone(),
+                s.stream_id.clone(),
+                s.account.clone(),
+                s.category.clone().unwrap_or_default(),
+                s.league.clone().unwrap_or_default(),
+                s.engine.clone(),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> Result<String, anyhow::Error> {
+        Ok(serde_json::to_string_pretty(&self.sessions)?)
+    }
+
+    /// Write the history to the Downloads folder (or the data directory) and
+    /// return the file written
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn export(&self, format: ExportFormat) -> Result<std::path::PathBuf, anyhow::Error> {
+        let dir = directories::UserDirs::new()
+            .and_then(|u| u.download_dir().map(|d| d.to_path_buf()))
+            .or_else(|| Self::path().and_then(|p| p.parent().map(|d| d.to_path_buf())))
+            .ok_or_else(|| anyhow::anyhow!("Cannot determine an export folder"))?;
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(
+            "watch-history-{}.{}",
+            Utc::now().format("%Y%m%d-%H%M%S"),
+            format.extension()
+        ));
+        let contents = match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => self.to_json()?,
+        };
+        std::fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    pub fn stats(&self, now: DateTime<Utc>) -> HistoryStats {
+        let mut channels: HashMap<&str, i64> = HashMap::new();
+        let mut categories: HashMap<&str, i64> = HashMap::new();
+        let mut leagues: HashMap<&str, i64> = HashMap::new();
+        let mut accounts: HashMap<&str, i64> = HashMap::new();
+
+        let this_week = week_start(now.date_naive());
+        let weeks: Vec<NaiveDate> = (0..WEEKS_SHOWN)
+            .rev()
+            .map(|i| this_week - Duration::weeks(i as i64))
+            .collect();
+        let mut weekly = vec![0i64; WEEKS_SHOWN];
+
+        let mut total_secs = 0;
+        for s in &self.sessions {
+            let secs = s.duration_secs();
+            total_secs += secs;
+            *channels.entry(&s.channel).or_default() += secs;
+            *accounts.entry(&s.account).or_default() += secs;
+            if let Some(category) = &s.category {
+                *categories.entry(category).or_default() += secs;
+            }
+            if let Some(league) = &s.league {
+                *leagues.entry(league).or_default() += secs;
+            }
+            let week = week_start(s.started_at.date_naive());
+            if let Some(slot) = weeks.iter().position(|w| *w == week) {
+                weekly[slot] += secs;
+            }
+        }
+
+        HistoryStats {
+            total_secs,
+            sessions: self.sessions.len(),
+            top_channels: ranked(channels),
+            weekly: weeks.into_iter().zip(weekly).collect(),
+            by_category: ranked(categories),
+            by_league: ranked(leagues),
+            by_account: ranked(accounts),
+        }
+    }
+}
+
+/// Aggregates for the statistics screen; rankings are (name, seconds), largest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryStats {
+    pub total_secs: i64,
+    pub sessions: usize,
+    pub top_channels: Vec<(String, i64)>,
+    /// (Monday of the week, seconds), oldest first, ending with the current week
+    pub weekly: Vec<(NaiveDate, i64)>,
+    pub by_category: Vec<(String, i64)>,
+    pub by_league: Vec<(String, i64)>,
+    pub by_account: Vec<(String, i64)>,
+}
+
+fn ranked(totals: HashMap<&str, i64>) -> Vec<(String, i64)> {
+    let mut rows: Vec<(String, i64)> = totals
+        .into_iter()
+        .map(|(name, secs)| (name.to_string(), secs))
+        .collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    rows.truncate(TOP_N);
+    rows
+}
+
+fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// The league a category or channel name refers to, if any
+pub fn detect_league(names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| {
+        // Pad and split on punctuation so "NBA" doesn't match inside "WNBA"
+        let words: String = name
+            .to_uppercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+            .collect();
+        let words = format!(
+            " {} ",
+            words.split_whitespace().collect::<Vec<_>>().join(" ")
+        );
+        LEAGUES
+            .iter()
+            .find(|(token, _)| words.contains(&format!(" {} ", token)))
+            .map(|(_, league)| league.to_string())
+    })
+}
+
+/// "3h 20m", "45m" or "30s"
+pub fn format_watch_time(secs: i64) -> String {
+    let (hours, minutes) = (secs / 3600, (secs % 3600) / 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{}s", secs.max(0)),
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {:02}m", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn session(channel: &str, category: &str, start: DateTime<Utc>, mins: i64) -> ViewingSession {
+        ViewingSession {
+            stream_id: channel.to_lowercase(),
+            channel: channel.to_string(),
+            account: "Main".to_string(),
+            category: Some(category.to_string()),
+            league: detect_league(&[category, channel]),
+            engine: "MPV".to_string(),
+            started_at: start,
+            ended_at: start + Duration::minutes(mins),
+        }
+    }
+
+    #[test]
+    fn stats_rank_channels_and_bucket_weeks() {
+        // Wednesday
+        let now = Utc.with_ymd_and_hms(2026, 3, 18, 20, 0, 0).unwrap();
+        let mut history = WatchHistory::default();
+        history.record(session(
+            "ESPN",
+            "US| NBA PASS",
+            now - Duration::hours(2),
+            90,
+        ));
+        history.record(session(
+            "BBC One",
+            "UK| GENERAL",
+            now - Duration::days(8),
+            60,
+        ));
+        history.record(session("ESPN", "US| NBA PASS", now - Duration::days(9), 30));
+        // Too short to count
+        assert!(!history.record(session("CNN", "US| NEWS", now, 0)));
+
+        let stats = history.stats(now);
+        assert_eq!(stats.sessions, 3);
+        assert_eq!(stats.total_secs, 180 * 60);
+        assert_eq!(stats.top_channels[0], ("ESPN".to_string(), 120 * 60));
+        assert_eq!(stats.by_league, vec![("NBA".to_string(), 120 * 60)]);
+        assert_eq!(stats.weekly.len(), WEEKS_SHOWN);
+        assert_eq!(
+            stats.weekly.last().unwrap(),
+            &(NaiveDate::from_ymd_opt(2026, 3, 16).unwrap(), 90 * 60)
+        );
+        assert_eq!(stats.weekly[WEEKS_SHOWN - 2].1, 90 * 60);
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_leagues_match_whole_words() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let mut history = WatchHistory::default();
+        history.record(session(
+            "Sky Sports, Main \"Event\"",
+            "UK| SPORTS",
+            start,
+            45,
+        ));
+        let csv = history.to_csv();
+        assert!(csv.contains("\"Sky Sports, Main \"\"Event\"\"\""));
+        assert_eq!(csv.lines().count(), 2);
+
+        assert_eq!(detect_league(&["US| WNBA"]).as_deref(), Some("WNBA"));
+        assert_eq!(
+            detect_league(&["UK: Premier-League 1"]).as_deref(),
+            Some("Premier League")
+        );
+        assert_eq!(detect_league(&["US| CNBA News"]), None);
+        assert_eq!(format_watch_time(3 * 3600 + 20 * 60), "3h 20m");
+    }
+}
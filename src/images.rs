@@ -0,0 +1,527 @@
+//! Channel logos and VOD posters in the terminal.
+//!
+//! Images are downloaded once, shrunk and kept as PNG files in the cache
+//! directory (one folder per account). At draw time they are encoded for the
+//! terminal's graphics protocol: kitty, iTerm2 inline images, sixel, or unicode
+//! half blocks as the fallback that works with any true-colour terminal.
+//!
+//! Escape-sequence protocols do not go through ratatui's buffer: the renderer
+//! records a `Placement` and marks its cells as skipped, and the main loop
+//! writes the sequences after the frame (see `ImageCache::flush`), only when
+//! what is on screen changed.
+
+use crate::config::ImageProtocol;
+use base64::Engine;
+use image::{imageops::FilterType, RgbaImage};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Stored images are shrunk to fit this box
+pub const MAX_WIDTH: u32 = 300;
+pub const MAX_HEIGHT: u32 = 450;
+
+/// Larger downloads are not images we want to draw
+const MAX_DOWNLOAD_BYTES: usize = 8 * 1024 * 1024;
+
+/// Decoded images kept in memory
+const MAX_DECODED: usize = 64;
+
+/// Encoded sequences / resized pixels kept per (url, protocol, size)
+const MAX_ENCODED: usize = 32;
+
+/// Downloads running at once
+const MAX_IN_FLIGHT: usize = 6;
+
+/// Cell size in pixels when the terminal does not report one
+const FALLBACK_CELL_PX: (u32, u32) = (8, 16);
+
+/// Removes every kitty image (placements and data)
+const KITTY_DELETE_ALL: &str = "\x1b_Ga=d,d=A,q=2\x1b\\";
+
+/// Drawing method for `setting`, detecting the terminal for `Auto`
+pub fn resolve(setting: ImageProtocol) -> ImageProtocol {
+    match setting {
+        ImageProtocol::Auto => detect(),
+        other => other,
+    }
+}
+
+/// Best protocol the terminal advertises through its environment
+pub fn detect() -> ImageProtocol {
+    let var = |key: &str| std::env::var(key).unwrap_or_default();
+    let term = var("TERM");
+    let program = var("TERM_PROGRAM");
+
+    // Multiplexers swallow graphics escapes unless passthrough is configured
+    if std::env::var_os("TMUX").is_some() || term.starts_with("screen") {
+        return ImageProtocol::HalfBlocks;
+    }
+    if std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || term.contains("ghostty")
+        || program == "ghostty"
+    {
+        return ImageProtocol::Kitty;
+    }
+    if program == "iTerm.app" || program == "WezTerm" || var("LC_TERMINAL") == "iTerm2" {
+        return ImageProtocol::Iterm2;
+    }
+    if term.contains("sixel")
+        || matches!(term.as_str(), "foot" | "foot-extra" | "mlterm" | "contour")
+        || std::env::var_os("WT_SESSION").is_some()
+    {
+        return ImageProtocol::Sixel;
+    }
+    ImageProtocol::HalfBlocks
+}
+
+/// Terminal cell size in pixels
+pub fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            (size.width / size.columns).max(1) as u32,
+            (size.height / size.rows).max(1) as u32,
+        ),
+        _ => FALLBACK_CELL_PX,
+    }
+}
+
+/// Largest cell rectangle inside `cols` x `rows` that keeps the image's aspect ratio
+pub fn fit(image: (u32, u32), cols: u16, rows: u16, cell: (u32, u32)) -> (u16, u16) {
+    let (w, h) = (image.0.max(1) as f64, image.1.max(1) as f64);
+    let (cw, ch) = (cell.0.max(1) as f64, cell.1.max(1) as f64);
+    let scale = (cols as f64 * cw / w).min(rows as f64 * ch / h);
+    let fit_cols = ((w * scale / cw).round() as u16).clamp(1, cols.max(1));
+    let fit_rows = ((h * scale / ch).round() as u16).clamp(1, rows.max(1));
+    (fit_cols, fit_rows)
+}
+
+fn url_hash(s: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Folder holding an account's images: <cache_dir>/images/<account_name_hash>/
+pub fn image_dir(account_name: &str) -> Option<PathBuf> {
+    let paths = crate::storage::paths()?;
+    Some(
+        paths
+            .cache_dir
+            .join("images")
+            .join(url_hash(account_name).to_string()),
+    )
+}
+
+/// Delete an account's stored images
+pub fn invalidate(account_name: &str) {
+    if let Some(dir) = image_dir(account_name) {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+/// Load an image from the account's folder, or download, shrink and store it.
+/// None when it cannot be fetched or decoded.
+pub async fn fetch(url: String, dir: Option<PathBuf>) -> Option<RgbaImage> {
+    let path = dir.map(|d| d.join(format!("{:016x}.png", url_hash(&url))));
+    if let Some(path) = path.clone().filter(|p| p.exists()) {
+        return tokio::task::spawn_blocking(move || image::open(path).ok().map(|i| i.to_rgba8()))
+            .await
+            .ok()
+            .flatten();
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .ok()?;
+    let response = client.get(&url).send().await.ok()?;
+    if !response.status().is_success()
+        || response
+            .content_length()
+            .is_some_and(|len| len as usize > MAX_DOWNLOAD_BYTES)
+    {
+        return None;
+    }
+    let bytes = response.bytes().await.ok()?;
+    if bytes.len() > MAX_DOWNLOAD_BYTES {
+        return None;
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let decoded = image::load_from_memory(&bytes).ok()?;
+        let shrunk = if decoded.width() > MAX_WIDTH || decoded.height() > MAX_HEIGHT {
+            decoded.resize(MAX_WIDTH, MAX_HEIGHT, FilterType::Triangle)
+        } else {
+            decoded
+        };
+        let rgba = shrunk.to_rgba8();
+        if let Some(path) = path {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            let _ = rgba.save(&path);
+        }
+        Some(rgba)
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+/// An escape-sequence image waiting to be written at a cell position
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    pub area: Rect,
+    pub sequence: Arc<str>,
+}
+
+/// Ready-to-draw form of an image at one size
+#[derive(Clone)]
+pub enum Encoded {
+    /// Escape sequence for kitty, iTerm2 or sixel
+    Sequence(Arc<str>),
+    /// Pixels for half blocks: `cols` wide, `rows * 2` tall
+    Pixels(Arc<RgbaImage>),
+}
+
+type EncodedKey = (String, ImageProtocol, u16, u16);
+
+/// Decoded images plus what the renderer asked for and drew this frame.
+/// Rendering only has `&App`, so the per-frame parts sit behind mutexes.
+#[derive(Default)]
+pub struct ImageCache {
+    decoded: HashMap<String, Arc<RgbaImage>>,
+    order: VecDeque<String>,
+    failed: HashSet<String>,
+    in_flight: HashSet<String>,
+    wanted: Mutex<Vec<String>>,
+    encoded: Mutex<VecDeque<(EncodedKey, Encoded)>>,
+    frame: Mutex<Vec<Placement>>,
+    shown: Vec<Placement>,
+    screen: Rect,
+}
+
+impl ImageCache {
+    /// The image for `url` if it is loaded; otherwise ask the main loop to fetch it
+    pub fn get(&self, url: &str) -> Option<Arc<RgbaImage>> {
+        if let Some(image) = self.decoded.get(url) {
+            return Some(image.clone());
+        }
+        if !self.failed.contains(url) && !self.in_flight.contains(url) {
+            if let Ok(mut wanted) = self.wanted.lock() {
+                if !wanted.iter().any(|w| w == url) {
+                    wanted.push(url.to_string());
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether `url` could not be downloaded or decoded
+    pub fn has_failed(&self, url: &str) -> bool {
+        self.failed.contains(url)
+    }
+
+    /// Urls to start fetching now, most recently requested first
+    pub fn take_wanted(&mut self) -> Vec<String> {
+        let mut wanted = std::mem::take(self.wanted.get_mut().unwrap_or_else(|e| e.into_inner()));
+        wanted.reverse();
+        let room = MAX_IN_FLIGHT.saturating_sub(self.in_flight.len());
+        wanted.truncate(room);
+        self.in_flight.extend(wanted.iter().cloned());
+        wanted
+    }
+
+    /// Store a finished fetch
+    pub fn insert(&mut self, url: String, image: Option<Arc<RgbaImage>>) {
+        self.in_flight.remove(&url);
+        match image {
+            Some(image) => {
+                self.order.retain(|u| u != &url);
+                self.order.push_back(url.clone());
+                self.decoded.insert(url, image);
+                while self.order.len() > MAX_DECODED {
+                    if let Some(old) = self.order.pop_front() {
+                        self.decoded.remove(&old);
+                    }
+                }
+            }
+            None => {
+                self.failed.insert(url);
+            }
+        }
+    }
+
+    /// `image` encoded for `protocol` at `cols` x `rows` cells, memoized
+    pub fn encode(
+        &self,
+        url: &str,
+        image: &RgbaImage,
+        protocol: ImageProtocol,
+        cols: u16,
+        rows: u16,
+    ) -> Option<Encoded> {
+        let key = (url.to_string(), protocol, cols, rows);
+        let mut encoded = self.encoded.lock().ok()?;
+        if let Some((_, hit)) = encoded.iter().find(|(k, _)| *k == key) {
+            return Some(hit.clone());
+        }
+        let cell = cell_size();
+        let result = match protocol {
+            ImageProtocol::HalfBlocks => Encoded::Pixels(Arc::new(image::imageops::resize(
+                image,
+                cols as u32,
+                rows as u32 * 2,
+                FilterType::Triangle,
+            ))),
+            ImageProtocol::Kitty | ImageProtocol::Iterm2 | ImageProtocol::Sixel => {
+                let pixels = image::imageops::resize(
+                    image,
+                    cols as u32 * cell.0,
+                    rows as u32 * cell.1,
+                    FilterType::Triangle,
+                );
+                let sequence = match protocol {
+                    ImageProtocol::Kitty => kitty_sequence(&png_bytes(&pixels)?, cols, rows),
+                    ImageProtocol::Iterm2 => iterm2_sequence(&png_bytes(&pixels)?, cols, rows),
+                    _ => sixel_sequence(&pixels),
+                };
+                Encoded::Sequence(sequence.into())
+            }
+            ImageProtocol::Auto | ImageProtocol::Off => return None,
+        };
+        encoded.push_back((key, result.clone()));
+        if encoded.len() > MAX_ENCODED {
+            encoded.pop_front();
+        }
+        Some(result)
+    }
+
+    /// Queue an escape-sequence image for this frame
+    pub fn place(&self, placement: Placement) {
+        if let Ok(mut frame) = self.frame.lock() {
+            frame.push(placement);
+        }
+    }
+
+    /// Forget what is on screen (after the terminal was cleared), so the next
+    /// frame writes its images again
+    pub fn reset(&mut self) {
+        self.shown.clear();
+    }
+
+    /// After a frame is drawn: the bytes to write so the terminal shows this
+    /// frame's images. Placements whose cells were drawn over by a later widget
+    /// are dropped; nothing is written when the images did not change.
+    pub fn flush(&mut self, buffer: &Buffer) -> String {
+        let mut frame = std::mem::take(self.frame.get_mut().unwrap_or_else(|e| e.into_inner()));
+        frame.retain(|p| {
+            p.area
+                .positions()
+                .all(|pos| buffer.cell(pos).is_some_and(|c| c.skip))
+        });
+        // A resize clears the terminal
+        if buffer.area != self.screen {
+            self.screen = buffer.area;
+            self.shown.clear();
+        }
+        if frame == self.shown {
+            return String::new();
+        }
+
+        let mut out = String::new();
+        let had_kitty = self.shown.iter().any(|p| p.sequence.starts_with("\x1b_G"));
+        if had_kitty {
+            out.push_str(KITTY_DELETE_ALL);
+        }
+        for placement in &frame {
+            out.push_str(&format!(
+                "\x1b[{};{}H",
+                placement.area.y + 1,
+                placement.area.x + 1
+            ));
+            out.push_str(&placement.sequence);
+        }
+        self.shown = frame;
+        out
+    }
+}
+
+fn png_bytes(image: &RgbaImage) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    image
+        .write_to(
+            &mut std::io::Cursor::new(&mut bytes),
+            image::ImageFormat::Png,
+        )
+        .ok()?;
+    Some(bytes)
+}
+
+/// Kitty graphics: transmit and display a PNG scaled to the cell box, without
+/// moving the cursor. Payloads go out in 4096-byte chunks.
+fn kitty_sequence(png: &[u8], cols: u16, rows: u16) -> String {
+    let data = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks: Vec<&str> = data
+        .as_bytes()
+        .chunks(4096)
+        .map(|c| std::str::from_utf8(c).unwrap_or_default())
+        .collect();
+    let mut out = String::with_capacity(data.len() + chunks.len() * 32);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=100,q=2,C=1,c={},r={},m={};{}\x1b\\",
+                cols, rows, more, chunk
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    out
+}
+
+/// iTerm2 inline image stretched over the cell box
+fn iterm2_sequence(png: &[u8], cols: u16, rows: u16) -> String {
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+        png.len(),
+        cols,
+        rows,
+        base64::engine::general_purpose::STANDARD.encode(png)
+    )
+}
+
+/// Palette index on a 6x7x6 colour cube (252 colours), blended over black
+fn palette_index(pixel: &image::Rgba<u8>) -> usize {
+    let [r, g, b, a] = pixel.0;
+    let blend = |c: u8| c as u32 * a as u32 / 255;
+    let level = |c: u32, steps: u32| ((c * (steps - 1) + 127) / 255) as usize;
+    level(blend(r), 6) * 42 + level(blend(g), 7) * 6 + level(blend(b), 6)
+}
+
+/// Sixel image: quantized to the colour cube, run-length encoded per band of six rows
+fn sixel_sequence(image: &RgbaImage) -> String {
+    let (w, h) = image.dimensions();
+    let mut out = format!("\x1bPq\"1;1;{};{}", w, h);
+    for index in 0..252usize {
+        let (r, g, b) = (index / 42, (index / 6) % 7, index % 6);
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            index,
+            r * 100 / 5,
+            g * 100 / 6,
+            b * 100 / 5
+        ));
+    }
+
+    let indices: Vec<usize> = image.pixels().map(palette_index).collect();
+    for band in (0..h).step_by(6) {
+        let rows = 6.min(h - band);
+        let mut colours: Vec<usize> = (0..rows)
+            .flat_map(|dy| {
+                let start = ((band + dy) * w) as usize;
+                indices[start..start + w as usize].iter().copied()
+            })
+            .collect();
+        colours.sort_unstable();
+        colours.dedup();
+
+        for (n, colour) in colours.iter().enumerate() {
+            if n > 0 {
+                out.push('$');
+            }
+            out.push_str(&format!("#{}", colour));
+            let mut run: Option<(char, usize)> = None;
+            for x in 0..w {
+                let mut bits = 0u8;
+                for dy in 0..rows {
+                    if indices[((band + dy) * w + x) as usize] == *colour {
+                        bits |= 1 << dy;
+                    }
+                }
+                let ch = (63 + bits) as char;
+                run = match run {
+                    Some((c, len)) if c == ch => Some((c, len + 1)),
+                    Some(previous) => {
+                        push_run(&mut out, previous);
+                        Some((ch, 1))
+                    }
+                    None => Some((ch, 1)),
+                };
+            }
+            if let Some(last) = run {
+                push_run(&mut out, last);
+            }
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_run(out: &mut String, (ch, len): (char, usize)) {
+    if len > 3 {
+        out.push_str(&format!("!{}{}", len, ch));
+    } else {
+        out.extend(std::iter::repeat_n(ch, len));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_keeps_aspect_inside_the_box() {
+        // 2:3 poster in a 40x20 box with 8x16 cells: height-bound
+        let (cols, rows) = fit((200, 300), 40, 20, (8, 16));
+        assert_eq!(rows, 20);
+        assert_eq!(cols, 27);
+        // Wide logo: width-bound
+        let (cols, rows) = fit((400, 100), 10, 10, (8, 16));
+        assert_eq!((cols, rows), (10, 1));
+    }
+
+    #[test]
+    fn sixel_run_length_encodes_solid_bands() {
+        let image = RgbaImage::from_pixel(10, 6, image::Rgba([255, 0, 0, 255]));
+        let sixel = sixel_sequence(&image);
+        assert!(sixel.starts_with("\x1bPq\"1;1;10;6"));
+        assert!(sixel.ends_with("-\x1b\\"));
+        let red = palette_index(&image::Rgba([255, 0, 0, 255]));
+        // One colour, all six rows set across ten columns
+        assert!(sixel.contains(&format!("#{}!10~-", red)));
+    }
+
+    #[test]
+    fn flush_writes_only_on_change_and_clears_kitty_images() {
+        let mut cache = ImageCache::default();
+        let area = Rect::new(2, 1, 3, 2);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 10, 5));
+        for pos in area.positions() {
+            buffer[pos].set_skip(true);
+        }
+        let placement = Placement {
+            area,
+            sequence: kitty_sequence(b"png", 3, 2).into(),
+        };
+
+        cache.place(placement.clone());
+        let first = cache.flush(&buffer);
+        assert!(first.starts_with("\x1b[2;3H\x1b_Ga=T"));
+        cache.place(placement);
+        assert!(cache.flush(&buffer).is_empty());
+
+        // Next frame has no image: kitty needs an explicit delete
+        let cleared = Buffer::empty(Rect::new(0, 0, 10, 5));
+        assert_eq!(cache.flush(&cleared), KITTY_DELETE_ALL);
+    }
+}
@@ -0,0 +1,462 @@
+//! Kodi as a cast target over its JSON-RPC API (HTTP transport at
+//! `http://host:8080/jsonrpc`; needs "Allow remote control via HTTP" in Kodi).
+//! Hosts are configured in Settings, there is no discovery.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::cast::{CastState, CastStatus};
+
+/// Kodi's default web server port
+pub const DEFAULT_PORT: u16 = 8080;
+
+fn default_port() -> u16 {
+    DEFAULT_PORT
+}
+
+/// A Kodi box that can be cast to
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct KodiHost {
+    pub name: String,
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// A password not in a credential store yet, like
+    /// [`Account::password`](crate::config::Account); cast sessions read the
+    /// stored one with `with_password()`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Where the password is kept (see `crate::vault`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential: Option<String>,
+}
+
+// Passwords never reach debug output
+impl std::fmt::Debug for KodiHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KodiHost")
+            .field("name", &self.name)
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("credential", &self.credential)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for KodiHost {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.password.zeroize();
+    }
+}
+
+impl KodiHost {
+    /// Parse `[user[:password]@]host[:port]` as typed into Settings
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let input = input
+            .strip_prefix("http://")
+            .unwrap_or(input)
+            .trim_end_matches('/');
+        let (credentials, address) = match input.rsplit_once('@') {
+            Some((credentials, address)) => (Some(credentials), address),
+            None => (None, input),
+        };
+        let (host, port) = match address.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse::<u16>()
+                    .map_err(|_| format!("Invalid port '{}'", port))?,
+            ),
+            None => (address, DEFAULT_PORT),
+        };
+        if host.is_empty() || host.contains(['/', ' ']) {
+            return Err("Enter a host like 192.168.1.20 or kodi.local:8080".to_string());
+        }
+        let (username, password) = match credentials {
+            Some(c) => match c.split_once(':') {
+                Some((user, pass)) => (Some(user.to_string()), Some(pass.to_string())),
+                None => (Some(c.to_string()), None),
+            },
+            None => (None, None),
+        };
+        Ok(Self {
+            name: host.to_string(),
+            host: host.to_string(),
+            port,
+            username,
+            password,
+            credential: None,
+        })
+    }
+
+    /// `host:port`, as shown in the cast picker
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    pub fn endpoint(&self) -> String {
+        format!("http://{}/jsonrpc", self.address())
+    }
+}
+
+/// JSON-RPC 2.0 request body
+pub fn request(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })
+}
+
+/// The `result` of a JSON-RPC response, or Kodi's error message
+pub fn parse_response(response: &Value) -> Result<Value, String> {
+    if let Some(error) = response.get("error") {
+        return Err(error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or("Unknown error")
+            .to_string());
+    }
+    response
+        .get("result")
+        .cloned()
+        .ok_or_else(|| "Response has no result".to_string())
+}
+
+/// Seconds from a Kodi `{hours, minutes, seconds, milliseconds}` time
+pub fn parse_time(time: &Value) -> Option<f64> {
+    let part = |key: &str| time.get(key).and_then(|v| v.as_f64());
+    Some(
+        part("hours")? * 3600.0
+            + part("minutes")? * 60.0
+            + part("seconds")?
+            + part("milliseconds").unwrap_or(0.0) / 1000.0,
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl KodiHost {
+    async fn call(
+        &self,
+        client: &reqwest::Client,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, anyhow::Error> {
+        let mut req = client.post(self.endpoint()).json(&request(method, params));
+        if let Some(user) = &self.username {
+            req = req.basic_auth(user, self.password.as_deref());
+        }
+        let resp = req
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to reach Kodi '{}': {}", self.name, e))?;
+        if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(anyhow::anyhow!(
+                "Kodi '{}' rejected the username or password",
+                self.name
+            ));
+        }
+        let body: Value = resp.json().await?;
+        parse_response(&body)
+            .map_err(|e| anyhow::anyhow!("Kodi '{}' {} failed: {}", self.name, method, e))
+    }
+
+    /// Check the host answers JSON-RPC
+    pub async fn ping(&self, client: &reqwest::Client) -> Result<(), anyhow::Error> {
+        self.call(client, "JSONRPC.Ping", json!({})).await?;
+        Ok(())
+    }
+
+    /// Start playing a stream (Player.Open)
+    pub async fn open(&self, client: &reqwest::Client, url: &str) -> Result<(), anyhow::Error> {
+        self.call(client, "Player.Open", json!({ "item": { "file": url } }))
+            .await?;
+        Ok(())
+    }
+
+    /// Id of the running player, preferring video over audio
+    async fn active_player(&self, client: &reqwest::Client) -> Result<Option<i64>, anyhow::Error> {
+        let players = self
+            .call(client, "Player.GetActivePlayers", json!({}))
+            .await?;
+        let players = players.as_array().cloned().unwrap_or_default();
+        let id = |p: &Value| p.get("playerid").and_then(|id| id.as_i64());
+        Ok(players
+            .iter()
+            .find(|p| p.get("type").and_then(|t| t.as_str()) == Some("video"))
+            .or(players.first())
+            .and_then(id))
+    }
+
+    async fn require_player(&self, client: &reqwest::Client) -> Result<i64, anyhow::Error> {
+        self.active_player(client)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Nothing is playing on Kodi '{}'", self.name))
+    }
+
+    pub async fn play_pause(&self, client: &reqwest::Client) -> Result<(), anyhow::Error> {
+        let player = self.require_player(client).await?;
+        self.call(client, "Player.PlayPause", json!({ "playerid": player }))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn stop(&self, client: &reqwest::Client) -> Result<(), anyhow::Error> {
+        if let Some(player) = self.active_player(client).await? {
+            self.call(client, "Player.Stop", json!({ "playerid": player }))
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Relative seek in seconds
+    pub async fn seek(&self, client: &reqwest::Client, secs: i32) -> Result<(), anyhow::Error> {
+        let player = self.require_player(client).await?;
+        self.call(
+            client,
+            "Player.Seek",
+            json!({ "playerid": player, "value": { "seconds": secs } }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Volume in percent and mute state
+    pub async fn volume(&self, client: &reqwest::Client) -> Result<(f64, bool), anyhow::Error> {
+        let props = self
+            .call(
+                client,
+                "Application.GetProperties",
+                json!({ "properties": ["volume", "muted"] }),
+            )
+            .await?;
+        Ok((
+            props.get("volume").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            props
+                .get("muted")
+                .and_then(|m| m.as_bool())
+                .unwrap_or(false),
+        ))
+    }
+
+    /// Change the volume by `delta` percent
+    pub async fn change_volume(
+        &self,
+        client: &reqwest::Client,
+        delta: i32,
+    ) -> Result<(), anyhow::Error> {
+        let (current, _) = self.volume(client).await?;
+        let volume = (current as i32 + delta).clamp(0, 100);
+        self.call(client, "Application.SetVolume", json!({ "volume": volume }))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn toggle_mute(&self, client: &reqwest::Client) -> Result<(), anyhow::Error> {
+        self.call(client, "Application.SetMute", json!({ "mute": "toggle" }))
+            .await?;
+        Ok(())
+    }
+
+    /// Poll playback state. `url` is what we cast; another file means someone
+    /// else has started something on the box.
+    pub async fn status(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+    ) -> Result<CastStatus, anyhow::Error> {
+        let (volume, muted) = self.volume(client).await?;
+        let mut status = CastStatus {
+            volume: Some(volume),
+            muted,
+            ..CastStatus::default()
+        };
+        let Some(player) = self.active_player(client).await? else {
+            status.idle_reason = Some("stopped".to_string());
+            return Ok(status);
+        };
+
+        let item = self
+            .call(
+                client,
+                "Player.GetItem",
+                json!({ "playerid": player, "properties": ["file"] }),
+            )
+            .await?;
+        status.taken_over = item
+            .pointer("/item/file")
+            .and_then(|f| f.as_str())
+            .is_some_and(|file| !file.is_empty() && file != url);
+
+        let props = self
+            .call(
+                client,
+                "Player.GetProperties",
+                json!({ "playerid": player, "properties": ["speed", "time"] }),
+            )
+            .await?;
+        status.state = match props.get("speed").and_then(|s| s.as_f64()) {
+            Some(0.0) => CastState::Paused,
+            _ => CastState::Playing,
+        };
+        status.position_secs = props.get("time").and_then(parse_time);
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Stub Kodi: keeps the opened file, pause state and volume, records methods
+    async fn kodi_stub() -> (KodiHost, Arc<Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let methods = Arc::new(Mutex::new(Vec::new()));
+        let state = Arc::new(Mutex::new((None::<String>, 1.0f64, 40i64)));
+        let seen = methods.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let seen = seen.clone();
+                let state = state.clone();
+                tokio::spawn(async move {
+                    let mut data = Vec::new();
+                    let mut buf = [0u8; 4096];
+                    let body = loop {
+                        let n = socket.read(&mut buf).await.unwrap();
+                        data.extend_from_slice(&buf[..n]);
+                        let text = String::from_utf8_lossy(&data).to_string();
+                        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                            let length = head
+                                .lines()
+                                .find_map(|l| {
+                                    let (k, v) = l.split_once(':')?;
+                                    k.eq_ignore_ascii_case("content-length")
+                                        .then(|| v.trim().parse::<usize>().unwrap())
+                                })
+                                .unwrap_or(0);
+                            if body.len() >= length || n == 0 {
+                                break body.to_string();
+                            }
+                        }
+                    };
+                    let req: Value = serde_json::from_str(&body).unwrap();
+                    let method = req["method"].as_str().unwrap().to_string();
+                    let params = &req["params"];
+                    seen.lock().unwrap().push(method.clone());
+
+                    let reply = {
+                        let mut state = state.lock().unwrap();
+                        let result = match method.as_str() {
+                            "Player.Open" => {
+                                state.0 = params["item"]["file"].as_str().map(|s| s.to_string());
+                                state.1 = 1.0;
+                                json!("OK")
+                            }
+                            "Player.GetActivePlayers" => match state.0 {
+                                Some(_) => json!([{ "playerid": 1, "type": "video" }]),
+                                None => json!([]),
+                            },
+                            "Player.PlayPause" => {
+                                state.1 = if state.1 == 0.0 { 1.0 } else { 0.0 };
+                                json!({ "speed": state.1 })
+                            }
+                            "Player.Stop" => {
+                                state.0 = None;
+                                json!("OK")
+                            }
+                            "Player.GetItem" => json!({ "item": { "file": state.0 } }),
+                            "Player.GetProperties" => json!({
+                                "speed": state.1,
+                                "time": { "hours": 0, "minutes": 2, "seconds": 5, "milliseconds": 500 }
+                            }),
+                            "Application.GetProperties" => {
+                                json!({ "volume": state.2, "muted": false })
+                            }
+                            "Application.SetVolume" => {
+                                state.2 = params["volume"].as_i64().unwrap();
+                                json!(state.2)
+                            }
+                            _ => Value::Null,
+                        };
+                        if method == "Player.Seek" && state.0.is_none() {
+                        json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32100, "message": "Failed to execute method." } })
+                    } else {
+                        json!({ "jsonrpc": "2.0", "id": 1, "result": result })
+                    }
+                    .to_string()
+                    };
+                    let resp = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        reply.len(),
+                        reply
+                    );
+                    let _ = socket.write_all(resp.as_bytes()).await;
+                });
+            }
+        });
+        let host = KodiHost::parse(&format!("127.0.0.1:{}", port)).unwrap();
+        (host, methods)
+    }
+
+    #[test]
+    fn parses_host_input() {
+        let host = KodiHost::parse("kodi:secret@192.168.1.20:8081").unwrap();
+        assert_eq!(host.host, "192.168.1.20");
+        assert_eq!(host.port, 8081);
+        assert_eq!(host.username.as_deref(), Some("kodi"));
+        assert_eq!(host.password.as_deref(), Some("secret"));
+        assert_eq!(host.endpoint(), "http://192.168.1.20:8081/jsonrpc");
+        assert!(!format!("{:?}", host).contains("secret"));
+
+        let host = KodiHost::parse(" http://livingroom.local/ ").unwrap();
+        assert_eq!(host.address(), "livingroom.local:8080");
+        assert_eq!(host.username, None);
+
+        assert!(KodiHost::parse("box:http").is_err());
+        assert!(KodiHost::parse("").is_err());
+    }
+
+    #[tokio::test]
+    async fn drives_a_kodi_stub() {
+        let (host, methods) = kodi_stub().await;
+        let client = crate::cast::lan_client().unwrap();
+        host.ping(&client).await.unwrap();
+
+        host.open(&client, "http://host/live/1.ts").await.unwrap();
+        let status = host.status(&client, "http://host/live/1.ts").await.unwrap();
+        assert_eq!(status.state, CastState::Playing);
+        assert_eq!(status.position_secs, Some(125.5));
+        assert_eq!(status.volume, Some(40.0));
+        assert!(!status.taken_over);
+        assert!(
+            host.status(&client, "http://other/2.ts")
+                .await
+                .unwrap()
+                .taken_over
+        );
+
+        host.play_pause(&client).await.unwrap();
+        let status = host.status(&client, "http://host/live/1.ts").await.unwrap();
+        assert_eq!(status.state, CastState::Paused);
+
+        host.change_volume(&client, 5).await.unwrap();
+        assert_eq!(host.volume(&client).await.unwrap().0, 45.0);
+        host.seek(&client, 30).await.unwrap();
+
+        host.stop(&client).await.unwrap();
+        let status = host.status(&client, "http://host/live/1.ts").await.unwrap();
+        assert_eq!(status.state, CastState::Idle);
+        assert_eq!(status.idle_reason.as_deref(), Some("stopped"));
+        let err = host.play_pause(&client).await.unwrap_err().to_string();
+        assert!(err.contains("Nothing is playing"), "{}", err);
+
+        let methods = methods.lock().unwrap();
+        assert!(methods.contains(&"Player.Open".to_string()));
+        assert!(methods.contains(&"Player.Seek".to_string()));
+        assert!(methods.contains(&"Player.Stop".to_string()));
+    }
+}
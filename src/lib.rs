@@ -1,26 +1,47 @@
 pub mod api;
 pub mod app;
 pub mod cache;
-#[cfg(all(not(target_arch = "wasm32"), feature = "chromecast"))]
 pub mod cast;
 pub mod config;
+pub mod config_schema;
+pub mod dlna;
 pub mod doh;
 pub mod errors;
 pub mod flex_id;
 pub mod handlers;
+pub mod history;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod images;
+pub mod kodi;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod matrix_rain;
+pub mod multiview;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod onboarding;
 pub mod parser;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod player;
+pub mod player_control;
+pub mod player_profile;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod preflight;
 pub mod preprocessing;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod recorder;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod relay;
+pub mod scheduler;
 pub mod scores;
+pub mod search_index;
 pub mod setup;
 pub mod sports;
 pub mod state;
+pub mod storage;
 pub mod ui;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod vault;
+pub mod watchdog;
+pub mod whats_new;
 
 // Wasm module
 #[cfg(target_arch = "wasm32")]
@@ -30,12 +51,142 @@ pub mod wasm_client;
 mod tests {
     use crate::app::{App, CurrentScreen};
 
+    use crate::config::{Account, AccountType, AppConfig, CategorySortOrder};
+    use crate::config_schema::CURRENT_SCHEMA_VERSION;
+    use crate::storage::{ConfigBackend, ConfigStorage};
+
+    fn account(name: &str) -> Account {
+        Account {
+            name: name.to_string(),
+            base_url: "http://example.com".to_string(),
+            username: "user".to_string(),
+            password: "pass".to_string(),
+            credential: None,
+            account_type: AccountType::Xtream,
+            epg_url: None,
+            last_refreshed: None,
+            total_channels: None,
+            total_movies: None,
+            total_series: None,
+            server_timezone: None,
+            hidden_categories: std::collections::HashSet::new(),
+            radio_overrides: std::collections::HashMap::new(),
+            category_sort_order: CategorySortOrder::Default,
+        }
+    }
+
     #[test]
     fn test_app_new() {
-        let app = App::new();
+        let app = App::with_storage(ConfigStorage::memory(None));
         assert_eq!(app.current_screen, CurrentScreen::Home);
     }
 
-    // test_update_account_logic removed to prevent overwriting user config.json
-    // TODO: Refactor AppConfig to accept a custom path for testing.
+    #[test]
+    fn test_update_account_logic() {
+        let storage = ConfigStorage::memory(None);
+        let mut app = App::with_storage(storage.clone());
+        app.config.add_account(account("Old")).unwrap();
+        app.config.update_account(0, account("New")).unwrap();
+
+        // Saved to the injected storage, never the user's config.json
+        let reloaded = AppConfig::load_from(storage).unwrap();
+        assert_eq!(reloaded.accounts.len(), 1);
+        assert_eq!(reloaded.accounts[0].name, "New");
+        assert_eq!(reloaded.accounts[0].password, "pass");
+    }
+
+    #[test]
+    fn test_duplicate_account_names_are_rejected_before_saving() {
+        let storage = ConfigStorage::memory(None);
+        let mut app = App::with_storage(storage.clone());
+        let saved: Vec<bool> = ["Main", " Main "]
+            .iter()
+            .map(|name| {
+                app.input_name = tui_input::Input::new(name.to_string());
+                app.input_url = tui_input::Input::new("http://example.com".to_string());
+                app.input_username = tui_input::Input::new("user".to_string());
+                app.input_password = tui_input::Input::new("pass".to_string());
+                app.save_account().is_ok()
+            })
+            .collect();
+        assert_eq!(saved, [true, false], "the trimmed name is taken");
+        assert_eq!(
+            app.input_name.value(),
+            " Main ",
+            "rejected form stays filled in"
+        );
+        assert!(app.config.add_account(account("Main")).is_err());
+        assert!(app.config.add_account(account("   ")).is_err());
+
+        // The saved config still loads
+        let reloaded = AppConfig::load_from(storage).unwrap();
+        assert_eq!(reloaded.accounts.len(), 1);
+        assert_eq!(reloaded.accounts[0].name, "Main");
+    }
+
+    /// Memory backend that lets the test see the copies kept aside
+    #[derive(Default)]
+    struct Recorded {
+        content: std::sync::Mutex<Option<String>>,
+        copies: std::sync::Mutex<Vec<(String, String)>>,
+    }
+
+    impl ConfigBackend for std::sync::Arc<Recorded> {
+        fn read(&self) -> anyhow::Result<Option<String>> {
+            Ok(self.content.lock().unwrap().clone())
+        }
+        fn write(&self, content: &str) -> anyhow::Result<()> {
+            *self.content.lock().unwrap() = Some(content.to_string());
+            Ok(())
+        }
+        fn keep_copy(&self, suffix: &str, content: &str) -> anyhow::Result<String> {
+            self.copies
+                .lock()
+                .unwrap()
+                .push((suffix.to_string(), content.to_string()));
+            Ok(format!("config.json.{}", suffix))
+        }
+        fn describe(&self) -> String {
+            "recorded".to_string()
+        }
+        fn persistent(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn migration_backs_up_the_old_file_first() {
+        let old = r#"{"accounts": [], "use_default_mpv": true}"#;
+        let backend = std::sync::Arc::new(Recorded::default());
+        *backend.content.lock().unwrap() = Some(old.to_string());
+
+        let config = AppConfig::load_from(ConfigStorage::new(backend.clone())).unwrap();
+        assert_eq!(config.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(
+            *backend.copies.lock().unwrap(),
+            vec![("v0.bak".to_string(), old.to_string())]
+        );
+        let saved = backend.content.lock().unwrap().clone().unwrap();
+        assert!(saved.contains(&format!("\"schema_version\": {}", CURRENT_SCHEMA_VERSION)));
+    }
+
+    #[test]
+    fn broken_config_is_kept_aside_and_defaults_used() {
+        let broken = r#"{"accounts": [], "relay_port": "abc"}"#;
+        let backend = std::sync::Arc::new(Recorded::default());
+        *backend.content.lock().unwrap() = Some(broken.to_string());
+
+        let app = App::with_storage(ConfigStorage::new(backend.clone()));
+        assert!(app.config.accounts.is_empty());
+        let notice = app.login_error.unwrap();
+        assert!(notice.contains("relay_port"), "{}", notice);
+
+        let copies = backend.copies.lock().unwrap();
+        assert_eq!(copies.len(), 1);
+        assert!(copies[0].0.starts_with("broken-"));
+        assert_eq!(copies[0].1, broken);
+        // The defaults replaced the broken file and load cleanly
+        drop(copies);
+        assert!(AppConfig::load_from(ConfigStorage::new(backend)).is_ok());
+    }
 }
@@ -13,6 +13,9 @@ use crossterm::{
 
 use matrix_iptv_lib::api::get_id_str;
 use matrix_iptv_lib::app::{App, AsyncAction, CurrentScreen, Pane};
+use matrix_iptv_lib::config::{AppConfig, ScheduleRule};
+#[cfg(not(target_arch = "wasm32"))]
+use matrix_iptv_lib::storage::ConfigStorage;
 use matrix_iptv_lib::{handlers, player, setup, sports, ui};
 #[cfg(not(target_arch = "wasm32"))]
 use ratatui::{backend::CrosstermBackend, Terminal};
@@ -36,6 +39,11 @@ struct Args {
     /// Skip checking for updates on startup
     #[arg(long)]
     skip_update: bool,
+
+    /// Config file to use instead of the default (also MATRIX_IPTV_CONFIG).
+    /// Data and cache are kept in the same folder.
+    #[arg(long, value_name = "PATH")]
+    config: Option<std::path::PathBuf>,
 }
 
 #[derive(clap::Subcommand, Debug)]
@@ -44,11 +52,41 @@ enum Command {
     Update,
 }
 
+/// Load the config; when it cannot be used, offer to start with defaults and
+/// keep the broken file aside
+#[cfg(not(target_arch = "wasm32"))]
+fn load_config_or_recover() -> Result<AppConfig, anyhow::Error> {
+    use std::io::{BufRead, IsTerminal, Write};
+
+    let storage = ConfigStorage::platform();
+    let error = match AppConfig::load_from(storage.clone()) {
+        Ok(config) => return Ok(config),
+        Err(e) => e,
+    };
+    eprintln!("Config {}: {}", storage.describe(), error);
+    if !io::stdin().is_terminal() {
+        return Err(error.into());
+    }
+    eprint!("Start with default settings and keep the broken file aside? [y/N] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    if !answer.trim().eq_ignore_ascii_case("y") {
+        return Err(error.into());
+    }
+    let (config, notice) = AppConfig::recover(storage, &error);
+    eprintln!("{}", notice);
+    Ok(config)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     use clap::Parser;
     let args = Args::parse();
+    if let Some(path) = args.config.clone() {
+        matrix_iptv_lib::storage::set_config_path(path);
+    }
 
     // -- CLI MODE --
     if let Some(Command::Update) = args.command {
@@ -75,6 +113,7 @@ async fn main() -> Result<(), anyhow::Error> {
         // For now just print ok as verifying needs full async client setup which is in TUI logic
         // But we can check if config exists
         let config = matrix_iptv_lib::config::AppConfig::load()?;
+        println!("Config: {}", config.storage().describe());
         println!("Loaded config for {} accounts.", config.accounts.len());
         return Ok(());
     }
@@ -99,6 +138,13 @@ async fn main() -> Result<(), anyhow::Error> {
     // Check Dependencies First
     setup::check_and_install_dependencies()?;
 
+    // Unlock saved passwords while the console can still ask for a passphrase
+    matrix_iptv_lib::vault::unlock();
+
+    // Load (and migrate) the config before the terminal is taken over, so a
+    // broken file can be reported and recovery offered on the console
+    let config = load_config_or_recover()?;
+
     // Setup Terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -112,16 +158,15 @@ async fn main() -> Result<(), anyhow::Error> {
     let mut terminal = Terminal::new(backend)?;
 
     // App State
-    let mut app = App::new();
+    let mut app = App::with_config(config);
     let player = player::Player::new();
+    player.set_profile(app.config.active_player_profile().cloned());
 
     // Check if FTUE is needed
     if app.config.accounts.is_empty() {
         if let Ok(Some(new_account)) = matrix_iptv_lib::onboarding::run_onboarding(&mut terminal) {
-            app.config.accounts.push(new_account);
-            if app.config.save().is_err() {
-                // Ignore save error here, it will be handled when main loop starts
-            }
+            // Adding seals the password; a save error is handled when the main loop starts
+            let _ = app.config.add_account(new_account);
             // re-init app state now that we have an account
             // State will be updated by normal app loop
             // app.apply_category_filters(); // Optional: explicitly update filters
@@ -170,6 +215,7 @@ async fn main() -> Result<(), anyhow::Error> {
     });
 
     let res = run_app(&mut terminal, &mut app, &player, tx, &mut rx).await;
+    app.end_viewing();
 
     // Restore Terminal
     disable_raw_mode()?;
@@ -237,13 +283,23 @@ async fn run_app<B: ratatui::backend::Backend>(
 
         // Debounce expired: the full UI projection is now zero-cost and renders immediately.
 
-        terminal
+        let frame = terminal
             .draw(|f| ui::ui(f, app))
             .map_err(|e| std::io::Error::other(e.to_string()))?;
 
+        // Logos and posters drawn with terminal graphics go out after the frame
+        let images = app.images.flush(frame.buffer);
+        if !images.is_empty() {
+            use std::io::Write;
+            let mut stdout = io::stdout();
+            let _ = stdout.write_all(images.as_bytes());
+            let _ = stdout.flush();
+        }
+
         // Handle Ctrl+L clear request
         if app.needs_clear {
             let _ = terminal.clear();
+            app.images.reset();
             app.needs_clear = false;
         }
 
@@ -582,6 +638,187 @@ async fn run_app<B: ratatui::backend::Backend>(
             }
         }
 
+        // 1.13 Score ticker refresh — faster than the 60s background poll so
+        // score changes show up promptly while the ticker is visible
+        if app.config.score_ticker
+            && app
+                .last_ticker_refresh
+                .is_none_or(|t| t.elapsed() >= Duration::from_secs(20))
+        {
+            app.last_ticker_refresh = Some(std::time::Instant::now());
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let service = matrix_iptv_lib::scores::ScoreService::new();
+                if let Ok(scores) = service.fetch_scores().await {
+                    let _ = tx.send(AsyncAction::ScoresLoaded(scores)).await;
+                }
+            });
+        }
+
+        // 1.14 Now-playing bar + stats panel: poll mpv over IPC, drop both once the player exits
+        if app.now_playing.is_some()
+            && app
+                .last_player_poll
+                .is_none_or(|t| t.elapsed() >= Duration::from_secs(1))
+        {
+            app.last_player_poll = Some(std::time::Instant::now());
+            if !player.is_running() {
+                app.now_playing = None;
+                app.arm_watchdog(None);
+                app.end_viewing();
+            } else if let Some(controller) = player.controller() {
+                {
+                    let tx = tx.clone();
+                    let player = player.clone();
+                    let controller = controller.clone();
+                    tokio::spawn(async move {
+                        if let Ok(status) = controller.status().await {
+                            let _ = tx.send(AsyncAction::PlayerStatus(Some(status))).await;
+                        }
+                        if let Ok(mut stats) = controller.stats().await {
+                            stats.reconnects = player.reconnect_count();
+                            let _ = tx.send(AsyncAction::PlayerStats(stats)).await;
+                        }
+                    });
+                }
+
+                // Channel up/down keys inside the mpv window arrive as client messages
+                if player.begin_key_listener() {
+                    let tx = tx.clone();
+                    let player = player.clone();
+                    tokio::spawn(async move {
+                        let bindings = [("PGUP", "channel-up"), ("PGDWN", "channel-down")];
+                        let _ = controller
+                            .forward_key_messages(&bindings, |message| {
+                                let delta = match message {
+                                    "channel-up" => -1,
+                                    "channel-down" => 1,
+                                    _ => return,
+                                };
+                                let _ = tx.try_send(AsyncAction::ChannelZapRequested(delta));
+                            })
+                            .await;
+                        player.end_key_listener();
+                    });
+                }
+            }
+        }
+
+        // 1.15 Channel up/down requested from the mpv window (see 1.14)
+        if let Some(delta) = app.pending_zap.take() {
+            handlers::input::zap_channel(app, &tx, player, delta);
+        }
+
+        // 1.16 Keep the recordings list current while it is open (~2s), so finished
+        // recordings move from the active section to the file list
+        if app.current_screen == CurrentScreen::Recordings
+            && app.session.loading_tick.is_multiple_of(60)
+        {
+            app.refresh_recording_files();
+        }
+
+        // 1.17 Recording scheduler: start/stop captures about once a second, and
+        // refresh the guide for series rules every 15 minutes
+        if app.session.loading_tick.is_multiple_of(30) {
+            app.tick_recording_scheduler();
+        }
+        let guide_stale = app
+            .last_schedule_guide_refresh
+            .is_none_or(|t| t.elapsed() >= Duration::from_secs(15 * 60));
+        if guide_stale && !app.config.recording_schedules.is_empty() {
+            if let Some(client) = app.session.current_client.clone() {
+                app.last_schedule_guide_refresh = Some(std::time::Instant::now());
+                let mut stream_ids: Vec<String> = app
+                    .config
+                    .recording_schedules
+                    .iter()
+                    .filter(|s| matches!(s.rule, ScheduleRule::Series { .. }))
+                    .map(|s| s.stream_id.clone())
+                    .collect();
+                stream_ids.sort();
+                stream_ids.dedup();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    for sid in stream_ids {
+                        if let Ok(epg) = client.get_short_epg(&sid).await {
+                            let _ = tx
+                                .send(AsyncAction::ScheduleGuideLoaded(sid, epg.epg_listings))
+                                .await;
+                        }
+                    }
+                });
+            }
+        }
+
+        // 1.18 Local relay and pre-flight stream checks follow the active account
+        if app.session.loading_tick.is_multiple_of(30) {
+            app.sync_relay();
+            player.set_http_client(
+                app.session
+                    .current_client
+                    .as_ref()
+                    .map(|client| client.http_client()),
+            );
+        }
+
+        // 1.19 Stall watchdog for the live channel that just started (armed by the
+        // async handlers); notices from earlier recoveries fade out
+        if let Some(plan) = app.pending_watchdog.take() {
+            let task = tokio::spawn(matrix_iptv_lib::watchdog::run(
+                player.clone(),
+                plan,
+                app.config.watchdog_stall_secs,
+                app.config.preferred_player,
+                app.config.use_default_mpv,
+                app.config.smooth_motion,
+                tx.clone(),
+            ));
+            app.watchdog_task = Some(task.abort_handle());
+        }
+        app.expire_toasts();
+
+        // 1.20 Cast session: poll the device (~2s) for the cast bar and to notice it
+        // being switched off or taken over by another app
+        if let Some(session) = app.cast_session.clone() {
+            if !app.cast_polling && app.session.loading_tick.is_multiple_of(60) {
+                app.cast_polling = true;
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let status = session.status().await.map_err(|e| e.to_string());
+                    let _ = tx.send(AsyncAction::CastStatus(status)).await;
+                });
+            }
+        }
+
+        // 1.21 Offline mode: try the provider again every 30s; queued actions run
+        // once it answers (see AsyncAction::Reconnected)
+        if app.session.offline {
+            let due = app
+                .last_reconnect_attempt
+                .is_none_or(|t| t.elapsed() >= Duration::from_secs(30));
+            if due {
+                app.last_reconnect_attempt = Some(std::time::Instant::now());
+                handlers::async_actions::spawn_reconnect(app, &tx);
+            }
+        }
+
+        // 1.22 Fetch the logos and posters the last frame asked for
+        let image_dir = app
+            .config
+            .accounts
+            .get(app.session.selected_account_index)
+            .and_then(|a| matrix_iptv_lib::images::image_dir(&a.name));
+        for url in app.images.take_wanted() {
+            let tx = tx.clone();
+            let dir = image_dir.clone();
+            tokio::spawn(async move {
+                let image = matrix_iptv_lib::images::fetch(url.clone(), dir)
+                    .await
+                    .map(std::sync::Arc::new);
+                let _ = tx.send(AsyncAction::ImageLoaded(url, image)).await;
+            });
+        }
+
         // FTUE: Handle Matrix rain animation
         if app.show_matrix_rain {
             if let Ok(size) = terminal.size() {
@@ -0,0 +1,192 @@
+//! Multiview: several live channels in one player window.
+//!
+//! mpv gets the first stream as its main file and the rest via `--external-file`,
+//! then a `--lavfi-complex` graph tiles the video tracks into a grid and routes
+//! exactly one audio track (the "audio focus") to the output. VLC has no filter
+//! graph for this, so it gets one window per stream tiled with explicit geometry.
+
+use crate::config::MultiviewTile;
+
+/// A multiview that is currently playing
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiviewSession {
+    pub tiles: Vec<MultiviewTile>,
+    /// Tile whose audio is playing (0-based)
+    pub audio_focus: usize,
+}
+
+/// Most tiles a multiview can hold (2x2 grid)
+pub const MAX_TILES: usize = 4;
+
+/// Size every tile is scaled/padded to before stacking
+const TILE_WIDTH: u32 = 960;
+const TILE_HEIGHT: u32 = 540;
+
+/// How many tiles the account can feed at once: `MAX_TILES`, or fewer if the
+/// provider caps simultaneous connections (each tile is one connection) and
+/// `active_connections` of them are already in use.
+pub fn tile_limit(max_connections: Option<usize>, active_connections: usize) -> usize {
+    match max_connections {
+        Some(max) if max > 0 => max.saturating_sub(active_connections).min(MAX_TILES),
+        _ => MAX_TILES,
+    }
+}
+
+/// The `--lavfi-complex` graph for `tiles` streams with audio from tile `audio_focus` (0-based)
+pub fn lavfi_graph(tiles: usize, audio_focus: usize) -> String {
+    let tiles = tiles.clamp(1, MAX_TILES);
+    let mut graph = String::new();
+
+    for i in 1..=tiles {
+        graph.push_str(&format!(
+            "[vid{i}]scale={w}:{h}:force_original_aspect_ratio=decrease,\
+             pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1[v{i}];",
+            i = i,
+            w = TILE_WIDTH,
+            h = TILE_HEIGHT
+        ));
+    }
+
+    let inputs: String = (1..=tiles).map(|i| format!("[v{}]", i)).collect();
+    let stack = match tiles {
+        1 => "null".to_string(),
+        2 => "hstack=inputs=2".to_string(),
+        3 => "xstack=inputs=3:layout=0_0|w0_0|0_h0:fill=black".to_string(),
+        _ => "xstack=inputs=4:layout=0_0|w0_0|0_h0|w0_h0".to_string(),
+    };
+    graph.push_str(&format!("{}{}[vo];", inputs, stack));
+
+    let focus = audio_focus.min(tiles - 1) + 1;
+    graph.push_str(&format!("[aid{}]anull[ao]", focus));
+    graph
+}
+
+/// Screen sizes offered in Settings for the VLC fallback
+pub const SCREEN_SIZES: &[(u32, u32)] = &[(1280, 720), (1920, 1080), (2560, 1440), (3840, 2160)];
+
+/// Used when the screen can't be detected and none is configured
+const FALLBACK_SCREEN: (u32, u32) = (1920, 1080);
+
+/// Screen to tile VLC windows over: the configured size, else the detected
+/// one, else 1080p
+#[cfg(not(target_arch = "wasm32"))]
+pub fn screen_size(configured: Option<(u32, u32)>) -> (u32, u32) {
+    configured
+        .or_else(detect_screen_size)
+        .unwrap_or(FALLBACK_SCREEN)
+}
+
+/// Size of the primary display as reported by the desktop
+#[cfg(not(target_arch = "wasm32"))]
+fn detect_screen_size() -> Option<(u32, u32)> {
+    let (program, args): (&str, &[&str]) = if cfg!(target_os = "macos") {
+        ("system_profiler", &["SPDisplaysDataType"])
+    } else if cfg!(windows) {
+        (
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "Get-CimInstance Win32_VideoController | Select-Object -First 1 | \
+                 ForEach-Object { \"Resolution: $($_.CurrentHorizontalResolution) x $($_.CurrentVerticalResolution)\" }",
+            ],
+        )
+    } else {
+        ("xrandr", &["--current"])
+    };
+    let output = std::process::Command::new(program)
+        .args(args)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    parse_screen_size(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// First `W x H` after "current" (xrandr) or "Resolution:" (macOS, Windows)
+fn parse_screen_size(text: &str) -> Option<(u32, u32)> {
+    text.lines().find_map(|line| {
+        let rest = line
+            .split_once("current ")
+            .or_else(|| line.split_once("Resolution: "))?
+            .1;
+        let mut numbers = rest
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|n| !n.is_empty());
+        let w = numbers.next()?.parse().ok()?;
+        let h = numbers.next()?.parse().ok()?;
+        (w > 0 && h > 0).then_some((w, h))
+    })
+}
+
+/// Window rectangle `(x, y, width, height)` of tile `index` when tiling `count`
+/// separate player windows over a `screen_w` x `screen_h` screen (VLC fallback)
+pub fn tile_geometry(
+    index: usize,
+    count: usize,
+    screen_w: u32,
+    screen_h: u32,
+) -> (u32, u32, u32, u32) {
+    let cols = if count <= 1 { 1 } else { 2 };
+    let rows = count.div_ceil(cols).max(1) as u32;
+    let w = screen_w / cols as u32;
+    let h = screen_h / rows;
+    let col = (index % cols) as u32;
+    let row = (index / cols) as u32;
+    (col * w, row * h, w, h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_stacks_every_tile_and_routes_focused_audio() {
+        let graph = lavfi_graph(2, 1);
+        assert!(graph.contains("[vid1]") && graph.contains("[vid2]"));
+        assert!(graph.contains("[v1][v2]hstack=inputs=2[vo]"));
+        assert!(graph.ends_with("[aid2]anull[ao]"));
+
+        let graph = lavfi_graph(4, 0);
+        assert!(graph.contains("[v1][v2][v3][v4]xstack=inputs=4:layout=0_0|w0_0|0_h0|w0_h0[vo]"));
+        assert!(graph.ends_with("[aid1]anull[ao]"));
+    }
+
+    #[test]
+    fn graph_clamps_focus_to_existing_tiles() {
+        assert!(lavfi_graph(3, 7).ends_with("[aid3]anull[ao]"));
+    }
+
+    #[test]
+    fn tile_limit_respects_max_connections() {
+        assert_eq!(tile_limit(None, 0), 4);
+        assert_eq!(tile_limit(Some(0), 0), 4);
+        assert_eq!(tile_limit(Some(2), 0), 2);
+        assert_eq!(tile_limit(Some(10), 0), 4);
+    }
+
+    #[test]
+    fn tile_limit_leaves_room_for_connections_in_use() {
+        assert_eq!(tile_limit(Some(3), 1), 2);
+        assert_eq!(tile_limit(Some(6), 1), 4);
+        assert_eq!(tile_limit(Some(2), 2), 0);
+        assert_eq!(tile_limit(Some(1), 3), 0);
+        assert_eq!(tile_limit(None, 3), 4);
+    }
+
+    #[test]
+    fn tiles_fill_a_two_column_grid() {
+        assert_eq!(tile_geometry(0, 2, 1920, 1080), (0, 0, 960, 1080));
+        assert_eq!(tile_geometry(1, 2, 1920, 1080), (960, 0, 960, 1080));
+        assert_eq!(tile_geometry(3, 4, 1920, 1080), (960, 540, 960, 540));
+        assert_eq!(tile_geometry(2, 3, 1920, 1080), (0, 540, 960, 540));
+    }
+
+    #[test]
+    fn screen_size_is_read_from_desktop_tools() {
+        let xrandr = "Screen 0: minimum 320 x 200, current 2560 x 1440, maximum 16384 x 16384";
+        assert_eq!(parse_screen_size(xrandr), Some((2560, 1440)));
+        let mac = "Displays:\n  Color LCD:\n    Resolution: 3024 x 1964 Retina";
+        assert_eq!(parse_screen_size(mac), Some((3024, 1964)));
+        assert_eq!(parse_screen_size("Resolution:  x "), None);
+    }
+}
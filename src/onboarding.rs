@@ -115,6 +115,7 @@ where
                                 base_url: url.clone(),
                                 username: username.clone(),
                                 password: password.clone(),
+                                credential: None,
                                 account_type: if crate::app::App::is_m3u_url(
                                     &url, &username, &password,
                                 ) {
@@ -129,6 +130,7 @@ where
                                 total_series: None,
                                 server_timezone: None,
                                 hidden_categories: HashSet::new(),
+                                radio_overrides: std::collections::HashMap::new(),
                                 category_sort_order: CategorySortOrder::Default,
                             });
                             transition_to(&mut state, OnboardingStep::Success);
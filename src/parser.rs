@@ -453,6 +453,7 @@ pub enum ContentType {
     Movies,
     Kids,
     Music,
+    Radio,
     Documentary,
     Entertainment,
     Religious,
@@ -465,6 +466,7 @@ impl ContentType {
         match self {
             ContentType::Sports => "\u{26be}", // Default sports
             ContentType::PPV => "\u{1f3df}",   // Stadium/Event
+            ContentType::Radio => "\u{1f4fb}", // Radio
             _ => "",
         }
     }
@@ -966,6 +968,13 @@ pub fn parse_category(name: &str) -> ParsedCategory {
     // Detect content type
     if original_upper.contains("PPV") || upper.contains("PPV") {
         content_type = Some(ContentType::PPV);
+    } else if upper
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word == "RADIO" || word == "RADIOS")
+    {
+        // Whole word only ("Radiohead Live" is video). Before sports so
+        // "Sports Radio" commentary plays audio-only
+        content_type = Some(ContentType::Radio);
     } else if upper.contains("SPORT")
         || ["NBA", "NFL", "MLB", "NHL", "UFC", "F1"]
             .iter()
@@ -1958,6 +1967,34 @@ mod tests {
         assert_eq!(ppv_event.content_type, Some(ContentType::PPV));
     }
 
+    #[test]
+    fn test_radio_categories() {
+        assert_eq!(
+            parse_category("UK| RADIO").content_type,
+            Some(ContentType::Radio)
+        );
+        assert_eq!(
+            parse_category("US| SPORTS RADIO").content_type,
+            Some(ContentType::Radio)
+        );
+        assert_eq!(
+            parse_category("US| MUSIC CHOICE").content_type,
+            Some(ContentType::Music)
+        );
+        assert_eq!(
+            parse_category("FR| RADIOS FM").content_type,
+            Some(ContentType::Radio)
+        );
+        assert_ne!(
+            parse_category("UK| RADIOHEAD LIVE").content_type,
+            Some(ContentType::Radio)
+        );
+        assert_ne!(
+            parse_category("US| RADIOACTIVE DOCS").content_type,
+            Some(ContentType::Radio)
+        );
+    }
+
     #[test]
     fn test_parse_4k_category() {
         let parsed = parse_category("4K| RELAX ᵁᴴᴰ ³⁸⁴⁰ᴾ");
@@ -1,6 +1,10 @@
 #![allow(dead_code)]
 use crate::config::PlayerEngine;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::player_control::ReconnectCounter;
 use serde::{Deserialize, Serialize};
+#[cfg(not(target_arch = "wasm32"))]
+use serde_json::json;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -35,14 +39,100 @@ impl StreamFormat {
     }
 }
 
+const MPV_LOG_FILE: &str = "mpv_playback.log";
+
+/// Unique mpv IPC socket/pipe for this session
+#[cfg(not(target_arch = "wasm32"))]
+fn ipc_pipe_name() -> String {
+    if cfg!(target_os = "windows") {
+        format!("\\\\.\\pipe\\mpv_ipc_{}", std::process::id())
+    } else {
+        format!("/tmp/mpv_ipc_{}", std::process::id())
+    }
+}
+
+/// Extra HTTP headers a stream host expects (e.g. CDNs behind embed pages).
+/// Unset fields fall back to the player defaults.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StreamHeaders {
+    pub referrer: Option<String>,
+    pub origin: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl StreamHeaders {
+    /// Referrer to send: the explicit one, or `scheme://host/` of the stream URL.
+    pub(crate) fn referrer_for(&self, url: &str) -> Option<String> {
+        if let Some(referrer) = &self.referrer {
+            return Some(referrer.clone());
+        }
+        let scheme_end = url.find("://")?;
+        let rest = &url[scheme_end + 3..];
+        let path_start = rest.find('/')?;
+        Some(format!("{}://{}/", &url[..scheme_end], &rest[..path_start]))
+    }
+
+    fn user_agent(&self) -> &str {
+        // Same browser identity the embed page was scraped with
+        self.user_agent
+            .as_deref()
+            .unwrap_or(crate::sports::EMBED_USER_AGENT)
+    }
+}
+
+/// Engine and flags a player process was launched with. A channel change can
+/// reuse the running process only while these stay the same.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LaunchOptions {
+    engine: PlayerEngine,
+    use_default_mpv: bool,
+    smooth_motion: bool,
+    audio_only: bool,
+}
+
+/// Marks the handle made by `Player::new`. Clones handed to playback and
+/// key-listener tasks share the process but never own it.
+#[derive(Debug)]
+struct Owner(bool);
+
+impl Clone for Owner {
+    fn clone(&self) -> Self {
+        Owner(false)
+    }
+}
+
 #[derive(Clone)]
 pub struct Player {
     #[cfg(not(target_arch = "wasm32"))]
     process: Arc<Mutex<Option<Child>>>,
     #[cfg(not(target_arch = "wasm32"))]
+    owner: Owner,
+    #[cfg(not(target_arch = "wasm32"))]
     ipc_path: Arc<Mutex<Option<PathBuf>>>,
     #[cfg(not(target_arch = "wasm32"))]
     last_error: Arc<Mutex<Option<String>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    reconnects: Arc<Mutex<ReconnectCounter>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    launch_options: Arc<Mutex<Option<LaunchOptions>>>,
+    /// Additional windows when VLC plays a multiview (one process per tile)
+    #[cfg(not(target_arch = "wasm32"))]
+    extra_processes: Arc<Mutex<Vec<Child>>>,
+    /// Set while a task is forwarding key presses from the mpv window
+    #[cfg(not(target_arch = "wasm32"))]
+    key_listener_active: Arc<std::sync::atomic::AtomicBool>,
+    /// Profile launched by `PlayerEngine::Custom`
+    #[cfg(not(target_arch = "wasm32"))]
+    profile: Arc<Mutex<Option<crate::player_profile::PlayerProfile>>>,
+    /// Title of what is about to play, for the `{title}` profile placeholder
+    #[cfg(not(target_arch = "wasm32"))]
+    media_title: Arc<Mutex<Option<String>>>,
+    /// Active account's HTTP client, used for pre-flight checks (see `crate::preflight`)
+    #[cfg(not(target_arch = "wasm32"))]
+    http_client: Arc<Mutex<Option<reqwest::Client>>>,
+    /// Launch without video (radio, background listening); always uses mpv
+    #[cfg(not(target_arch = "wasm32"))]
+    audio_only: Arc<std::sync::atomic::AtomicBool>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -64,6 +154,14 @@ pub enum PlaybackErrorType {
     Unknown,
 }
 
+impl std::fmt::Display for PlaybackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for PlaybackError {}
+
 impl PlaybackError {
     pub fn new(error_type: PlaybackErrorType, message: String) -> Self {
         let (hint, recoverable) = match &error_type {
@@ -115,8 +213,17 @@ impl Player {
         {
             Self {
                 process: Arc::new(Mutex::new(None)),
+                owner: Owner(true),
                 ipc_path: Arc::new(Mutex::new(None)),
                 last_error: Arc::new(Mutex::new(None)),
+                reconnects: Arc::new(Mutex::new(ReconnectCounter::default())),
+                launch_options: Arc::new(Mutex::new(None)),
+                extra_processes: Arc::new(Mutex::new(Vec::new())),
+                key_listener_active: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+                profile: Arc::new(Mutex::new(None)),
+                media_title: Arc::new(Mutex::new(None)),
+                http_client: Arc::new(Mutex::new(None)),
+                audio_only: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             }
         }
         #[cfg(target_arch = "wasm32")]
@@ -132,6 +239,37 @@ impl Player {
         }
     }
 
+    /// Profile to launch when the engine is `PlayerEngine::Custom`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_profile(&self, profile: Option<crate::player_profile::PlayerProfile>) {
+        if let Ok(mut guard) = self.profile.lock() {
+            *guard = profile;
+        }
+    }
+
+    /// Client for pre-flight stream checks; None skips them
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_http_client(&self, client: Option<reqwest::Client>) {
+        if let Ok(mut guard) = self.http_client.lock() {
+            *guard = client;
+        }
+    }
+
+    /// Title for the next launch (shown by players whose profile uses `{title}`)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_media_title(&self, title: &str) {
+        if let Ok(mut guard) = self.media_title.lock() {
+            *guard = Some(title.to_string());
+        }
+    }
+
+    /// Play the next launch without video (mpv `--no-video`, no window)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_audio_only(&self, audio_only: bool) {
+        self.audio_only
+            .store(audio_only, std::sync::atomic::Ordering::SeqCst);
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub fn get_last_error(&self) -> Option<String> {
         self.last_error.lock().ok().and_then(|g| g.clone())
@@ -142,6 +280,13 @@ impl Player {
         None
     }
 
+    /// IPC controller for the running mpv instance (`None` for VLC or when stopped)
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn controller(&self) -> Option<crate::player_control::PlayerController> {
+        let path = self.ipc_path.lock().ok()?.clone()?;
+        Some(crate::player_control::PlayerController::new(path))
+    }
+
     /// Start the selected player engine with automatic retry and fallback
     #[cfg(not(target_arch = "wasm32"))]
     pub async fn play(
@@ -151,26 +296,195 @@ impl Player {
         use_default_mpv: bool,
         smooth_motion: bool,
     ) -> Result<(), anyhow::Error> {
+        self.play_with_headers(
+            url,
+            &StreamHeaders::default(),
+            engine,
+            use_default_mpv,
+            smooth_motion,
+        )
+        .await
+    }
+
+    /// Same as `play`, but sends the given referrer/origin/user agent to the stream host
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn play_with_headers(
+        &self,
+        url: &str,
+        headers: &StreamHeaders,
+        engine: PlayerEngine,
+        use_default_mpv: bool,
+        smooth_motion: bool,
+    ) -> Result<(), anyhow::Error> {
+        let url = self.preflight(url, headers).await?;
+        let url = url.as_str();
+        let audio_only = self.audio_only.load(std::sync::atomic::Ordering::SeqCst);
+        // The terminal display of an audio-only stream needs mpv's IPC metadata
+        let engine = if audio_only {
+            PlayerEngine::Mpv
+        } else {
+            engine
+        };
+        let options = LaunchOptions {
+            engine,
+            use_default_mpv,
+            smooth_motion,
+            audio_only,
+        };
+        if engine == PlayerEngine::Mpv && self.try_reuse_mpv(url, headers, options).await {
+            return Ok(());
+        }
+
         self.stop();
 
-        match engine {
+        let launched = match engine {
             PlayerEngine::Mpv => {
                 match self
-                    .play_mpv_with_retry(url, use_default_mpv, smooth_motion, 0)
+                    .play_mpv_with_retry(url, headers, use_default_mpv, smooth_motion, 0)
                     .await
                 {
-                    Ok(_) => Ok(()),
+                    Ok(_) => Ok(engine),
                     Err(e) => {
                         if crate::setup::get_vlc_path().is_some() {
-                            self.play_vlc(url, smooth_motion)
+                            self.play_vlc(url, headers, smooth_motion)
+                                .map(|_| PlayerEngine::Vlc)
                         } else {
                             Err(e)
                         }
                     }
                 }
             }
-            PlayerEngine::Vlc => self.play_vlc(url, smooth_motion),
+            PlayerEngine::Vlc => self
+                .play_vlc(url, headers, smooth_motion)
+                .map(|_| PlayerEngine::Vlc),
+            PlayerEngine::Custom => self
+                .play_profile(url, headers)
+                .map(|_| PlayerEngine::Custom),
+        }?;
+
+        if let Ok(mut guard) = self.launch_options.lock() {
+            *guard = Some(LaunchOptions {
+                engine: launched,
+                ..options
+            });
         }
+        Ok(())
+    }
+
+    /// Switch the running mpv to `url` with IPC `loadfile` instead of respawning it,
+    /// keeping the window where it is. Returns false if a fresh process is needed.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn try_reuse_mpv(
+        &self,
+        url: &str,
+        headers: &StreamHeaders,
+        options: LaunchOptions,
+    ) -> bool {
+        let same_options = self
+            .launch_options
+            .lock()
+            .map(|guard| *guard == Some(options))
+            .unwrap_or(false);
+        if !same_options || !self.is_running() {
+            return false;
+        }
+        let Some(controller) = self.controller() else {
+            return false;
+        };
+
+        // Per-stream HTTP settings are plain properties, so they can change between files
+        let header_fields: Vec<String> = headers
+            .origin
+            .iter()
+            .map(|origin| format!("Origin: {}", origin))
+            .collect();
+        let setup = controller
+            .command_batch(vec![
+                vec![
+                    json!("set_property"),
+                    json!("referrer"),
+                    json!(headers.referrer_for(url).unwrap_or_default()),
+                ],
+                vec![
+                    json!("set_property"),
+                    json!("user-agent"),
+                    json!(headers.user_agent()),
+                ],
+                vec![
+                    json!("set_property"),
+                    json!("http-header-fields"),
+                    json!(header_fields),
+                ],
+            ])
+            .await;
+        if setup.is_err() {
+            return false;
+        }
+
+        if controller
+            .command(vec![json!("loadfile"), json!(url), json!("replace")])
+            .await
+            .is_err()
+        {
+            return false;
+        }
+        self.set_last_error(None);
+        if let Ok(mut counter) = self.reconnects.lock() {
+            // Same log file keeps growing; only count reconnects of the new stream
+            counter.skip_to_end(std::path::Path::new(MPV_LOG_FILE));
+        }
+        true
+    }
+
+    /// Claim the mpv key listener slot; false if a listener is already running
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn begin_key_listener(&self) -> bool {
+        !self
+            .key_listener_active
+            .swap(true, std::sync::atomic::Ordering::SeqCst)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn end_key_listener(&self) {
+        self.key_listener_active
+            .store(false, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Validate a remote stream before launching anything. Returns the URL to play,
+    /// which is another format of the same stream when the requested one isn't served.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn preflight(&self, url: &str, headers: &StreamHeaders) -> Result<String, anyhow::Error> {
+        let client = self.http_client.lock().ok().and_then(|c| c.clone());
+        let Some(client) = client.filter(|_| url.starts_with("http")) else {
+            return Ok(url.to_string());
+        };
+        let referrer = headers.referrer_for(url);
+        let error = match crate::preflight::check(&client, url, referrer.as_deref()).await {
+            Ok(_) => return Ok(url.to_string()),
+            Err(e) => e,
+        };
+
+        // A missing or unplayable format is often served in another one
+        if matches!(
+            error.error_type,
+            PlaybackErrorType::StreamUnreachable | PlaybackErrorType::InvalidFormat
+        ) {
+            if let Some(base_url) = self.extract_stream_base_url(url) {
+                for format in ["ts", "m3u8", "mp4"] {
+                    let candidate = format!("{}.{}", base_url, format);
+                    if candidate != url
+                        && crate::preflight::check(&client, &candidate, referrer.as_deref())
+                            .await
+                            .is_ok()
+                    {
+                        return Ok(candidate);
+                    }
+                }
+            }
+        }
+
+        self.set_last_error(Some(error.message.clone()));
+        Err(anyhow::Error::new(error))
     }
 
     /// Try playing with different stream formats as fallback
@@ -178,11 +492,12 @@ impl Player {
     async fn play_mpv_with_retry(
         &self,
         url: &str,
+        headers: &StreamHeaders,
         use_default_mpv: bool,
         smooth_motion: bool,
         attempt: u32,
     ) -> Result<(), anyhow::Error> {
-        let result = self.play_mpv(url, use_default_mpv, smooth_motion);
+        let result = self.play_mpv(url, headers, use_default_mpv, smooth_motion);
 
         if result.is_err() && attempt < 3 {
             if let Some(base_url) = self.extract_stream_base_url(url) {
@@ -196,6 +511,7 @@ impl Player {
                     let new_url = format!("{}.{}", base_url, format);
                     return Box::pin(self.play_mpv_with_retry(
                         &new_url,
+                        headers,
                         use_default_mpv,
                         smooth_motion,
                         attempt + 1,
@@ -221,6 +537,14 @@ impl Player {
         }
     }
 
+    /// Diagnose a failed `play`, keeping the pre-flight classification when there is one
+    pub fn diagnose_error(&self, error: &anyhow::Error) -> PlaybackError {
+        error
+            .downcast_ref::<PlaybackError>()
+            .cloned()
+            .unwrap_or_else(|| self.diagnose_playback_failure(&error.to_string()))
+    }
+
     /// Diagnose playback failure and return helpful error message
     pub fn diagnose_playback_failure(&self, error: &str) -> PlaybackError {
         let error_lower = error.to_lowercase();
@@ -317,9 +641,31 @@ impl Player {
     fn play_mpv(
         &self,
         url: &str,
+        headers: &StreamHeaders,
         use_default_mpv: bool,
         smooth_motion: bool,
     ) -> Result<(), anyhow::Error> {
+        let (mut cmd, mpv_path, pipe_name) =
+            self.mpv_command(url, headers, use_default_mpv, smooth_motion)?;
+        if self.audio_only.load(std::sync::atomic::Ordering::SeqCst) {
+            // Later flags win, so this also overrides --force-window=immediate
+            cmd.arg("--no-video")
+                .arg("--force-window=no")
+                .arg("--audio-display=no");
+        }
+        self.spawn_mpv(cmd, &mpv_path, &pipe_name)
+    }
+
+    /// Build the mpv invocation (path, flags, IPC pipe) without starting it.
+    /// Returns the command, the mpv path used and the IPC pipe name.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn mpv_command(
+        &self,
+        url: &str,
+        headers: &StreamHeaders,
+        use_default_mpv: bool,
+        smooth_motion: bool,
+    ) -> Result<(Command, String, String), anyhow::Error> {
         // Find mpv executable, checking PATH and common installation locations
         let mpv_path = crate::setup::get_mpv_path().ok_or_else(|| {
             let hint = if cfg!(target_os = "macos") {
@@ -338,32 +684,24 @@ impl Player {
             )
         })?;
 
-        // Create a unique IPC path for this session
-        let pipe_name = if cfg!(target_os = "windows") {
-            format!("\\\\.\\pipe\\mpv_ipc_{}", std::process::id())
-        } else {
-            format!("/tmp/mpv_ipc_{}", std::process::id())
-        };
-
+        let pipe_name = ipc_pipe_name();
         let mut cmd = Command::new(&mpv_path);
 
         // Add Referrer validation (Common anti-scraping measure)
-        if let Some(scheme_end) = url.find("://") {
-            let rest = &url[scheme_end + 3..];
-            if let Some(path_start) = rest.find('/') {
-                let host = &rest[..path_start];
-                let base = format!("{}://{}/", &url[..scheme_end], host);
-                cmd.arg(format!("--referrer={}", base));
-            }
+        if let Some(referrer) = headers.referrer_for(url) {
+            cmd.arg(format!("--referrer={}", referrer));
+        }
+        if let Some(origin) = &headers.origin {
+            cmd.arg(format!("--http-header-fields=Origin: {}", origin));
         }
 
         let _is_live = url.contains("/live/") || url.contains(".m3u8");
 
         cmd.arg(url)
-           .arg("--force-window=immediate")
-           .arg("--no-fs")
-           .arg("--osc=yes")
-           .arg("--user-agent=Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36");
+            .arg("--force-window=immediate")
+            .arg("--no-fs")
+            .arg("--osc=yes")
+            .arg(format!("--user-agent={}", headers.user_agent()));
 
         // Apply smooth motion interpolation if enabled
         if smooth_motion {
@@ -401,19 +739,85 @@ impl Player {
 
         // Common settings for both modes
         cmd.arg("--msg-level=all=no")
-           .arg("--term-status-msg=no")
-           .arg("--input-terminal=no")
-           .arg("--terminal=no")
-           .arg("--user-agent=Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-           .arg("--keep-open=no")
-           .arg("--log-file=mpv_playback.log")
-           .arg(format!("--input-ipc-server={}", pipe_name));
+            .arg("--term-status-msg=no")
+            .arg("--input-terminal=no")
+            .arg("--terminal=no")
+            .arg(format!("--user-agent={}", headers.user_agent()))
+            .arg("--keep-open=no")
+            .arg(format!("--log-file={}", MPV_LOG_FILE))
+            .arg(format!("--input-ipc-server={}", pipe_name));
 
         // Disconnect from terminal input/output to prevent hotkey conflicts
         cmd.stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null());
 
+        Ok((cmd, mpv_path, pipe_name))
+    }
+
+    /// Launch the user-defined profile set with `set_profile`
+    #[cfg(not(target_arch = "wasm32"))]
+    fn play_profile(&self, url: &str, headers: &StreamHeaders) -> Result<(), anyhow::Error> {
+        use crate::player_profile::{IpcFlavour, TemplateVars};
+
+        let profile = self
+            .profile
+            .lock()
+            .ok()
+            .and_then(|p| p.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!("No custom player selected. Pick one in Settings > Player Engine.")
+            })?;
+        let title = self
+            .media_title
+            .lock()
+            .ok()
+            .and_then(|t| t.clone())
+            .unwrap_or_else(|| url.to_string());
+        let referrer = headers.referrer_for(url).unwrap_or_default();
+        let ipc = (profile.ipc == IpcFlavour::Mpv).then(ipc_pipe_name);
+
+        let mut cmd = Command::new(&profile.executable);
+        cmd.args(profile.expand_args(&TemplateVars {
+            url,
+            title: &title,
+            user_agent: headers.user_agent(),
+            referrer: &referrer,
+            ipc: ipc.as_deref().unwrap_or(""),
+        }))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const DETACHED_PROCESS: u32 = 0x00000008;
+            cmd.creation_flags(DETACHED_PROCESS);
+        }
+
+        let child = cmd
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Failed to start {}: {}", profile.name, e))?;
+        if let Ok(mut guard) = self.process.lock() {
+            *guard = Some(child);
+        }
+        if let Ok(mut counter) = self.reconnects.lock() {
+            *counter = ReconnectCounter::default();
+        }
+        if let Ok(mut ipc_guard) = self.ipc_path.lock() {
+            *ipc_guard = ipc.map(PathBuf::from);
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spawn_mpv(
+        &self,
+        mut cmd: Command,
+        mpv_path: &str,
+        pipe_name: &str,
+    ) -> Result<(), anyhow::Error> {
         let child = cmd.spawn();
 
         match child {
@@ -425,12 +829,15 @@ impl Player {
                         .map_err(|e| anyhow::anyhow!("Failed to lock process mutex: {}", e))?;
                     *guard = Some(child);
                 }
+                if let Ok(mut counter) = self.reconnects.lock() {
+                    *counter = ReconnectCounter::default();
+                }
                 {
                     let mut ipc_guard = self
                         .ipc_path
                         .lock()
                         .map_err(|e| anyhow::anyhow!("Failed to lock IPC path mutex: {}", e))?;
-                    *ipc_guard = Some(PathBuf::from(&pipe_name));
+                    *ipc_guard = Some(PathBuf::from(pipe_name));
                 }
                 Ok(())
             }
@@ -451,7 +858,33 @@ impl Player {
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    fn play_vlc(&self, url: &str, smooth_motion: bool) -> Result<(), anyhow::Error> {
+    fn play_vlc(
+        &self,
+        url: &str,
+        headers: &StreamHeaders,
+        smooth_motion: bool,
+    ) -> Result<(), anyhow::Error> {
+        let mut cmd = self.vlc_command(url, headers, smooth_motion)?;
+        let child = cmd.spawn()?;
+
+        {
+            let mut guard = self
+                .process
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Failed to lock process mutex: {}", e))?;
+            *guard = Some(child);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn vlc_command(
+        &self,
+        url: &str,
+        headers: &StreamHeaders,
+        smooth_motion: bool,
+    ) -> Result<Command, anyhow::Error> {
         // Find vlc executable
         let vlc_path = crate::setup::get_vlc_path()
             .ok_or_else(|| anyhow::anyhow!("VLC not found. Please install VLC."))?;
@@ -459,24 +892,19 @@ impl Player {
         let mut cmd = Command::new(&vlc_path);
 
         // Add Referrer validation (Common anti-scraping measure)
-        // Manual parsing to avoid adding 'url' crate dependency
-        if let Some(scheme_end) = url.find("://") {
-            let rest = &url[scheme_end + 3..];
-            if let Some(path_start) = rest.find('/') {
-                let host = &rest[..path_start];
-                let base = format!("{}://{}/", &url[..scheme_end], host);
-                cmd.arg(format!("--http-referrer={}", base));
-            }
+        // VLC has no generic header option, so Origin can't be forwarded here
+        if let Some(referrer) = headers.referrer_for(url) {
+            cmd.arg(format!("--http-referrer={}", referrer));
         }
 
         cmd.arg(url)
-           .arg("--no-video-title-show") 
-           .arg("--http-user-agent=Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-           .arg("--http-reconnect")
-           .arg("--http-continuous")
-           .arg("--clock-jitter=500")           // Allow more jitter in stream clock
-           .arg("--network-caching=15000")      // 15 second buffer for TS streams
-           .arg("--gnutls-verify-trust-ee=no"); // For VLC HTTPS stability
+            .arg("--no-video-title-show")
+            .arg(format!("--http-user-agent={}", headers.user_agent()))
+            .arg("--http-reconnect")
+            .arg("--http-continuous")
+            .arg("--clock-jitter=500") // Allow more jitter in stream clock
+            .arg("--network-caching=15000") // 15 second buffer for TS streams
+            .arg("--gnutls-verify-trust-ee=no"); // For VLC HTTPS stability
 
         // Apply smooth motion (deinterlacing) if enabled
         if smooth_motion {
@@ -495,17 +923,88 @@ impl Player {
             cmd.creation_flags(DETACHED_PROCESS);
         }
 
-        let child = cmd.spawn()?;
+        Ok(cmd)
+    }
 
-        {
-            let mut guard = self
-                .process
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Failed to lock process mutex: {}", e))?;
-            *guard = Some(child);
+    /// Play 2–4 live streams at once (see `crate::multiview`). mpv tiles them in one
+    /// window with audio from `audio_focus`; VLC opens one window per stream, tiled
+    /// over a `screen` (width, height) desktop.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn play_multiview(
+        &self,
+        urls: &[String],
+        audio_focus: usize,
+        engine: PlayerEngine,
+        use_default_mpv: bool,
+        smooth_motion: bool,
+        screen: (u32, u32),
+    ) -> Result<(), anyhow::Error> {
+        let Some(first) = urls.first() else {
+            return Err(anyhow::anyhow!("Multiview needs at least one stream"));
+        };
+        self.stop();
+        let headers = StreamHeaders::default();
+
+        match engine {
+            PlayerEngine::Mpv => {
+                let (mut cmd, mpv_path, pipe_name) =
+                    self.mpv_command(first, &headers, use_default_mpv, smooth_motion)?;
+                for url in &urls[1..] {
+                    cmd.arg(format!("--external-file={}", url));
+                }
+                cmd.arg(format!(
+                    "--lavfi-complex={}",
+                    crate::multiview::lavfi_graph(urls.len(), audio_focus)
+                ));
+                self.spawn_mpv(cmd, &mpv_path, &pipe_name)
+            }
+            PlayerEngine::Custom => Err(anyhow::anyhow!(
+                "Multiview needs MPV or VLC. Switch engines in Settings > Player Engine."
+            )),
+            PlayerEngine::Vlc => {
+                let (screen_w, screen_h) = screen;
+                for (i, url) in urls.iter().enumerate() {
+                    let mut cmd = self.vlc_command(url, &headers, smooth_motion)?;
+                    let (x, y, w, h) =
+                        crate::multiview::tile_geometry(i, urls.len(), screen_w, screen_h);
+                    cmd.arg("--no-embedded-video")
+                        .arg(format!("--video-x={}", x))
+                        .arg(format!("--video-y={}", y))
+                        .arg(format!("--width={}", w))
+                        .arg(format!("--height={}", h));
+                    if i != audio_focus {
+                        cmd.arg("--no-audio");
+                    }
+                    let child = cmd.spawn()?;
+                    if i == 0 {
+                        if let Ok(mut guard) = self.process.lock() {
+                            *guard = Some(child);
+                        }
+                    } else if let Ok(mut extra) = self.extra_processes.lock() {
+                        extra.push(child);
+                    }
+                }
+                Ok(())
+            }
         }
+    }
 
-        Ok(())
+    /// Move multiview audio to tile `focus` by swapping the running mpv's filter graph
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn set_multiview_audio_focus(
+        &self,
+        tiles: usize,
+        focus: usize,
+    ) -> Result<(), anyhow::Error> {
+        let controller = self
+            .controller()
+            .ok_or_else(|| anyhow::anyhow!("Audio focus switching requires MPV"))?;
+        controller
+            .set_property(
+                "lavfi-complex",
+                json!(crate::multiview::lavfi_graph(tiles, focus)),
+            )
+            .await
     }
 
     /// Read the last few lines of the player logs to find errors
@@ -582,95 +1081,10 @@ impl Player {
         Ok(self.is_running())
     }
 
-    /// Monitor MPV IPC socket for error events
-    /// This provides real-time error detection during playback
-    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "windows")))]
-    pub async fn monitor_ipc_errors(&self, duration_ms: u64) -> Option<String> {
-        let ipc_path = self.ipc_path.lock().ok()?.clone()?;
-        let duration = Duration::from_millis(duration_ms);
-        let start = std::time::Instant::now();
-
-        while start.elapsed() < duration {
-            if !self.is_running() {
-                return self.get_last_error_from_log();
-            }
-
-            // Try to read from IPC socket
-            if let Ok(ipc_data) = self.read_ipc_socket(&ipc_path).await {
-                if let Some(error) = self.parse_ipc_error(&ipc_data) {
-                    return Some(error);
-                }
-            }
-
-            sleep(Duration::from_millis(500)).await;
-        }
-        None
-    }
-
-    /// Read from MPV IPC socket
-    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "windows")))]
-    async fn read_ipc_socket(&self, path: &PathBuf) -> Result<String, std::io::Error> {
-        use tokio::io::AsyncReadExt;
-        use tokio::net::UnixStream;
-
-        let mut stream = match UnixStream::connect(path).await {
-            Ok(s) => s,
-            Err(_) => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::NotConnected,
-                    "IPC not ready",
-                ))
-            }
-        };
-
-        let mut buf = [0u8; 4096];
-        match stream.read(&mut buf).await {
-            Ok(0) => Err(std::io::Error::new(
-                std::io::ErrorKind::UnexpectedEof,
-                "IPC closed",
-            )),
-            Ok(n) => Ok(String::from_utf8_lossy(&buf[..n]).to_string()),
-            Err(e) => Err(e),
-        }
-    }
-
-    /// Windows stub for read_ipc_socket
-    #[cfg(all(not(target_arch = "wasm32"), target_os = "windows"))]
-    async fn read_ipc_socket(&self, _path: &PathBuf) -> Result<String, std::io::Error> {
-        Err(std::io::Error::other(
-            "Unix sockets not available on Windows",
-        ))
-    }
-
-    /// Windows stub for monitor_ipc_errors
-    #[cfg(all(not(target_arch = "wasm32"), target_os = "windows"))]
-    pub async fn monitor_ipc_errors(&self, _duration_ms: u64) -> Option<String> {
-        None
-    }
-
-    /// Parse IPC data for error messages
-    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "windows")))]
-    fn parse_ipc_error(&self, data: &str) -> Option<String> {
-        let error_patterns = ["error", "failed", "abort", "network"];
-        let lower = data.to_lowercase();
-
-        for pattern in error_patterns {
-            if lower.contains(pattern) {
-                if let Some(line) = data.lines().find(|l| l.to_lowercase().contains(pattern)) {
-                    return Some(line.to_string());
-                }
-            }
-        }
-        None
-    }
-
-    #[cfg(all(not(target_arch = "wasm32"), target_os = "windows"))]
-    fn parse_ipc_error(&self, _data: &str) -> Option<String> {
-        None
-    }
-
-    /// Enhanced playback check with IPC error monitoring
-    #[cfg(all(not(target_arch = "wasm32"), not(target_os = "windows")))]
+    /// Wait until mpv confirms over IPC that media is actually playing (`playback-time`
+    /// is available), instead of guessing from how long the process stayed alive.
+    /// Players without IPC (VLC) fall back to the process-liveness check.
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn wait_for_playback_with_monitoring(
         &self,
         timeout_ms: u64,
@@ -691,14 +1105,16 @@ impl Player {
                 return Ok(false);
             }
 
-            // Check for IPC errors after initial startup
-            if start.elapsed() > Duration::from_millis(3000) {
-                if let Some(ipc_error) = self.monitor_ipc_errors(2000).await {
-                    self.set_last_error(Some(ipc_error.clone()));
-                    return Err(anyhow::anyhow!("Playback error: {}", ipc_error));
+            match self.controller() {
+                Some(controller) => {
+                    if let Ok(pos) = controller.get_property("playback-time").await {
+                        if pos.is_number() {
+                            return Ok(true);
+                        }
+                    }
                 }
-
-                return Ok(true);
+                None if start.elapsed() > Duration::from_millis(3000) => return Ok(true),
+                None => {}
             }
 
             sleep(Duration::from_millis(200)).await;
@@ -707,33 +1123,13 @@ impl Player {
         Ok(self.is_running())
     }
 
-    /// Simplified playback check for Windows (no IPC monitoring)
-    #[cfg(all(not(target_arch = "wasm32"), target_os = "windows"))]
-    pub async fn wait_for_playback_with_monitoring(
-        &self,
-        timeout_ms: u64,
-    ) -> Result<bool, anyhow::Error> {
-        let start = std::time::Instant::now();
-        let timeout = Duration::from_millis(timeout_ms);
-
-        sleep(Duration::from_millis(500)).await;
-
-        while start.elapsed() < timeout {
-            if !self.is_running() {
-                if let Some(log_error) = self.get_last_error_from_log() {
-                    return Err(anyhow::anyhow!("Playback failed: {}", log_error));
-                }
-                return Ok(false);
-            }
-
-            if start.elapsed() > Duration::from_millis(3000) {
-                return Ok(true);
-            }
-
-            sleep(Duration::from_millis(200)).await;
-        }
-
-        Ok(self.is_running())
+    /// Upstream reconnects ffmpeg has logged during the current mpv session
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reconnect_count(&self) -> usize {
+        self.reconnects
+            .lock()
+            .map(|mut counter| counter.update(std::path::Path::new(MPV_LOG_FILE)))
+            .unwrap_or(0)
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -760,9 +1156,20 @@ impl Player {
             }
         }
 
+        if let Ok(mut extra) = self.extra_processes.lock() {
+            for mut child in extra.drain(..) {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+        }
+
         if let Ok(mut ipc_guard) = self.ipc_path.lock() {
             *ipc_guard = None;
         }
+
+        if let Ok(mut options) = self.launch_options.lock() {
+            *options = None;
+        }
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -830,7 +1237,37 @@ impl Player {
 
 impl Drop for Player {
     fn drop(&mut self) {
+        // Only the handle from `Player::new` stops the player: a task's clone
+        // finishing must not close mpv, and a clone outliving the owner must
+        // not keep it open
         #[cfg(not(target_arch = "wasm32"))]
-        self.stop();
+        if self.owner.0 {
+            self.stop();
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn spawn_sleeper(player: &Player) {
+        let child = Command::new("sleep").arg("30").spawn().unwrap();
+        *player.process.lock().unwrap() = Some(child);
+    }
+
+    #[test]
+    fn only_the_owner_stops_the_player_on_drop() {
+        let owner = Player::new();
+        spawn_sleeper(&owner);
+
+        // A task's clone finishing leaves playback running
+        drop(owner.clone());
+        assert!(owner.is_running());
+
+        // The owner going away stops it even while a clone is still held
+        let stale = owner.clone();
+        drop(owner);
+        assert!(!stale.is_running());
     }
 }
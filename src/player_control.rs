@@ -0,0 +1,718 @@
+//! Remote control for a running mpv instance over its JSON IPC socket
+//! (`--input-ipc-server`). Each request is one JSON line; mpv answers with a
+//! line carrying the same `request_id`, interleaved with unsolicited events.
+
+use serde_json::{json, Value};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+
+/// How long to wait for mpv to answer a single request
+#[cfg(not(target_arch = "wasm32"))]
+const IPC_TIMEOUT_MS: u64 = 1500;
+
+/// First `script-message` argument identifying messages meant for this app
+pub const CLIENT_MESSAGE_TARGET: &str = "matrix-iptv";
+
+/// A transport action the now-playing bar can send to the player
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransportCommand {
+    TogglePause,
+    /// Relative seek in seconds (negative = backwards)
+    Seek(i32),
+    /// Relative volume change in percent
+    Volume(i32),
+    ToggleMute,
+    CycleAudioTrack,
+    CycleSubtitleTrack,
+    ToggleFullscreen,
+}
+
+impl TransportCommand {
+    /// The mpv `command` array for this action
+    pub fn to_ipc_args(self) -> Vec<Value> {
+        match self {
+            Self::TogglePause => vec![json!("cycle"), json!("pause")],
+            Self::Seek(secs) => vec![json!("seek"), json!(secs), json!("relative")],
+            Self::Volume(delta) => vec![json!("add"), json!("volume"), json!(delta)],
+            Self::ToggleMute => vec![json!("cycle"), json!("mute")],
+            Self::CycleAudioTrack => vec![json!("cycle"), json!("aid")],
+            Self::CycleSubtitleTrack => vec![json!("cycle"), json!("sid")],
+            Self::ToggleFullscreen => vec![json!("cycle"), json!("fullscreen")],
+        }
+    }
+
+    /// Short confirmation shown in the now-playing bar
+    pub fn label(self) -> String {
+        match self {
+            Self::TogglePause => "pause".to_string(),
+            Self::Seek(secs) if secs >= 0 => format!("+{}s", secs),
+            Self::Seek(secs) => format!("{}s", secs),
+            Self::Volume(delta) if delta >= 0 => format!("vol +{}", delta),
+            Self::Volume(delta) => format!("vol {}", delta),
+            Self::ToggleMute => "mute".to_string(),
+            Self::CycleAudioTrack => "audio track".to_string(),
+            Self::CycleSubtitleTrack => "subtitles".to_string(),
+            Self::ToggleFullscreen => "fullscreen".to_string(),
+        }
+    }
+}
+
+/// Snapshot of the player state shown in the now-playing bar
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlaybackStatus {
+    pub paused: bool,
+    pub position_secs: Option<f64>,
+    pub duration_secs: Option<f64>,
+    pub volume: Option<f64>,
+    pub muted: bool,
+    /// Selected audio track id (`None` = no audio)
+    pub audio_track: Option<i64>,
+    /// Selected subtitle track id (`None` = subtitles off)
+    pub subtitle_track: Option<i64>,
+    pub fullscreen: bool,
+    pub media_title: Option<String>,
+    /// A video track is selected (false for radio and audio-only playback)
+    pub has_video: bool,
+    /// Current song of an internet radio stream (ICY metadata)
+    pub icy_title: Option<String>,
+}
+
+/// Properties fetched for `PlaybackStatus`, in request order
+const STATUS_PROPERTIES: &[&str] = &[
+    "pause",
+    "time-pos",
+    "duration",
+    "volume",
+    "mute",
+    "aid",
+    "sid",
+    "fullscreen",
+    "media-title",
+    "vid",
+    "metadata/by-key/icy-title",
+];
+
+impl PlaybackStatus {
+    /// Build a status from `(property, value)` pairs; unknown or unavailable
+    /// properties (mpv returns an error for e.g. `duration` on live streams) are skipped.
+    pub fn from_properties(props: &[(&str, Value)]) -> Self {
+        let mut status = Self::default();
+        for (name, value) in props {
+            match *name {
+                "pause" => status.paused = value.as_bool().unwrap_or(false),
+                "time-pos" => status.position_secs = value.as_f64(),
+                "duration" => status.duration_secs = value.as_f64(),
+                "volume" => status.volume = value.as_f64(),
+                "mute" => status.muted = value.as_bool().unwrap_or(false),
+                // "no" / false when disabled, otherwise the numeric track id
+                "aid" => status.audio_track = value.as_i64(),
+                "sid" => status.subtitle_track = value.as_i64(),
+                "fullscreen" => status.fullscreen = value.as_bool().unwrap_or(false),
+                "media-title" => status.media_title = value.as_str().map(|s| s.to_string()),
+                "vid" => status.has_video = value.as_i64().is_some(),
+                "metadata/by-key/icy-title" => {
+                    status.icy_title = value
+                        .as_str()
+                        .map(str::trim)
+                        .filter(|t| !t.is_empty())
+                        .map(str::to_string)
+                }
+                _ => {}
+            }
+        }
+        status
+    }
+}
+
+/// Properties fetched for `PlaybackStats`
+const STATS_PROPERTIES: &[&str] = &[
+    "width",
+    "height",
+    "video-codec",
+    "audio-codec-name",
+    "estimated-vf-fps",
+    "container-fps",
+    "video-bitrate",
+    "audio-bitrate",
+    "demuxer-cache-duration",
+    "cache-speed",
+    "frame-drop-count",
+    "decoder-frame-drop-count",
+    "paused-for-cache",
+];
+
+/// Decoder and network statistics for the now-playing stats panel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlaybackStats {
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub fps: Option<f64>,
+    /// Video + audio bitrate of the stream itself, bits/s
+    pub bitrate: Option<f64>,
+    /// Seconds of media buffered ahead of the playhead
+    pub cache_secs: Option<f64>,
+    /// Rate we are actually receiving data at, bytes/s
+    pub input_rate: Option<f64>,
+    /// Frames dropped by the video output and by the decoder
+    pub dropped_frames: u64,
+    /// Times ffmpeg had to reopen the upstream connection (from the mpv log)
+    pub reconnects: usize,
+    pub buffering: bool,
+}
+
+impl PlaybackStats {
+    pub fn from_properties(props: &[(&str, Value)]) -> Self {
+        let mut stats = Self::default();
+        let mut container_fps = None;
+        for (name, value) in props {
+            match *name {
+                "width" => stats.width = value.as_i64(),
+                "height" => stats.height = value.as_i64(),
+                "video-codec" => stats.video_codec = value.as_str().map(short_codec_name),
+                "audio-codec-name" => stats.audio_codec = value.as_str().map(|s| s.to_string()),
+                "estimated-vf-fps" => stats.fps = value.as_f64(),
+                "container-fps" => container_fps = value.as_f64(),
+                "video-bitrate" | "audio-bitrate" => {
+                    if let Some(rate) = value.as_f64() {
+                        stats.bitrate = Some(stats.bitrate.unwrap_or(0.0) + rate);
+                    }
+                }
+                "demuxer-cache-duration" => stats.cache_secs = value.as_f64(),
+                "cache-speed" => stats.input_rate = value.as_f64(),
+                "frame-drop-count" | "decoder-frame-drop-count" => {
+                    stats.dropped_frames += value.as_u64().unwrap_or(0)
+                }
+                "paused-for-cache" => stats.buffering = value.as_bool().unwrap_or(false),
+                _ => {}
+            }
+        }
+        stats.fps = stats.fps.or(container_fps);
+        stats
+    }
+
+    /// Best guess at where a stutter comes from, once there is enough data to tell
+    pub fn diagnosis(&self) -> Option<&'static str> {
+        let starved = self.buffering || self.cache_secs.is_some_and(|c| c < 1.0);
+        if self.reconnects > 0 && starved {
+            return Some("Provider dropping the stream");
+        }
+        if let (Some(input), Some(bitrate)) = (self.input_rate, self.bitrate) {
+            // cache-speed is bytes/s, bitrates are bits/s
+            if starved && input * 8.0 < bitrate {
+                return Some("Download slower than stream: network");
+            }
+        }
+        if starved {
+            return Some("Buffer starved: provider or network");
+        }
+        if self.dropped_frames > 0 {
+            return Some("Frames dropped locally: CPU/GPU");
+        }
+        None
+    }
+}
+
+/// `"h264 (H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10)"` → `"h264"`
+fn short_codec_name(codec: &str) -> String {
+    codec.split_whitespace().next().unwrap_or(codec).to_string()
+}
+
+/// Human-readable bit rate, e.g. `5.2 Mbps`
+pub fn format_bitrate(bits_per_sec: f64) -> String {
+    if bits_per_sec >= 1_000_000.0 {
+        format!("{:.1} Mbps", bits_per_sec / 1_000_000.0)
+    } else {
+        format!("{:.0} kbps", bits_per_sec / 1_000.0)
+    }
+}
+
+/// Count ffmpeg reconnects in the mpv log, reading only what was appended since the last call
+#[derive(Debug, Default)]
+pub struct ReconnectCounter {
+    offset: u64,
+    count: usize,
+}
+
+impl ReconnectCounter {
+    pub fn update(&mut self, log_path: &std::path::Path) -> usize {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let Ok(mut file) = std::fs::File::open(log_path) else {
+            return self.count;
+        };
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if len < self.offset {
+            // Log was truncated by a new mpv session
+            *self = Self::default();
+        }
+        if file.seek(SeekFrom::Start(self.offset)).is_err() {
+            return self.count;
+        }
+        let mut appended = Vec::new();
+        if file.read_to_end(&mut appended).is_ok() {
+            // Only consume complete lines so a message split across reads isn't missed
+            let complete = appended
+                .iter()
+                .rposition(|b| *b == b'\n')
+                .map_or(0, |i| i + 1);
+            let text = String::from_utf8_lossy(&appended[..complete]);
+            self.count += text
+                .lines()
+                .filter(|l| l.contains("Will reconnect at"))
+                .count();
+            self.offset += complete as u64;
+        }
+        self.count
+    }
+
+    /// Start counting from zero at the current end of the log (stream switched in place)
+    pub fn skip_to_end(&mut self, log_path: &std::path::Path) {
+        self.update(log_path);
+        self.count = 0;
+    }
+}
+
+/// The stream currently open in the external player, shown in the now-playing bar
+#[derive(Debug, Clone, Default)]
+pub struct NowPlaying {
+    pub title: String,
+    /// Latest IPC snapshot (`None` until the first poll, or for players without IPC)
+    pub status: Option<PlaybackStatus>,
+    /// Last transport action and the loading_tick it was sent at
+    pub last_action: Option<(String, u64)>,
+    pub stats: Option<PlaybackStats>,
+    /// Recent demuxer cache fill in tenths of a second, oldest first (stats sparkline)
+    pub cache_history: std::collections::VecDeque<u64>,
+}
+
+impl NowPlaying {
+    pub fn new(title: String) -> Self {
+        Self {
+            title,
+            ..Self::default()
+        }
+    }
+
+    /// Keep this many cache samples (one per poll, ~2 minutes)
+    pub const CACHE_HISTORY_LEN: usize = 120;
+
+    pub fn record_stats(&mut self, stats: PlaybackStats) {
+        if self.cache_history.len() >= Self::CACHE_HISTORY_LEN {
+            self.cache_history.pop_front();
+        }
+        let cache_tenths = stats.cache_secs.map_or(0, |c| (c.max(0.0) * 10.0) as u64);
+        self.cache_history.push_back(cache_tenths);
+        self.stats = Some(stats);
+    }
+
+    /// Title to display: ours if known, else whatever mpv reports
+    pub fn display_title(&self) -> &str {
+        if !self.title.is_empty() {
+            return &self.title;
+        }
+        self.status
+            .as_ref()
+            .and_then(|s| s.media_title.as_deref())
+            .unwrap_or("Now Playing")
+    }
+}
+
+/// Format seconds as `m:ss` or `h:mm:ss`
+pub fn format_timestamp(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    let (h, m, s) = (total / 3600, (total % 3600) / 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+/// Sends commands to mpv over its IPC socket. Cheap to clone; every request
+/// opens a short-lived connection so a dead player never wedges the UI.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub struct PlayerController {
+    ipc_path: PathBuf,
+    next_request_id: Arc<AtomicU64>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PlayerController {
+    pub fn new(ipc_path: PathBuf) -> Self {
+        Self {
+            ipc_path,
+            next_request_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+
+    pub fn ipc_path(&self) -> &PathBuf {
+        &self.ipc_path
+    }
+
+    /// Run a raw mpv command and return its `data` field
+    pub async fn command(&self, args: Vec<Value>) -> Result<Value, anyhow::Error> {
+        self.command_batch(vec![args])
+            .await?
+            .pop()
+            .unwrap_or_else(|| Err(anyhow::anyhow!("mpv sent no reply")))
+    }
+
+    /// Run several commands over one connection. The outer error means mpv was
+    /// unreachable; each command still succeeds or fails on its own.
+    pub async fn command_batch(
+        &self,
+        commands: Vec<Vec<Value>>,
+    ) -> Result<Vec<Result<Value, anyhow::Error>>, anyhow::Error> {
+        let mut payload = String::new();
+        let mut request_ids = Vec::with_capacity(commands.len());
+        for args in commands {
+            let request_id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+            payload.push_str(&json!({ "command": args, "request_id": request_id }).to_string());
+            payload.push('\n');
+            request_ids.push(request_id);
+        }
+
+        let exchange = self.exchange(payload, &request_ids);
+        match tokio::time::timeout(std::time::Duration::from_millis(IPC_TIMEOUT_MS), exchange).await
+        {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!("mpv IPC request timed out")),
+        }
+    }
+
+    pub async fn send(&self, cmd: TransportCommand) -> Result<(), anyhow::Error> {
+        self.command(cmd.to_ipc_args()).await.map(|_| ())
+    }
+
+    pub async fn get_property(&self, name: &str) -> Result<Value, anyhow::Error> {
+        self.command(vec![json!("get_property"), json!(name)]).await
+    }
+
+    pub async fn set_property(&self, name: &str, value: Value) -> Result<(), anyhow::Error> {
+        self.command(vec![json!("set_property"), json!(name), value])
+            .await
+            .map(|_| ())
+    }
+
+    /// Fetch several properties at once, skipping the ones mpv can't provide right now
+    pub async fn get_properties<'a>(
+        &self,
+        names: &[&'a str],
+    ) -> Result<Vec<(&'a str, Value)>, anyhow::Error> {
+        let commands = names
+            .iter()
+            .map(|name| vec![json!("get_property"), json!(name)])
+            .collect();
+        let replies = self.command_batch(commands).await?;
+        Ok(names
+            .iter()
+            .zip(replies)
+            .filter_map(|(name, reply)| reply.ok().map(|value| (*name, value)))
+            .collect())
+    }
+
+    /// Query everything the now-playing bar shows. Fails only if mpv is unreachable.
+    pub async fn status(&self) -> Result<PlaybackStatus, anyhow::Error> {
+        let props = self.get_properties(STATUS_PROPERTIES).await?;
+        Ok(PlaybackStatus::from_properties(&props))
+    }
+
+    /// Query the decoder/network statistics for the stats panel
+    pub async fn stats(&self) -> Result<PlaybackStats, anyhow::Error> {
+        let props = self.get_properties(STATS_PROPERTIES).await?;
+        Ok(PlaybackStats::from_properties(&props))
+    }
+
+    #[cfg(unix)]
+    async fn connect(&self) -> std::io::Result<tokio::net::UnixStream> {
+        tokio::net::UnixStream::connect(&self.ipc_path).await
+    }
+
+    #[cfg(windows)]
+    async fn connect(&self) -> std::io::Result<tokio::net::windows::named_pipe::NamedPipeClient> {
+        tokio::net::windows::named_pipe::ClientOptions::new().open(&self.ipc_path)
+    }
+
+    async fn exchange(
+        &self,
+        payload: String,
+        request_ids: &[u64],
+    ) -> Result<Vec<Result<Value, anyhow::Error>>, anyhow::Error> {
+        let stream = self.connect().await?;
+        Self::exchange_over(stream, payload, request_ids).await
+    }
+
+    /// Bind keys inside the mpv window to `script-message <CLIENT_MESSAGE_TARGET> <message>`
+    /// and hand each message back as it arrives. Runs until mpv closes the connection,
+    /// so the bindings keep working across `loadfile` channel switches.
+    pub async fn forward_key_messages<F>(
+        &self,
+        bindings: &[(&str, &str)],
+        mut on_message: F,
+    ) -> Result<(), anyhow::Error>
+    where
+        F: FnMut(&str),
+    {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let mut payload = String::new();
+        for (key, message) in bindings {
+            let command = format!("script-message {} {}", CLIENT_MESSAGE_TARGET, message);
+            payload.push_str(&json!({ "command": ["keybind", key, command] }).to_string());
+            payload.push('\n');
+        }
+
+        let mut reader = BufReader::new(self.connect().await?);
+        reader.get_mut().write_all(payload.as_bytes()).await?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 {
+                return Ok(());
+            }
+            if let Some(message) = parse_client_message(&line) {
+                on_message(&message);
+            }
+        }
+    }
+
+    async fn exchange_over<S>(
+        stream: S,
+        payload: String,
+        request_ids: &[u64],
+    ) -> Result<Vec<Result<Value, anyhow::Error>>, anyhow::Error>
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+    {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let mut reader = BufReader::new(stream);
+        reader.get_mut().write_all(payload.as_bytes()).await?;
+
+        let mut replies: Vec<Option<Result<Value, anyhow::Error>>> =
+            request_ids.iter().map(|_| None).collect();
+        let mut remaining = request_ids.len();
+        let mut line = String::new();
+        while remaining > 0 {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 {
+                return Err(anyhow::anyhow!("mpv closed the IPC connection"));
+            }
+            for (slot, request_id) in replies.iter_mut().zip(request_ids) {
+                if slot.is_none() {
+                    if let Some(result) = parse_reply(&line, *request_id) {
+                        *slot = Some(result);
+                        remaining -= 1;
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(replies.into_iter().flatten().collect())
+    }
+}
+
+/// Extract our `script-message` payload from a `client-message` event line
+pub fn parse_client_message(line: &str) -> Option<String> {
+    let event: Value = serde_json::from_str(line.trim()).ok()?;
+    if event.get("event").and_then(Value::as_str) != Some("client-message") {
+        return None;
+    }
+    let args = event.get("args")?.as_array()?;
+    if args.first()?.as_str()? != CLIENT_MESSAGE_TARGET {
+        return None;
+    }
+    args.get(1)?.as_str().map(|s| s.to_string())
+}
+
+/// Parse one IPC line; `None` if it is an event or a reply to another request
+pub fn parse_reply(line: &str, request_id: u64) -> Option<Result<Value, anyhow::Error>> {
+    let reply: Value = serde_json::from_str(line.trim()).ok()?;
+    if reply.get("request_id").and_then(Value::as_u64) != Some(request_id) {
+        return None;
+    }
+    match reply.get("error").and_then(Value::as_str) {
+        Some("success") | None => Some(Ok(reply.get("data").cloned().unwrap_or(Value::Null))),
+        Some(err) => Some(Err(anyhow::anyhow!("mpv: {}", err))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transport_commands_map_to_mpv_commands() {
+        assert_eq!(
+            TransportCommand::Seek(-10).to_ipc_args(),
+            vec![json!("seek"), json!(-10), json!("relative")]
+        );
+        assert_eq!(
+            TransportCommand::Volume(5).to_ipc_args(),
+            vec![json!("add"), json!("volume"), json!(5)]
+        );
+        assert_eq!(
+            TransportCommand::CycleSubtitleTrack.to_ipc_args(),
+            vec![json!("cycle"), json!("sid")]
+        );
+    }
+
+    #[test]
+    fn replies_are_matched_by_request_id() {
+        let event = r#"{"event":"playback-restart"}"#;
+        assert!(parse_reply(event, 3).is_none());
+        assert!(parse_reply(r#"{"data":true,"error":"success","request_id":2}"#, 3).is_none());
+
+        let ok = parse_reply(r#"{"data":12.5,"error":"success","request_id":3}"#, 3);
+        assert_eq!(ok.unwrap().unwrap(), json!(12.5));
+
+        let err = parse_reply(r#"{"error":"property unavailable","request_id":3}"#, 3);
+        assert!(err.unwrap().is_err());
+    }
+
+    #[test]
+    fn client_messages_are_filtered_by_target() {
+        let ours = r#"{"event":"client-message","args":["matrix-iptv","channel-up"]}"#;
+        assert_eq!(parse_client_message(ours).as_deref(), Some("channel-up"));
+
+        let other_script = r#"{"event":"client-message","args":["uosc","open-menu"]}"#;
+        assert!(parse_client_message(other_script).is_none());
+        assert!(parse_client_message(r#"{"event":"idle"}"#).is_none());
+    }
+
+    #[test]
+    fn status_tolerates_disabled_tracks() {
+        let status = PlaybackStatus::from_properties(&[
+            ("pause", json!(true)),
+            ("time-pos", json!(75.2)),
+            ("aid", json!(1)),
+            ("sid", json!(false)),
+            ("vid", json!(false)),
+            ("metadata/by-key/icy-title", json!("Artist - Song ")),
+        ]);
+        assert!(status.paused);
+        assert_eq!(status.audio_track, Some(1));
+        assert_eq!(status.subtitle_track, None);
+        assert!(!status.has_video);
+        assert_eq!(status.icy_title.as_deref(), Some("Artist - Song"));
+        assert_eq!(status.duration_secs, None);
+        assert_eq!(format_timestamp(75.2), "1:15");
+        assert_eq!(format_timestamp(3725.0), "1:02:05");
+    }
+
+    #[test]
+    fn stats_combine_bitrates_and_drop_counters() {
+        let stats = PlaybackStats::from_properties(&[
+            (
+                "video-codec",
+                json!("h264 (H.264 / AVC / MPEG-4 AVC / MPEG-4 part 10)"),
+            ),
+            ("container-fps", json!(25.0)),
+            ("video-bitrate", json!(4_800_000.0)),
+            ("audio-bitrate", json!(128_000.0)),
+            ("frame-drop-count", json!(3)),
+            ("decoder-frame-drop-count", json!(2)),
+            ("demuxer-cache-duration", json!(8.5)),
+        ]);
+        assert_eq!(stats.video_codec.as_deref(), Some("h264"));
+        assert_eq!(stats.fps, Some(25.0));
+        assert_eq!(stats.bitrate, Some(4_928_000.0));
+        assert_eq!(stats.dropped_frames, 5);
+        assert_eq!(format_bitrate(4_928_000.0), "4.9 Mbps");
+        assert_eq!(stats.diagnosis(), Some("Frames dropped locally: CPU/GPU"));
+    }
+
+    #[test]
+    fn diagnosis_separates_network_from_provider() {
+        let slow_network = PlaybackStats {
+            bitrate: Some(6_000_000.0),
+            input_rate: Some(400_000.0),
+            cache_secs: Some(0.2),
+            ..PlaybackStats::default()
+        };
+        assert_eq!(
+            slow_network.diagnosis(),
+            Some("Download slower than stream: network")
+        );
+
+        let flaky_provider = PlaybackStats {
+            buffering: true,
+            reconnects: 2,
+            ..slow_network
+        };
+        assert_eq!(
+            flaky_provider.diagnosis(),
+            Some("Provider dropping the stream")
+        );
+
+        let healthy = PlaybackStats {
+            cache_secs: Some(12.0),
+            ..PlaybackStats::default()
+        };
+        assert_eq!(healthy.diagnosis(), None);
+    }
+
+    #[test]
+    fn reconnect_counter_reads_appended_log_lines() {
+        use std::io::Write;
+
+        let path =
+            std::env::temp_dir().join(format!("mpv_reconnect_test_{}.log", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(file, "[ffmpeg] http: Will reconnect at 1024 in 0 second(s)").unwrap();
+        writeln!(file, "[cplayer] playing").unwrap();
+
+        let mut counter = ReconnectCounter::default();
+        assert_eq!(counter.update(&path), 1);
+        assert_eq!(counter.update(&path), 1);
+
+        write!(file, "[ffmpeg] http: Will reconnect at 2048").unwrap();
+        assert_eq!(counter.update(&path), 1); // partial line not counted yet
+        writeln!(file, " in 1 second(s)").unwrap();
+        assert_eq!(counter.update(&path), 2);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn controller_round_trips_over_unix_socket() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let path = std::env::temp_dir().join(format!("mpv_ipc_test_{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+
+        // Fake mpv: emit an unrelated event first, then answer the request
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            reader.read_line(&mut line).await.unwrap();
+            let req: Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(req["command"], json!(["cycle", "pause"]));
+            let id = req["request_id"].as_u64().unwrap();
+            let reply = format!(
+                "{{\"event\":\"pause\"}}\n{{\"data\":null,\"error\":\"success\",\"request_id\":{}}}\n",
+                id
+            );
+            reader.get_mut().write_all(reply.as_bytes()).await.unwrap();
+        });
+
+        let controller = PlayerController::new(path.clone());
+        controller
+            .send(TransportCommand::TogglePause)
+            .await
+            .unwrap();
+        server.await.unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+}
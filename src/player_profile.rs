@@ -0,0 +1,221 @@
+//! User-defined external players (IINA, ffplay, mplayer, scripts, ...).
+//!
+//! A profile is an executable plus an argument template. Arguments are split on
+//! whitespace first and placeholders substituted afterwards, so a URL or title
+//! containing spaces always stays a single argument.
+
+use serde::{Deserialize, Serialize};
+
+/// How the app can talk to a running profile player
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum IpcFlavour {
+    /// No remote control: stats, zapping and multiview audio are unavailable
+    #[default]
+    None,
+    /// mpv JSON IPC (mpv wrappers such as IINA or custom mpv scripts)
+    Mpv,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PlayerProfile {
+    pub name: String,
+    pub executable: String,
+    /// e.g. `--title {title} --user-agent {user_agent} {url}`
+    pub args: String,
+    #[serde(default)]
+    pub ipc: IpcFlavour,
+}
+
+/// Values substituted into a profile's argument template
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TemplateVars<'a> {
+    pub url: &'a str,
+    pub title: &'a str,
+    pub user_agent: &'a str,
+    pub referrer: &'a str,
+    /// IPC socket/pipe path (empty without IPC)
+    pub ipc: &'a str,
+}
+
+impl PlayerProfile {
+    /// Parse `name | executable | arguments` as typed into Settings. Arguments
+    /// default to `{url}`; using `{ipc}` in them turns on mpv IPC.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut parts = input.splitn(3, '|').map(str::trim);
+        let name = parts.next().unwrap_or_default();
+        let executable = parts.next().unwrap_or_default();
+        let args = parts.next().filter(|a| !a.is_empty()).unwrap_or("{url}");
+        if name.is_empty() || executable.is_empty() {
+            return Err("Enter name | executable | arguments".to_string());
+        }
+        Ok(Self {
+            name: name.to_string(),
+            executable: executable.to_string(),
+            args: args.to_string(),
+            ipc: if args.contains("{ipc}") {
+                IpcFlavour::Mpv
+            } else {
+                IpcFlavour::None
+            },
+        })
+    }
+
+    /// Command-line arguments for one launch. With mpv IPC and no `{ipc}` in the
+    /// template, `--input-ipc-server={ipc}` is appended.
+    pub fn expand_args(&self, vars: &TemplateVars) -> Vec<String> {
+        let mut template: Vec<&str> = self.args.split_whitespace().collect();
+        if self.ipc == IpcFlavour::Mpv && !self.args.contains("{ipc}") {
+            template.push("--input-ipc-server={ipc}");
+        }
+        if !self.args.contains("{url}") {
+            template.push("{url}");
+        }
+        template
+            .into_iter()
+            .map(|arg| {
+                arg.replace("{url}", vars.url)
+                    .replace("{title}", vars.title)
+                    .replace("{user_agent}", vars.user_agent)
+                    .replace("{referrer}", vars.referrer)
+                    .replace("{ipc}", vars.ipc)
+            })
+            .collect()
+    }
+}
+
+/// A well-known player the setup code can look for
+pub struct KnownPlayer {
+    pub name: &'static str,
+    /// Executable names looked up on PATH
+    pub commands: &'static [&'static str],
+    /// Absolute paths checked when it isn't on PATH
+    pub candidate_paths: &'static [&'static str],
+    pub args: &'static str,
+    pub ipc: IpcFlavour,
+}
+
+impl KnownPlayer {
+    pub fn profile(&self, executable: String) -> PlayerProfile {
+        PlayerProfile {
+            name: self.name.to_string(),
+            executable,
+            args: self.args.to_string(),
+            ipc: self.ipc,
+        }
+    }
+}
+
+pub const KNOWN_PLAYERS: &[KnownPlayer] = &[
+    KnownPlayer {
+        name: "IINA",
+        commands: &["iina-cli", "iina"],
+        candidate_paths: &[
+            "/Applications/IINA.app/Contents/MacOS/iina-cli",
+            "/opt/homebrew/bin/iina",
+            "/usr/local/bin/iina",
+        ],
+        args: "--no-stdin {url} --mpv-force-media-title={title} --mpv-user-agent={user_agent} --mpv-referrer={referrer} --mpv-input-ipc-server={ipc}",
+        ipc: IpcFlavour::Mpv,
+    },
+    KnownPlayer {
+        name: "Celluloid",
+        commands: &["celluloid"],
+        candidate_paths: &["/usr/bin/celluloid", "/app/bin/celluloid"],
+        args: "--new-window {url}",
+        ipc: IpcFlavour::None,
+    },
+    KnownPlayer {
+        name: "ffplay",
+        commands: &["ffplay"],
+        candidate_paths: &[
+            "/opt/homebrew/bin/ffplay",
+            "/usr/local/bin/ffplay",
+            "/usr/bin/ffplay",
+        ],
+        args: "-window_title {title} -user_agent {user_agent} -referer {referrer} {url}",
+        ipc: IpcFlavour::None,
+    },
+    KnownPlayer {
+        name: "MPlayer",
+        commands: &["mplayer"],
+        candidate_paths: &[
+            "/opt/homebrew/bin/mplayer",
+            "/usr/local/bin/mplayer",
+            "/usr/bin/mplayer",
+        ],
+        args: "-title {title} -user-agent {user_agent} -referrer {referrer} {url}",
+        ipc: IpcFlavour::None,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> TemplateVars<'static> {
+        TemplateVars {
+            url: "http://host/live/u/p/1.ts",
+            title: "BBC One HD",
+            user_agent: "UA/1.0",
+            referrer: "http://host/",
+            ipc: "/tmp/ipc",
+        }
+    }
+
+    #[test]
+    fn placeholders_expand_without_splitting_values() {
+        let profile = PlayerProfile {
+            name: "ffplay".into(),
+            executable: "ffplay".into(),
+            args: "-window_title {title} -user_agent {user_agent} -referer={referrer} {url}".into(),
+            ipc: IpcFlavour::None,
+        };
+        assert_eq!(
+            profile.expand_args(&vars()),
+            vec![
+                "-window_title",
+                "BBC One HD",
+                "-user_agent",
+                "UA/1.0",
+                "-referer=http://host/",
+                "http://host/live/u/p/1.ts",
+            ]
+        );
+    }
+
+    #[test]
+    fn url_and_mpv_ipc_are_added_when_missing() {
+        let profile = PlayerProfile {
+            name: "script".into(),
+            executable: "/usr/local/bin/watch".into(),
+            args: "--fs".into(),
+            ipc: IpcFlavour::Mpv,
+        };
+        assert_eq!(
+            profile.expand_args(&vars()),
+            vec![
+                "--fs",
+                "--input-ipc-server=/tmp/ipc",
+                "http://host/live/u/p/1.ts"
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_profile_input() {
+        let profile =
+            PlayerProfile::parse(" Watch | /usr/local/bin/watch | --fs {url} --ipc={ipc} ")
+                .unwrap();
+        assert_eq!(profile.name, "Watch");
+        assert_eq!(profile.executable, "/usr/local/bin/watch");
+        assert_eq!(profile.args, "--fs {url} --ipc={ipc}");
+        assert_eq!(profile.ipc, IpcFlavour::Mpv);
+
+        let profile = PlayerProfile::parse("ffplay|ffplay").unwrap();
+        assert_eq!(profile.args, "{url}");
+        assert_eq!(profile.ipc, IpcFlavour::None);
+
+        assert!(PlayerProfile::parse("just a name").is_err());
+        assert!(PlayerProfile::parse(" | ffplay").is_err());
+    }
+}
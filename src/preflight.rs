@@ -0,0 +1,270 @@
+//! Pre-flight stream validation.
+//!
+//! Before a player is spawned, a small ranged GET through the account's client
+//! follows redirects and sniffs the first bytes, so dead channels, expired
+//! subscriptions and block pages are reported immediately instead of seconds
+//! later from the player log.
+
+use crate::player::{PlaybackError, PlaybackErrorType};
+use std::time::Duration;
+
+/// Bytes requested from the stream; enough for two TS packets or a playlist header
+const PROBE_BYTES: usize = 4096;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// What the stream host actually served
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamKind {
+    TransportStream,
+    Hls,
+    Mp4,
+    /// Binary data we can't identify; the player may still cope
+    Unknown,
+}
+
+/// A stream that passed the check
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preflight {
+    /// URL after redirects
+    pub final_url: String,
+    pub kind: StreamKind,
+}
+
+/// Probe `url` and classify it. `referrer` is sent as the player would send it.
+pub async fn check(
+    client: &reqwest::Client,
+    url: &str,
+    referrer: Option<&str>,
+) -> Result<Preflight, PlaybackError> {
+    let mut request = client
+        .get(url)
+        .header(
+            reqwest::header::RANGE,
+            format!("bytes=0-{}", PROBE_BYTES - 1),
+        )
+        .timeout(PROBE_TIMEOUT);
+    if let Some(referrer) = referrer {
+        request = request.header(reqwest::header::REFERER, referrer);
+    }
+
+    let mut resp = request.send().await.map_err(|e| {
+        if e.is_timeout() {
+            PlaybackError::new(
+                PlaybackErrorType::NetworkTimeout,
+                "The stream server did not answer in time.".to_string(),
+            )
+        } else {
+            PlaybackError::new(
+                PlaybackErrorType::StreamUnreachable,
+                format!("Could not reach the stream server: {}", e),
+            )
+        }
+    })?;
+
+    let final_url = resp.url().to_string();
+    let status = resp.status();
+    let content_type = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_lowercase();
+
+    // Servers that ignore Range send the whole stream; stop after the first bytes
+    let mut head = Vec::new();
+    while head.len() < PROBE_BYTES {
+        match tokio::time::timeout(PROBE_TIMEOUT, resp.chunk()).await {
+            Ok(Ok(Some(chunk))) => head.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+
+    if let Some(error) = classify_status(status.as_u16()) {
+        return Err(error);
+    }
+    let redirect = (final_url != url).then_some(final_url.as_str());
+    let kind = classify_body(&content_type, &head, redirect)?;
+    Ok(Preflight { final_url, kind })
+}
+
+/// Map an HTTP error status to a playback error (None for success)
+pub fn classify_status(status: u16) -> Option<PlaybackError> {
+    let (kind, message) = match status {
+        200..=399 => return None,
+        401 | 402 => (
+            PlaybackErrorType::AuthExpired,
+            "The provider rejected your credentials for this stream.",
+        ),
+        403 | 451 => (
+            PlaybackErrorType::ProviderBlocked,
+            "The provider refused this stream (403 Forbidden).",
+        ),
+        404 | 410 => (
+            PlaybackErrorType::StreamUnreachable,
+            "This channel is not available on the server (404). It may be offline.",
+        ),
+        429 => (
+            PlaybackErrorType::ProviderBlocked,
+            "Too many connections. Close other streams on this account and try again.",
+        ),
+        500..=599 => (
+            PlaybackErrorType::StreamUnreachable,
+            "The stream server reported an internal error. The channel may be down.",
+        ),
+        _ => (
+            PlaybackErrorType::Unknown,
+            "The stream server returned an unexpected response.",
+        ),
+    };
+    Some(PlaybackError::new(
+        kind,
+        format!("{} (HTTP {})", message, status),
+    ))
+}
+
+/// Identify the stream from its first bytes, or explain why it isn't one.
+/// `redirect` is the final URL when the request was redirected.
+pub fn classify_body(
+    content_type: &str,
+    head: &[u8],
+    redirect: Option<&str>,
+) -> Result<StreamKind, PlaybackError> {
+    let redirect = redirect.unwrap_or("").to_lowercase();
+    if ["expired", "suspended", "disabled"]
+        .iter()
+        .any(|w| redirect.contains(w))
+    {
+        return Err(PlaybackError::new(
+            PlaybackErrorType::AuthExpired,
+            "The provider redirected to a \"subscription expired\" notice instead of the channel."
+                .to_string(),
+        ));
+    }
+
+    if head.is_empty() {
+        return Err(PlaybackError::new(
+            PlaybackErrorType::StreamUnreachable,
+            "The stream server sent no data. The channel may be offline.".to_string(),
+        ));
+    }
+
+    if head[0] == 0x47 && head.get(188).is_none_or(|&b| b == 0x47) {
+        return Ok(StreamKind::TransportStream);
+    }
+    let text = String::from_utf8_lossy(&head[..head.len().min(1024)]);
+    let trimmed = text.trim_start_matches('\u{feff}').trim_start();
+    if trimmed.starts_with("#EXTM3U") {
+        return Ok(StreamKind::Hls);
+    }
+    if head.len() >= 8 && &head[4..8] == b"ftyp" {
+        return Ok(StreamKind::Mp4);
+    }
+
+    let looks_textual = content_type.starts_with("text/")
+        || content_type.contains("json")
+        || trimmed.starts_with('<')
+        || trimmed.starts_with('{');
+    if !looks_textual {
+        return Ok(StreamKind::Unknown);
+    }
+
+    let lower = trimmed.to_lowercase();
+    let (kind, message) = if ["expired", "subscription", "suspended", "inactive"]
+        .iter()
+        .any(|w| lower.contains(w))
+    {
+        (
+            PlaybackErrorType::AuthExpired,
+            "The provider answered with a subscription notice instead of video.",
+        )
+    } else if ["blocked", "banned", "forbidden", "not allowed", "vpn"]
+        .iter()
+        .any(|w| lower.contains(w))
+    {
+        (
+            PlaybackErrorType::ProviderBlocked,
+            "The provider answered with a block page instead of video.",
+        )
+    } else {
+        (
+            PlaybackErrorType::InvalidFormat,
+            "The provider answered with a web page instead of video.",
+        )
+    };
+    Err(PlaybackError::new(kind, message.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts_packets() -> Vec<u8> {
+        let mut data = vec![0u8; 376];
+        data[0] = 0x47;
+        data[188] = 0x47;
+        data
+    }
+
+    #[test]
+    fn recognises_stream_formats() {
+        assert_eq!(
+            classify_body("video/mp2t", &ts_packets(), None).unwrap(),
+            StreamKind::TransportStream
+        );
+        assert_eq!(
+            classify_body(
+                "application/vnd.apple.mpegurl",
+                b"\xef\xbb\xbf#EXTM3U\n#EXT-X-VERSION:3",
+                None
+            )
+            .unwrap(),
+            StreamKind::Hls
+        );
+        assert_eq!(
+            classify_body("video/mp4", b"\0\0\0\x20ftypisom", None).unwrap(),
+            StreamKind::Mp4
+        );
+    }
+
+    #[test]
+    fn classifies_provider_error_pages() {
+        let expired = classify_body(
+            "text/html",
+            b"<html><body>Your subscription has expired</body></html>",
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(expired.error_type, PlaybackErrorType::AuthExpired));
+
+        let blocked = classify_body("text/html", b"<h1>Access blocked</h1>", None).unwrap_err();
+        assert!(matches!(
+            blocked.error_type,
+            PlaybackErrorType::ProviderBlocked
+        ));
+
+        let redirected = classify_body(
+            "video/mp4",
+            b"\0\0\0\x20ftypisom",
+            Some("http://cdn/expired.mp4"),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            redirected.error_type,
+            PlaybackErrorType::AuthExpired
+        ));
+    }
+
+    #[test]
+    fn classifies_http_statuses() {
+        assert!(classify_status(206).is_none());
+        assert!(matches!(
+            classify_status(401).unwrap().error_type,
+            PlaybackErrorType::AuthExpired
+        ));
+        assert!(matches!(
+            classify_status(404).unwrap().error_type,
+            PlaybackErrorType::StreamUnreachable
+        ));
+    }
+}
@@ -133,6 +133,25 @@ pub fn preprocess_categories(
     });
 }
 
+/// Whether a stream named `name` passes the playlist filters for `modes`
+/// (the same rules `preprocess_streams` filters with)
+pub fn matches_modes(name: &str, is_live: bool, modes: &[crate::config::ProcessingMode]) -> bool {
+    use crate::config::ProcessingMode;
+    let use_merica = modes.contains(&ProcessingMode::Merica);
+    let use_sports = modes.contains(&ProcessingMode::Sports);
+    let use_all_english = modes.contains(&ProcessingMode::AllEnglish);
+
+    if use_sports && !crate::parser::is_sports_content(name) {
+        return false;
+    }
+    if is_live {
+        (!use_merica || crate::parser::is_american_live(name))
+            && (!use_all_english || crate::parser::is_english_live(name))
+    } else {
+        !(use_merica || use_all_english) || crate::parser::is_english_vod(name)
+    }
+}
+
 pub fn preprocess_streams(
     streams: &mut Vec<Stream>,
     favorites: &HashSet<String>,
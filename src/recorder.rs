@@ -0,0 +1,639 @@
+//! Live recording (DVR).
+//!
+//! A recording pulls a channel over the account's own `reqwest::Client` (so the
+//! provider sees the same User-Agent and DoH resolution as the catalog requests)
+//! and appends it to a `.ts` file. Plain TS streams are copied byte for byte; HLS
+//! playlists are polled and each new segment is appended in order. When the
+//! upstream drops, the task reconnects and keeps appending to the same file.
+
+use chrono::{DateTime, Local};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Notify;
+
+/// File extension recordings are written with
+pub const RECORDING_EXTENSION: &str = "ts";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordingStatus {
+    Recording,
+    /// Upstream dropped; waiting before reconnect attempt `n`
+    Reconnecting(u32),
+    Finished,
+    Failed(String),
+}
+
+impl RecordingStatus {
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            RecordingStatus::Recording | RecordingStatus::Reconnecting(_)
+        )
+    }
+}
+
+/// How hard a recording tries to get the stream back after a drop
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Consecutive failed reconnects before the recording gives up
+    pub max_attempts: u32,
+    pub delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 10,
+            delay: Duration::from_secs(3),
+        }
+    }
+}
+
+/// A running (or finished) recording. Cheap to clone; all clones share state.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub stream_id: String,
+    pub title: String,
+    pub path: PathBuf,
+    pub started_at: DateTime<Local>,
+    bytes: Arc<AtomicU64>,
+    status: Arc<Mutex<RecordingStatus>>,
+    stop_requested: Arc<AtomicBool>,
+    stop_notify: Arc<Notify>,
+}
+
+impl Recording {
+    /// Start recording `url` into `path` on the current tokio runtime
+    pub fn start(
+        client: reqwest::Client,
+        url: String,
+        path: PathBuf,
+        stream_id: String,
+        title: String,
+    ) -> Self {
+        Self::start_with_policy(client, url, path, stream_id, title, RetryPolicy::default())
+    }
+
+    pub fn start_with_policy(
+        client: reqwest::Client,
+        url: String,
+        path: PathBuf,
+        stream_id: String,
+        title: String,
+        policy: RetryPolicy,
+    ) -> Self {
+        let recording = Self {
+            stream_id,
+            title,
+            path,
+            started_at: Local::now(),
+            bytes: Arc::new(AtomicU64::new(0)),
+            status: Arc::new(Mutex::new(RecordingStatus::Recording)),
+            stop_requested: Arc::new(AtomicBool::new(false)),
+            stop_notify: Arc::new(Notify::new()),
+        };
+
+        let task = recording.clone();
+        tokio::spawn(async move {
+            let final_status = match task.run(&client, &url, policy).await {
+                Ok(()) => RecordingStatus::Finished,
+                Err(e) => RecordingStatus::Failed(e.to_string()),
+            };
+            task.set_status(final_status);
+        });
+
+        recording
+    }
+
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn status(&self) -> RecordingStatus {
+        self.status
+            .lock()
+            .map(|s| s.clone())
+            .unwrap_or(RecordingStatus::Finished)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status().is_active()
+    }
+
+    /// Ask the recording to finish; the file is closed once the current write completes
+    pub fn stop(&self) {
+        self.stop_requested.store(true, Ordering::SeqCst);
+        self.stop_notify.notify_waiters();
+    }
+
+    fn stopped(&self) -> bool {
+        self.stop_requested.load(Ordering::SeqCst)
+    }
+
+    fn set_status(&self, status: RecordingStatus) {
+        if let Ok(mut s) = self.status.lock() {
+            *s = status;
+        }
+    }
+
+    async fn run(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        policy: RetryPolicy,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+
+        // Last HLS segment written, so a reconnect doesn't append segments twice
+        let mut last_segment: Option<SegmentMark> = None;
+        let mut failures = 0u32;
+
+        loop {
+            let before = self.bytes_written();
+            let result = self
+                .record_once(client, url, &mut file, &mut last_segment)
+                .await;
+            file.flush().await?;
+
+            if self.stopped() {
+                return Ok(());
+            }
+            if let Ok(true) = result {
+                // HLS playlist reached #EXT-X-ENDLIST: the event is over
+                return Ok(());
+            }
+
+            // Any progress means the stream came back; only count consecutive failures
+            if self.bytes_written() > before {
+                failures = 0;
+            }
+            failures += 1;
+            if failures > policy.max_attempts {
+                return Err(match result {
+                    Err(e) => e.context("Stream dropped and did not come back"),
+                    Ok(_) => anyhow::anyhow!("Stream keeps ending; gave up reconnecting"),
+                });
+            }
+
+            self.set_status(RecordingStatus::Reconnecting(failures));
+            tokio::select! {
+                _ = tokio::time::sleep(policy.delay) => {}
+                _ = self.stop_notify.notified() => {}
+            }
+            if self.stopped() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// One connection's worth of recording. `Ok(true)` means the stream ended for good.
+    async fn record_once(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        file: &mut tokio::fs::File,
+        last_segment: &mut Option<SegmentMark>,
+    ) -> Result<bool, anyhow::Error> {
+        let mut resp = client.get(url).send().await?.error_for_status()?;
+        let is_hls = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.to_ascii_lowercase().contains("mpegurl"))
+            || resp.url().path().ends_with(".m3u8");
+
+        if is_hls {
+            let base = resp.url().clone();
+            let body = resp.text().await?;
+            return self
+                .record_hls(client, base, body, file, last_segment)
+                .await;
+        }
+
+        self.set_status(RecordingStatus::Recording);
+        loop {
+            let chunk = tokio::select! {
+                chunk = resp.chunk() => chunk?,
+                _ = self.stop_notify.notified() => return Ok(false),
+            };
+            match chunk {
+                Some(bytes) => self.write(file, &bytes).await?,
+                None => return Ok(false), // Live TS should never end; treat as a drop
+            }
+            if self.stopped() {
+                return Ok(false);
+            }
+        }
+    }
+
+    async fn record_hls(
+        &self,
+        client: &reqwest::Client,
+        mut playlist_url: reqwest::Url,
+        mut body: String,
+        file: &mut tokio::fs::File,
+        last_segment: &mut Option<SegmentMark>,
+    ) -> Result<bool, anyhow::Error> {
+        loop {
+            let playlist = match parse_hls_playlist(&body, &playlist_url) {
+                HlsPlaylist::Master(variants) => {
+                    // Record the best quality the provider offers
+                    let (_, url) = variants
+                        .into_iter()
+                        .max_by_key(|(bandwidth, _)| *bandwidth)
+                        .ok_or_else(|| anyhow::anyhow!("HLS master playlist has no variants"))?;
+                    playlist_url = url;
+                    body = fetch_text(client, &playlist_url).await?;
+                    continue;
+                }
+                HlsPlaylist::Media(media) => media,
+            };
+
+            self.set_status(RecordingStatus::Recording);
+            let start = first_new_segment(
+                *last_segment,
+                playlist.discontinuity_sequence,
+                playlist.media_sequence,
+                playlist.segments.len(),
+            );
+            for (offset, segment_url) in playlist.segments.iter().enumerate().skip(start) {
+                let sequence = playlist.media_sequence + offset as u64;
+                let mut resp = client
+                    .get(segment_url.clone())
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                while let Some(bytes) = resp.chunk().await? {
+                    self.write(file, &bytes).await?;
+                }
+                *last_segment = Some(SegmentMark {
+                    sequence,
+                    discontinuity: playlist.discontinuity_sequence,
+                });
+                if self.stopped() {
+                    return Ok(false);
+                }
+            }
+
+            if playlist.ended {
+                return Ok(true);
+            }
+
+            // Refresh at half the target duration, as the HLS spec suggests
+            let wait = Duration::from_secs_f64((playlist.target_duration / 2.0).max(1.0));
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = self.stop_notify.notified() => return Ok(false),
+            }
+            body = fetch_text(client, &playlist_url).await?;
+        }
+    }
+
+    async fn write(&self, file: &mut tokio::fs::File, bytes: &[u8]) -> Result<(), anyhow::Error> {
+        file.write_all(bytes).await?;
+        self.bytes.fetch_add(bytes.len() as u64, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// A playlist whose newest segment is this many sequence numbers behind the
+/// last one written has restarted its numbering; closer is a lagging CDN edge
+const SEQUENCE_RESET_GAP: u64 = 20;
+
+/// The last HLS segment written: its media sequence and the playlist's
+/// discontinuity sequence at the time
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct SegmentMark {
+    sequence: u64,
+    discontinuity: u64,
+}
+
+/// Index of the first segment not yet written, for a media playlist of `count`
+/// segments starting at sequence `first` (discontinuity sequence `discontinuity`)
+/// when `last` was written most recently. A playlist behind `last` is usually a
+/// lagging CDN edge whose segments are already on disk; only a much lower
+/// sequence or a newer discontinuity sequence means the provider restarted its
+/// numbering (usually after a reconnect), so all of it is new.
+fn first_new_segment(
+    last: Option<SegmentMark>,
+    discontinuity: u64,
+    first: u64,
+    count: usize,
+) -> usize {
+    let Some(last) = last else {
+        return 0;
+    };
+    if count == 0 {
+        return 0;
+    }
+    let newest = first + count as u64 - 1;
+    let restarted = newest <= last.sequence
+        && (discontinuity > last.discontinuity || newest + SEQUENCE_RESET_GAP < last.sequence);
+    if restarted {
+        return 0;
+    }
+    (last.sequence + 1).saturating_sub(first).min(count as u64) as usize
+}
+
+async fn fetch_text(client: &reqwest::Client, url: &reqwest::Url) -> Result<String, anyhow::Error> {
+    Ok(client
+        .get(url.clone())
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum HlsPlaylist {
+    /// Variant streams as `(bandwidth, url)`
+    Master(Vec<(u64, reqwest::Url)>),
+    Media(HlsMediaPlaylist),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct HlsMediaPlaylist {
+    pub media_sequence: u64,
+    pub discontinuity_sequence: u64,
+    pub target_duration: f64,
+    pub segments: Vec<reqwest::Url>,
+    pub ended: bool,
+}
+
+/// Parse an HLS playlist, resolving relative URIs against `base`
+pub fn parse_hls_playlist(body: &str, base: &reqwest::Url) -> HlsPlaylist {
+    let mut variants = Vec::new();
+    let mut pending_bandwidth: Option<u64> = None;
+    let mut media = HlsMediaPlaylist {
+        media_sequence: 0,
+        discontinuity_sequence: 0,
+        target_duration: 6.0,
+        segments: Vec::new(),
+        ended: false,
+    };
+
+    for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+            pending_bandwidth = Some(
+                attrs
+                    .split(',')
+                    .find_map(|a| a.strip_prefix("BANDWIDTH="))
+                    .and_then(|b| b.parse().ok())
+                    .unwrap_or(0),
+            );
+        } else if let Some(seq) = line.strip_prefix("#EXT-X-MEDIA-SEQUENCE:") {
+            media.media_sequence = seq.trim().parse().unwrap_or(0);
+        } else if let Some(seq) = line.strip_prefix("#EXT-X-DISCONTINUITY-SEQUENCE:") {
+            media.discontinuity_sequence = seq.trim().parse().unwrap_or(0);
+        } else if let Some(duration) = line.strip_prefix("#EXT-X-TARGETDURATION:") {
+            media.target_duration = duration.trim().parse().unwrap_or(6.0);
+        } else if line.starts_with("#EXT-X-ENDLIST") {
+            media.ended = true;
+        } else if !line.starts_with('#') {
+            let Ok(url) = base.join(line) else {
+                continue;
+            };
+            match pending_bandwidth.take() {
+                Some(bandwidth) => variants.push((bandwidth, url)),
+                None => media.segments.push(url),
+            }
+        }
+    }
+
+    if variants.is_empty() {
+        HlsPlaylist::Media(media)
+    } else {
+        HlsPlaylist::Master(variants)
+    }
+}
+
+/// `Channel - Programme - 2024-05-01 2000.ts`, with characters that are invalid
+/// in file names (on any platform) replaced
+pub fn recording_file_name(channel: &str, programme: Option<&str>, at: DateTime<Local>) -> String {
+    let mut parts = vec![sanitize_file_component(channel)];
+    if let Some(programme) = programme.map(sanitize_file_component) {
+        if !programme.is_empty() {
+            parts.push(programme);
+        }
+    }
+    parts.push(at.format("%Y-%m-%d %H%M").to_string());
+    format!("{}.{}", parts.join(" - "), RECORDING_EXTENSION)
+}
+
+fn sanitize_file_component(s: &str) -> String {
+    let cleaned: String = s
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    cleaned.trim().trim_matches('.').trim().to_string()
+}
+
+/// A finished recording on disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordingFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<std::time::SystemTime>,
+}
+
+/// Recordings in `dir`, newest first
+pub fn list_recordings(dir: &Path) -> Vec<RecordingFile> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<RecordingFile> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(RECORDING_EXTENSION))
+        })
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            Some(RecordingFile {
+                path: e.path(),
+                size: meta.len(),
+                modified: meta.modified().ok(),
+            })
+        })
+        .collect();
+    files.sort_by_key(|f| std::cmp::Reverse(f.modified));
+    files
+}
+
+/// Human-readable size, e.g. `1.4 GB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn mark(sequence: u64) -> Option<SegmentMark> {
+        Some(SegmentMark {
+            sequence,
+            discontinuity: 0,
+        })
+    }
+
+    #[test]
+    fn segments_resume_after_the_last_written_one() {
+        assert_eq!(first_new_segment(None, 0, 120, 3), 0);
+        assert_eq!(first_new_segment(mark(120), 0, 120, 3), 1);
+        assert_eq!(first_new_segment(mark(122), 0, 120, 3), 3);
+        assert_eq!(first_new_segment(mark(100), 0, 120, 3), 0);
+        // The provider restarted its sequence numbers after a reconnect
+        assert_eq!(first_new_segment(mark(500), 0, 0, 3), 0);
+        assert_eq!(first_new_segment(mark(30), 1, 0, 3), 0);
+    }
+
+    #[test]
+    fn lagging_playlist_is_not_written_again() {
+        // A CDN edge a few segments behind the last one written
+        assert_eq!(first_new_segment(mark(125), 0, 118, 5), 5);
+        assert_eq!(first_new_segment(mark(125), 0, 121, 5), 5);
+        // ...including one still on an older discontinuity sequence
+        let last = Some(SegmentMark {
+            sequence: 125,
+            discontinuity: 2,
+        });
+        assert_eq!(first_new_segment(last, 1, 119, 5), 5);
+        // Once it catches up only the new segments are written
+        assert_eq!(first_new_segment(mark(125), 0, 123, 5), 3);
+    }
+
+    #[test]
+    fn media_playlist_resolves_segments_and_sequence() {
+        let base = reqwest::Url::parse("http://host/live/ch1/index.m3u8").unwrap();
+        let body = "#EXTM3U\n#EXT-X-TARGETDURATION:4\n#EXT-X-MEDIA-SEQUENCE:120\n\
+                    #EXT-X-DISCONTINUITY-SEQUENCE:3\n\
+                    #EXTINF:4.0,\nseg120.ts\n#EXTINF:4.0,\nhttp://cdn/seg121.ts\n";
+        let HlsPlaylist::Media(media) = parse_hls_playlist(body, &base) else {
+            panic!("expected media playlist");
+        };
+        assert_eq!(media.media_sequence, 120);
+        assert_eq!(media.discontinuity_sequence, 3);
+        assert_eq!(media.target_duration, 4.0);
+        assert!(!media.ended);
+        assert_eq!(media.segments[0].as_str(), "http://host/live/ch1/seg120.ts");
+        assert_eq!(media.segments[1].as_str(), "http://cdn/seg121.ts");
+    }
+
+    #[test]
+    fn master_playlist_lists_variants_with_bandwidth() {
+        let base = reqwest::Url::parse("http://host/master.m3u8").unwrap();
+        let body = "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=800000,RESOLUTION=640x360\nlow.m3u8\n\
+                    #EXT-X-STREAM-INF:BANDWIDTH=5000000,RESOLUTION=1920x1080\nhigh.m3u8\n";
+        let HlsPlaylist::Master(variants) = parse_hls_playlist(body, &base) else {
+            panic!("expected master playlist");
+        };
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[1].0, 5_000_000);
+        assert_eq!(variants[1].1.as_str(), "http://host/high.m3u8");
+    }
+
+    #[test]
+    fn file_name_uses_programme_and_strips_invalid_chars() {
+        let at = Local.with_ymd_and_hms(2024, 5, 1, 20, 0, 0).unwrap();
+        assert_eq!(
+            recording_file_name("US: ESPN HD", Some("NBA: Lakers / Celtics"), at),
+            "US_ ESPN HD - NBA_ Lakers _ Celtics - 2024-05-01 2000.ts"
+        );
+        assert_eq!(
+            recording_file_name("BBC One", None, at),
+            "BBC One - 2024-05-01 2000.ts"
+        );
+    }
+
+    #[test]
+    fn sizes_are_human_readable() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    /// Serves `body` once per connection with no Content-Length, then hangs up,
+    /// like a live TS stream dropping
+    async fn dropping_ts_server(body: &'static [u8]) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = socket.read(&mut buf).await;
+                let _ = socket
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\nContent-Type: video/mp2t\r\nConnection: close\r\n\r\n",
+                    )
+                    .await;
+                let _ = socket.write_all(body).await;
+                let _ = socket.shutdown().await;
+            }
+        });
+        format!("http://{}/live/user/pass/1.ts", addr)
+    }
+
+    #[tokio::test]
+    async fn recording_resumes_into_the_same_file_after_a_drop() {
+        let url = dropping_ts_server(b"chunk").await;
+        let path =
+            std::env::temp_dir().join(format!("matrix-iptv-rec-test-{}.ts", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let recording = Recording::start_with_policy(
+            reqwest::Client::new(),
+            url,
+            path.clone(),
+            "1".to_string(),
+            "Test".to_string(),
+            RetryPolicy {
+                max_attempts: 5,
+                delay: Duration::from_millis(20),
+            },
+        );
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while recording.bytes_written() < 15 && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        recording.stop();
+        while recording.is_active() && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(recording.status(), RecordingStatus::Finished);
+        let written = std::fs::read(&path).unwrap();
+        assert!(written.len() >= 15, "three connections appended");
+        assert!(written.chunks(5).all(|c| c == b"chunk"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,337 @@
+//! Local restreaming relay.
+//!
+//! Serves `http://<bind>:<port>/live/{stream_id}` to devices that can't cope with
+//! the provider directly. The relay fetches upstream with the account's
+//! `reqwest::Client` (User-Agent, DoH, TLS leniency and any system proxy) plus
+//! the same referrer the player sends, and fans the bytes out to every local
+//! viewer of that channel over one shared upstream connection.
+
+use crate::api::IptvClient;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, Notify};
+
+/// Chunks buffered per channel before a slow viewer starts skipping
+const CHANNEL_BUFFER: usize = 256;
+
+/// Upstream reconnects before viewers of a channel are disconnected
+const MAX_UPSTREAM_RETRIES: u32 = 3;
+
+type Chunk = Arc<[u8]>;
+
+#[derive(Default)]
+struct RelayState {
+    client: Mutex<Option<IptvClient>>,
+    /// stream_id -> fan-out of the shared upstream connection
+    channels: Mutex<HashMap<String, broadcast::Sender<Chunk>>>,
+}
+
+/// A running relay. Dropping the handle leaves it running; call [`Relay::stop`].
+pub struct Relay {
+    addr: SocketAddr,
+    state: Arc<RelayState>,
+    shutdown: Arc<Notify>,
+}
+
+impl Relay {
+    /// Bind `bind:port` and start serving (port 0 picks a free port).
+    /// Must be called from within the tokio runtime.
+    pub fn start(bind: &str, port: u16) -> Result<Self, anyhow::Error> {
+        let listener = std::net::TcpListener::bind((bind, port))
+            .map_err(|e| anyhow::anyhow!("Relay could not listen on {}:{}: {}", bind, port, e))?;
+        listener.set_nonblocking(true)?;
+        let listener = TcpListener::from_std(listener)?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(RelayState::default());
+        let shutdown = Arc::new(Notify::new());
+
+        let accept_state = state.clone();
+        let accept_shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => {
+                        if let Ok((socket, _)) = accepted {
+                            tokio::spawn(serve_viewer(socket, accept_state.clone()));
+                        }
+                    }
+                    _ = accept_shutdown.notified() => break,
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            shutdown,
+        })
+    }
+
+    /// Point the relay at the active account (None = answer 503)
+    pub fn set_client(&self, client: Option<IptvClient>) {
+        if let Ok(mut current) = self.state.client.lock() {
+            *current = client;
+        }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// URL other devices use for `stream_id`
+    pub fn url_for(&self, stream_id: &str) -> String {
+        let host = if self.addr.ip().is_unspecified() {
+            lan_address().unwrap_or_else(|| "localhost".to_string())
+        } else {
+            self.addr.ip().to_string()
+        };
+        format!("http://{}:{}/live/{}", host, self.addr.port(), stream_id)
+    }
+
+    /// Channels currently pulled from the provider
+    pub fn upstream_count(&self) -> usize {
+        self.state.channels.lock().map(|c| c.len()).unwrap_or(0)
+    }
+
+    /// Local viewers across all channels
+    pub fn viewer_count(&self) -> usize {
+        self.state
+            .channels
+            .lock()
+            .map(|c| c.values().map(|tx| tx.receiver_count()).sum())
+            .unwrap_or(0)
+    }
+
+    /// Stop accepting viewers. Open viewer connections end with their upstream.
+    pub fn stop(&self) {
+        self.shutdown.notify_waiters();
+        if let Ok(mut channels) = self.state.channels.lock() {
+            channels.clear();
+        }
+    }
+}
+
+/// This computer's address on the network, found by routing (not sending)
+/// a UDP packet
+fn lan_address() -> Option<String> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    Some(socket.local_addr().ok()?.ip().to_string())
+}
+
+/// `/live/{id}` or `/live/{id}.ts` -> `id`
+fn stream_id_from_path(path: &str) -> Option<&str> {
+    let id = path.strip_prefix("/live/")?;
+    let id = id.split(['?', '#']).next()?;
+    let id = id.strip_suffix(".ts").unwrap_or(id);
+    (!id.is_empty() && !id.contains('/')).then_some(id)
+}
+
+async fn serve_viewer(mut socket: TcpStream, state: Arc<RelayState>) {
+    let Some(path) = read_request_path(&mut socket).await else {
+        let _ = respond(&mut socket, "400 Bad Request").await;
+        return;
+    };
+    let Some(stream_id) = stream_id_from_path(&path) else {
+        let _ = respond(&mut socket, "404 Not Found").await;
+        return;
+    };
+    let Some(mut rx) = subscribe(&state, stream_id) else {
+        let _ = respond(&mut socket, "503 Service Unavailable").await;
+        return;
+    };
+
+    let header = "HTTP/1.1 200 OK\r\nContent-Type: video/mp2t\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n";
+    if socket.write_all(header.as_bytes()).await.is_err() {
+        return;
+    }
+    loop {
+        match rx.recv().await {
+            Ok(chunk) => {
+                if socket.write_all(&chunk).await.is_err() {
+                    break; // Viewer went away
+                }
+            }
+            // A slow viewer just misses some packets; TS players resync
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Read the request head and return the path of a GET request
+async fn read_request_path(socket: &mut TcpStream) -> Option<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = tokio::time::timeout(Duration::from_secs(10), socket.read(&mut buf))
+            .await
+            .ok()?
+            .ok()?;
+        if n == 0 || head.len() > 16 * 1024 {
+            return None;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next()?.split_whitespace();
+    match (request_line.next()?, request_line.next()) {
+        ("GET", Some(path)) => Some(path.to_string()),
+        _ => None,
+    }
+}
+
+async fn respond(socket: &mut TcpStream, status: &str) -> std::io::Result<()> {
+    socket
+        .write_all(
+            format!(
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            )
+            .as_bytes(),
+        )
+        .await
+}
+
+/// Join the channel's shared upstream, starting it if this is the first viewer
+fn subscribe(state: &Arc<RelayState>, stream_id: &str) -> Option<broadcast::Receiver<Chunk>> {
+    let mut channels = state.channels.lock().ok()?;
+    if let Some(tx) = channels.get(stream_id) {
+        return Some(tx.subscribe());
+    }
+
+    let client = state.client.lock().ok()?.clone()?;
+    let (tx, rx) = broadcast::channel(CHANNEL_BUFFER);
+    channels.insert(stream_id.to_string(), tx.clone());
+    tokio::spawn(pump_upstream(
+        state.clone(),
+        stream_id.to_string(),
+        client,
+        tx,
+    ));
+    Some(rx)
+}
+
+/// Copy the provider's stream into the channel until the last viewer leaves
+async fn pump_upstream(
+    state: Arc<RelayState>,
+    stream_id: String,
+    client: IptvClient,
+    tx: broadcast::Sender<Chunk>,
+) {
+    let url = client.get_stream_url(&stream_id, "ts");
+    let referrer = crate::player::StreamHeaders::default().referrer_for(&url);
+    let http = client.http_client();
+    let mut failures = 0;
+
+    'connect: while tx.receiver_count() > 0 && failures <= MAX_UPSTREAM_RETRIES {
+        let mut request = http.get(&url);
+        if let Some(referrer) = &referrer {
+            request = request.header(reqwest::header::REFERER, referrer);
+        }
+        if let Ok(mut resp) = request.send().await.and_then(|r| r.error_for_status()) {
+            while let Ok(Some(bytes)) = resp.chunk().await {
+                failures = 0;
+                if tx.send(Arc::from(&bytes[..])).is_err() {
+                    break 'connect; // No viewers left
+                }
+            }
+        }
+        failures += 1;
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    // Only remove our own entry; a new upstream may already have replaced it
+    if let Ok(mut channels) = state.channels.lock() {
+        if channels
+            .get(&stream_id)
+            .is_some_and(|t| t.same_channel(&tx))
+        {
+            channels.remove(&stream_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn paths_map_to_stream_ids() {
+        assert_eq!(stream_id_from_path("/live/123"), Some("123"));
+        assert_eq!(stream_id_from_path("/live/123.ts?token=x"), Some("123"));
+        assert_eq!(stream_id_from_path("/live/"), None);
+        assert_eq!(stream_id_from_path("/live/a/b"), None);
+        assert_eq!(stream_id_from_path("/movie/1"), None);
+    }
+
+    /// Fake provider streaming TS packets forever; counts connections
+    async fn fake_provider() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = socket.read(&mut buf).await;
+                    let _ = socket
+                        .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: video/mp2t\r\n\r\n")
+                        .await;
+                    while socket.write_all(&[0x47; 188]).await.is_ok() {
+                        tokio::time::sleep(Duration::from_millis(5)).await;
+                    }
+                });
+            }
+        });
+        (base, connections)
+    }
+
+    async fn read_some(url: &str) -> Vec<u8> {
+        let mut resp = reqwest::get(url).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        let mut got = Vec::new();
+        while got.len() < 1000 {
+            got.extend_from_slice(&resp.chunk().await.unwrap().unwrap());
+        }
+        got
+    }
+
+    #[tokio::test]
+    async fn viewers_share_one_upstream_connection() {
+        let (base, connections) = fake_provider().await;
+        let relay = Relay::start("127.0.0.1", 0).unwrap();
+        relay.set_client(Some(IptvClient::Xtream(crate::api::XtreamClient::new(
+            base,
+            "user".to_string(),
+            "pass".to_string(),
+        ))));
+
+        let url = relay.url_for("42");
+        let (a, b) = tokio::join!(read_some(&url), read_some(&url));
+        assert!(a.iter().chain(&b).all(|&byte| byte == 0x47));
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+        assert_eq!(relay.upstream_count(), 1);
+
+        relay.stop();
+    }
+
+    #[tokio::test]
+    async fn answers_503_without_an_account() {
+        let relay = Relay::start("127.0.0.1", 0).unwrap();
+        let resp = reqwest::get(relay.url_for("1")).await.unwrap();
+        assert_eq!(resp.status(), 503);
+        let resp = reqwest::get(format!("http://127.0.0.1:{}/nope", relay.port()))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 404);
+        relay.stop();
+    }
+}
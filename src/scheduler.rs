@@ -0,0 +1,576 @@
+//! Scheduled recordings.
+//!
+//! Schedules live in the config as rules (one programme, or a series matched
+//! against the guide). Each tick they are expanded into concrete [`Airing`]s and
+//! the [`RecordingScheduler`] starts a [`Recording`](crate::recorder::Recording)
+//! when an airing's padded window opens and stops it when the window closes.
+//! Time comes from a [`Clock`] so the whole flow can be driven by a [`MockClock`].
+
+use crate::api::EpgListing;
+use crate::config::{RecordingSchedule, ScheduleRule};
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Source of "now" for the scheduler
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl MockClock {
+    pub fn new(at: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(at),
+        }
+    }
+
+    pub fn set(&self, at: DateTime<Utc>) {
+        if let Ok(mut now) = self.now.lock() {
+            *now = at;
+        }
+    }
+
+    pub fn advance(&self, by: chrono::Duration) {
+        if let Ok(mut now) = self.now.lock() {
+            *now += by;
+        }
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now.lock().map(|n| *n).unwrap_or_else(|_| Utc::now())
+    }
+}
+
+/// How early a recording starts and how long it runs over
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Padding {
+    pub before: chrono::Duration,
+    pub after: chrono::Duration,
+}
+
+impl Padding {
+    pub fn from_minutes(before: u32, after: u32) -> Self {
+        Self {
+            before: chrono::Duration::minutes(before as i64),
+            after: chrono::Duration::minutes(after as i64),
+        }
+    }
+}
+
+/// One programme from the guide
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuideEntry {
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Guide entries from `get_short_epg` listings (entries without timestamps are skipped)
+pub fn guide_entries(listings: &[EpgListing]) -> Vec<GuideEntry> {
+    let timestamp = |id: &Option<crate::flex_id::FlexId>| {
+        id.as_ref()
+            .and_then(|t| t.to_string_value())
+            .and_then(|t| t.trim().parse::<i64>().ok())
+            .and_then(|t| Utc.timestamp_opt(t, 0).single())
+    };
+    listings
+        .iter()
+        .filter_map(|l| {
+            Some(GuideEntry {
+                title: l.title.trim().to_string(),
+                start: timestamp(&l.start_timestamp)?,
+                end: timestamp(&l.stop_timestamp)?,
+            })
+        })
+        .filter(|e| e.end > e.start)
+        .collect()
+}
+
+/// The show name a series rule should match: the part of an episode title before
+/// its episode/matchup suffix ("Monday Night Football: Jets at 49ers" -> "Monday Night Football")
+pub fn series_title(title: &str) -> &str {
+    [":", " - ", " | "]
+        .iter()
+        .filter_map(|sep| title.find(sep))
+        .min()
+        .map(|i| title[..i].trim())
+        .filter(|t| !t.is_empty())
+        .unwrap_or(title.trim())
+}
+
+/// A concrete programme a schedule wants recorded
+#[derive(Debug, Clone, PartialEq)]
+pub struct Airing {
+    pub schedule_id: u64,
+    pub account: String,
+    pub stream_id: String,
+    pub channel_name: String,
+    pub title: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Airing {
+    /// Identifies one airing of one schedule across ticks
+    pub fn key(&self) -> (u64, i64) {
+        (self.schedule_id, self.start.timestamp())
+    }
+
+    pub fn window(&self, padding: Padding) -> (DateTime<Utc>, DateTime<Utc>) {
+        (self.start - padding.before, self.end + padding.after)
+    }
+}
+
+/// Expand schedules into airings, sorted by start. Series rules are matched
+/// against `guide` (stream_id -> entries), weekdays evaluated in `tz`.
+pub fn expand_airings<T: TimeZone>(
+    schedules: &[RecordingSchedule],
+    guide: &HashMap<String, Vec<GuideEntry>>,
+    tz: &T,
+) -> Vec<Airing> {
+    let mut airings = Vec::new();
+    for schedule in schedules {
+        let airing = |title: &str, start, end| Airing {
+            schedule_id: schedule.id,
+            account: schedule.account.clone(),
+            stream_id: schedule.stream_id.clone(),
+            channel_name: schedule.channel_name.clone(),
+            title: title.to_string(),
+            start,
+            end,
+        };
+        match &schedule.rule {
+            ScheduleRule::Once { title, start, end } => {
+                if let (Some(start), Some(end)) = (
+                    Utc.timestamp_opt(*start, 0).single(),
+                    Utc.timestamp_opt(*end, 0).single(),
+                ) {
+                    airings.push(airing(title, start, end));
+                }
+            }
+            ScheduleRule::Series { title, weekday } => {
+                let needle = title.to_lowercase();
+                let entries = guide.get(&schedule.stream_id).into_iter().flatten();
+                for entry in entries {
+                    let on_day =
+                        weekday.is_none_or(|day| entry.start.with_timezone(tz).weekday() == day);
+                    if on_day && entry.title.to_lowercase().contains(&needle) {
+                        airings.push(airing(&entry.title, entry.start, entry.end));
+                    }
+                }
+            }
+        }
+    }
+    airings.sort_by_key(|a| a.start);
+    airings.dedup_by(|a, b| a.key() == b.key());
+    airings
+}
+
+/// Airings that would need more simultaneous connections than the account allows
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub at: DateTime<Utc>,
+    pub account: String,
+    /// [`Airing::key`]s of the overlapping airings
+    pub airings: Vec<(u64, i64)>,
+    pub titles: Vec<String>,
+}
+
+/// Find moments where more than `max_connections` padded airings on the same
+/// account overlap
+pub fn find_conflicts(
+    airings: &[Airing],
+    padding: Padding,
+    max_connections: usize,
+) -> Vec<Conflict> {
+    // (time, is_start, index); ends sort before starts at the same instant so
+    // back-to-back programmes don't count as overlapping
+    let mut edges: Vec<(DateTime<Utc>, bool, usize)> = Vec::new();
+    for (i, airing) in airings.iter().enumerate() {
+        let (start, end) = airing.window(padding);
+        edges.push((start, true, i));
+        edges.push((end, false, i));
+    }
+    edges.sort_by_key(|(at, is_start, _)| (*at, *is_start));
+
+    // Each account is its own connection pool
+    let mut live: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut conflicts = Vec::new();
+    for (at, is_start, i) in edges {
+        let account = airings[i].account.as_str();
+        let live = live.entry(account).or_default();
+        if is_start {
+            live.push(i);
+            if live.len() > max_connections {
+                conflicts.push(Conflict {
+                    at,
+                    account: account.to_string(),
+                    airings: live.iter().map(|&j| airings[j].key()).collect(),
+                    titles: live.iter().map(|&j| airings[j].title.clone()).collect(),
+                });
+            }
+        } else {
+            live.retain(|&j| j != i);
+        }
+    }
+    conflicts
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use runtime::{RecordingScheduler, SchedulerEvent};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod runtime {
+    use super::{Airing, Clock, Padding};
+    use crate::recorder::{recording_file_name, Recording};
+    use std::collections::{HashMap, HashSet};
+    use std::path::Path;
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    pub enum SchedulerEvent {
+        Started(Recording),
+        Stopped(String),
+        /// Airing title, reason
+        Failed(String, String),
+    }
+
+    /// Starts and stops recordings as airings' padded windows open and close
+    pub struct RecordingScheduler {
+        clock: Arc<dyn Clock>,
+        pub padding: Padding,
+        active: HashMap<(u64, i64), Recording>,
+        /// Airings already recorded (or given up on) this session
+        done: HashSet<(u64, i64)>,
+    }
+
+    impl RecordingScheduler {
+        pub fn new(clock: Arc<dyn Clock>, padding: Padding) -> Self {
+            Self {
+                clock,
+                padding,
+                active: HashMap::new(),
+                done: HashSet::new(),
+            }
+        }
+
+        pub fn now(&self) -> chrono::DateTime<chrono::Utc> {
+            self.clock.now()
+        }
+
+        pub fn is_recording(&self, airing: &Airing) -> bool {
+            self.active.contains_key(&airing.key())
+        }
+
+        /// Bring recordings in line with `airings` at the clock's current time.
+        /// `resolve` gives the HTTP client and stream URL for an airing's channel.
+        pub fn tick<F>(&mut self, airings: &[Airing], dir: &Path, resolve: F) -> Vec<SchedulerEvent>
+        where
+            F: Fn(&Airing) -> Option<(reqwest::Client, String)>,
+        {
+            let now = self.clock.now();
+            let mut events = Vec::new();
+
+            // Stop recordings whose window closed or whose schedule was removed
+            let wanted: HashMap<(u64, i64), &Airing> =
+                airings.iter().map(|a| (a.key(), a)).collect();
+            let finished: Vec<(u64, i64)> = self
+                .active
+                .iter()
+                .filter(|(key, rec)| {
+                    !rec.is_active()
+                        || wanted
+                            .get(key)
+                            .is_none_or(|a| now >= a.window(self.padding).1)
+                })
+                .map(|(key, _)| *key)
+                .collect();
+            for key in finished {
+                if let Some(rec) = self.active.remove(&key) {
+                    rec.stop();
+                    self.done.insert(key);
+                    events.push(SchedulerEvent::Stopped(rec.title.clone()));
+                }
+            }
+
+            for airing in airings {
+                let key = airing.key();
+                let (start, end) = airing.window(self.padding);
+                if now < start
+                    || now >= end
+                    || self.active.contains_key(&key)
+                    || self.done.contains(&key)
+                {
+                    continue;
+                }
+                let Some((client, url)) = resolve(airing) else {
+                    self.done.insert(key);
+                    let reason = if airing.account.is_empty() {
+                        "Not connected to the channel's playlist".to_string()
+                    } else {
+                        format!("Can't reach the channel on \"{}\"", airing.account)
+                    };
+                    events.push(SchedulerEvent::Failed(airing.title.clone(), reason));
+                    continue;
+                };
+                let file_name = recording_file_name(
+                    &airing.channel_name,
+                    Some(&airing.title),
+                    airing.start.with_timezone(&chrono::Local),
+                );
+                let recording = Recording::start(
+                    client,
+                    url,
+                    dir.join(file_name),
+                    airing.stream_id.clone(),
+                    format!("{} ({})", airing.title, airing.channel_name),
+                );
+                self.active.insert(key, recording.clone());
+                events.push(SchedulerEvent::Started(recording));
+            }
+
+            events
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{RecordingSchedule, ScheduleRule};
+    use chrono::Weekday;
+
+    fn at(h: u32, m: u32) -> DateTime<Utc> {
+        // 2024-09-09 is a Monday
+        Utc.with_ymd_and_hms(2024, 9, 9, h, m, 0).unwrap()
+    }
+
+    fn once(id: u64, title: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> RecordingSchedule {
+        RecordingSchedule {
+            id,
+            account: "Main".to_string(),
+            stream_id: id.to_string(),
+            channel_name: format!("CH {}", id),
+            rule: ScheduleRule::Once {
+                title: title.to_string(),
+                start: start.timestamp(),
+                end: end.timestamp(),
+            },
+        }
+    }
+
+    #[test]
+    fn series_rule_matches_title_on_the_chosen_weekday() {
+        let schedule = RecordingSchedule {
+            id: 1,
+            account: "Main".to_string(),
+            stream_id: "espn".to_string(),
+            channel_name: "ESPN".to_string(),
+            rule: ScheduleRule::Series {
+                title: "monday night football".to_string(),
+                weekday: Some(Weekday::Mon),
+            },
+        };
+        let entry = |title: &str, start: DateTime<Utc>| GuideEntry {
+            title: title.to_string(),
+            start,
+            end: start + chrono::Duration::hours(3),
+        };
+        let tuesday = at(20, 0) + chrono::Duration::days(1);
+        let guide = HashMap::from([(
+            "espn".to_string(),
+            vec![
+                entry("Monday Night Football: Jets at 49ers", at(20, 0)),
+                entry("SportsCenter", at(23, 0)),
+                entry("Monday Night Football Replay", tuesday),
+            ],
+        )]);
+
+        let airings = expand_airings(&[schedule], &guide, &Utc);
+        assert_eq!(airings.len(), 1);
+        assert_eq!(airings[0].title, "Monday Night Football: Jets at 49ers");
+        assert_eq!(airings[0].channel_name, "ESPN");
+    }
+
+    #[test]
+    fn series_title_drops_episode_suffix() {
+        assert_eq!(
+            series_title("Monday Night Football: Jets at 49ers"),
+            "Monday Night Football"
+        );
+        assert_eq!(series_title("The News - Late Edition"), "The News");
+        assert_eq!(series_title("SportsCenter"), "SportsCenter");
+        assert_eq!(series_title(": odd"), ": odd");
+    }
+
+    #[test]
+    fn conflicts_only_when_overlap_exceeds_connections() {
+        let padding = Padding::from_minutes(0, 0);
+        let airings = expand_airings(
+            &[
+                once(1, "A", at(20, 0), at(21, 0)),
+                once(2, "B", at(20, 30), at(21, 30)),
+                once(3, "C", at(21, 0), at(22, 0)), // starts as A ends
+            ],
+            &HashMap::new(),
+            &Utc,
+        );
+
+        assert!(find_conflicts(&airings, padding, 2).is_empty());
+        let conflicts = find_conflicts(&airings, padding, 1);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].titles, ["A", "B"]);
+        assert_eq!(conflicts[1].titles, ["B", "C"]);
+
+        // Padding turns the back-to-back pair into an overlap
+        let conflicts = find_conflicts(&airings, Padding::from_minutes(2, 5), 2);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].titles, ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn conflicts_are_counted_per_account() {
+        let padding = Padding::from_minutes(0, 0);
+        let on = |account: &str, schedule: RecordingSchedule| RecordingSchedule {
+            account: account.to_string(),
+            ..schedule
+        };
+        // Same title on both accounts, overlapping in time
+        let airings = expand_airings(
+            &[
+                on("Main", once(1, "Match", at(20, 0), at(22, 0))),
+                on("Backup", once(2, "Match", at(20, 0), at(22, 0))),
+                on("Main", once(3, "News", at(23, 0), at(23, 30))),
+            ],
+            &HashMap::new(),
+            &Utc,
+        );
+        assert!(find_conflicts(&airings, padding, 1).is_empty());
+
+        let airings = expand_airings(
+            &[
+                on("Main", once(1, "Match", at(20, 0), at(22, 0))),
+                on("Main", once(2, "Late Match", at(21, 0), at(23, 0))),
+                on("Backup", once(3, "Match", at(20, 0), at(22, 0))),
+            ],
+            &HashMap::new(),
+            &Utc,
+        );
+        let conflicts = find_conflicts(&airings, padding, 1);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].account, "Main");
+        assert_eq!(
+            conflicts[0].airings,
+            [(1, at(20, 0).timestamp()), (2, at(21, 0).timestamp())]
+        );
+    }
+
+    #[test]
+    fn guide_entries_need_timestamps() {
+        let listing = |start: Option<&str>| EpgListing {
+            id: None,
+            epg_id: None,
+            title: " News ".to_string(),
+            start: String::new(),
+            end: String::new(),
+            description: None,
+            start_timestamp: start.map(|s| crate::flex_id::FlexId::String(s.to_string())),
+            stop_timestamp: Some(crate::flex_id::FlexId::Number(1_700_003_600)),
+        };
+        let entries = guide_entries(&[listing(Some("1700000000")), listing(None)]);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].title, "News");
+        assert_eq!(entries[0].start.timestamp(), 1_700_000_000);
+    }
+
+    #[tokio::test]
+    async fn scheduler_starts_and_stops_with_the_clock() {
+        use std::sync::Arc;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Local stand-in for the provider: an endless TS-ish stream
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/live/u/p/7.ts", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = socket.read(&mut buf).await;
+                    let _ = socket
+                        .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: video/mp2t\r\n\r\n")
+                        .await;
+                    while socket.write_all(&[0x47; 188]).await.is_ok() {
+                        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                    }
+                });
+            }
+        });
+
+        let dir = std::env::temp_dir().join(format!("matrix-iptv-sched-{}", std::process::id()));
+        let clock = Arc::new(MockClock::new(at(19, 50)));
+        let mut scheduler = RecordingScheduler::new(clock.clone(), Padding::from_minutes(2, 5));
+        let airings = expand_airings(
+            &[once(7, "Match", at(20, 0), at(22, 0))],
+            &HashMap::new(),
+            &Utc,
+        );
+        let resolve = |_: &Airing| Some((reqwest::Client::new(), url.clone()));
+
+        assert!(
+            scheduler.tick(&airings, &dir, resolve).is_empty(),
+            "too early"
+        );
+
+        clock.set(at(19, 58));
+        let events = scheduler.tick(&airings, &dir, resolve);
+        let recording = match events.as_slice() {
+            [SchedulerEvent::Started(rec)] => rec.clone(),
+            other => panic!("expected a start, got {:?}", other),
+        };
+        assert!(scheduler.is_recording(&airings[0]));
+
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_secs(5);
+        while recording.bytes_written() == 0 && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(recording.bytes_written() > 0);
+
+        clock.set(at(22, 4));
+        assert!(
+            scheduler.tick(&airings, &dir, resolve).is_empty(),
+            "inside post-padding"
+        );
+
+        clock.set(at(22, 5));
+        let events = scheduler.tick(&airings, &dir, resolve);
+        assert!(
+            matches!(events.as_slice(), [SchedulerEvent::Stopped(t)] if t.starts_with("Match"))
+        );
+        while recording.is_active() && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(!recording.is_active());
+
+        // Rewinding the clock doesn't record the same airing twice
+        clock.set(at(20, 30));
+        assert!(scheduler.tick(&airings, &dir, resolve).is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
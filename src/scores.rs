@@ -184,6 +184,55 @@ pub struct ScoreGame {
     pub top_scorer: Option<String>,     // "Ja Morant - 24 PTS"
 }
 
+/// ESPN scoreboard endpoints we poll, paired with the league label shown in the UI
+const LEAGUES: &[(&str, &str)] = &[
+    ("football/nfl", "NFL"),
+    ("basketball/nba", "NBA"),
+    ("mixed-martial-arts/ufc", "UFC"), // Might be different structure
+    ("hockey/nhl", "NHL"),
+    ("baseball/mlb", "MLB"),
+    ("soccer/usa.1", "MLS"),
+    ("soccer/eng.1", "EPL"),
+];
+
+/// League labels in display order (e.g. for the ticker league picker)
+pub fn league_names() -> Vec<&'static str> {
+    LEAGUES.iter().map(|(_, name)| *name).collect()
+}
+
+impl ScoreGame {
+    pub fn is_in_progress(&self) -> bool {
+        self.status_state == "in"
+    }
+
+    /// Compact one-line summary for the score ticker, e.g. "NFL CHI 14-21 GB Q3 4:12"
+    pub fn ticker_label(&self) -> String {
+        format!(
+            "{} {} {}-{} {} {}",
+            self.league,
+            self.away_abbr,
+            self.away_score,
+            self.home_score,
+            self.home_abbr,
+            self.status_detail
+        )
+    }
+}
+
+/// Returns the ids of games whose score differs between two fetches.
+/// Games that only appear in `new` are not reported.
+pub fn changed_game_ids(old: &[ScoreGame], new: &[ScoreGame]) -> Vec<String> {
+    new.iter()
+        .filter(|game| {
+            old.iter().any(|prev| {
+                prev.id == game.id
+                    && (prev.home_score != game.home_score || prev.away_score != game.away_score)
+            })
+        })
+        .map(|game| game.id.clone())
+        .collect()
+}
+
 pub struct ScoreService {
     client: Client,
 }
@@ -206,19 +255,9 @@ impl ScoreService {
     }
 
     pub async fn fetch_scores(&self) -> Result<Vec<ScoreGame>> {
-        let leagues = vec![
-            ("football/nfl", "NFL"),
-            ("basketball/nba", "NBA"),
-            ("mixed-martial-arts/ufc", "UFC"), // Might be different structure
-            ("hockey/nhl", "NHL"),
-            ("baseball/mlb", "MLB"),
-            ("soccer/usa.1", "MLS"),
-            ("soccer/eng.1", "EPL"),
-        ];
-
         let mut all_games = Vec::new();
 
-        for (endpoint, league_name) in leagues {
+        for (endpoint, league_name) in LEAGUES {
             let url = format!(
                 "http://site.api.espn.com/apis/site/v2/sports/{}/scoreboard",
                 endpoint
@@ -365,3 +404,62 @@ impl ScoreService {
         Ok(all_games)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(id: &str, home_score: &str, away_score: &str) -> ScoreGame {
+        ScoreGame {
+            id: id.to_string(),
+            league: "NFL".to_string(),
+            start_time: String::new(),
+            status_state: "in".to_string(),
+            status_detail: "Q3 4:12".to_string(),
+            home_team: "Green Bay Packers".to_string(),
+            home_score: home_score.to_string(),
+            home_abbr: "GB".to_string(),
+            home_color: None,
+            home_record: None,
+            home_logo: None,
+            away_team: "Chicago Bears".to_string(),
+            away_score: away_score.to_string(),
+            away_abbr: "CHI".to_string(),
+            away_color: None,
+            away_record: None,
+            away_logo: None,
+            display_clock: "4:12".to_string(),
+            period: 3,
+            venue_name: None,
+            venue_city: None,
+            venue_state: None,
+            broadcasts: Vec::new(),
+            last_play: None,
+            home_win_pct: None,
+            away_win_pct: None,
+            headline: None,
+            series_summary: None,
+            top_scorer: None,
+        }
+    }
+
+    #[test]
+    fn changed_game_ids_reports_only_score_changes() {
+        let old = vec![game("1", "14", "7"), game("2", "0", "0")];
+        let new = vec![
+            game("1", "21", "7"),
+            game("2", "0", "0"),
+            game("3", "3", "0"),
+        ];
+
+        assert_eq!(changed_game_ids(&old, &new), vec!["1".to_string()]);
+    }
+
+    #[test]
+    fn ticker_label_lists_away_team_first() {
+        assert_eq!(
+            game("1", "21", "14").ticker_label(),
+            "NFL CHI 14-21 GB Q3 4:12"
+        );
+    }
+}
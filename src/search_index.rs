@@ -0,0 +1,273 @@
+//! Trigram inverted index over the global live, VOD and series lists, so
+//! global search only fuzzy-scores items that share n-grams with the query.
+//! Built after preprocessing and persisted as a section of the catalog cache.
+
+use crate::api::{get_id_str, Stream};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Queries shorter than this are answered by a plain substring scan
+pub const MIN_INDEXED_QUERY: usize = 3;
+
+/// Fuzzy candidates must contain at least 1/N of the query's trigrams
+const FUZZY_GRAM_DIVISOR: usize = 3;
+
+/// Results kept per query (exact substring matches first)
+pub const MAX_RESULTS: usize = 100;
+
+/// Documents per parallel build chunk
+const BUILD_CHUNK: usize = 16_384;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchIndex {
+    /// Number of live, VOD and series items, in document-id order
+    pub counts: [usize; 3],
+    /// Hash of every indexed stream_id, to tell whether the index still fits the lists
+    pub fingerprint: u64,
+    /// `App::catalog_generation` of the lists indexed; not stored, an index
+    /// loaded from disk is checked with `matches` instead
+    #[serde(skip)]
+    pub generation: u64,
+    /// Trigram -> ascending document ids
+    postings: HashMap<u64, Vec<u32>>,
+}
+
+/// Pack three chars into one key (21 bits each covers all of Unicode)
+fn gram_key(a: char, b: char, c: char) -> u64 {
+    ((a as u64) << 42) | ((b as u64) << 21) | c as u64
+}
+
+/// Distinct trigrams of a lowercase string
+fn grams(text: &str) -> Vec<u64> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut keys: Vec<u64> = chars
+        .windows(3)
+        .map(|w| gram_key(w[0], w[1], w[2]))
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    keys
+}
+
+fn fingerprint(sources: [&[Arc<Stream>]; 3]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for list in sources {
+        list.len().hash(&mut hasher);
+        for s in list {
+            get_id_str(&s.stream_id).hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+impl SearchIndex {
+    /// Index the `search_name` of every item in the three lists
+    pub fn build(live: &[Arc<Stream>], vod: &[Arc<Stream>], series: &[Arc<Stream>]) -> Self {
+        let docs: Vec<&Arc<Stream>> = live.iter().chain(vod).chain(series).collect();
+        let partials: Vec<HashMap<u64, Vec<u32>>> = docs
+            .par_chunks(BUILD_CHUNK)
+            .enumerate()
+            .map(|(chunk, streams)| {
+                let mut postings: HashMap<u64, Vec<u32>> = HashMap::new();
+                for (offset, stream) in streams.iter().enumerate() {
+                    let doc = (chunk * BUILD_CHUNK + offset) as u32;
+                    for key in grams(&stream.search_name) {
+                        postings.entry(key).or_default().push(doc);
+                    }
+                }
+                postings
+            })
+            .collect();
+
+        // Chunks come back in order, so appending keeps every list ascending
+        let mut postings: HashMap<u64, Vec<u32>> = HashMap::new();
+        for partial in partials {
+            for (key, docs) in partial {
+                postings.entry(key).or_default().extend(docs);
+            }
+        }
+        Self {
+            counts: [live.len(), vod.len(), series.len()],
+            fingerprint: fingerprint([live, vod, series]),
+            generation: 0,
+            postings,
+        }
+    }
+
+    /// Cheap check that the lists have not been replaced since the index was
+    /// built: every replacement bumps `generation`, even one of the same length
+    pub fn fits(
+        &self,
+        generation: u64,
+        live: &[Arc<Stream>],
+        vod: &[Arc<Stream>],
+        series: &[Arc<Stream>],
+    ) -> bool {
+        self.generation == generation && self.counts == [live.len(), vod.len(), series.len()]
+    }
+
+    /// Full check against the stream ids, for an index loaded from disk
+    pub fn matches(
+        &self,
+        live: &[Arc<Stream>],
+        vod: &[Arc<Stream>],
+        series: &[Arc<Stream>],
+    ) -> bool {
+        self.counts == [live.len(), vod.len(), series.len()]
+            && self.fingerprint == fingerprint([live, vod, series])
+    }
+
+    /// Document ids worth scoring for `query` (lowercase), ascending. None when
+    /// the query is too short to use trigrams.
+    pub fn candidates(&self, query: &str) -> Option<Vec<u32>> {
+        let keys = grams(query);
+        if query.chars().count() < MIN_INDEXED_QUERY || keys.is_empty() {
+            return None;
+        }
+        let needed = keys.len().div_ceil(FUZZY_GRAM_DIVISOR);
+        let total: usize = self.counts.iter().sum();
+        let mut hits = vec![0u16; total];
+        let mut docs = Vec::new();
+        for key in &keys {
+            for &doc in self
+                .postings
+                .get(key)
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+            {
+                let count = &mut hits[doc as usize];
+                *count += 1;
+                if *count as usize == needed {
+                    docs.push(doc);
+                }
+            }
+        }
+        docs.sort_unstable();
+        Some(docs)
+    }
+}
+
+/// Global search over the three lists: substring matches, plus fuzzy matches
+/// for queries of 3+ characters, exact substring hits first. With an index that
+/// fits the lists (built for catalog `generation`) only its candidates are scored.
+pub fn search(
+    index: Option<&SearchIndex>,
+    generation: u64,
+    live: &[Arc<Stream>],
+    vod: &[Arc<Stream>],
+    series: &[Arc<Stream>],
+    query: &str,
+) -> Vec<Arc<Stream>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let use_fuzzy = query.len() >= 3;
+    let matches =
+        |s: &Arc<Stream>| s.search_name.contains(query) || (use_fuzzy && s.fuzzy_match(query, 70));
+
+    let candidates = index
+        .filter(|i| i.fits(generation, live, vod, series))
+        .and_then(|i| i.candidates(query));
+    let mut hits: Vec<Arc<Stream>> = match candidates {
+        Some(docs) => docs
+            .par_iter()
+            .filter_map(|&doc| {
+                let doc = doc as usize;
+                let stream = if doc < live.len() {
+                    &live[doc]
+                } else if doc < live.len() + vod.len() {
+                    &vod[doc - live.len()]
+                } else {
+                    series.get(doc - live.len() - vod.len())?
+                };
+                matches(stream).then(|| stream.clone())
+            })
+            .collect(),
+        None => live
+            .par_iter()
+            .filter(|s| matches(s))
+            .chain(vod.par_iter().filter(|s| matches(s)))
+            .chain(series.par_iter().filter(|s| matches(s)))
+            .cloned()
+            .collect(),
+    };
+
+    // Prioritize exact substring matches
+    hits.sort_by_cached_key(|s| !s.search_name.contains(query));
+    hits.truncate(MAX_RESULTS);
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flex_id::FlexId;
+
+    fn streams(names: &[&str], first_id: i64) -> Vec<Arc<Stream>> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                Arc::new(Stream {
+                    name: name.to_string(),
+                    search_name: name.to_lowercase(),
+                    stream_id: FlexId::Number(first_id + i as i64),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn indexed_search_matches_full_scan() {
+        let live = streams(&["CNN HD", "BBC News", "Sky Sports F1"], 1);
+        let vod = streams(&["Breaking Point", "The Batman", "News of the World"], 10);
+        let series = streams(&["Breaking Bad", "Newsroom"], 20);
+        let index = SearchIndex::build(&live, &vod, &series);
+        assert!(index.matches(&live, &vod, &series));
+
+        for query in ["news", "breaking", "breakng bad", "f1", "bat", "xyz"] {
+            let names = |results: Vec<Arc<Stream>>| {
+                let mut names: Vec<String> = results.iter().map(|s| s.name.clone()).collect();
+                names.sort();
+                names
+            };
+            assert_eq!(
+                names(search(Some(&index), 0, &live, &vod, &series, query)),
+                names(search(None, 0, &live, &vod, &series, query)),
+                "query {:?}",
+                query
+            );
+        }
+
+        let hits = search(Some(&index), 0, &live, &vod, &series, "news");
+        assert_eq!(hits.len(), 3);
+        assert!(index.candidates("ne").is_none());
+    }
+
+    #[test]
+    fn index_no_longer_fits_replaced_lists() {
+        let live = streams(&["CNN HD"], 1);
+        let index = SearchIndex::build(&live, &[], &[]);
+        let other = streams(&["BBC News"], 2);
+        assert!(index.fits(0, &live, &[], &[]));
+        assert!(!index.fits(1, &other, &[], &[]));
+        assert!(!index.matches(&other, &[], &[]));
+        assert!(!index.fits(0, &streams(&["A", "B"], 1), &[], &[]));
+    }
+
+    #[test]
+    fn same_length_replacement_is_searched_in_full() {
+        let live = streams(&["CNN HD", "BBC News"], 1);
+        let index = SearchIndex::build(&live, &[], &[]);
+
+        // The lists are swapped for ones of the same length: generation 1
+        let other = streams(&["Sky News", "ESPN"], 10);
+        let hits = search(Some(&index), 1, &other, &[], &[], "sky news");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].name, "Sky News");
+    }
+}
@@ -110,6 +110,48 @@ pub fn get_vlc_path() -> Option<String> {
     None
 }
 
+/// Returns the path of an executable named `command` on PATH
+#[cfg(not(target_arch = "wasm32"))]
+fn find_on_path(command: &str) -> Option<String> {
+    let file_name = if cfg!(windows) {
+        format!("{}.exe", command)
+    } else {
+        command.to_string()
+    };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(&file_name))
+        .find(|p| p.is_file())
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Known external players (IINA, ffplay, ...) installed on this machine, as
+/// ready-made profiles for Settings > Player Engine
+#[cfg(not(target_arch = "wasm32"))]
+pub fn detect_known_players() -> Vec<crate::player_profile::PlayerProfile> {
+    crate::player_profile::KNOWN_PLAYERS
+        .iter()
+        .filter_map(|known| {
+            let path = known
+                .commands
+                .iter()
+                .find_map(|c| find_on_path(c))
+                .or_else(|| {
+                    known
+                        .candidate_paths
+                        .iter()
+                        .find(|p| Path::new(p).is_file())
+                        .map(|p| p.to_string())
+                })?;
+            Some(known.profile(path))
+        })
+        .collect()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn detect_known_players() -> Vec<crate::player_profile::PlayerProfile> {
+    Vec::new()
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn check_and_install_dependencies() -> Result<(), anyhow::Error> {
     check_and_install_dependencies_with_output(false)
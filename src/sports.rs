@@ -505,6 +505,44 @@ mod tests {
         assert_eq!(res.team1, "Cowboys");
         assert_eq!(res.team2, "Commanders");
     }
+
+    const EMBED_URL: &str = "https://embedme.top/embed/alpha/lakers-vs-celtics/1";
+
+    #[test]
+    fn extracts_escaped_manifest_from_player_config() {
+        let html = include_str!("../tests/fixtures/streamed_embed_direct.html");
+        let resolved = extract_hls_from_embed(html, EMBED_URL).unwrap();
+        assert_eq!(
+            resolved.url,
+            "https://cdn.example-hls.net/live/lakers-celtics/index.m3u8?token=abc123"
+        );
+        assert_eq!(resolved.referrer, "https://embedme.top/");
+        assert_eq!(resolved.origin, "https://embedme.top");
+    }
+
+    #[test]
+    fn extracts_relative_and_base64_manifests() {
+        let html = include_str!("../tests/fixtures/streamed_embed_atob.html");
+        let resolved = extract_hls_from_embed(html, EMBED_URL).unwrap();
+        assert_eq!(
+            resolved.url,
+            "https://rr.example-cdn.io/s/alpha/1/playlist.m3u8"
+        );
+
+        let relative = r#"<script>player.setup({ file: "/hls/match-1/index.m3u8" });</script>"#;
+        let resolved = extract_hls_from_embed(relative, EMBED_URL).unwrap();
+        assert_eq!(resolved.url, "https://embedme.top/hls/match-1/index.m3u8");
+    }
+
+    #[test]
+    fn follows_iframe_when_no_manifest_on_page() {
+        let html = include_str!("../tests/fixtures/streamed_embed_iframe.html");
+        assert!(extract_hls_from_embed(html, EMBED_URL).is_none());
+        assert_eq!(
+            extract_iframe_src(html, EMBED_URL).as_deref(),
+            Some("https://player.example-host.com/e/alpha/1")
+        );
+    }
 }
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamedTeam {
@@ -562,3 +600,150 @@ pub async fn fetch_streamed_links(source: &str, id: &str) -> Result<Vec<Streamed
     let streams: Vec<StreamedStream> = res.json().await?;
     Ok(streams)
 }
+
+/// Browser user agent used when scraping embed pages; embed hosts serve an
+/// empty player to anything that doesn't look like a desktop browser.
+pub const EMBED_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// How many nested iframes we follow before giving up on an embed page.
+const MAX_EMBED_DEPTH: usize = 3;
+
+/// A directly playable stream extracted from a Streamed.pk embed page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedEmbed {
+    /// HLS manifest URL
+    pub url: String,
+    /// Referer the CDN expects (the page that hosted the player)
+    pub referrer: String,
+    /// Origin the CDN expects
+    pub origin: String,
+}
+
+/// Returns `scheme://host` for a URL, or None if it isn't absolute.
+fn url_origin(url: &str) -> Option<String> {
+    let scheme_end = url.find("://")?;
+    let rest = &url[scheme_end + 3..];
+    let host_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    if host_end == 0 {
+        return None;
+    }
+    Some(format!("{}://{}", &url[..scheme_end], &rest[..host_end]))
+}
+
+/// Resolves a possibly relative or protocol-relative link against the page it came from.
+fn absolutize_url(link: &str, page_url: &str) -> Option<String> {
+    let link = link.trim();
+    if link.starts_with("http://") || link.starts_with("https://") {
+        return Some(link.to_string());
+    }
+    if let Some(rest) = link.strip_prefix("//") {
+        let scheme = page_url.split("://").next().unwrap_or("https");
+        return Some(format!("{}://{}", scheme, rest));
+    }
+    let origin = url_origin(page_url)?;
+    if link.starts_with('/') {
+        return Some(format!("{}{}", origin, link));
+    }
+    let base = match page_url.rfind('/') {
+        Some(pos) if pos > origin.len() => &page_url[..pos],
+        _ => origin.as_str(),
+    };
+    Some(format!("{}/{}", base, link))
+}
+
+/// Scans an embed page for an HLS manifest. Handles plain and JSON-escaped
+/// URLs, relative `file:` / `source:` entries, and `atob("...")`-wrapped links.
+pub fn extract_hls_from_embed(html: &str, page_url: &str) -> Option<ResolvedEmbed> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    static RE_QUOTED_M3U8: OnceLock<regex::Regex> = OnceLock::new();
+    static RE_ATOB: OnceLock<regex::Regex> = OnceLock::new();
+
+    let re_quoted = RE_QUOTED_M3U8.get_or_init(|| {
+        regex::Regex::new(r#"["']([^"'\s<>]*?\.m3u8(?:\?[^"'\s<>]*)?)["']"#).unwrap()
+    });
+    let re_atob = RE_ATOB
+        .get_or_init(|| regex::Regex::new(r#"atob\(\s*["']([A-Za-z0-9+/=]+)["']\s*\)"#).unwrap());
+
+    let unescaped = html.replace("\\/", "/");
+
+    let mut candidates: Vec<String> = re_quoted
+        .captures_iter(&unescaped)
+        .filter_map(|c| c.get(1).map(|m| m.as_str().to_string()))
+        .collect();
+
+    for caps in re_atob.captures_iter(&unescaped) {
+        if let Some(decoded) = caps
+            .get(1)
+            .and_then(|m| general_purpose::STANDARD.decode(m.as_str()).ok())
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+        {
+            if decoded.contains(".m3u8") {
+                candidates.push(decoded);
+            }
+        }
+    }
+
+    let url = candidates
+        .iter()
+        .find_map(|c| absolutize_url(c, page_url))?;
+    let origin = url_origin(page_url)?;
+
+    Some(ResolvedEmbed {
+        url,
+        referrer: format!("{}/", origin),
+        origin,
+    })
+}
+
+/// Returns the first `<iframe src=...>` on the page, resolved to an absolute URL.
+pub fn extract_iframe_src(html: &str, page_url: &str) -> Option<String> {
+    static RE_IFRAME: OnceLock<regex::Regex> = OnceLock::new();
+    let re = RE_IFRAME.get_or_init(|| {
+        regex::Regex::new(r#"(?is)<iframe[^>]*?\ssrc\s*=\s*["']([^"']+)["']"#).unwrap()
+    });
+
+    re.captures_iter(html)
+        .filter_map(|c| c.get(1))
+        .map(|m| m.as_str())
+        .find(|src| !src.starts_with("about:") && !src.starts_with("javascript:"))
+        .and_then(|src| absolutize_url(src, page_url))
+}
+
+/// Follows a Streamed.pk embed page (and any nested player iframes) until an
+/// HLS manifest is found.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn resolve_embed_stream(embed_url: &str) -> Result<ResolvedEmbed> {
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .user_agent(EMBED_USER_AGENT)
+        .build()?;
+
+    let mut page_url = embed_url.to_string();
+    let mut referrer = "https://streamed.pk/".to_string();
+
+    for _ in 0..MAX_EMBED_DEPTH {
+        let html = client
+            .get(&page_url)
+            .header("Referer", &referrer)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        if let Some(resolved) = extract_hls_from_embed(&html, &page_url) {
+            return Ok(resolved);
+        }
+
+        match extract_iframe_src(&html, &page_url) {
+            Some(next) => {
+                referrer = page_url;
+                page_url = next;
+            }
+            None => break,
+        }
+    }
+
+    Err(anyhow::anyhow!("No playable stream found in embed page"))
+}
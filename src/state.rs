@@ -61,6 +61,8 @@ pub struct SessionState {
     pub background_refresh_active: bool,
     /// Session loaded from cache
     pub cache_loaded: bool,
+    /// Browsing the cached catalog without a provider session
+    pub offline: bool,
     /// Global loading state
     pub state_loading: bool,
     /// Loading message display
@@ -99,6 +101,7 @@ impl SessionState {
         self.cached_user_timezone.clear();
         self.background_refresh_active = false;
         self.cache_loaded = false;
+        self.offline = false;
         self.state_loading = false;
         self.loading_message = None;
         self.loading_progress = None;
@@ -0,0 +1,312 @@
+//! Where the config and the app's data live.
+//!
+//! By default everything follows the platform directories for
+//! com.vibecoding.vibe-iptv. `--config <path>` or `MATRIX_IPTV_CONFIG` points
+//! at another config file; its folder then becomes the root for data and cache
+//! as well (`<root>/data`, `<root>/cache`), so portable installs and several
+//! profiles can run side by side without sharing state.
+//!
+//! `ConfigStorage` is what `AppConfig` reads from and writes to: a file, memory
+//! (tests), or localStorage in the browser.
+
+use std::sync::{Arc, Mutex};
+
+/// Alternative config file, same as `--config`
+pub const CONFIG_ENV: &str = "MATRIX_IPTV_CONFIG";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use paths::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod paths {
+    use super::CONFIG_ENV;
+    use once_cell::sync::OnceCell;
+    use std::path::PathBuf;
+
+    static CONFIG_OVERRIDE: OnceCell<PathBuf> = OnceCell::new();
+
+    /// Config file and the folders derived from it
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Paths {
+        pub config_file: PathBuf,
+        pub config_dir: PathBuf,
+        pub data_dir: PathBuf,
+        pub cache_dir: PathBuf,
+    }
+
+    impl Paths {
+        /// Everything under the folder holding `config_file`
+        pub fn rooted_at(config_file: PathBuf) -> Self {
+            let root = config_file
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_default();
+            Self {
+                config_dir: root.clone(),
+                data_dir: root.join("data"),
+                cache_dir: root.join("cache"),
+                config_file,
+            }
+        }
+    }
+
+    /// Use `path` as the config file for the rest of the process. Call before
+    /// anything loads; later calls are ignored.
+    pub fn set_config_path(path: PathBuf) {
+        let _ = CONFIG_OVERRIDE.set(path);
+    }
+
+    /// The override from `--config` or `MATRIX_IPTV_CONFIG`, if any
+    pub fn config_override() -> Option<PathBuf> {
+        CONFIG_OVERRIDE.get().cloned().or_else(|| {
+            std::env::var_os(CONFIG_ENV)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+        })
+    }
+
+    /// Current config file and data roots
+    pub fn paths() -> Option<Paths> {
+        if let Some(config_file) = config_override() {
+            return Some(Paths::rooted_at(config_file));
+        }
+        let proj = directories::ProjectDirs::from("com", "vibecoding", "vibe-iptv")?;
+        Some(Paths {
+            config_file: proj.config_dir().join("config.json"),
+            config_dir: proj.config_dir().to_path_buf(),
+            data_dir: proj.data_dir().to_path_buf(),
+            cache_dir: proj.cache_dir().to_path_buf(),
+        })
+    }
+}
+
+/// A place the config JSON is kept
+pub trait ConfigBackend: Send + Sync {
+    /// The stored JSON, or None when nothing has been saved yet
+    fn read(&self) -> anyhow::Result<Option<String>>;
+    fn write(&self, content: &str) -> anyhow::Result<()>;
+    /// Store a copy of `content` beside the config, named with `suffix`
+    /// (backups, broken files). Returns where it went.
+    fn keep_copy(&self, suffix: &str, content: &str) -> anyhow::Result<String>;
+    /// Shown by `--check` and in debug output
+    fn describe(&self) -> String;
+    /// Whether writes outlive the process. Passwords are only moved to the
+    /// credential vault for persistent storage.
+    fn persistent(&self) -> bool {
+        true
+    }
+}
+
+/// Shared handle to the config backend
+#[derive(Clone)]
+pub struct ConfigStorage(Arc<dyn ConfigBackend>);
+
+impl ConfigStorage {
+    pub fn new(backend: impl ConfigBackend + 'static) -> Self {
+        Self(Arc::new(backend))
+    }
+
+    /// Nothing on disk; starts from `initial` (e.g. a test fixture)
+    pub fn memory(initial: Option<String>) -> Self {
+        Self::new(MemoryStorage {
+            content: Mutex::new(initial),
+            copies: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// The config file at `path`
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn file(path: std::path::PathBuf) -> Self {
+        Self::new(FileStorage { path, legacy: None })
+    }
+
+    /// The config file from `paths()`. Without an override, a config left by
+    /// the old iptv-cli name is picked up on first read.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn platform() -> Self {
+        let legacy = if config_override().is_none() {
+            directories::ProjectDirs::from("com", "vibecoding", "iptv-cli")
+                .map(|p| p.config_dir().join("config.json"))
+        } else {
+            None
+        };
+        match paths() {
+            Some(paths) => Self::new(FileStorage {
+                path: paths.config_file,
+                legacy,
+            }),
+            None => Self::memory(None),
+        }
+    }
+
+    /// The browser's localStorage
+    #[cfg(target_arch = "wasm32")]
+    pub fn platform() -> Self {
+        Self::new(LocalStorage {
+            key: "app_config".to_string(),
+        })
+    }
+
+    pub fn read(&self) -> anyhow::Result<Option<String>> {
+        self.0.read()
+    }
+
+    pub fn write(&self, content: &str) -> anyhow::Result<()> {
+        self.0.write(content)
+    }
+
+    pub fn keep_copy(&self, suffix: &str, content: &str) -> anyhow::Result<String> {
+        self.0.keep_copy(suffix, content)
+    }
+
+    pub fn describe(&self) -> String {
+        self.0.describe()
+    }
+
+    pub fn persistent(&self) -> bool {
+        self.0.persistent()
+    }
+}
+
+impl Default for ConfigStorage {
+    fn default() -> Self {
+        Self::platform()
+    }
+}
+
+impl std::fmt::Debug for ConfigStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ConfigStorage")
+            .field(&self.0.describe())
+            .finish()
+    }
+}
+
+struct MemoryStorage {
+    content: Mutex<Option<String>>,
+    copies: Mutex<Vec<(String, String)>>,
+}
+
+impl ConfigBackend for MemoryStorage {
+    fn read(&self) -> anyhow::Result<Option<String>> {
+        Ok(self
+            .content
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone())
+    }
+
+    fn write(&self, content: &str) -> anyhow::Result<()> {
+        *self.content.lock().unwrap_or_else(|e| e.into_inner()) = Some(content.to_string());
+        Ok(())
+    }
+
+    fn keep_copy(&self, suffix: &str, content: &str) -> anyhow::Result<String> {
+        let name = format!("memory.{}", suffix);
+        self.copies
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((name.clone(), content.to_string()));
+        Ok(name)
+    }
+
+    fn describe(&self) -> String {
+        "memory".to_string()
+    }
+
+    fn persistent(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct FileStorage {
+    path: std::path::PathBuf,
+    /// Older location copied over when `path` does not exist yet
+    legacy: Option<std::path::PathBuf>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ConfigBackend for FileStorage {
+    fn read(&self) -> anyhow::Result<Option<String>> {
+        if self.path.exists() {
+            return Ok(Some(std::fs::read_to_string(&self.path)?));
+        }
+        match self.legacy.as_ref().filter(|p| p.exists()) {
+            Some(legacy) => {
+                // MIGRATION: Copy old to new
+                let content = std::fs::read_to_string(legacy)?;
+                let _ = self.write(&content);
+                Ok(Some(content))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn write(&self, content: &str) -> anyhow::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    fn keep_copy(&self, suffix: &str, content: &str) -> anyhow::Result<String> {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{}", suffix));
+        let path = self.path.with_file_name(name);
+        std::fs::write(&path, content)?;
+        Ok(path.display().to_string())
+    }
+
+    fn describe(&self) -> String {
+        self.path.display().to_string()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+struct LocalStorage {
+    key: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl ConfigBackend for LocalStorage {
+    fn read(&self) -> anyhow::Result<Option<String>> {
+        Ok(web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+            .and_then(|s| s.get_item(&self.key).ok().flatten()))
+    }
+
+    fn write(&self, content: &str) -> anyhow::Result<()> {
+        if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = storage.set_item(&self.key, content);
+        }
+        Ok(())
+    }
+
+    fn keep_copy(&self, suffix: &str, content: &str) -> anyhow::Result<String> {
+        let key = format!("{}.{}", self.key, suffix);
+        if let Some(storage) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = storage.set_item(&key, content);
+        }
+        Ok(format!("localStorage[{}]", key))
+    }
+
+    fn describe(&self) -> String {
+        format!("localStorage[{}]", self.key)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn override_roots_data_and_cache_next_to_the_config() {
+        let paths = Paths::rooted_at(PathBuf::from("/profiles/kids/config.json"));
+        assert_eq!(paths.config_dir, PathBuf::from("/profiles/kids"));
+        assert_eq!(paths.data_dir, PathBuf::from("/profiles/kids/data"));
+        assert_eq!(paths.cache_dir, PathBuf::from("/profiles/kids/cache"));
+    }
+}
@@ -1,15 +1,67 @@
 use crate::app::{App, CurrentScreen, InputMode, SettingsState};
-use crate::ui::colors::{MATRIX_GREEN, MODERN_BG, SOFT_GREEN, TEXT_DIM, TEXT_SECONDARY};
+use crate::cast::{CastSession, CastState};
+use crate::player_control::{format_timestamp, NowPlaying};
+use crate::ui::colors::{
+    MATRIX_GREEN, MODERN_BG, SOFT_GREEN, STATUS_LIVE, STATUS_WARN, TEXT_DIM, TEXT_PRIMARY,
+    TEXT_SECONDARY,
+};
 use crate::ui::loading::get_loading_status_line;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style, Stylize},
+    style::{Color, Modifier, Style, Stylize},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
 
+/// Footer rows needed for the current state (bordered bar + optional now-playing bar,
+/// cast bar and score ticker)
+pub fn footer_height(app: &App) -> u16 {
+    2 + app.now_playing.is_some() as u16
+        + app.cast_session.is_some() as u16
+        + show_score_ticker(app) as u16
+}
+
+fn show_score_ticker(app: &App) -> bool {
+    app.config.score_ticker && !app.ticker_games().is_empty()
+}
+
 pub fn render_footer(f: &mut Frame, app: &App, area: Rect) {
+    let area = if show_score_ticker(app) && area.height >= 3 {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(2), Constraint::Length(1)])
+            .split(area);
+        render_score_ticker(f, app, chunks[1]);
+        chunks[0]
+    } else {
+        area
+    };
+
+    let area = match &app.now_playing {
+        Some(now_playing) if area.height >= 3 => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(2)])
+                .split(area);
+            render_now_playing_bar(f, app, now_playing, chunks[0]);
+            chunks[1]
+        }
+        _ => area,
+    };
+
+    let area = match &app.cast_session {
+        Some(session) if area.height >= 3 => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(2)])
+                .split(area);
+            render_cast_bar(f, app, session, chunks[0]);
+            chunks[1]
+        }
+        _ => area,
+    };
+
     // If loading, render inline loading status instead of normal hints
     if let Some(loading_line) = get_loading_status_line(app) {
         render_loading_footer(f, app, area, loading_line);
@@ -66,6 +118,10 @@ fn render_normal_footer(f: &mut Frame, app: &App, area: Rect) {
             hint!("e", "edit");
             hint!("d", "del");
             hint!("s", "sports");
+            hint!("D", "recordings");
+            hint!("H", "stats");
+            hint!("w", "what's new");
+            hint!("o", "offline");
             hint!("m", "mode");
             hint!("x", "settings");
             hint!("?", "help");
@@ -103,6 +159,13 @@ fn render_normal_footer(f: &mut Frame, app: &App, area: Rect) {
                         hint!("v", "fav");
                         hint!("g", "add group");
                         hint!("G", "groups");
+                        hint!("w/W", "multiview");
+                        hint!("o/O", "rec/schedule");
+                        hint!("A", "audio-only");
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if app.relay.is_some() {
+                            hint!("L", "relay url");
+                        }
                         hint!("?", "help");
                     }
                     crate::app::Pane::Episodes => {}
@@ -211,6 +274,21 @@ fn render_normal_footer(f: &mut Frame, app: &App, area: Rect) {
             hint!("↑↓", "navigate");
             hint!("R", "refresh");
         }
+        CurrentScreen::Recordings => {
+            hint!("esc", "back");
+            hint!("enter", "play");
+            hint!("o", "stop");
+            hint!("d", "del");
+        }
+        CurrentScreen::WatchStats => {
+            hint!("esc", "back");
+            hint!("c", "export csv");
+            hint!("J", "export json");
+        }
+        CurrentScreen::WhatsNew => {
+            hint!("esc", "back");
+            hint!("↑↓", "navigate");
+        }
         CurrentScreen::GroupManagement => {
             hint!("esc", "back");
             hint!("n", "new");
@@ -281,3 +359,255 @@ fn render_normal_footer(f: &mut Frame, app: &App, area: Rect) {
         f.render_widget(right_p, bar_chunks[1]);
     }
 }
+
+/// Scrolling single-line ticker of in-progress games. Games whose score just
+/// changed are highlighted; Ctrl+T jumps to the highlighted game's channel.
+fn render_score_ticker(f: &mut Frame, app: &App, area: Rect) {
+    let highlighted_id = app.highlighted_ticker_game().map(|g| g.id.clone());
+
+    let mut segments: Vec<(String, Style)> = Vec::new();
+    for game in app.ticker_games() {
+        let style = if app.is_ticker_highlighted(&game.id) {
+            Style::default()
+                .fg(STATUS_WARN)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(TEXT_PRIMARY)
+        };
+        let marker = if highlighted_id.as_deref() == Some(game.id.as_str()) {
+            "▸ "
+        } else {
+            "● "
+        };
+        segments.push((marker.to_string(), Style::default().fg(STATUS_LIVE)));
+        segments.push((game.ticker_label(), style));
+        segments.push(("   ".to_string(), Style::default()));
+    }
+
+    let prefix = vec![
+        Span::styled(" ctrl+t", Style::default().fg(MATRIX_GREEN)),
+        Span::styled(" watch │ ", Style::default().fg(TEXT_DIM)),
+    ];
+    let prefix_width: usize = prefix.iter().map(|s| s.content.chars().count()).sum();
+    let width = (area.width as usize).saturating_sub(prefix_width);
+
+    // Advance one column every 4 frames (~7 cols/sec)
+    let offset = (app.session.loading_tick / 4) as usize;
+
+    let mut spans = prefix;
+    spans.extend(scroll_segments(&segments, offset, width));
+    f.render_widget(Paragraph::new(Line::from(spans)).bg(MODERN_BG), area);
+}
+
+/// One-line transport bar for the stream running in the external player
+fn render_now_playing_bar(f: &mut Frame, app: &App, now_playing: &NowPlaying, area: Rect) {
+    const ACTION_FEEDBACK_TICKS: u64 = 90; // ~3s at the 33ms frame cadence
+
+    let key_style = Style::default().fg(MATRIX_GREEN);
+    let label_style = Style::default().fg(TEXT_SECONDARY);
+    let dim_style = Style::default().fg(TEXT_DIM);
+
+    let paused = now_playing.status.as_ref().is_some_and(|s| s.paused);
+    let audio_only = now_playing.status.as_ref().is_some_and(|s| !s.has_video);
+    let (icon, icon_style) = if paused {
+        ("❚❚ ", Style::default().fg(STATUS_WARN))
+    } else if audio_only {
+        ("♪ ", Style::default().fg(MATRIX_GREEN))
+    } else {
+        ("▶ ", Style::default().fg(STATUS_LIVE))
+    };
+
+    let mut spans = vec![
+        Span::styled(format!(" {}", icon), icon_style),
+        Span::styled(
+            now_playing.display_title().to_string(),
+            Style::default()
+                .fg(TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+
+    if let Some(status) = &now_playing.status {
+        if audio_only {
+            let song = status.icy_title.as_deref().unwrap_or("audio only");
+            spans.push(Span::styled(
+                format!("  ♫ {}", song),
+                Style::default().fg(MATRIX_GREEN),
+            ));
+        }
+        let position = status.position_secs.map(format_timestamp);
+        let time = match (position, status.duration_secs) {
+            (Some(pos), Some(dur)) => format!("{} / {}", pos, format_timestamp(dur)),
+            (Some(pos), None) => format!("{} live", pos),
+            _ => String::new(),
+        };
+        if !time.is_empty() {
+            spans.push(Span::styled(format!("  {}", time), label_style));
+        }
+
+        let volume = if status.muted {
+            "muted".to_string()
+        } else {
+            status
+                .volume
+                .map(|v| format!("vol {:.0}", v))
+                .unwrap_or_default()
+        };
+        let subs = status
+            .subtitle_track
+            .map(|id| format!("sub {}", id))
+            .unwrap_or_else(|| "sub off".to_string());
+        let audio = status
+            .audio_track
+            .map(|id| format!("aud {}", id))
+            .unwrap_or_else(|| "aud off".to_string());
+        spans.push(Span::styled(
+            format!("  {} · {} · {}", volume, audio, subs),
+            dim_style,
+        ));
+    }
+
+    if let Some((label, tick)) = &now_playing.last_action {
+        if app.session.loading_tick.wrapping_sub(*tick) < ACTION_FEEDBACK_TICKS {
+            spans.push(Span::styled(
+                format!("  [{}]", label),
+                Style::default().fg(MATRIX_GREEN),
+            ));
+        }
+    }
+
+    if let Some(session) = &app.multiview_session {
+        if let Some(tile) = session.tiles.get(session.audio_focus) {
+            spans.push(Span::styled(
+                format!("  ♪ {} {}", session.audio_focus + 1, tile.name),
+                Style::default().fg(STATUS_WARN),
+            ));
+        }
+    }
+
+    spans.push(Span::styled(" │ ", dim_style));
+    if app.multiview_session.is_some() {
+        spans.push(Span::styled("alt+1-4", key_style));
+        spans.push(Span::styled(" audio tile ", label_style));
+    }
+    for (key, label) in [
+        ("alt+space", "pause"),
+        ("alt+←→", "±10s"),
+        ("alt+↑↓", "±60s"),
+        ("alt+-/=", "vol"),
+        ("alt+m", "mute"),
+        ("alt+a/s", "audio/subs"),
+        ("alt+f", "full"),
+        ("alt+v", "audio-only"),
+        ("alt+pgup/pgdn", "ch-/ch+"),
+    ] {
+        spans.push(Span::styled(key, key_style));
+        spans.push(Span::styled(format!(" {} ", label), label_style));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)).bg(MODERN_BG), area);
+}
+
+/// One-line remote control bar for the stream playing on a cast device
+fn render_cast_bar(f: &mut Frame, app: &App, session: &CastSession, area: Rect) {
+    const ACTION_FEEDBACK_TICKS: u64 = 90; // ~3s at the 33ms frame cadence
+
+    let key_style = Style::default().fg(MATRIX_GREEN);
+    let label_style = Style::default().fg(TEXT_SECONDARY);
+    let dim_style = Style::default().fg(TEXT_DIM);
+
+    let state = app.cast_status.as_ref().map(|s| s.state);
+    let icon_style = match state {
+        Some(CastState::Playing) => Style::default().fg(STATUS_LIVE),
+        Some(CastState::Paused | CastState::Buffering) => Style::default().fg(STATUS_WARN),
+        _ => dim_style,
+    };
+
+    let mut spans = vec![
+        Span::styled(" ⇪ ", icon_style),
+        Span::styled(
+            session.title.clone(),
+            Style::default()
+                .fg(TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!(" → {}", session.device_name()), label_style),
+    ];
+
+    match &app.cast_status {
+        Some(status) => {
+            let mut details = vec![status.state.label().to_string()];
+            if let Some(pos) = status.position_secs {
+                details.push(format_timestamp(pos));
+            }
+            if status.muted {
+                details.push("muted".to_string());
+            } else if let Some(volume) = status.volume {
+                details.push(format!("vol {:.0}", volume));
+            }
+            spans.push(Span::styled(
+                format!("  {}", details.join(" · ")),
+                dim_style,
+            ));
+        }
+        None => spans.push(Span::styled("  connecting…", dim_style)),
+    }
+
+    if let Some((label, tick)) = &app.cast_last_action {
+        if app.session.loading_tick.wrapping_sub(*tick) < ACTION_FEEDBACK_TICKS {
+            spans.push(Span::styled(
+                format!("  [{}]", label),
+                Style::default().fg(MATRIX_GREEN),
+            ));
+        }
+    }
+
+    spans.push(Span::styled(" │ ", dim_style));
+    for (key, label) in [
+        ("alt+space", "pause"),
+        ("alt+←→", "±10s"),
+        ("alt+-/=", "vol"),
+        ("alt+m", "mute"),
+        ("alt+pgup/pgdn", "ch-/ch+"),
+        ("alt+x", "stop casting"),
+    ] {
+        spans.push(Span::styled(key, key_style));
+        spans.push(Span::styled(format!(" {} ", label), label_style));
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)).bg(MODERN_BG), area);
+}
+
+/// Cuts a `width`-column window out of the looped segment text starting at `offset`.
+fn scroll_segments(
+    segments: &[(String, Style)],
+    offset: usize,
+    width: usize,
+) -> Vec<Span<'static>> {
+    let chars: Vec<(char, Style)> = segments
+        .iter()
+        .flat_map(|(text, style)| text.chars().map(move |c| (c, *style)))
+        .collect();
+    if chars.is_empty() || width == 0 {
+        return Vec::new();
+    }
+
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut current = String::new();
+    let mut current_style: Option<Style> = None;
+    for i in 0..width {
+        let (c, style) = chars[(offset + i) % chars.len()];
+        if current_style != Some(style) && !current.is_empty() {
+            spans.push(Span::styled(
+                std::mem::take(&mut current),
+                current_style.unwrap_or_default(),
+            ));
+        }
+        current_style = Some(style);
+        current.push(c);
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(current, current_style.unwrap_or_default()));
+    }
+    spans
+}
@@ -571,18 +571,18 @@ pub fn render_settings(f: &mut Frame, app: &mut App, area: Rect) {
             );
         }
         SettingsState::PlayerEngineSelection => {
-            let engines = crate::config::PlayerEngine::all();
-            let items: Vec<ListItem> = engines
+            let choices = app.player_choices();
+            let items: Vec<ListItem> = choices
                 .iter()
-                .map(|e| {
-                    let is_current = *e == app.config.preferred_player;
+                .map(|c| {
+                    let is_current = app.is_current_player(c);
                     ListItem::new(Line::from(vec![
                         Span::styled(
                             if is_current { "✓ " } else { "  " },
                             Style::default().fg(if is_current { MATRIX_GREEN } else { TEXT_DIM }),
                         ),
                         Span::styled(
-                            e.display_name(),
+                            c.label(),
                             Style::default().fg(if is_current {
                                 MATRIX_GREEN
                             } else {
@@ -597,7 +597,7 @@ pub fn render_settings(f: &mut Frame, app: &mut App, area: Rect) {
                 match idx {
                     0 => "MPV: High performance, advanced upscaling, best for high-end machines.",
                     1 => "VLC: High stability, optimized for jittery streams and low-end hardware.",
-                    _ => "",
+                    _ => "External player from Settings › Player Profiles. Arguments use {url}, {title}, {user_agent}, {referrer} and {ipc}; stats and zapping need mpv IPC.",
                 }
             } else {
                 ""
@@ -610,10 +610,254 @@ pub fn render_settings(f: &mut Frame, app: &mut App, area: Rect) {
                 items,
                 &mut app.player_engine_list_state,
                 desc,
-                6,
+                choices.len() as u16 + 4,
                 nav_hints(),
             );
         }
+        SettingsState::RecordingsFolder => {
+            let items = vec![ListItem::new(Line::from(vec![
+                Span::styled("> ", Style::default().fg(MATRIX_GREEN)),
+                Span::styled(
+                    format!("{}█", app.recordings_dir_input.value()),
+                    Style::default().fg(MATRIX_GREEN),
+                ),
+            ]))];
+            #[cfg(not(target_arch = "wasm32"))]
+            let desc = format!(
+                "Folder for new recordings; the recordings screen lists it too. Leave empty for the default ({}).",
+                crate::config::AppConfig::default_recordings_path().display()
+            );
+            #[cfg(target_arch = "wasm32")]
+            let desc = "Folder for new recordings. Leave empty for the default.".to_string();
+
+            let key_style = Style::default().fg(MATRIX_GREEN);
+            let label_style = Style::default().fg(TEXT_SECONDARY);
+            let hints = Line::from(vec![
+                Span::styled("enter", key_style),
+                Span::styled(" save", label_style),
+                Span::styled(" · ", Style::default().fg(TEXT_DIM)),
+                Span::styled("esc", key_style),
+                Span::styled(" cancel", label_style),
+            ]);
+            render_settings_subscreen(
+                f,
+                area,
+                "recordings folder",
+                items,
+                &mut ratatui::widgets::ListState::default(),
+                &desc,
+                5,
+                hints,
+            );
+        }
+        SettingsState::RelayPort => {
+            let items = vec![ListItem::new(Line::from(vec![
+                Span::styled("> ", Style::default().fg(MATRIX_GREEN)),
+                Span::styled(
+                    format!("{}█", app.relay_port_input.value()),
+                    Style::default().fg(MATRIX_GREEN),
+                ),
+            ]))];
+            let key_style = Style::default().fg(MATRIX_GREEN);
+            let label_style = Style::default().fg(TEXT_SECONDARY);
+            let hints = Line::from(vec![
+                Span::styled("enter", key_style),
+                Span::styled(" save", label_style),
+                Span::styled(" · ", Style::default().fg(TEXT_DIM)),
+                Span::styled("esc", key_style),
+                Span::styled(" cancel", label_style),
+            ]);
+            render_settings_subscreen(
+                f,
+                area,
+                "relay port",
+                items,
+                &mut ratatui::widgets::ListState::default(),
+                "Port the relay listens on (1-65535). A running relay moves to it right away.",
+                5,
+                hints,
+            );
+        }
+        SettingsState::PlayerProfiles => {
+            let mut items: Vec<ListItem> = app
+                .config
+                .player_profiles
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    let name = match (&app.player_profile_input, app.player_profile_renaming) {
+                        (Some(input), Some(idx)) if idx == i => format!("{}█", input.value()),
+                        _ => p.name.clone(),
+                    };
+                    let active = app.config.preferred_player == crate::config::PlayerEngine::Custom
+                        && app.config.active_player_profile.as_deref() == Some(p.name.as_str());
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("  {}", name),
+                            Style::default().fg(if active { MATRIX_GREEN } else { TEXT_PRIMARY }),
+                        ),
+                        Span::styled(
+                            format!(
+                                "  {} {}{}",
+                                p.executable,
+                                p.args,
+                                if p.ipc == crate::player_profile::IpcFlavour::Mpv {
+                                    " · ipc"
+                                } else {
+                                    ""
+                                }
+                            ),
+                            Style::default().fg(TEXT_SECONDARY),
+                        ),
+                    ]))
+                })
+                .collect();
+            match (&app.player_profile_input, app.player_profile_renaming) {
+                (Some(input), None) => items.push(ListItem::new(Line::from(vec![
+                    Span::styled("+ ", Style::default().fg(MATRIX_GREEN)),
+                    Span::styled(
+                        format!("{}█", input.value()),
+                        Style::default().fg(MATRIX_GREEN),
+                    ),
+                ]))),
+                _ if items.is_empty() => items.push(ListItem::new(Span::styled(
+                    "  No player profiles yet",
+                    Style::default().fg(TEXT_DIM),
+                ))),
+                _ => {}
+            }
+
+            let desc = match &app.login_error {
+                Some(err) if app.player_profile_input.is_some() => err.as_str(),
+                _ if app.player_profile_renaming.is_some() => "Type the new name.",
+                _ => "Enter name | executable | arguments, e.g. ffplay | ffplay | -window_title {title} {url}. Arguments may use {url}, {title}, {user_agent}, {referrer} and {ipc}; {ipc} turns on mpv IPC for stats and zapping.",
+            };
+
+            let key_style = Style::default().fg(MATRIX_GREEN);
+            let label_style = Style::default().fg(TEXT_SECONDARY);
+            let sep_style = Style::default().fg(TEXT_DIM);
+            let hints = if app.player_profile_input.is_some() {
+                Line::from(vec![
+                    Span::styled("enter", key_style),
+                    Span::styled(" save", label_style),
+                    Span::styled(" · ", sep_style),
+                    Span::styled("esc", key_style),
+                    Span::styled(" cancel", label_style),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::styled("a", key_style),
+                    Span::styled(" add", label_style),
+                    Span::styled(" · ", sep_style),
+                    Span::styled("e", key_style),
+                    Span::styled(" rename", label_style),
+                    Span::styled(" · ", sep_style),
+                    Span::styled("d", key_style),
+                    Span::styled(" delete", label_style),
+                    Span::styled(" · ", sep_style),
+                    Span::styled("esc", key_style),
+                    Span::styled(" back", label_style),
+                ])
+            };
+
+            let list_height = items.len() as u16 + 4;
+            let mut list_state = app.player_profile_list_state;
+            if app.player_profile_input.is_some() && app.player_profile_renaming.is_none() {
+                list_state.select(None);
+            }
+            render_settings_subscreen(
+                f,
+                area,
+                "player profiles",
+                items,
+                &mut list_state,
+                desc,
+                list_height,
+                hints,
+            );
+        }
+        SettingsState::KodiHosts => {
+            let mut items: Vec<ListItem> = app
+                .config
+                .kodi_hosts
+                .iter()
+                .map(|h| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("  {}", h.name), Style::default().fg(TEXT_PRIMARY)),
+                        Span::styled(
+                            format!(
+                                "  {}{}",
+                                h.address(),
+                                if h.username.is_some() { " · auth" } else { "" }
+                            ),
+                            Style::default().fg(TEXT_SECONDARY),
+                        ),
+                    ]))
+                })
+                .collect();
+            if let Some(input) = &app.kodi_host_input {
+                items.push(ListItem::new(Line::from(vec![
+                    Span::styled("+ ", Style::default().fg(MATRIX_GREEN)),
+                    Span::styled(
+                        format!("{}█", input.value()),
+                        Style::default().fg(MATRIX_GREEN),
+                    ),
+                ])));
+            } else if items.is_empty() {
+                items.push(ListItem::new(Span::styled(
+                    "  No Kodi hosts yet",
+                    Style::default().fg(TEXT_DIM),
+                )));
+            }
+
+            let desc = match &app.login_error {
+                Some(err) if app.kodi_host_input.is_some() => err.as_str(),
+                _ => "Enter [user[:password]@]host[:port] (port defaults to 8080). In Kodi, turn on Settings › Services › Control › Allow remote control via HTTP.",
+            };
+
+            let key_style = Style::default().fg(MATRIX_GREEN);
+            let label_style = Style::default().fg(TEXT_SECONDARY);
+            let sep_style = Style::default().fg(TEXT_DIM);
+            let hints = if app.kodi_host_input.is_some() {
+                Line::from(vec![
+                    Span::styled("enter", key_style),
+                    Span::styled(" save", label_style),
+                    Span::styled(" · ", sep_style),
+                    Span::styled("esc", key_style),
+                    Span::styled(" cancel", label_style),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::styled("a", key_style),
+                    Span::styled(" add", label_style),
+                    Span::styled(" · ", sep_style),
+                    Span::styled("d", key_style),
+                    Span::styled(" delete", label_style),
+                    Span::styled(" · ", sep_style),
+                    Span::styled("enter", key_style),
+                    Span::styled(" test", label_style),
+                    Span::styled(" · ", sep_style),
+                    Span::styled("esc", key_style),
+                    Span::styled(" back", label_style),
+                ])
+            };
+
+            let list_height = items.len() as u16 + 4;
+            let mut list_state = app.kodi_host_list_state;
+            if app.kodi_host_input.is_some() {
+                list_state.select(None);
+            }
+            render_settings_subscreen(
+                f,
+                area,
+                "kodi hosts",
+                items,
+                &mut list_state,
+                desc,
+                list_height,
+                hints,
+            );
+        }
         SettingsState::PlaylistModeSelection => {
             let modes = crate::config::ProcessingMode::all();
 
@@ -772,6 +1016,78 @@ pub fn render_settings(f: &mut Frame, app: &mut App, area: Rect) {
                 nav_hints(),
             );
         }
+        SettingsState::TickerLeagueSelection => {
+            let leagues = crate::scores::league_names();
+            let ticker_on = app.config.score_ticker;
+            let all_leagues = ticker_on && app.config.ticker_leagues.is_empty();
+
+            let radio = |active: bool, label: &'static str, hint: &'static str| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        if active { "◉ " } else { "○ " },
+                        Style::default().fg(if active { MATRIX_GREEN } else { TEXT_DIM }),
+                    ),
+                    Span::styled(
+                        label,
+                        Style::default().fg(if active { MATRIX_GREEN } else { TEXT_PRIMARY }),
+                    ),
+                    Span::styled(hint, Style::default().fg(TEXT_SECONDARY)),
+                ]))
+            };
+
+            let mut items: Vec<ListItem> = vec![
+                radio(!ticker_on, "Off", "  hide the ticker"),
+                radio(all_leagues, "All Leagues", "  every in-progress game"),
+            ];
+            items.extend(leagues.iter().map(|league| {
+                let is_selected =
+                    ticker_on && app.config.ticker_leagues.iter().any(|l| l == league);
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        if is_selected { "◉ " } else { "○ " },
+                        Style::default().fg(if is_selected { MATRIX_GREEN } else { TEXT_DIM }),
+                    ),
+                    Span::styled(
+                        *league,
+                        Style::default().fg(if is_selected {
+                            MATRIX_GREEN
+                        } else {
+                            TEXT_PRIMARY
+                        }),
+                    ),
+                ]))
+            }));
+
+            let desc = match app.ticker_league_list_state.selected() {
+                Some(0) => "Turn the score ticker off.",
+                Some(1) => "Show in-progress games from every supported league.",
+                Some(_) => "Follow only the checked leagues. Ctrl+T jumps to the channel carrying the highlighted game.",
+                None => "",
+            };
+
+            let key_style = Style::default().fg(MATRIX_GREEN);
+            let label_style = Style::default().fg(TEXT_SECONDARY);
+            let sep_style = Style::default().fg(TEXT_DIM);
+            let hints = Line::from(vec![
+                Span::styled("space", key_style),
+                Span::styled(" toggle", label_style),
+                Span::styled(" · ", sep_style),
+                Span::styled("esc", key_style),
+                Span::styled(" done", label_style),
+            ]);
+
+            // +2 for the Off / All Leagues rows, +2 for borders
+            render_settings_subscreen(
+                f,
+                area,
+                "score ticker (space to toggle)",
+                items,
+                &mut app.ticker_league_list_state,
+                desc,
+                (leagues.len() + 4) as u16,
+                hints,
+            );
+        }
         SettingsState::CategoryManagement => {
             render_category_management(f, app, area);
         }
@@ -861,13 +1177,25 @@ pub fn render_category_management(f: &mut Frame, app: &mut App, area: Rect) {
                 Style::default().fg(TEXT_PRIMARY)
             };
 
-            ListItem::new(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(
                     checkbox,
                     Style::default().fg(if is_hidden { TEXT_DIM } else { MATRIX_GREEN }),
                 ),
                 Span::styled(cat.category_name.as_str(), style),
-            ]))
+            ];
+            if content_type == ContentType::Live {
+                let radio = match acc.radio_overrides.get(&cat.category_id) {
+                    Some(true) => Some("  📻 radio (set)"),
+                    Some(false) => Some("  video (set)"),
+                    None if app.is_radio_category(cat) => Some("  📻 radio"),
+                    None => None,
+                };
+                if let Some(radio) = radio {
+                    spans.push(Span::styled(radio, Style::default().fg(TEXT_SECONDARY)));
+                }
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -904,6 +1232,8 @@ pub fn render_category_management(f: &mut Frame, app: &mut App, area: Rect) {
     let hints = Line::from(vec![
         Span::styled("space", key_style),
         Span::styled(" toggle visibility  ", label_style),
+        Span::styled("a", key_style),
+        Span::styled(" radio  ", label_style),
         Span::styled("tab", key_style),
         Span::styled(" cycle type  ", label_style),
         Span::styled("s", key_style),
@@ -1,5 +1,5 @@
 use crate::app::{App, CurrentScreen};
-use crate::ui::colors::{MATRIX_GREEN, TEXT_DIM, TEXT_PRIMARY, TEXT_SECONDARY};
+use crate::ui::colors::{MATRIX_GREEN, STATUS_WARN, TEXT_DIM, TEXT_PRIMARY, TEXT_SECONDARY};
 use chrono::{TimeZone, Utc};
 use chrono_tz::Tz;
 use ratatui::{
@@ -151,6 +151,18 @@ pub fn render_header(f: &mut Frame, app: &App, area: Rect) {
             add_breadcrumb("home", false);
             add_breadcrumb("sports", true);
         }
+        CurrentScreen::Recordings => {
+            add_breadcrumb("home", false);
+            add_breadcrumb("recordings", true);
+        }
+        CurrentScreen::WatchStats => {
+            add_breadcrumb("home", false);
+            add_breadcrumb("statistics", true);
+        }
+        CurrentScreen::WhatsNew => {
+            add_breadcrumb("home", false);
+            add_breadcrumb("what's new", true);
+        }
         CurrentScreen::GlobalSearch => {
             add_breadcrumb("home", false);
             add_breadcrumb("search", true);
@@ -166,6 +178,18 @@ pub fn render_header(f: &mut Frame, app: &App, area: Rect) {
         ));
     }
 
+    // Offline mode: cached catalog, provider unreachable
+    if app.session.offline {
+        let queued = match app.offline_queue.len() {
+            0 => String::new(),
+            n => format!(" · {} queued", n),
+        };
+        left_spans.push(Span::styled(
+            format!("  ⚠ offline{}", queued),
+            Style::default().fg(STATUS_WARN),
+        ));
+    }
+
     let tabs = Paragraph::new(Line::from(left_spans));
     f.render_widget(tabs, left_inner);
 
@@ -0,0 +1,129 @@
+use crate::app::App;
+use crate::history::format_watch_time;
+use crate::ui::colors::{
+    MATRIX_GREEN, SOFT_GREEN, STATUS_WARN, TEXT_DIM, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Width of the bars in the rankings and weekly chart
+const BAR_WIDTH: usize = 16;
+
+/// Render the watch statistics screen: totals, hours per week and rankings
+pub fn render_watch_stats(f: &mut Frame, app: &App, area: Rect) {
+    let stats = app.watch_history.stats(chrono::Utc::now());
+    let inner = crate::ui::common::render_matrix_box(
+        f,
+        area,
+        &format!(" watch statistics ({} sessions) ", stats.sessions),
+        SOFT_GREEN,
+    );
+
+    if stats.sessions == 0 {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "  Nothing watched yet. Sessions longer than 30 seconds are recorded here.",
+                Style::default().fg(TEXT_SECONDARY),
+            )),
+            inner,
+        );
+        return;
+    }
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(inner);
+    let this_week = stats.weekly.last().map_or(0, |(_, secs)| *secs);
+    let summary = Line::from(vec![
+        Span::styled("  total ", Style::default().fg(TEXT_DIM)),
+        Span::styled(
+            format_watch_time(stats.total_secs),
+            Style::default()
+                .fg(TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("   this week ", Style::default().fg(TEXT_DIM)),
+        Span::styled(
+            format_watch_time(this_week),
+            Style::default()
+                .fg(TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(summary), rows[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(34),
+            Constraint::Percentage(33),
+            Constraint::Percentage(33),
+        ])
+        .split(rows[1]);
+
+    let weekly: Vec<(String, i64)> = stats
+        .weekly
+        .iter()
+        .map(|(week, secs)| (week.format("%d %b").to_string(), *secs))
+        .collect();
+
+    let mut left = section("top channels", &stats.top_channels);
+    left.push(Line::from(""));
+    left.extend(section("hours per week", &weekly));
+    f.render_widget(Paragraph::new(left), columns[0]);
+
+    let mut middle = section("by category", &stats.by_category);
+    middle.push(Line::from(""));
+    middle.extend(section("by league", &stats.by_league));
+    f.render_widget(Paragraph::new(middle), columns[1]);
+
+    f.render_widget(
+        Paragraph::new(section("by account", &stats.by_account)),
+        columns[2],
+    );
+}
+
+/// A heading plus one bar per row, scaled to the largest value
+fn section(title: &str, rows: &[(String, i64)]) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled(
+        format!("  {}", title),
+        Style::default()
+            .fg(TEXT_PRIMARY)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    if rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  none",
+            Style::default().fg(TEXT_DIM),
+        )));
+        return lines;
+    }
+
+    let max = rows.iter().map(|(_, secs)| *secs).max().unwrap_or(1).max(1);
+    for (name, secs) in rows {
+        let filled = ((*secs as f64 / max as f64) * BAR_WIDTH as f64).round() as usize;
+        let label: String = name.chars().take(22).collect();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<22} ", label),
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+            Span::styled("█".repeat(filled), Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "░".repeat(BAR_WIDTH - filled.min(BAR_WIDTH)),
+                Style::default().fg(TEXT_DIM),
+            ),
+            Span::styled(
+                format!(" {}", format_watch_time(*secs)),
+                Style::default().fg(STATUS_WARN),
+            ),
+        ]));
+    }
+    lines
+}
@@ -12,9 +12,9 @@ pub fn render_home(f: &mut Frame, app: &mut App, area: Rect) {
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(4), // Logo
-            Constraint::Min(0),    // Content
-            Constraint::Length(2), // Footer (bordered bar needs 2 rows)
+            Constraint::Length(4),                                     // Logo
+            Constraint::Min(0),                                        // Content
+            Constraint::Length(crate::ui::footer::footer_height(app)), // Footer (bordered bar + ticker)
         ])
         .split(area);
 
@@ -121,6 +121,18 @@ pub fn render_home(f: &mut Frame, app: &mut App, area: Rect) {
                 spans.push(Span::styled("  new", Style::default().fg(MATRIX_GREEN)));
             }
 
+            let unseen = app
+                .whats_new
+                .unseen_count(&acc.name, &app.config.processing_modes);
+            if unseen > 0 {
+                spans.push(Span::styled(
+                    format!("  ● {} new", unseen),
+                    Style::default()
+                        .fg(MATRIX_GREEN)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+
             ListItem::new(Line::from(spans))
         })
         .collect();
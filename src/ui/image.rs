@@ -0,0 +1,141 @@
+//! Logo and poster widget; downloading and encoding live in `crate::images`
+
+use crate::app::App;
+use ratatui::{layout::Rect, Frame};
+
+/// Draw the image at `url` centred in `area`, keeping its aspect ratio. Returns
+/// the cells it covers, or None when images are off or it is not loaded yet
+/// (the download is queued and a later frame draws it).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn render_image(f: &mut Frame, app: &App, area: Rect, url: &str) -> Option<Rect> {
+    use crate::config::ImageProtocol;
+    use crate::images::{Encoded, Placement};
+    use ratatui::style::Color;
+
+    if area.is_empty() || url.is_empty() || app.image_protocol == ImageProtocol::Off {
+        return None;
+    }
+    let image = app.images.get(url)?;
+    let (cols, rows) = crate::images::fit(
+        image.dimensions(),
+        area.width,
+        area.height,
+        crate::images::cell_size(),
+    );
+    let target = Rect {
+        x: area.x + (area.width - cols) / 2,
+        y: area.y + (area.height - rows) / 2,
+        width: cols,
+        height: rows,
+    };
+
+    match app
+        .images
+        .encode(url, &image, app.image_protocol, cols, rows)?
+    {
+        Encoded::Pixels(pixels) => {
+            // Blend over the black app background
+            let rgb = |p: &image::Rgba<u8>| {
+                let [r, g, b, a] = p.0;
+                let blend = |c: u8| (c as u16 * a as u16 / 255) as u8;
+                Color::Rgb(blend(r), blend(g), blend(b))
+            };
+            let buf = f.buffer_mut();
+            for row in 0..rows {
+                for col in 0..cols {
+                    let top = pixels.get_pixel(col as u32, row as u32 * 2);
+                    let bottom = pixels.get_pixel(col as u32, row as u32 * 2 + 1);
+                    if let Some(cell) = buf.cell_mut((target.x + col, target.y + row)) {
+                        cell.set_symbol("▀").set_fg(rgb(top)).set_bg(rgb(bottom));
+                    }
+                }
+            }
+        }
+        Encoded::Sequence(sequence) => {
+            // The terminal draws these cells; keep ratatui from writing over them
+            let buf = f.buffer_mut();
+            for pos in target.positions() {
+                if let Some(cell) = buf.cell_mut(pos) {
+                    cell.reset();
+                    cell.set_skip(true);
+                }
+            }
+            app.images.place(Placement {
+                area: target,
+                sequence,
+            });
+        }
+    }
+    Some(target)
+}
+
+/// WASM stub - no terminal graphics in the browser
+#[cfg(target_arch = "wasm32")]
+pub fn render_image(_f: &mut Frame, _app: &App, _area: Rect, _url: &str) -> Option<Rect> {
+    None
+}
+
+/// Whether space should be kept for the image at `url`: images are on and
+/// it has not failed to load
+#[cfg(not(target_arch = "wasm32"))]
+pub fn artwork_shown(app: &App, url: &str) -> bool {
+    app.image_protocol != crate::config::ImageProtocol::Off && !app.images.has_failed(url)
+}
+
+/// WASM stub - no terminal graphics in the browser
+#[cfg(target_arch = "wasm32")]
+pub fn artwork_shown(_app: &App, _url: &str) -> bool {
+    false
+}
+
+/// `render_image`, with a dim `placeholder` line while the image downloads
+pub fn render_artwork(f: &mut Frame, app: &App, area: Rect, url: &str, placeholder: &str) {
+    use crate::ui::colors::TEXT_DIM;
+    use ratatui::{layout::Alignment, style::Style, widgets::Paragraph};
+
+    if render_image(f, app, area, url).is_none() && area.height > 0 {
+        let middle = Rect {
+            y: area.y + area.height / 2,
+            height: 1,
+            ..area
+        };
+        f.render_widget(
+            Paragraph::new(placeholder)
+                .style(Style::default().fg(TEXT_DIM))
+                .alignment(Alignment::Center),
+            middle,
+        );
+    }
+}
+
+/// Poster for the play popup and VOD details: the provider's movie or series
+/// artwork, falling back to the focused item's icon
+pub fn poster_url(app: &App) -> Option<&str> {
+    fn from_info<'a>(info: Option<&'a serde_json::Value>, keys: &[&str]) -> Option<&'a str> {
+        let map = info?.as_object()?;
+        keys.iter()
+            .filter_map(|k| map.get(*k).and_then(|v| v.as_str()))
+            .find(|url| url.starts_with("http"))
+    }
+    from_info(
+        app.current_vod_info.as_ref().and_then(|v| v.info.as_ref()),
+        &["movie_image", "cover_big", "cover"],
+    )
+    .or_else(|| {
+        from_info(
+            app.current_series_info
+                .as_ref()
+                .and_then(|s| s.info.as_ref()),
+            &["cover", "cover_big"],
+        )
+    })
+    .or_else(|| logo_url(app.focused_playable_stream()?))
+}
+
+/// A stream's logo or cover, if it looks like a fetchable URL
+pub fn logo_url(stream: &crate::api::Stream) -> Option<&str> {
+    stream
+        .stream_icon
+        .as_deref()
+        .filter(|url| url.starts_with("http"))
+}
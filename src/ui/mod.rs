@@ -4,14 +4,18 @@ pub mod footer;
 pub mod form;
 pub mod groups;
 pub mod header;
+pub mod history;
 pub mod home;
+pub mod image;
 pub mod loading;
 pub mod panes;
 pub mod popups;
+pub mod recordings;
 pub mod series;
 pub mod sports;
 pub mod utils;
 pub mod vod;
+pub mod whats_new;
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -93,7 +97,10 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         CurrentScreen::UpdatePrompt => {
             popups::render_update_prompt(f, app, inner_area);
         }
-        CurrentScreen::SportsDashboard => {
+        CurrentScreen::SportsDashboard
+        | CurrentScreen::Recordings
+        | CurrentScreen::WatchStats
+        | CurrentScreen::WhatsNew => {
             render_main_layout(f, app, inner_area);
         }
     }
@@ -163,6 +170,18 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         popups::render_cast_picker_popup(f, app, area);
     }
 
+    if app.show_multiview {
+        popups::render_multiview_popup(f, app, area);
+    }
+
+    if app.schedule_picker_channel.is_some() {
+        popups::render_schedule_picker_popup(f, app, area);
+    }
+
+    if !app.toasts.is_empty() {
+        popups::render_toasts(f, app, area);
+    }
+
     if let Some(error) = &app.login_error {
         if app.current_screen != CurrentScreen::Login {
             popups::render_error_popup(f, area, error);
@@ -175,9 +194,9 @@ fn render_main_layout(f: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(header_height), // Header
-            Constraint::Min(0),                // Content
-            Constraint::Length(2),             // Footer (bordered bottom bar)
+            Constraint::Length(header_height),              // Header
+            Constraint::Min(0),                             // Content
+            Constraint::Length(footer::footer_height(app)), // Footer (bordered bottom bar)
         ])
         .split(area);
 
@@ -226,8 +245,20 @@ fn render_main_layout(f: &mut Frame, app: &mut App, area: Rect) {
                         panes::render_streams_pane(f, app, h_chunks[0], SOFT_GREEN);
                     }
 
-                    // Right detail panel (always visible)
-                    panes::render_channel_detail_panel(f, app, h_chunks[1], SOFT_GREEN);
+                    // Right detail panel (always visible), with playback stats while mpv runs
+                    if app.now_playing.is_some() {
+                        let detail_chunks = Layout::default()
+                            .direction(Direction::Vertical)
+                            .constraints([
+                                Constraint::Min(8),
+                                Constraint::Length(panes::PLAYBACK_STATS_HEIGHT),
+                            ])
+                            .split(h_chunks[1]);
+                        panes::render_channel_detail_panel(f, app, detail_chunks[0], SOFT_GREEN);
+                        panes::render_playback_stats_panel(f, app, detail_chunks[1], SOFT_GREEN);
+                    } else {
+                        panes::render_channel_detail_panel(f, app, h_chunks[1], SOFT_GREEN);
+                    }
                 } else {
                     // Narrow terminal: 1-column layout (streams only)
                     let h_chunks = Layout::default()
@@ -266,6 +297,15 @@ fn render_main_layout(f: &mut Frame, app: &mut App, area: Rect) {
         CurrentScreen::SportsDashboard => {
             sports::render_sports_view(f, app, content_area);
         }
+        CurrentScreen::Recordings => {
+            recordings::render_recordings_view(f, app, content_area);
+        }
+        CurrentScreen::WatchStats => {
+            history::render_watch_stats(f, app, content_area);
+        }
+        CurrentScreen::WhatsNew => {
+            whats_new::render_whats_new(f, app, content_area);
+        }
         CurrentScreen::Login => {
             form::render_login(f, app, content_area);
         }
@@ -1,8 +1,10 @@
 use crate::api::Category;
 use crate::app::{App, CurrentScreen};
 use crate::parser::{country_color, country_flag, parse_category, parse_stream};
+use crate::player_control::format_bitrate;
 use crate::ui::colors::{
-    HIGHLIGHT_BG, MATRIX_GREEN, SOFT_GREEN, TEXT_DIM, TEXT_PRIMARY, TEXT_SECONDARY,
+    HIGHLIGHT_BG, MATRIX_GREEN, SOFT_GREEN, STATUS_LIVE, STATUS_WARN, TEXT_DIM, TEXT_PRIMARY,
+    TEXT_SECONDARY,
 };
 use crate::ui::common::stylize_channel_name;
 use crate::ui::utils::visible_window;
@@ -12,7 +14,7 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Sparkline, Table},
     Frame,
 };
 use std::sync::Arc;
@@ -512,6 +514,23 @@ pub fn render_streams_pane(f: &mut Frame, app: &mut App, area: Rect, border_colo
                 ));
             }
 
+            // 4b. Multiview tile number
+            if let Some(tile) = app.multiview_tiles.iter().position(|t| t.stream_id == s_id) {
+                spans.push(ratatui::text::Span::styled(
+                    format!("[{}] ", tile + 1),
+                    Style::default().fg(STATUS_WARN),
+                ));
+            }
+
+            // 4c. Recording indicator
+            #[cfg(not(target_arch = "wasm32"))]
+            if app.active_recording(&s_id).is_some() {
+                spans.push(ratatui::text::Span::styled(
+                    "● ",
+                    Style::default().fg(STATUS_LIVE),
+                ));
+            }
+
             // 5. Country Flag
             if let Some(ref country) = parsed.country {
                 let is_us_en =
@@ -1102,7 +1121,19 @@ pub fn render_channel_detail_panel(f: &mut Frame, app: &mut App, area: Rect, bor
         parse_stream(&s.name, app.session.provider_timezone.as_deref())
     };
 
-    let inner = crate::ui::common::render_matrix_box(f, area, "details", border_color);
+    let mut inner = crate::ui::common::render_matrix_box(f, area, "details", border_color);
+
+    // Channel logo strip above the details when the panel is tall enough
+    if inner.height >= 24 {
+        if let Some(url) =
+            crate::ui::image::logo_url(s).filter(|url| crate::ui::image::artwork_shown(app, url))
+        {
+            let logo_area = Rect { height: 5, ..inner };
+            crate::ui::image::render_artwork(f, app, logo_area, url, "loading logo…");
+            inner.y += 6;
+            inner.height -= 6;
+        }
+    }
 
     let label_color = MATRIX_GREEN;
     let value_style = Style::default().fg(TEXT_PRIMARY);
@@ -1519,6 +1550,108 @@ pub fn render_channel_detail_panel(f: &mut Frame, app: &mut App, area: Rect, bor
     f.render_widget(Paragraph::new(lines), inner);
 }
 
+/// Rows the playback stats panel needs (header rows + sparkline)
+pub const PLAYBACK_STATS_HEIGHT: u16 = 14;
+
+/// Live decoder/network statistics for the stream playing in mpv, polled over IPC
+pub fn render_playback_stats_panel(f: &mut Frame, app: &App, area: Rect, border_color: Color) {
+    let inner = crate::ui::common::render_matrix_box(f, area, "playback", border_color);
+    let Some(stats) = app.now_playing.as_ref().and_then(|np| np.stats.as_ref()) else {
+        let msg = Paragraph::new("Waiting for player…")
+            .style(Style::default().fg(TEXT_DIM))
+            .alignment(ratatui::layout::Alignment::Center);
+        f.render_widget(msg, inner);
+        return;
+    };
+
+    let label_style = Style::default().fg(MATRIX_GREEN);
+    let value_style = Style::default().fg(TEXT_PRIMARY);
+    let dim_style = Style::default().fg(TEXT_DIM);
+    let dash = || "—".to_string();
+
+    let resolution = match (stats.width, stats.height) {
+        (Some(w), Some(h)) => format!("{}x{}", w, h),
+        _ => dash(),
+    };
+    let fps = stats
+        .fps
+        .map(|fps| format!(" @ {:.0}fps", fps))
+        .unwrap_or_default();
+    let codecs = format!(
+        "{} / {}",
+        stats.video_codec.clone().unwrap_or_else(dash),
+        stats.audio_codec.clone().unwrap_or_else(dash)
+    );
+    let bitrate = stats.bitrate.map(format_bitrate).unwrap_or_else(dash);
+    let input = stats
+        .input_rate
+        .map(|rate| format!(" (in {})", format_bitrate(rate * 8.0)))
+        .unwrap_or_default();
+    let cache = stats
+        .cache_secs
+        .map(|c| format!("{:.1}s", c))
+        .unwrap_or_else(dash);
+    let cache_style = if stats.buffering || stats.cache_secs.is_some_and(|c| c < 1.0) {
+        Style::default().fg(STATUS_LIVE)
+    } else {
+        value_style
+    };
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Video   ", label_style),
+            Span::styled(format!("{}{}", resolution, fps), value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Codec   ", label_style),
+            Span::styled(codecs, value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Bitrate ", label_style),
+            Span::styled(bitrate, value_style),
+            Span::styled(input, dim_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Dropped ", label_style),
+            Span::styled(format!("{} frames", stats.dropped_frames), value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Reconn. ", label_style),
+            Span::styled(stats.reconnects.to_string(), value_style),
+        ]),
+        Line::from(vec![
+            Span::styled("Cache   ", label_style),
+            Span::styled(cache, cache_style),
+            Span::styled(
+                if stats.buffering { " buffering" } else { "" },
+                Style::default().fg(STATUS_WARN),
+            ),
+        ]),
+    ];
+    let verdict = match stats.diagnosis() {
+        Some(reason) => Span::styled(reason, Style::default().fg(STATUS_WARN)),
+        None => Span::styled("Healthy", Style::default().fg(SOFT_GREEN)),
+    };
+    lines.push(Line::from(verdict));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(lines.len() as u16), Constraint::Min(1)])
+        .split(inner);
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+
+    // Cache fill over time; the newest samples that fit the panel width
+    if let Some(np) = &app.now_playing {
+        let width = chunks[1].width as usize;
+        let skip = np.cache_history.len().saturating_sub(width);
+        let samples: Vec<u64> = np.cache_history.iter().skip(skip).copied().collect();
+        let sparkline = Sparkline::default()
+            .data(&samples)
+            .style(Style::default().fg(SOFT_GREEN));
+        f.render_widget(sparkline, chunks[1]);
+    }
+}
+
 pub fn render_stream_details_pane(f: &mut Frame, app: &mut App, area: Rect, border_color: Color) {
     let title = " match intelligence ";
     let inner_area = crate::ui::common::render_matrix_box(f, area, title, border_color);
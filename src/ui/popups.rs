@@ -12,11 +12,6 @@ use ratatui::{
 };
 
 pub fn render_help_popup(f: &mut Frame, area: Rect) {
-    let area = centered_rect(64, 82, area);
-    f.render_widget(Clear, area);
-
-    let inner_area = crate::ui::common::render_composite_block(f, area, Some("keyboard shortcuts"));
-
     let help_text = vec![
         Line::from(vec![Span::styled(
             "  navigation",
@@ -106,6 +101,156 @@ pub fn render_help_popup(f: &mut Frame, area: Rect) {
             Span::styled("  ctrl+space  ", Style::default().fg(MATRIX_GREEN)),
             Span::styled("search", Style::default().fg(TEXT_SECONDARY)),
         ]),
+        Line::from(vec![
+            Span::styled("  ctrl+t      ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled("watch ticker game", Style::default().fg(TEXT_SECONDARY)),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "  now playing (mpv)",
+            Style::default()
+                .fg(TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  alt+space   ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled("pause / resume", Style::default().fg(TEXT_SECONDARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  alt+← →     ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled("seek -/+ 10s", Style::default().fg(TEXT_SECONDARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  alt+↓ ↑     ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled("seek -/+ 60s", Style::default().fg(TEXT_SECONDARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  alt+- =     ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled("volume down / up", Style::default().fg(TEXT_SECONDARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  alt+m       ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled("mute", Style::default().fg(TEXT_SECONDARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  alt+a / s   ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "cycle audio / subtitle track",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  alt+f       ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled("toggle fullscreen", Style::default().fg(TEXT_SECONDARY)),
+        ]),
+        Line::from(vec![
+            Span::styled("  A / alt+v   ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "audio-only mode (radio categories always play audio-only)",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  C           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "cast to a Chromecast, DLNA or Kodi device (alt keys control it, alt+x stops)",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  alt+pgup/dn ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "channel up / down (pgup/pgdn in the mpv window)",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  alt+1-4     ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "multiview: audio from tile",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "  multiview",
+            Style::default()
+                .fg(TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  w           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "mark / unmark channel as a tile (up to 4)",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  W           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "open multiview: launch, save or load presets",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "  recording",
+            Style::default()
+                .fg(TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("  o           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "start / stop recording the channel",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  O           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "schedule from the guide (once, weekly or every airing)",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  D           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "recordings: play, stop or delete",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  L           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "copy the channel's local relay URL (Settings › Local Relay)",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  H           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "watch statistics and history export (home screen)",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  w           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "what's new in the playlist since you last looked (home screen)",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  o           ", Style::default().fg(MATRIX_GREEN)),
+            Span::styled(
+                "browse the cached playlist offline, reconnecting when the provider is back (home screen)",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "  features",
@@ -145,10 +290,37 @@ pub fn render_help_popup(f: &mut Frame, area: Rect) {
         )]),
     ];
 
-    f.render_widget(
-        Paragraph::new(help_text).wrap(Wrap { trim: true }),
-        inner_area,
-    );
+    // Two columns, split at a section break, once the list outgrows the popup
+    let one_column = centered_rect(64, 82, area);
+    let two_columns = help_text.len() > one_column.height.saturating_sub(2) as usize;
+    let area = if two_columns {
+        centered_rect(92, 90, area)
+    } else {
+        one_column
+    };
+    f.render_widget(Clear, area);
+    let inner_area = crate::ui::common::render_composite_block(f, area, Some("keyboard shortcuts"));
+
+    if !two_columns {
+        f.render_widget(
+            Paragraph::new(help_text).wrap(Wrap { trim: true }),
+            inner_area,
+        );
+        return;
+    }
+    let half = help_text.len() / 2;
+    let split = (0..help_text.len())
+        .filter(|&i| help_text[i].width() == 0)
+        .min_by_key(|&i| i.abs_diff(half))
+        .unwrap_or(half);
+    let mut left = help_text;
+    let right = left.split_off(split + 1);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner_area);
+    f.render_widget(Paragraph::new(left).wrap(Wrap { trim: true }), columns[0]);
+    f.render_widget(Paragraph::new(right).wrap(Wrap { trim: true }), columns[1]);
 }
 
 pub fn render_guide_popup(f: &mut Frame, app: &App, area: Rect) {
@@ -422,6 +594,36 @@ pub fn render_content_type_selection(f: &mut Frame, app: &mut App, area: Rect) {
     f.render_widget(Paragraph::new(hints).alignment(Alignment::Left), layout[3]);
 }
 
+/// Stack of short notices in the bottom-right corner, newest at the bottom
+pub fn render_toasts(f: &mut Frame, app: &App, area: Rect) {
+    let width = area.width.saturating_sub(4).min(56);
+    // Keep clear of the footer
+    let mut bottom = area.bottom().saturating_sub(4);
+    for (message, _) in app.toasts.iter().rev() {
+        let height = 3;
+        if width < 10 || bottom < area.y + height {
+            break;
+        }
+        let rect = Rect::new(
+            area.right().saturating_sub(width + 2),
+            bottom - height,
+            width,
+            height,
+        );
+        bottom -= height;
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(crate::ui::colors::STATUS_WARN));
+        let text = Paragraph::new(message.as_str())
+            .style(Style::default().fg(TEXT_PRIMARY))
+            .block(block);
+        f.render_widget(Clear, rect);
+        f.render_widget(text, rect);
+    }
+}
+
 pub fn render_error_popup(f: &mut Frame, area: Rect, error: &str) {
     let block = Block::default()
         .title(Span::styled(
@@ -555,11 +757,28 @@ pub fn render_play_details_popup(f: &mut Frame, app: &App, area: Rect) {
         )]));
     }
 
+    // Poster to the left of the metadata when the terminal can draw it
+    let mut details_area = chunks[1];
+    if let Some(url) =
+        crate::ui::image::poster_url(app).filter(|url| crate::ui::image::artwork_shown(app, url))
+    {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length((details_area.width / 3).min(30)),
+                Constraint::Length(2),
+                Constraint::Min(0),
+            ])
+            .split(details_area);
+        crate::ui::image::render_artwork(f, app, columns[0], url, "loading poster…");
+        details_area = columns[2];
+    }
+
     f.render_widget(
         Paragraph::new(details)
             .wrap(Wrap { trim: true })
             .block(Block::default().borders(Borders::NONE)),
-        chunks[1],
+        details_area,
     );
 
     let controls = Paragraph::new(vec![Line::from(vec![
@@ -736,11 +955,11 @@ pub fn render_cast_picker_popup(f: &mut Frame, app: &App, area: Rect) {
         .split(inner);
 
     let title_text = if app.cast_discovering {
-        "⟳ Scanning for Chromecast devices..."
+        "⟳ Scanning for Chromecast and DLNA devices..."
     } else if app.cast_devices.is_empty() {
-        "No Chromecast devices found"
+        "No cast devices found"
     } else {
-        "Select a Chromecast device:"
+        "Select a cast device:"
     };
 
     let title = Paragraph::new(title_text)
@@ -766,12 +985,14 @@ pub fn render_cast_picker_popup(f: &mut Frame, app: &App, area: Rect) {
         let items: Vec<ListItem> = app
             .cast_devices
             .iter()
-            .map(|device| {
-                let model_str = device.model.as_deref().unwrap_or("Chromecast");
+            .map(|target| {
                 ListItem::new(Line::from(vec![
                     Span::styled("  ", Style::default()),
-                    Span::styled(&device.name, Style::default().fg(TEXT_PRIMARY)),
-                    Span::styled(format!("  ({})", model_str), Style::default().fg(TEXT_DIM)),
+                    Span::styled(target.name(), Style::default().fg(TEXT_PRIMARY)),
+                    Span::styled(
+                        format!("  ({})", target.describe()),
+                        Style::default().fg(TEXT_DIM),
+                    ),
                 ]))
             })
             .collect();
@@ -815,3 +1036,203 @@ pub fn render_cast_picker_popup(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_widget(controls, chunks[2]);
 }
+
+pub fn render_multiview_popup(f: &mut Frame, app: &App, area: Rect) {
+    let area = centered_rect(50, 50, area);
+    f.render_widget(Clear, area);
+    let inner = crate::ui::common::render_composite_block(f, area, Some("multiview"));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(inner);
+
+    let limit = app.multiview_tile_limit();
+    let title = Paragraph::new(format!(
+        "{}/{} tiles marked",
+        app.multiview_tiles.len(),
+        limit
+    ))
+    .alignment(Alignment::Center)
+    .style(
+        Style::default()
+            .fg(TEXT_PRIMARY)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_widget(title, chunks[0]);
+
+    let mut items: Vec<ListItem> = app
+        .multiview_tiles
+        .iter()
+        .enumerate()
+        .map(|(i, tile)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("  [{}] ", i + 1), Style::default().fg(MATRIX_GREEN)),
+                Span::styled(&tile.name, Style::default().fg(TEXT_PRIMARY)),
+            ]))
+        })
+        .collect();
+    if app.multiview_tiles.is_empty() {
+        items.push(ListItem::new(Span::styled(
+            "  Mark channels with 'w' in a stream list",
+            Style::default().fg(TEXT_DIM),
+        )));
+    }
+    for preset in &app.config.multiview_presets {
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("  preset ", Style::default().fg(TEXT_DIM)),
+            Span::styled(&preset.name, Style::default().fg(TEXT_PRIMARY)),
+            Span::styled(
+                format!("  ({} tiles)", preset.tiles.len()),
+                Style::default().fg(TEXT_DIM),
+            ),
+        ])));
+    }
+
+    // The hint row shifts presets down by one when no tiles are marked
+    let mut list_state = app.multiview_list_state;
+    if app.multiview_tiles.is_empty() {
+        list_state.select(list_state.selected().map(|i| i + 1));
+    }
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(HIGHLIGHT_BG)
+                .fg(MATRIX_GREEN)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(" ▎");
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    let key_style = Style::default()
+        .fg(MATRIX_GREEN)
+        .add_modifier(Modifier::BOLD);
+    let controls = match &app.multiview_preset_name {
+        Some(name) => Line::from(vec![
+            Span::styled("preset name: ", Style::default().fg(TEXT_DIM)),
+            Span::styled(format!("{}_", name), Style::default().fg(TEXT_PRIMARY)),
+        ]),
+        None => Line::from(vec![
+            Span::styled("enter", key_style),
+            Span::styled(" launch/load   ", Style::default().fg(TEXT_PRIMARY)),
+            Span::styled("s", key_style),
+            Span::styled(" save   ", Style::default().fg(TEXT_PRIMARY)),
+            Span::styled("d", key_style),
+            Span::styled(" remove   ", Style::default().fg(TEXT_PRIMARY)),
+            Span::styled("c", key_style),
+            Span::styled(" clear   ", Style::default().fg(TEXT_PRIMARY)),
+            Span::styled(
+                "esc",
+                Style::default()
+                    .fg(ratatui::style::Color::Rgb(255, 100, 100))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" close", Style::default().fg(TEXT_PRIMARY)),
+        ]),
+    };
+    f.render_widget(
+        Paragraph::new(controls).alignment(Alignment::Center),
+        chunks[2],
+    );
+}
+
+pub fn render_schedule_picker_popup(f: &mut Frame, app: &App, area: Rect) {
+    let Some((stream_id, channel_name)) = &app.schedule_picker_channel else {
+        return;
+    };
+    let area = centered_rect(60, 60, area);
+    f.render_widget(Clear, area);
+    let inner = crate::ui::common::render_composite_block(f, area, Some("schedule recording"));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(3),
+        ])
+        .split(inner);
+
+    let title = Paragraph::new(channel_name.as_str())
+        .alignment(Alignment::Center)
+        .style(
+            Style::default()
+                .fg(TEXT_PRIMARY)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_widget(title, chunks[0]);
+
+    let entries = app.schedule_picker_entries();
+    if entries.is_empty() {
+        let msg = if app.schedule_guide.contains_key(stream_id) {
+            "No upcoming programmes in this channel's guide"
+        } else {
+            "Loading guide..."
+        };
+        f.render_widget(
+            Paragraph::new(msg)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(TEXT_DIM)),
+            chunks[1],
+        );
+    } else {
+        let tz: chrono_tz::Tz = app
+            .config
+            .get_user_timezone()
+            .parse()
+            .unwrap_or(chrono_tz::UTC);
+        let items: Vec<ListItem> = entries
+            .iter()
+            .map(|e| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(
+                            "  {}-{}  ",
+                            e.start.with_timezone(&tz).format("%a %H:%M"),
+                            e.end.with_timezone(&tz).format("%H:%M")
+                        ),
+                        Style::default().fg(TEXT_DIM),
+                    ),
+                    Span::styled(e.title.clone(), Style::default().fg(TEXT_PRIMARY)),
+                ]))
+            })
+            .collect();
+        let mut list_state = app.schedule_picker_state;
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(HIGHLIGHT_BG)
+                    .fg(MATRIX_GREEN)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(" ▎");
+        f.render_stateful_widget(list, chunks[1], &mut list_state);
+    }
+
+    let key_style = Style::default()
+        .fg(MATRIX_GREEN)
+        .add_modifier(Modifier::BOLD);
+    let controls = Paragraph::new(Line::from(vec![
+        Span::styled("enter", key_style),
+        Span::styled(" this one   ", Style::default().fg(TEXT_PRIMARY)),
+        Span::styled("s", key_style),
+        Span::styled(" weekly   ", Style::default().fg(TEXT_PRIMARY)),
+        Span::styled("a", key_style),
+        Span::styled(" every airing   ", Style::default().fg(TEXT_PRIMARY)),
+        Span::styled(
+            "esc",
+            Style::default()
+                .fg(ratatui::style::Color::Rgb(255, 100, 100))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" cancel", Style::default().fg(TEXT_PRIMARY)),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(controls, chunks[2]);
+}
@@ -0,0 +1,154 @@
+use crate::app::App;
+use ratatui::{layout::Rect, Frame};
+
+/// Render the Recordings (DVR) screen: in-progress recordings first, then files on disk
+#[cfg(not(target_arch = "wasm32"))]
+pub fn render_recordings_view(f: &mut Frame, app: &mut App, area: Rect) {
+    use crate::app::RecordingRow;
+    use crate::recorder::{format_size, RecordingStatus};
+    use crate::ui::colors::{
+        HIGHLIGHT_BG, MATRIX_GREEN, SOFT_GREEN, STATUS_LIVE, STATUS_WARN, TEXT_DIM, TEXT_PRIMARY,
+        TEXT_SECONDARY,
+    };
+    use ratatui::{
+        layout::{Constraint, Direction, Layout},
+        style::{Modifier, Style},
+        text::{Line, Span},
+        widgets::{List, ListItem, Paragraph},
+    };
+
+    let rows = app.recording_rows();
+    let tz: chrono_tz::Tz = app
+        .config
+        .get_user_timezone()
+        .parse()
+        .unwrap_or(chrono_tz::UTC);
+    let active = rows
+        .iter()
+        .filter(|r| matches!(r, RecordingRow::Active(_)))
+        .count();
+
+    let items: Vec<ListItem> = if rows.is_empty() {
+        vec![ListItem::new(Line::from(vec![
+            Span::styled(
+                "  No recordings yet. Press ",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+            Span::styled(
+                "o",
+                Style::default()
+                    .fg(MATRIX_GREEN)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                " on a live channel to record it, or O to schedule one.",
+                Style::default().fg(TEXT_SECONDARY),
+            ),
+        ]))]
+    } else {
+        rows.iter()
+            .map(|row| match row {
+                RecordingRow::Active(rec) => {
+                    let (state, color) = match rec.status() {
+                        RecordingStatus::Reconnecting(n) => {
+                            (format!("reconnecting ({})", n), STATUS_WARN)
+                        }
+                        _ => ("● rec".to_string(), STATUS_LIVE),
+                    };
+                    let elapsed = chrono::Local::now() - rec.started_at;
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("  {} ", state), Style::default().fg(color)),
+                        Span::styled(
+                            rec.title.clone(),
+                            Style::default()
+                                .fg(TEXT_PRIMARY)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!(
+                                "  {}:{:02}  {}",
+                                elapsed.num_minutes(),
+                                elapsed.num_seconds() % 60,
+                                format_size(rec.bytes_written())
+                            ),
+                            Style::default().fg(TEXT_DIM),
+                        ),
+                    ]))
+                }
+                RecordingRow::Scheduled(row) => {
+                    let when = match row.start {
+                        Some(start) => start
+                            .with_timezone(&tz)
+                            .format("%a %d %b %H:%M")
+                            .to_string(),
+                        None => "series · waiting for guide".to_string(),
+                    };
+                    let mut spans = vec![
+                        Span::styled("  ◷ ", Style::default().fg(SOFT_GREEN)),
+                        Span::styled(row.title.clone(), Style::default().fg(TEXT_PRIMARY)),
+                        Span::styled(
+                            format!("  {} · {}", row.channel_name, when),
+                            Style::default().fg(TEXT_DIM),
+                        ),
+                    ];
+                    if row.conflict {
+                        spans.push(Span::styled(
+                            "  ⚠ over connection limit",
+                            Style::default().fg(STATUS_WARN),
+                        ));
+                    }
+                    ListItem::new(Line::from(spans))
+                }
+                RecordingRow::File(file) => {
+                    let name = file
+                        .path
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    ListItem::new(Line::from(vec![
+                        Span::styled("  ", Style::default()),
+                        Span::styled(name, Style::default().fg(MATRIX_GREEN)),
+                        Span::styled(
+                            format!("  {}", format_size(file.size)),
+                            Style::default().fg(TEXT_DIM),
+                        ),
+                    ]))
+                }
+            })
+            .collect()
+    };
+
+    let inner_area = crate::ui::common::render_matrix_box(
+        f,
+        area,
+        &format!(" recordings ({} active) ", active),
+        SOFT_GREEN,
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner_area);
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(HIGHLIGHT_BG)
+                .fg(MATRIX_GREEN)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(" ▎");
+    f.render_stateful_widget(list, chunks[0], &mut app.recordings_list_state);
+
+    let folder = Paragraph::new(Span::styled(
+        format!("  saving to {}", app.config.recordings_path().display()),
+        Style::default().fg(TEXT_DIM),
+    ));
+    f.render_widget(folder, chunks[1]);
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn render_recordings_view(f: &mut Frame, _app: &mut App, area: Rect) {
+    let msg = ratatui::widgets::Paragraph::new("Recording is not available in the browser");
+    f.render_widget(msg, area);
+}
@@ -412,6 +412,26 @@ pub fn render_vod_details_pane(f: &mut Frame, app: &mut App, area: Rect) {
         return;
     }
 
+    // Poster above the text when the pane is tall enough
+    let mut text_area = inner_area;
+    if inner_area.height >= 20 {
+        if let Some(url) = crate::ui::image::poster_url(app)
+            .filter(|url| crate::ui::image::artwork_shown(app, url))
+        {
+            let poster_h = (inner_area.height * 2 / 5).min(16);
+            let poster_area = Rect {
+                height: poster_h,
+                ..inner_area
+            };
+            crate::ui::image::render_artwork(f, app, poster_area, url, "loading poster…");
+            text_area = Rect {
+                y: inner_area.y + poster_h + 1,
+                height: inner_area.height - poster_h - 1,
+                ..inner_area
+            };
+        }
+    }
+
     let paragraph = Paragraph::new(details_text).wrap(Wrap { trim: true });
-    f.render_widget(paragraph, inner_area);
+    f.render_widget(paragraph, text_area);
 }
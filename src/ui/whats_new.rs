@@ -0,0 +1,116 @@
+use crate::app::App;
+use crate::ui::colors::{
+    HIGHLIGHT_BG, MATRIX_GREEN, SOFT_GREEN, STATUS_WARN, TEXT_DIM, TEXT_PRIMARY, TEXT_SECONDARY,
+};
+use crate::whats_new::{ChangeKind, Section};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, Paragraph},
+    Frame,
+};
+use std::collections::HashMap;
+
+/// Render the What's New screen: catalog additions since the last look, newest first
+pub fn render_whats_new(f: &mut Frame, app: &mut App, area: Rect) {
+    let now = chrono::Utc::now();
+    let since = match app.whats_new_since {
+        Some(t) => format!("since {}", ago(now - t)),
+        None => "last 30 days".to_string(),
+    };
+    let inner = crate::ui::common::render_matrix_box(
+        f,
+        area,
+        &format!(" what's new ({}) ", since),
+        SOFT_GREEN,
+    );
+
+    let category_names: HashMap<(Section, &str), &str> = [
+        (Section::Live, &app.all_categories),
+        (Section::Movies, &app.all_vod_categories),
+        (Section::Series, &app.all_series_categories),
+    ]
+    .into_iter()
+    .flat_map(|(section, cats)| {
+        cats.iter()
+            .map(move |c| ((section, c.category_id.as_str()), c.category_name.as_str()))
+    })
+    .collect();
+
+    let removed = app
+        .whats_new_items
+        .iter()
+        .filter(|c| c.kind == ChangeKind::Removed)
+        .count();
+    let items: Vec<ListItem> = app
+        .whats_new_items
+        .iter()
+        .filter(|c| c.is_new())
+        .map(|change| {
+            let (marker, label) = match change.kind {
+                ChangeKind::NewEpisodes => ("  ↻ ", "new episodes".to_string()),
+                _ => ("  + ", format!("new {}", change.section.label())),
+            };
+            let category = change
+                .category_id
+                .as_deref()
+                .and_then(|id| category_names.get(&(change.section, id)))
+                .map(|name| format!(" · {}", name))
+                .unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, Style::default().fg(MATRIX_GREEN)),
+                Span::styled(change.name.clone(), Style::default().fg(TEXT_PRIMARY)),
+                Span::styled(
+                    format!("  {}{} · {}", label, category, ago(now - change.at)),
+                    Style::default().fg(TEXT_DIM),
+                ),
+            ]))
+        })
+        .collect();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    if items.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "  Nothing new. Additions show up here after the playlist refreshes.",
+                Style::default().fg(TEXT_SECONDARY),
+            )),
+            chunks[0],
+        );
+    } else {
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(HIGHLIGHT_BG)
+                    .fg(MATRIX_GREEN)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(" ▎");
+        f.render_stateful_widget(list, chunks[0], &mut app.whats_new_list_state);
+    }
+
+    if removed > 0 {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                format!("  {} removed by the provider", removed),
+                Style::default().fg(STATUS_WARN),
+            )),
+            chunks[1],
+        );
+    }
+}
+
+fn ago(elapsed: chrono::Duration) -> String {
+    if elapsed.num_hours() < 1 {
+        "just now".to_string()
+    } else if elapsed.num_hours() < 24 {
+        format!("{}h ago", elapsed.num_hours())
+    } else {
+        format!("{}d ago", elapsed.num_days())
+    }
+}
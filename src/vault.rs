@@ -0,0 +1,526 @@
+//! Encrypted storage for account and Kodi host passwords.
+//!
+//! config.json only keeps a reference per password (`Account.credential`,
+//! `KodiHost.credential`):
+//! `keyring:<id>` for the OS credential store (macOS Keychain, Windows
+//! Credential Manager, the Secret Service on Linux desktops), or `vault:<id>`
+//! for `vault.json` next to the config. That file is used where no keyring
+//! answers, e.g. headless Linux: every entry is sealed with XChaCha20-Poly1305
+//! under a key derived from a passphrase with Argon2id.
+//!
+//! The file vault is unlocked once at startup (`unlock`), before the terminal
+//! enters raw mode, so the passphrase can be asked for on the console or taken
+//! from `MATRIX_IPTV_VAULT_PASSPHRASE`. Secrets, the passphrase and the key are
+//! wiped from memory when dropped; no secret is cached here between calls.
+
+use anyhow::{anyhow, bail, Context};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use once_cell::sync::Lazy;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+/// Keyring service name every entry is filed under
+const SERVICE: &str = "matrix-iptv";
+
+/// Passphrase for the file vault, for scripts and services without a console
+pub const PASSPHRASE_ENV: &str = "MATRIX_IPTV_VAULT_PASSPHRASE";
+
+const KEYRING_PREFIX: &str = "keyring:";
+const VAULT_PREFIX: &str = "vault:";
+
+/// Sealed with the key so a wrong passphrase is caught before any entry is read
+const CHECK_PLAINTEXT: &[u8] = b"matrix-iptv vault";
+
+const PASSPHRASE_ATTEMPTS: usize = 3;
+const NONCE_LEN: usize = 24;
+const VAULT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    /// Argon2id salt and cost, base64 / KiB / passes / lanes
+    salt: String,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+    /// `CHECK_PLAINTEXT`, sealed
+    check: String,
+    /// Entry id -> base64(nonce || ciphertext)
+    entries: BTreeMap<String, String>,
+}
+
+/// Passphrase-encrypted password file
+pub struct FileVault {
+    path: PathBuf,
+    key: Zeroizing<[u8; 32]>,
+    file: VaultFile,
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    params: Params,
+) -> anyhow::Result<Zeroizing<[u8; 32]>> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| anyhow!("key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+fn seal(key: &[u8; 32], plaintext: &[u8]) -> anyhow::Result<String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    let sealed = XChaCha20Poly1305::new(key.into())
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .map_err(|_| anyhow!("encryption failed"))?;
+    let mut out = nonce.to_vec();
+    out.extend(sealed);
+    Ok(B64.encode(out))
+}
+
+fn open_sealed(key: &[u8; 32], sealed: &str) -> anyhow::Result<Zeroizing<Vec<u8>>> {
+    let bytes = B64.decode(sealed)?;
+    if bytes.len() < NONCE_LEN {
+        bail!("vault entry is truncated");
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(key.into())
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map(Zeroizing::new)
+        .map_err(|_| anyhow!("wrong passphrase or damaged vault"))
+}
+
+impl FileVault {
+    /// Start an empty vault at `path`, written immediately
+    pub fn create(path: &Path, passphrase: &str, params: Params) -> anyhow::Result<Self> {
+        let mut salt = [0u8; 16];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        let key = derive_key(passphrase, &salt, params.clone())?;
+        let vault = Self {
+            path: path.to_path_buf(),
+            file: VaultFile {
+                version: VAULT_VERSION,
+                salt: B64.encode(salt),
+                m_cost: params.m_cost(),
+                t_cost: params.t_cost(),
+                p_cost: params.p_cost(),
+                check: seal(&key, CHECK_PLAINTEXT)?,
+                entries: BTreeMap::new(),
+            },
+            key,
+        };
+        vault.write()?;
+        Ok(vault)
+    }
+
+    /// Unlock the vault at `path`; fails on a wrong passphrase
+    pub fn open(path: &Path, passphrase: &str) -> anyhow::Result<Self> {
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let file: VaultFile = serde_json::from_str(&content).context("vault file is damaged")?;
+        if file.version > VAULT_VERSION {
+            bail!("vault was written by a newer version");
+        }
+        let params = Params::new(file.m_cost, file.t_cost, file.p_cost, Some(32))
+            .map_err(|e| anyhow!("vault file is damaged: {}", e))?;
+        let key = derive_key(passphrase, &B64.decode(&file.salt)?, params)?;
+        if open_sealed(&key, &file.check)?.as_slice() != CHECK_PLAINTEXT {
+            bail!("wrong passphrase or damaged vault");
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            key,
+            file,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Option<Zeroizing<String>> {
+        let plain = open_sealed(&self.key, self.file.entries.get(id)?).ok()?;
+        std::str::from_utf8(&plain)
+            .ok()
+            .map(|s| Zeroizing::new(s.to_string()))
+    }
+
+    pub fn set(&mut self, id: &str, secret: &str) -> anyhow::Result<()> {
+        self.file
+            .entries
+            .insert(id.to_string(), seal(&self.key, secret.as_bytes())?);
+        self.write()
+    }
+
+    pub fn remove(&mut self, id: &str) -> anyhow::Result<()> {
+        if self.file.entries.remove(id).is_some() {
+            self.write()?;
+        }
+        Ok(())
+    }
+
+    fn write(&self) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&self.file)?)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o600));
+        }
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// Whether the OS keyring answered, probed on first use
+    keyring: Option<bool>,
+    /// `unlock` already ran
+    unlock_attempted: bool,
+    file: Option<FileVault>,
+}
+
+static STATE: Lazy<Mutex<State>> = Lazy::new(|| Mutex::new(State::default()));
+
+fn lock() -> std::sync::MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+mod keyring_store {
+    use super::SERVICE;
+    use keyring::{Entry, Error};
+    use zeroize::Zeroizing;
+
+    pub fn available() -> bool {
+        match Entry::new(SERVICE, "probe").and_then(|e| e.get_password()) {
+            Ok(_) | Err(Error::NoEntry) => true,
+            Err(_) => false,
+        }
+    }
+
+    pub fn get(id: &str) -> Option<Zeroizing<String>> {
+        Entry::new(SERVICE, id)
+            .and_then(|e| e.get_password())
+            .ok()
+            .map(Zeroizing::new)
+    }
+
+    pub fn set(id: &str, secret: &str) -> bool {
+        Entry::new(SERVICE, id)
+            .and_then(|e| e.set_password(secret))
+            .is_ok()
+    }
+
+    pub fn remove(id: &str) {
+        let _ = Entry::new(SERVICE, id).and_then(|e| e.delete_credential());
+    }
+}
+
+/// Platforms without a supported keyring always use the file vault
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+mod keyring_store {
+    use zeroize::Zeroizing;
+
+    pub fn available() -> bool {
+        false
+    }
+
+    pub fn get(_id: &str) -> Option<Zeroizing<String>> {
+        None
+    }
+
+    pub fn set(_id: &str, _secret: &str) -> bool {
+        false
+    }
+
+    pub fn remove(_id: &str) {}
+}
+
+fn keyring_available(state: &mut State) -> bool {
+    *state.keyring.get_or_insert_with(keyring_store::available)
+}
+
+/// Location of the file vault: <config_dir>/vault.json
+pub fn vault_path() -> Option<PathBuf> {
+    crate::storage::paths().map(|p| p.config_dir.join("vault.json"))
+}
+
+/// Whether `reference` is one this module hands out
+pub fn is_reference(reference: &str) -> bool {
+    reference.starts_with(KEYRING_PREFIX) || reference.starts_with(VAULT_PREFIX)
+}
+
+/// Whether a new password can be stored: the keyring answers or the file
+/// vault is unlocked
+pub fn available() -> bool {
+    let mut state = lock();
+    state.file.is_some() || keyring_available(&mut state)
+}
+
+fn fetch_locked(state: &State, reference: &str) -> Option<Zeroizing<String>> {
+    if let Some(id) = reference.strip_prefix(KEYRING_PREFIX) {
+        keyring_store::get(id)
+    } else if let Some(id) = reference.strip_prefix(VAULT_PREFIX) {
+        state.file.as_ref()?.get(id)
+    } else {
+        None
+    }
+}
+
+/// The secret behind `reference`, or None when its store is locked or it is gone
+pub fn fetch(reference: &str) -> Option<Zeroizing<String>> {
+    fetch_locked(&lock(), reference)
+}
+
+/// Store `secret` for the entry `id`, in the store `reference` already points
+/// to when given and usable, else the keyring, else the unlocked file vault.
+/// Returns the reference to save, or None when every store is unavailable.
+pub fn store(reference: Option<&str>, id: &str, secret: &str) -> Option<String> {
+    let mut state = lock();
+    // Editing an account without changing its password shouldn't rewrite it
+    if let Some(reference) = reference {
+        if fetch_locked(&state, reference).is_some_and(|stored| stored.as_str() == secret) {
+            return Some(reference.to_string());
+        }
+    }
+
+    let id = reference
+        .and_then(|r| {
+            r.strip_prefix(KEYRING_PREFIX)
+                .or_else(|| r.strip_prefix(VAULT_PREFIX))
+        })
+        .unwrap_or(id)
+        .to_string();
+    let prefer_file = reference.is_some_and(|r| r.starts_with(VAULT_PREFIX));
+
+    if !prefer_file && keyring_available(&mut state) && keyring_store::set(&id, secret) {
+        Some(format!("{}{}", KEYRING_PREFIX, id))
+    } else if let Some(file) = state.file.as_mut() {
+        file.set(&id, secret)
+            .ok()
+            .map(|_| format!("{}{}", VAULT_PREFIX, id))
+    } else {
+        None
+    }
+}
+
+/// Delete the secret behind `reference`
+pub fn forget(reference: &str) {
+    let mut state = lock();
+    if let Some(id) = reference.strip_prefix(KEYRING_PREFIX) {
+        keyring_store::remove(id);
+    } else if let Some(id) = reference.strip_prefix(VAULT_PREFIX) {
+        if let Some(file) = state.file.as_mut() {
+            let _ = file.remove(id);
+        }
+    }
+}
+
+/// Unlock (or create) the file vault when it is needed: when it already
+/// exists, or when there is no keyring to hold passwords. Runs once per
+/// process; `AppConfig::load` calls it, and the TUI calls it earlier, before
+/// the terminal enters raw mode, since it may ask on the console. Leaves the
+/// vault locked if the passphrase is not given; passwords kept in it are then
+/// unavailable and new ones stay in config.json until a later start (see
+/// `AppConfig::unsealed_passwords`, which the app warns with).
+pub fn unlock() {
+    {
+        let mut state = lock();
+        if std::mem::replace(&mut state.unlock_attempted, true) {
+            return;
+        }
+    }
+    let Some(path) = vault_path() else {
+        return;
+    };
+    let exists = path.exists();
+    if !exists && keyring_available(&mut lock()) {
+        return;
+    }
+    let from_env = std::env::var(PASSPHRASE_ENV).ok().map(Zeroizing::new);
+
+    let opened = if exists {
+        match from_env {
+            Some(passphrase) => FileVault::open(&path, &passphrase)
+                .map_err(|e| eprintln!("Credential vault: {}", e))
+                .ok(),
+            None => (0..PASSPHRASE_ATTEMPTS).find_map(|_| {
+                let passphrase = read_hidden("Credential vault passphrase (Esc to skip): ")?;
+                FileVault::open(&path, &passphrase)
+                    .map_err(|e| eprintln!("Credential vault: {}", e))
+                    .ok()
+            }),
+        }
+    } else {
+        let passphrase = from_env.or_else(|| {
+            eprintln!("No system keyring found; saved passwords go in an encrypted vault.");
+            let first = read_hidden("Choose a vault passphrase (Esc to skip): ")?;
+            let again = read_hidden("Repeat the passphrase: ")?;
+            if first.is_empty() || first != again {
+                eprintln!("Passphrases were empty or did not match.");
+                return None;
+            }
+            Some(first)
+        });
+        passphrase.and_then(|p| {
+            FileVault::create(&path, &p, Params::default())
+                .map_err(|e| eprintln!("Credential vault: {}", e))
+                .ok()
+        })
+    };
+
+    match opened {
+        Some(vault) => lock().file = Some(vault),
+        None => eprintln!("Credential vault left locked for this session."),
+    }
+}
+
+/// Read a line from the console without echoing it
+fn read_hidden(prompt: &str) -> Option<Zeroizing<String>> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use std::io::{IsTerminal, Write};
+
+    if !std::io::stdin().is_terminal() {
+        return None;
+    }
+    eprint!("{}", prompt);
+    let _ = std::io::stderr().flush();
+    crossterm::terminal::enable_raw_mode().ok()?;
+    // Reserved up front so typing never reallocates and leaves copies behind
+    let mut line = Zeroizing::new(String::with_capacity(256));
+    let entered = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Enter => break true,
+                KeyCode::Esc => break false,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break false,
+                KeyCode::Backspace => {
+                    line.pop();
+                }
+                KeyCode::Char(c) if line.len() + c.len_utf8() <= line.capacity() => line.push(c),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(_) => break false,
+        }
+    };
+    let _ = crossterm::terminal::disable_raw_mode();
+    eprintln!();
+    entered.then_some(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_params() -> Params {
+        Params::new(Params::MIN_M_COST, 1, 1, Some(32)).unwrap()
+    }
+
+    #[test]
+    fn file_vault_round_trips_and_rejects_wrong_passphrase() {
+        let path =
+            std::env::temp_dir().join(format!("matrix-iptv-vault-{}.json", std::process::id()));
+        let mut vault = FileVault::create(&path, "correct horse", fast_params()).unwrap();
+        vault.set("a1", "hunter2").unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("hunter2"));
+
+        let reopened = FileVault::open(&path, "correct horse").unwrap();
+        assert_eq!(reopened.get("a1").unwrap().as_str(), "hunter2");
+        assert!(reopened.get("missing").is_none());
+        assert!(FileVault::open(&path, "wrong").is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn accounts_keep_only_a_reference_to_the_stored_password() {
+        let path = std::env::temp_dir().join(format!(
+            "matrix-iptv-vault-store-{}.json",
+            std::process::id()
+        ));
+        {
+            let mut state = lock();
+            // Never touch a real keyring from tests
+            state.keyring = Some(false);
+            state.unlock_attempted = true;
+            state.file = Some(FileVault::create(&path, "pass", fast_params()).unwrap());
+        }
+
+        let reference = store(None, "acc1", "s3cret").unwrap();
+        assert_eq!(reference, "vault:acc1");
+        assert_eq!(fetch(&reference).unwrap().as_str(), "s3cret");
+
+        let account: crate::config::Account = serde_json::from_value(serde_json::json!({
+            "name": "Home",
+            "base_url": "http://example.com",
+            "username": "me",
+            "password": "",
+            "credential": reference,
+            "epg_url": null,
+            "last_refreshed": null,
+            "total_channels": null,
+            "total_movies": null,
+            "total_series": null,
+            "server_timezone": null
+        }))
+        .unwrap();
+        let json = serde_json::to_string(&account).unwrap();
+        assert!(json.contains("vault:acc1") && !json.contains("password"));
+
+        let mut with_password = account.clone();
+        with_password.password = "s3cret".to_string();
+        assert!(!format!("{:?}", with_password).contains("s3cret"));
+        // Sealed accounts read the password from the store when logging in
+        assert_eq!(account.secret().as_str(), "s3cret");
+
+        // Storing the same secret again keeps the reference
+        assert_eq!(
+            store(Some(&reference), "other", "s3cret").as_deref(),
+            Some("vault:acc1")
+        );
+
+        forget(&reference);
+        assert!(fetch(&reference).is_none());
+        assert!(store(None, "acc2", "x").is_some());
+
+        // Kodi host passwords go the same way
+        let config_path = path.with_extension("config.json");
+        let storage = crate::storage::ConfigStorage::file(config_path.clone());
+        let mut config = crate::config::AppConfig::with_storage(storage.clone());
+        config.add_kodi_host(crate::kodi::KodiHost::parse("kodi:secret@10.0.0.5").unwrap());
+        let saved = std::fs::read_to_string(&config_path).unwrap();
+        assert!(saved.contains("\"credential\": \"vault:") && !saved.contains("secret"));
+        let mut loaded = crate::config::AppConfig::load_from(storage).unwrap();
+        assert_eq!(loaded.kodi_hosts[0].password, None);
+        assert_eq!(
+            loaded.kodi_hosts[0].with_password().password.as_deref(),
+            Some("secret")
+        );
+        assert!(loaded.unsealed_passwords().is_empty());
+
+        // Nothing to put it in: the caller is told instead of it being dropped
+        lock().file = None;
+        assert!(!available());
+        assert!(store(None, "acc3", "x").is_none());
+        loaded.add_kodi_host(crate::kodi::KodiHost::parse("kodi:other@10.0.0.6").unwrap());
+        assert_eq!(loaded.unsealed_passwords(), ["10.0.0.6"]);
+        assert!(std::fs::read_to_string(&config_path)
+            .unwrap()
+            .contains("other"));
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&config_path);
+    }
+}
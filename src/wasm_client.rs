@@ -78,7 +78,7 @@ impl WasmClient {
                         if let Some(acc) = app.get_selected_account() {
                             let base_url = acc.base_url.clone();
                             let username = acc.username.clone();
-                            let password = acc.password.clone();
+                            let password = acc.secret().to_string();
 
                             // Auto-inject localhost:8081 for POC testing if not already present
                             let proxy_url = if !base_url.contains("localhost") {
@@ -225,8 +225,8 @@ impl WasmClient {
                                     LoginField::Password => LoginField::EpgUrl,
                                     LoginField::EpgUrl => {
                                         // Final field, try to save
-                                        let name = app.input_name.value().to_string();
-                                        let url = app.input_url.value().to_string();
+                                        let name = app.input_name.value().trim().to_string();
+                                        let url = app.input_url.value().trim().to_string();
                                         let user = app.input_username.value().to_string();
                                         let pass = app.input_password.value().to_string();
                                         let epg = app.input_epg_url.value().to_string();
@@ -238,6 +238,7 @@ impl WasmClient {
                                                 base_url: url,
                                                 username: user,
                                                 password: pass,
+                                                credential: None,
                                                 account_type: crate::config::AccountType::Xtream,
                                                 epg_url: epg_opt,
                                                 last_refreshed: None,
@@ -246,7 +247,10 @@ impl WasmClient {
                                                 total_series: None,
                                                 server_timezone: None,
                                             };
-                                            app.config.add_account(acc);
+                                            if let Err(e) = app.config.add_account(acc) {
+                                                app.login_error = Some(e);
+                                                return;
+                                            }
                                             app.toggle_input_mode(); // Done editing
                                             app.current_screen = CurrentScreen::Home;
                                             app.input_name = tui_input::Input::default();
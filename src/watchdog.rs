@@ -0,0 +1,464 @@
+//! Stall watchdog for live streams.
+//!
+//! Live IPTV streams often freeze without any error: mpv keeps the last frame
+//! on screen (`--keep-open`) and never exits. While a live channel plays, the
+//! watchdog samples mpv over IPC once a second. When `time-pos` stops advancing
+//! for the configured time it walks a recovery ladder: reload the same URL, try
+//! the other stream formats, then fail over to another feed of the channel.
+
+use crate::api::Stream;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Properties sampled for `WatchdogSample`
+pub const SAMPLE_PROPERTIES: &[&str] = &[
+    "time-pos",
+    "pause",
+    "paused-for-cache",
+    "demuxer-cache-time",
+];
+
+/// Playback without a stall for this long starts the ladder over
+pub const HEALTHY_AFTER_SECS: f64 = 120.0;
+
+/// Alternate feeds added to a recovery plan at most
+const MAX_ALTERNATES: usize = 3;
+
+/// Name tokens that distinguish feeds of the same channel
+const FEED_TOKENS: &[&str] = &[
+    "hd", "fhd", "uhd", "sd", "4k", "hq", "lq", "hevc", "h265", "raw", "backup", "alt", "50fps",
+    "60fps",
+];
+
+/// One IPC sample of the running player
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WatchdogSample {
+    pub time_pos: Option<f64>,
+    /// Paused by the viewer; never counts as a stall
+    pub paused: bool,
+    pub paused_for_cache: bool,
+    /// Stream time the demuxer cache reaches
+    pub cache_time: Option<f64>,
+}
+
+impl WatchdogSample {
+    /// Build a sample from `(property, value)` pairs (see `SAMPLE_PROPERTIES`)
+    pub fn from_properties(props: &[(&str, Value)]) -> Self {
+        let mut sample = Self::default();
+        for (name, value) in props {
+            match *name {
+                "time-pos" => sample.time_pos = value.as_f64(),
+                "pause" => sample.paused = value.as_bool().unwrap_or(false),
+                "paused-for-cache" => sample.paused_for_cache = value.as_bool().unwrap_or(false),
+                "demuxer-cache-time" => sample.cache_time = value.as_f64(),
+                _ => {}
+            }
+        }
+        sample
+    }
+}
+
+/// How a stalled stream looks from the player's side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StallKind {
+    /// Waiting for data that isn't arriving
+    Buffering,
+    /// Data arrives but the picture doesn't move
+    Frozen,
+    /// No position and no cache: the stream ended or dropped
+    Dropped,
+}
+
+impl StallKind {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            StallKind::Buffering => "stuck buffering",
+            StallKind::Frozen => "frozen",
+            StallKind::Dropped => "dropped",
+        }
+    }
+}
+
+/// Decides from successive samples whether playback has stalled.
+/// Times are seconds on any monotonic clock.
+#[derive(Debug, Clone)]
+pub struct StallDetector {
+    stall_after: f64,
+    last_pos: Option<f64>,
+    last_cache: Option<f64>,
+    /// When the position last moved (or the detector was reset)
+    last_progress: f64,
+    /// When the cache end last moved
+    last_cache_progress: f64,
+}
+
+impl StallDetector {
+    pub fn new(stall_after_secs: u64, now: f64) -> Self {
+        Self {
+            stall_after: stall_after_secs as f64,
+            last_pos: None,
+            last_cache: None,
+            last_progress: now,
+            last_cache_progress: now,
+        }
+    }
+
+    /// Start a fresh grace period (after a recovery or a viewer pause)
+    pub fn reset(&mut self, now: f64) {
+        self.last_pos = None;
+        self.last_cache = None;
+        self.last_progress = now;
+        self.last_cache_progress = now;
+    }
+
+    pub fn observe(&mut self, sample: &WatchdogSample, now: f64) -> Option<StallKind> {
+        if sample.paused && !sample.paused_for_cache {
+            self.reset(now);
+            return None;
+        }
+
+        if let Some(cache) = sample.cache_time {
+            if self
+                .last_cache
+                .is_some_and(|last| (cache - last).abs() > 0.01)
+            {
+                self.last_cache_progress = now;
+            }
+            self.last_cache = Some(cache);
+        }
+        if let Some(pos) = sample.time_pos {
+            if self.last_pos.is_some_and(|last| (pos - last).abs() > 0.05) {
+                self.last_progress = now;
+            }
+            self.last_pos = Some(pos);
+        }
+
+        if now - self.last_progress < self.stall_after {
+            return None;
+        }
+        Some(
+            if sample.time_pos.is_none() && sample.cache_time.is_none() {
+                StallKind::Dropped
+            } else if sample.paused_for_cache || now - self.last_cache_progress >= self.stall_after
+            {
+                StallKind::Buffering
+            } else {
+                StallKind::Frozen
+            },
+        )
+    }
+}
+
+/// One rung of the recovery ladder
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recovery {
+    /// Load the current URL again
+    Reload,
+    SwitchFormat {
+        extension: String,
+        url: String,
+    },
+    /// Another feed of the same channel (other quality or source)
+    Failover {
+        stream_id: String,
+        name: String,
+        url: String,
+    },
+}
+
+/// What the watchdog may try for the channel that is playing
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecoveryPlan {
+    pub name: String,
+    pub url: String,
+    /// Tried in order; a recovery that keeps playing moves on to the next rung
+    pub steps: Vec<Recovery>,
+}
+
+impl RecoveryPlan {
+    /// `formats` are `(extension, url)` pairs for the same stream, `alternates`
+    /// `(stream_id, name, url)` for other feeds of the channel.
+    pub fn new(
+        name: String,
+        url: String,
+        formats: Vec<(String, String)>,
+        alternates: Vec<(String, String, String)>,
+    ) -> Self {
+        let mut steps = vec![Recovery::Reload];
+        let mut seen = vec![url.clone()];
+        for (extension, format_url) in formats {
+            if !seen.contains(&format_url) {
+                seen.push(format_url.clone());
+                steps.push(Recovery::SwitchFormat {
+                    extension,
+                    url: format_url,
+                });
+            }
+        }
+        for (stream_id, alt_name, alt_url) in alternates {
+            if !seen.contains(&alt_url) {
+                seen.push(alt_url.clone());
+                steps.push(Recovery::Failover {
+                    stream_id,
+                    name: alt_name,
+                    url: alt_url,
+                });
+            }
+        }
+        Self { name, url, steps }
+    }
+}
+
+/// Lowercase name without feed markers, e.g. "UK: BBC One FHD" -> "uk bbc one"
+fn channel_key(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty() && !FEED_TOKENS.contains(token))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Other entries for the same channel: same EPG id, or the same name apart
+/// from quality/backup markers
+pub fn alternate_feeds(streams: &[Arc<Stream>], current: &Stream) -> Vec<Arc<Stream>> {
+    let current_id = crate::api::get_id_str(&current.stream_id);
+    let current_epg = current
+        .epg_channel_id
+        .as_deref()
+        .filter(|id| !id.trim().is_empty());
+    let current_key = channel_key(&current.name);
+
+    streams
+        .iter()
+        .filter(|s| crate::api::get_id_str(&s.stream_id) != current_id)
+        .filter(|s| {
+            let same_epg = current_epg.is_some() && s.epg_channel_id.as_deref() == current_epg;
+            same_epg || (!current_key.is_empty() && channel_key(&s.name) == current_key)
+        })
+        .take(MAX_ALTERNATES)
+        .cloned()
+        .collect()
+}
+
+/// Events the watchdog reports to the UI
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchdogEvent {
+    /// A recovery was attempted; `switched_to` is set on failover
+    Recovered {
+        message: String,
+        switched_to: Option<(String, String)>,
+    },
+    /// Every rung failed; the watchdog stops
+    GaveUp(String),
+}
+
+/// Watch the running player until it exits, recovering stalls with `plan`.
+/// Ends silently when the player has no IPC (VLC, profiles without mpv IPC).
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn run(
+    player: crate::player::Player,
+    plan: RecoveryPlan,
+    stall_after_secs: u64,
+    engine: crate::config::PlayerEngine,
+    use_default_mpv: bool,
+    smooth_motion: bool,
+    tx: tokio::sync::mpsc::Sender<crate::app::AsyncAction>,
+) {
+    use crate::app::AsyncAction;
+    use std::time::{Duration, Instant};
+
+    let started = Instant::now();
+    let now = || started.elapsed().as_secs_f64();
+    let mut detector = StallDetector::new(stall_after_secs, now());
+    let mut current_url = plan.url.clone();
+    let mut current_name = plan.name.clone();
+    let mut next_step = 0;
+    let mut last_recovery: Option<f64> = None;
+
+    loop {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        if !player.is_running() {
+            return;
+        }
+        let Some(controller) = player.controller() else {
+            return;
+        };
+        // mpv is briefly unreachable while it restarts; wait for the next sample
+        let Ok(props) = controller.get_properties(SAMPLE_PROPERTIES).await else {
+            continue;
+        };
+        let sample = WatchdogSample::from_properties(&props);
+
+        if last_recovery.is_some_and(|at| now() - at >= HEALTHY_AFTER_SECS) {
+            next_step = 0;
+            last_recovery = None;
+        }
+        let Some(stall) = detector.observe(&sample, now()) else {
+            continue;
+        };
+
+        let Some(step) = plan.steps.get(next_step) else {
+            let _ = tx
+                .send(AsyncAction::Watchdog(WatchdogEvent::GaveUp(format!(
+                    "{} is still {} after every recovery; the watchdog has stopped",
+                    current_name,
+                    stall.describe()
+                ))))
+                .await;
+            return;
+        };
+        next_step += 1;
+
+        let (url, message, switched_to) = match step {
+            Recovery::Reload => (
+                current_url.clone(),
+                format!("{} {}: reloaded the stream", current_name, stall.describe()),
+                None,
+            ),
+            Recovery::SwitchFormat { extension, url } => (
+                url.clone(),
+                format!(
+                    "{} {}: switched to .{}",
+                    current_name,
+                    stall.describe(),
+                    extension
+                ),
+                None,
+            ),
+            Recovery::Failover {
+                stream_id,
+                name,
+                url,
+            } => (
+                url.clone(),
+                format!(
+                    "{} {}: failed over to {}",
+                    current_name,
+                    stall.describe(),
+                    name
+                ),
+                Some((stream_id.clone(), name.clone())),
+            ),
+        };
+
+        player.set_media_title(switched_to.as_ref().map_or(&current_name, |(_, n)| n));
+        let message = match player
+            .play(&url, engine, use_default_mpv, smooth_motion)
+            .await
+        {
+            Ok(()) => {
+                current_url = url;
+                if let Some((_, name)) = &switched_to {
+                    current_name = name.clone();
+                }
+                message
+            }
+            // Move on to the next rung right away
+            Err(e) => format!("{} (failed: {})", message, e),
+        };
+        let _ = tx
+            .send(AsyncAction::Watchdog(WatchdogEvent::Recovered {
+                message,
+                switched_to,
+            }))
+            .await;
+        last_recovery = Some(now());
+        detector.reset(now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playing(pos: f64) -> WatchdogSample {
+        WatchdogSample {
+            time_pos: Some(pos),
+            cache_time: Some(pos + 5.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn detects_frozen_and_buffering_streams() {
+        let mut detector = StallDetector::new(10, 0.0);
+        for t in 0..30 {
+            assert_eq!(detector.observe(&playing(t as f64), t as f64), None);
+        }
+
+        // Position stuck while the cache keeps growing
+        for t in 30..39 {
+            let sample = WatchdogSample {
+                cache_time: Some(t as f64 + 10.0),
+                ..playing(29.0)
+            };
+            assert_eq!(detector.observe(&sample, t as f64), None);
+        }
+        let sample = WatchdogSample {
+            cache_time: Some(50.0),
+            ..playing(29.0)
+        };
+        assert_eq!(detector.observe(&sample, 40.0), Some(StallKind::Frozen));
+
+        let mut detector = StallDetector::new(10, 0.0);
+        let waiting = WatchdogSample {
+            paused_for_cache: true,
+            ..playing(3.0)
+        };
+        assert_eq!(detector.observe(&waiting, 5.0), None);
+        assert_eq!(detector.observe(&waiting, 12.0), Some(StallKind::Buffering));
+    }
+
+    #[test]
+    fn viewer_pause_never_stalls() {
+        let mut detector = StallDetector::new(5, 0.0);
+        let paused = WatchdogSample {
+            paused: true,
+            ..playing(1.0)
+        };
+        for t in 0..60 {
+            assert_eq!(detector.observe(&paused, t as f64), None);
+        }
+        assert_eq!(detector.observe(&playing(1.0), 61.0), None);
+    }
+
+    #[test]
+    fn plan_skips_duplicate_urls_and_finds_alternates() {
+        let stream = |id: &str, name: &str, epg: Option<&str>| {
+            Arc::new(Stream {
+                stream_id: crate::flex_id::FlexId::from_number(id.parse().unwrap()),
+                name: name.to_string(),
+                epg_channel_id: epg.map(str::to_string),
+                ..Default::default()
+            })
+        };
+        let streams = vec![
+            stream("1", "UK: BBC One HD", None),
+            stream("2", "UK: BBC One FHD", None),
+            stream("3", "BBC 1 Backup", Some("bbc1.uk")),
+            stream("4", "UK: BBC Two HD", None),
+        ];
+        let current = stream("1", "UK: BBC One HD", Some("bbc1.uk"));
+        let alternates: Vec<String> = alternate_feeds(&streams, &current)
+            .iter()
+            .map(|s| s.name.clone())
+            .collect();
+        assert_eq!(alternates, vec!["UK: BBC One FHD", "BBC 1 Backup"]);
+
+        let plan = RecoveryPlan::new(
+            "BBC One".into(),
+            "http://h/1.ts".into(),
+            vec![
+                ("ts".into(), "http://h/1.ts".into()),
+                ("m3u8".into(), "http://h/1.m3u8".into()),
+            ],
+            vec![("2".into(), "BBC One FHD".into(), "http://h/2.ts".into())],
+        );
+        assert_eq!(plan.steps.len(), 3);
+        assert_eq!(plan.steps[0], Recovery::Reload);
+        assert!(
+            matches!(&plan.steps[1], Recovery::SwitchFormat { extension, .. } if extension == "m3u8")
+        );
+        assert!(matches!(&plan.steps[2], Recovery::Failover { stream_id, .. } if stream_id == "2"));
+    }
+}
@@ -0,0 +1,369 @@
+//! Catalog changes between refreshes. Each full channel, movie or series load
+//! is diffed by stream_id against a snapshot of the previous one (kept next to
+//! the catalog cache), and the changes are logged in `whats_new.json` in the
+//! data directory for the "What's New" screen and the Home badge.
+
+use crate::api::{get_id_str, Stream};
+use crate::config::ProcessingMode;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Oldest changes are dropped beyond this many
+const MAX_CHANGES: usize = 5_000;
+
+/// Changes older than this are dropped
+const RETENTION_DAYS: i64 = 30;
+
+/// Part of the catalog that is loaded (and diffed) as a whole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Section {
+    Live,
+    Movies,
+    Series,
+}
+
+impl Section {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Section::Live => "channel",
+            Section::Movies => "movie",
+            Section::Series => "series",
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Section::Live => "live",
+            Section::Movies => "movies",
+            Section::Series => "series",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// A series whose last-modified stamp moved on
+    NewEpisodes,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogChange {
+    pub account: String,
+    pub section: Section,
+    pub kind: ChangeKind,
+    pub stream_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub category_id: Option<String>,
+    /// Processing modes the catalog was filtered with when the change was seen
+    #[serde(default)]
+    pub modes: Vec<ProcessingMode>,
+    pub at: DateTime<Utc>,
+}
+
+impl CatalogChange {
+    /// Additions and new episodes, as opposed to removals
+    pub fn is_new(&self) -> bool {
+        self.kind != ChangeKind::Removed
+    }
+
+    /// Whether the catalog filtered with `modes` would contain this item
+    pub fn matches_modes(&self, modes: &[ProcessingMode]) -> bool {
+        self.modes == modes
+            || crate::preprocessing::matches_modes(&self.name, self.section == Section::Live, modes)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotItem {
+    pub name: String,
+    pub category_id: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// One section of the catalog as it was at its last full load
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub modes: Vec<ProcessingMode>,
+    pub items: HashMap<String, SnapshotItem>,
+}
+
+impl Snapshot {
+    pub fn of(streams: &[Arc<Stream>], modes: &[ProcessingMode]) -> Self {
+        let items = streams
+            .iter()
+            .map(|s| {
+                let item = SnapshotItem {
+                    name: s.name.clone(),
+                    category_id: s.category_id.clone(),
+                    last_modified: s.last_modified.as_ref().and_then(|m| m.to_string_value()),
+                };
+                (get_id_str(&s.stream_id), item)
+            })
+            .collect();
+        Self {
+            modes: modes.to_vec(),
+            items,
+        }
+    }
+
+    /// Path: <cache_dir>/<account_name_hash>.<section>.snapshot.bin
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn path(account_name: &str, section: Section) -> Option<std::path::PathBuf> {
+        let cache = crate::cache::CachedCatalog::cache_path(account_name)?;
+        Some(cache.with_extension(format!("{}.snapshot.bin", section.key())))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(account_name: &str, section: Section) -> Option<Self> {
+        let data = std::fs::read(Self::path(account_name, section)?).ok()?;
+        bincode::deserialize(&data).ok()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, account_name: &str, section: Section) -> Result<(), anyhow::Error> {
+        let path = Self::path(account_name, section)
+            .ok_or_else(|| anyhow::anyhow!("Cannot determine cache directory"))?;
+        std::fs::write(path, bincode::serialize(self)?)?;
+        Ok(())
+    }
+
+    /// Delete every section's snapshot for a removed or renamed account
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn invalidate(account_name: &str) {
+        for section in [Section::Live, Section::Movies, Section::Series] {
+            if let Some(path) = Self::path(account_name, section) {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    /// WASM stub - snapshots are not kept in the browser
+    #[cfg(target_arch = "wasm32")]
+    pub fn load(_account_name: &str, _section: Section) -> Option<Self> {
+        None
+    }
+
+    /// WASM stub - snapshots are not kept in the browser
+    #[cfg(target_arch = "wasm32")]
+    pub fn save(&self, _account_name: &str, _section: Section) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    /// WASM stub - snapshots are not kept in the browser
+    #[cfg(target_arch = "wasm32")]
+    pub fn invalidate(_account_name: &str) {}
+}
+
+/// Changes from `previous` to `current`, sorted by name. There are none on the
+/// first load, or when the processing modes changed in between (they filter the
+/// catalog, so everything would look added or removed).
+pub fn diff(
+    previous: Option<&Snapshot>,
+    current: &Snapshot,
+    account: &str,
+    section: Section,
+    at: DateTime<Utc>,
+) -> Vec<CatalogChange> {
+    let Some(previous) = previous.filter(|p| p.modes == current.modes && !p.items.is_empty())
+    else {
+        return Vec::new();
+    };
+    let change = |kind, id: &String, item: &SnapshotItem| CatalogChange {
+        account: account.to_string(),
+        section,
+        kind,
+        stream_id: id.clone(),
+        name: item.name.clone(),
+        category_id: item.category_id.clone(),
+        modes: current.modes.clone(),
+        at,
+    };
+
+    let mut changes: Vec<CatalogChange> = current
+        .items
+        .iter()
+        .filter_map(|(id, item)| match previous.items.get(id) {
+            None => Some(change(ChangeKind::Added, id, item)),
+            Some(old)
+                if section == Section::Series
+                    && item.last_modified.is_some()
+                    && item.last_modified != old.last_modified =>
+            {
+                Some(change(ChangeKind::NewEpisodes, id, item))
+            }
+            Some(_) => None,
+        })
+        .collect();
+    changes.extend(
+        previous
+            .items
+            .iter()
+            .filter(|(id, _)| !current.items.contains_key(*id))
+            .map(|(id, item)| change(ChangeKind::Removed, id, item)),
+    );
+    changes.sort_by(|a, b| a.name.cmp(&b.name));
+    changes
+}
+
+/// Logged catalog changes for every account, and when each account last looked
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WhatsNew {
+    #[serde(default)]
+    pub changes: Vec<CatalogChange>,
+    #[serde(default)]
+    pub last_seen: HashMap<String, DateTime<Utc>>,
+}
+
+impl WhatsNew {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn path() -> Option<std::path::PathBuf> {
+        crate::storage::paths().map(|p| p.data_dir.join("whats_new.json"))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    /// WASM stub - the change log is not kept in the browser
+    #[cfg(target_arch = "wasm32")]
+    pub fn load() -> Self {
+        Self::default()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self) -> Result<(), anyhow::Error> {
+        let path =
+            Self::path().ok_or_else(|| anyhow::anyhow!("Cannot determine data directory"))?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Append changes, dropping expired ones and the oldest beyond the cap
+    pub fn record(&mut self, changes: Vec<CatalogChange>, now: DateTime<Utc>) {
+        self.changes.extend(changes);
+        let cutoff = now - Duration::days(RETENTION_DAYS);
+        self.changes.retain(|c| c.at >= cutoff);
+        if self.changes.len() > MAX_CHANGES {
+            let excess = self.changes.len() - MAX_CHANGES;
+            self.changes.drain(..excess);
+        }
+    }
+
+    pub fn last_seen(&self, account: &str) -> Option<DateTime<Utc>> {
+        self.last_seen.get(account).copied()
+    }
+
+    pub fn mark_seen(&mut self, account: &str, now: DateTime<Utc>) {
+        self.last_seen.insert(account.to_string(), now);
+    }
+
+    /// The account's changes since `since` visible under `modes`, newest first
+    pub fn since(
+        &self,
+        account: &str,
+        since: Option<DateTime<Utc>>,
+        modes: &[ProcessingMode],
+    ) -> Vec<CatalogChange> {
+        let mut changes: Vec<CatalogChange> = self
+            .changes
+            .iter()
+            .filter(|c| c.account == account && since.is_none_or(|t| c.at > t))
+            .filter(|c| c.matches_modes(modes))
+            .cloned()
+            .collect();
+        changes.sort_by_key(|c| std::cmp::Reverse(c.at));
+        changes
+    }
+
+    /// Badge count: additions and new episodes the account has not looked at
+    pub fn unseen_count(&self, account: &str, modes: &[ProcessingMode]) -> usize {
+        let since = self.last_seen(account);
+        self.changes
+            .iter()
+            .filter(|c| c.account == account && c.is_new() && since.is_none_or(|t| c.at > t))
+            .filter(|c| c.matches_modes(modes))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flex_id::FlexId;
+
+    fn stream(id: i64, name: &str, modified: Option<&str>) -> Arc<Stream> {
+        Arc::new(Stream {
+            name: name.to_string(),
+            stream_id: FlexId::Number(id),
+            last_modified: modified.map(|m| FlexId::String(m.to_string())),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn diffs_snapshots_by_stream_id() {
+        let at = Utc::now();
+        let before = Snapshot::of(
+            &[
+                stream(1, "Breaking Bad", Some("100")),
+                stream(2, "The Wire", Some("100")),
+            ],
+            &[],
+        );
+        let after = Snapshot::of(
+            &[
+                stream(1, "Breaking Bad", Some("200")),
+                stream(3, "Andor", Some("100")),
+            ],
+            &[],
+        );
+
+        assert!(diff(None, &after, "acc", Section::Series, at).is_empty());
+        let changes = diff(Some(&before), &after, "acc", Section::Series, at);
+        let summary: Vec<_> = changes.iter().map(|c| (c.kind, c.name.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ChangeKind::Added, "Andor"),
+                (ChangeKind::NewEpisodes, "Breaking Bad"),
+                (ChangeKind::Removed, "The Wire"),
+            ]
+        );
+
+        // A mode switch re-baselines instead of reporting the whole catalog
+        let filtered = Snapshot::of(&[stream(3, "Andor", None)], &[ProcessingMode::Sports]);
+        assert!(diff(Some(&before), &filtered, "acc", Section::Series, at).is_empty());
+    }
+
+    #[test]
+    fn counts_unseen_additions_per_account() {
+        let now = Utc::now();
+        let before = Snapshot::of(&[stream(1, "CNN", None)], &[]);
+        let after = Snapshot::of(&[stream(2, "BBC One", None), stream(3, "ITV", None)], &[]);
+        let mut log = WhatsNew::default();
+        log.record(diff(Some(&before), &after, "acc", Section::Live, now), now);
+
+        assert_eq!(log.unseen_count("acc", &[]), 2);
+        assert_eq!(log.unseen_count("other", &[]), 0);
+        assert_eq!(log.since("acc", None, &[]).len(), 3);
+
+        log.mark_seen("acc", now);
+        assert_eq!(log.unseen_count("acc", &[]), 0);
+
+        // Expired changes are pruned
+        log.record(Vec::new(), now + Duration::days(RETENTION_DAYS + 1));
+        assert!(log.changes.is_empty());
+    }
+}
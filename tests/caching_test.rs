@@ -1,12 +1,13 @@
 use matrix_iptv_lib::api::Stream;
 use matrix_iptv_lib::app::{App, CurrentScreen, Pane};
 use matrix_iptv_lib::flex_id::FlexId;
+use matrix_iptv_lib::storage::ConfigStorage;
 use std::sync::Arc;
 
 #[test]
 fn test_stream_caching_logic() {
     // Setup App
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     // Ensure we are in a state where update_search processes streams
     app.current_screen = CurrentScreen::Categories;
     app.active_pane = Pane::Streams;
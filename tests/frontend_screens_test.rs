@@ -1,6 +1,7 @@
 use matrix_iptv_lib::api::{Category, Stream};
 use matrix_iptv_lib::app::{App, CurrentScreen, Pane};
 use matrix_iptv_lib::flex_id::FlexId;
+use matrix_iptv_lib::storage::ConfigStorage;
 use ratatui::backend::TestBackend;
 use ratatui::Terminal;
 use ratatui::{layout::Rect, style::Color};
@@ -162,7 +163,7 @@ fn test_all_screens_render_empty_state() {
     ];
 
     for screen in screens {
-        let mut app = App::new();
+        let mut app = App::with_storage(ConfigStorage::memory(None));
         app.current_screen = screen.clone();
         render_frame(&mut app);
         // If we get here without panic, the screen rendered OK
@@ -171,7 +172,7 @@ fn test_all_screens_render_empty_state() {
 
 #[test]
 fn test_footer_hints_match_vod_and_series_stream_hotkeys() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.current_screen = CurrentScreen::VodStreams;
     app.active_pane = Pane::Streams;
     app.streams = generate_streams(2);
@@ -182,7 +183,7 @@ fn test_footer_hints_match_vod_and_series_stream_hotkeys() {
     assert!(!vod_text.contains("v fav"));
     assert!(!vod_text.contains("i info"));
 
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.current_screen = CurrentScreen::SeriesStreams;
     app.active_pane = Pane::Streams;
     app.series_streams = vec![make_series_stream(1, "US | Example Show")];
@@ -197,7 +198,7 @@ fn test_footer_hints_match_vod_and_series_stream_hotkeys() {
 
 #[test]
 fn test_help_popup_does_not_advertise_stale_hotkeys() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.show_help = true;
     let text = render_frame_text(&mut app);
     assert!(!text.contains("g/G         jump to top / bottom"));
@@ -210,7 +211,7 @@ fn test_help_popup_does_not_advertise_stale_hotkeys() {
 
 #[test]
 fn test_large_stream_list_rendering() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     let streams = generate_streams(2000);
     app.all_streams = streams;
     app.current_screen = CurrentScreen::Categories;
@@ -241,7 +242,7 @@ fn test_large_stream_list_rendering() {
 
 #[test]
 fn test_large_category_list_rendering() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_categories = generate_categories(200);
     app.current_screen = CurrentScreen::Categories;
     app.active_pane = Pane::Categories;
@@ -257,7 +258,7 @@ fn test_large_category_list_rendering() {
 
 #[test]
 fn test_search_filtering_live_tv() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_streams = vec![
         make_stream(1, "ESPN HD"),
         make_stream(2, "CNN International"),
@@ -308,7 +309,7 @@ fn test_search_filtering_live_tv() {
 
 #[test]
 fn test_search_filtering_vod() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_vod_streams = vec![
         make_vod_stream(1, "The Matrix (1999)"),
         make_vod_stream(2, "Inception (2010)"),
@@ -342,7 +343,7 @@ fn test_search_filtering_vod() {
 
 #[test]
 fn test_search_filtering_series() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_series_streams = vec![
         make_series_stream(1, "Breaking Bad"),
         make_series_stream(2, "Game of Thrones"),
@@ -372,7 +373,7 @@ fn test_search_filtering_series() {
 
 #[test]
 fn test_navigation_boundaries() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_streams = vec![
         make_stream(1, "Stream 1"),
         make_stream(2, "Stream 2"),
@@ -405,7 +406,7 @@ fn test_navigation_boundaries() {
 
 #[test]
 fn test_vod_series_screen_rendering() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
 
     // VOD
     app.all_vod_categories = vec![make_category("1", "Action Movies")];
@@ -442,7 +443,7 @@ fn test_vod_series_screen_rendering() {
 
 #[test]
 fn test_settings_screen_rendering() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.current_screen = CurrentScreen::Settings;
     render_frame(&mut app);
 
@@ -454,7 +455,7 @@ fn test_settings_screen_rendering() {
 
 #[test]
 fn test_global_search_rendering() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
 
     // Populate all content types into global pools
     app.global_all_streams = vec![make_stream(1, "ESPN HD"), make_stream(2, "CNN")];
@@ -494,7 +495,7 @@ fn test_global_search_rendering() {
 
 #[test]
 fn test_cache_prevents_redundant_parsing() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_streams = generate_streams(100);
     app.current_screen = CurrentScreen::Categories;
     app.active_pane = Pane::Streams;
@@ -549,7 +550,7 @@ fn test_cache_prevents_redundant_parsing() {
 
 #[test]
 fn test_render_performance_with_cache() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_streams = generate_streams(1000);
     app.all_categories = generate_categories(100);
     app.current_screen = CurrentScreen::Categories;
@@ -583,7 +584,7 @@ fn test_render_performance_with_cache() {
 
 #[test]
 fn test_sports_dashboard_empty() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.current_screen = CurrentScreen::SportsDashboard;
     render_frame(&mut app);
 }
@@ -592,7 +593,7 @@ fn test_sports_dashboard_empty() {
 
 #[test]
 fn test_cross_pane_search() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_categories = vec![
         make_category("1", "US Sports"),
         make_category("2", "UK News"),
@@ -617,7 +618,7 @@ fn test_cross_pane_search() {
 
 #[test]
 fn test_sports_now_playing_shows_clock_score_and_removes_health_column() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.current_screen = CurrentScreen::Streams;
     app.active_pane = Pane::Streams;
     app.categories = vec![Arc::new(Category {
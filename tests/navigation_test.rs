@@ -1,7 +1,8 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 use matrix_iptv_lib::api::{Category, Stream};
-use matrix_iptv_lib::app::{App, CurrentScreen, Pane};
+use matrix_iptv_lib::app::{App, AsyncAction, CurrentScreen, Pane};
 use matrix_iptv_lib::flex_id::FlexId;
+use matrix_iptv_lib::storage::ConfigStorage;
 use std::sync::Arc;
 
 fn make_key(code: KeyCode) -> KeyEvent {
@@ -126,7 +127,7 @@ fn setup_global_search(app: &mut App, result_count: usize) {
 
 #[tokio::test]
 async fn test_pageup_pagedown_live_streams() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     setup_live_screen(&mut app, 100);
 
     handle_key(&mut app, make_key(KeyCode::PageDown)).await;
@@ -145,7 +146,7 @@ async fn test_pageup_pagedown_live_streams() {
 
 #[tokio::test]
 async fn test_home_end_live_streams() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     setup_live_screen(&mut app, 100);
 
     handle_key(&mut app, make_key(KeyCode::End)).await;
@@ -163,7 +164,7 @@ async fn test_home_end_live_streams() {
 
 #[tokio::test]
 async fn test_ctrl_d_ctrl_u_live_streams() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     setup_live_screen(&mut app, 100);
 
     handle_key(
@@ -211,7 +212,7 @@ async fn test_ctrl_d_ctrl_u_live_streams() {
 
 #[tokio::test]
 async fn test_pageup_pagedown_vod_streams() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     setup_vod_screen(&mut app, 80);
 
     handle_key(&mut app, make_key(KeyCode::PageDown)).await;
@@ -235,7 +236,7 @@ async fn test_pageup_pagedown_vod_streams() {
 
 #[tokio::test]
 async fn test_pageup_pagedown_series_streams() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     setup_series_screen(&mut app, 60);
 
     handle_key(&mut app, make_key(KeyCode::PageDown)).await;
@@ -259,7 +260,7 @@ async fn test_pageup_pagedown_series_streams() {
 
 #[tokio::test]
 async fn test_pageup_pagedown_global_search() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     setup_global_search(&mut app, 50);
 
     handle_key(&mut app, make_key(KeyCode::PageDown)).await;
@@ -283,7 +284,7 @@ async fn test_pageup_pagedown_global_search() {
 
 #[tokio::test]
 async fn test_g_g_vim_style_vod_streams() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     setup_vod_screen(&mut app, 50);
 
     handle_key(&mut app, make_key(KeyCode::Char('g'))).await;
@@ -304,7 +305,7 @@ async fn test_g_g_vim_style_vod_streams() {
 
 #[tokio::test]
 async fn test_category_page_navigation_live() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.current_screen = CurrentScreen::Categories;
     app.active_pane = Pane::Categories;
     app.grid_cols = 1;
@@ -337,7 +338,7 @@ async fn test_category_page_navigation_live() {
 
 #[tokio::test]
 async fn test_series_episodes_page_navigation() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.current_screen = CurrentScreen::SeriesStreams;
     app.active_pane = Pane::Episodes;
     for i in 0..30 {
@@ -377,7 +378,7 @@ async fn test_series_episodes_page_navigation() {
 
 #[tokio::test]
 async fn test_pagedown_does_not_wrap() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     setup_live_screen(&mut app, 100);
 
     handle_key(&mut app, make_key(KeyCode::End)).await;
@@ -396,3 +397,318 @@ async fn test_pagedown_does_not_wrap() {
         "PageUp at top should not wrap around"
     );
 }
+
+#[tokio::test]
+async fn test_channel_zap_wraps_and_moves_selection() {
+    let mut app = App::with_storage(ConfigStorage::memory(None));
+    setup_live_screen(&mut app, 5);
+    app.selected_stream_index = 1;
+    let list = app.streams.clone();
+    app.capture_zap_playlist(&list, &list[1]);
+
+    let next = app.zap_target(1).expect("zap list captured");
+    assert_eq!(next.name, "CH 2");
+    assert_eq!(app.selected_stream_index, 2, "selection follows the zap");
+
+    app.zap_target(-3);
+    assert_eq!(
+        app.zap_position, 4,
+        "channel up wraps to the end of the list"
+    );
+
+    // Browsing another category keeps zapping through the original list
+    app.streams = vec![make_stream(99, "Other")];
+    let next = app.zap_target(1).unwrap();
+    assert_eq!(next.name, "CH 0");
+    assert_eq!(
+        app.selected_stream_index, 4,
+        "selection untouched on a different list"
+    );
+}
+
+#[tokio::test]
+async fn test_viewing_session_follows_the_played_stream_not_the_cursor() {
+    let mut app = App::with_storage(ConfigStorage::memory(None));
+    setup_live_screen(&mut app, 5);
+    let played = app.played_stream(&app.streams[1]);
+
+    // The cursor moves on while the stream is being checked
+    app.selected_stream_index = 3;
+    let (tx, _rx) = tokio::sync::mpsc::channel(4);
+    matrix_iptv_lib::handlers::async_actions::handle_async_action(
+        &mut app,
+        AsyncAction::PlayerStarted(played),
+        &tx,
+    )
+    .await;
+
+    let viewing = app.viewing.as_ref().expect("session started");
+    assert_eq!(viewing.channel, "CH 1");
+    assert_eq!(viewing.stream_id, "1");
+    assert_eq!(app.now_playing.as_ref().unwrap().title, "CH 1");
+}
+
+#[test]
+fn test_zap_list_keeps_live_channels_of_search_results() {
+    let mut app = App::with_storage(ConfigStorage::memory(None));
+    let movie = Arc::new(Stream {
+        name: "Movie".to_string(),
+        stream_id: FlexId::Number(50),
+        stream_type: "movie".to_string(),
+        ..Default::default()
+    });
+    let results = vec![
+        make_stream(1, "News"),
+        movie.clone(),
+        make_stream(2, "Sport"),
+    ];
+    app.current_screen = CurrentScreen::GlobalSearch;
+
+    app.capture_zap_playlist(&results, &results[2]);
+    assert_eq!(app.zap_playlist.len(), 2, "movies are not zapped to");
+    assert_eq!(app.zap_target(1).unwrap().name, "News");
+
+    // Playing a movie leaves the live zap list alone
+    app.capture_zap_playlist(&results, &movie);
+    assert_eq!(app.zap_target(1).unwrap().name, "Sport");
+}
+
+#[test]
+fn test_multiview_marks_respect_connection_limit() {
+    let mut app = App::with_storage(ConfigStorage::memory(None));
+    app.session.account_info = Some(matrix_iptv_lib::api::UserInfo {
+        auth: 1,
+        status: None,
+        exp_date: None,
+        max_connections: Some(FlexId::String("2".to_string())),
+        active_cons: None,
+        total_live_streams: None,
+        total_vod_streams: None,
+        total_series_streams: None,
+    });
+
+    assert_eq!(app.toggle_multiview_tile(&make_stream(1, "A")), Ok(true));
+    assert_eq!(app.toggle_multiview_tile(&make_stream(2, "B")), Ok(true));
+    assert!(
+        app.toggle_multiview_tile(&make_stream(3, "C")).is_err(),
+        "third tile exceeds the account's 2 connections"
+    );
+
+    // Unmarking frees a slot
+    assert_eq!(app.toggle_multiview_tile(&make_stream(1, "A")), Ok(false));
+    assert_eq!(app.toggle_multiview_tile(&make_stream(3, "C")), Ok(true));
+    let names: Vec<&str> = app
+        .multiview_tiles
+        .iter()
+        .map(|t| t.name.as_str())
+        .collect();
+    assert_eq!(names, ["B", "C"]);
+}
+
+#[test]
+fn test_multiview_marks_leave_room_for_active_connections() {
+    let mut app = App::with_storage(ConfigStorage::memory(None));
+    app.session.account_info = Some(matrix_iptv_lib::api::UserInfo {
+        auth: 1,
+        status: None,
+        exp_date: None,
+        max_connections: Some(FlexId::String("3".to_string())),
+        active_cons: Some(FlexId::String("1".to_string())),
+        total_live_streams: None,
+        total_vod_streams: None,
+        total_series_streams: None,
+    });
+
+    assert_eq!(app.multiview_tile_limit(), 2);
+    assert_eq!(app.toggle_multiview_tile(&make_stream(1, "A")), Ok(true));
+    assert_eq!(app.toggle_multiview_tile(&make_stream(2, "B")), Ok(true));
+    let err = app.toggle_multiview_tile(&make_stream(3, "C")).unwrap_err();
+    assert!(err.contains("1 are in use"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_settings_rows_open_the_item_they_show() {
+    use matrix_iptv_lib::app::{SettingsItem, SettingsState};
+
+    let mut app = App::with_storage(ConfigStorage::memory(None));
+    app.current_screen = CurrentScreen::Settings;
+    app.settings_state = SettingsState::Main;
+    assert_eq!(app.settings_options.len(), SettingsItem::ALL.len());
+
+    // Walk down to the Kodi row by what it shows, not by a hard-coded index
+    while !app.settings_options[app.selected_settings_index].starts_with("Kodi Hosts") {
+        handle_key(&mut app, make_key(KeyCode::Down)).await;
+    }
+    assert_eq!(app.selected_setting(), Some(SettingsItem::KodiHosts));
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert_eq!(app.settings_state, SettingsState::KodiHosts);
+
+    // A toggle row updates the label it rendered from
+    handle_key(&mut app, make_key(KeyCode::Esc)).await;
+    let smooth = SettingsItem::ALL
+        .iter()
+        .position(|i| *i == SettingsItem::SmoothMotion)
+        .unwrap();
+    while app.selected_settings_index != smooth {
+        handle_key(&mut app, make_key(KeyCode::Down)).await;
+    }
+    let before = app.settings_options[smooth].clone();
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert_ne!(app.settings_options[smooth], before);
+    assert_eq!(
+        app.settings_options[smooth],
+        app.settings_label(SettingsItem::SmoothMotion)
+    );
+}
+
+#[tokio::test]
+async fn test_player_profiles_can_be_added_renamed_and_deleted_in_settings() {
+    use matrix_iptv_lib::app::{SettingsItem, SettingsState};
+    use matrix_iptv_lib::config::PlayerEngine;
+
+    let mut app = App::with_storage(ConfigStorage::memory(None));
+    app.current_screen = CurrentScreen::Settings;
+    app.settings_state = SettingsState::Main;
+    while app.selected_setting() != Some(SettingsItem::PlayerProfiles) {
+        handle_key(&mut app, make_key(KeyCode::Down)).await;
+    }
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert_eq!(app.settings_state, SettingsState::PlayerProfiles);
+
+    handle_key(&mut app, make_key(KeyCode::Char('a'))).await;
+    for c in "Watch | /usr/local/bin/watch | --fs {url}".chars() {
+        handle_key(&mut app, make_key(KeyCode::Char(c))).await;
+    }
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert_eq!(app.config.player_profiles.len(), 1);
+    assert_eq!(
+        app.config.player_profiles[0].executable,
+        "/usr/local/bin/watch"
+    );
+    assert!(app.player_profile_input.is_none());
+
+    // Renaming the active profile keeps it active
+    app.config.preferred_player = PlayerEngine::Custom;
+    app.config.active_player_profile = Some("Watch".to_string());
+    handle_key(&mut app, make_key(KeyCode::Char('e'))).await;
+    for _ in 0.."Watch".len() {
+        handle_key(&mut app, make_key(KeyCode::Backspace)).await;
+    }
+    for c in "Big Screen".chars() {
+        handle_key(&mut app, make_key(KeyCode::Char(c))).await;
+    }
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert_eq!(app.config.player_profiles[0].name, "Big Screen");
+    assert_eq!(
+        app.config.active_player_profile.as_deref(),
+        Some("Big Screen")
+    );
+
+    // A second profile can't take the same name
+    handle_key(&mut app, make_key(KeyCode::Char('a'))).await;
+    for c in "Big Screen | ffplay".chars() {
+        handle_key(&mut app, make_key(KeyCode::Char(c))).await;
+    }
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert_eq!(app.config.player_profiles.len(), 1);
+    assert!(app.login_error.is_some());
+    // The first Esc dismisses the error, the second closes the input
+    handle_key(&mut app, make_key(KeyCode::Esc)).await;
+    handle_key(&mut app, make_key(KeyCode::Esc)).await;
+    assert!(app.player_profile_input.is_none());
+
+    // Deleting the active profile falls back to a built-in engine
+    handle_key(&mut app, make_key(KeyCode::Char('d'))).await;
+    assert!(app.config.player_profiles.is_empty());
+    assert_eq!(app.config.active_player_profile, None);
+    assert_eq!(app.config.preferred_player, PlayerEngine::Mpv);
+}
+
+#[tokio::test]
+async fn test_relay_access_and_port_can_be_changed_in_settings() {
+    use matrix_iptv_lib::app::{SettingsItem, SettingsState};
+
+    let mut app = App::with_storage(ConfigStorage::memory(None));
+    app.current_screen = CurrentScreen::Settings;
+    app.settings_state = SettingsState::Main;
+    assert!(!app.config.relay_on_lan());
+    while app.selected_setting() != Some(SettingsItem::RelayAccess) {
+        handle_key(&mut app, make_key(KeyCode::Down)).await;
+    }
+    // Opening the relay to the network warns about it
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert!(app.config.relay_on_lan());
+    assert_eq!(app.toasts.len(), 1);
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert!(!app.config.relay_on_lan());
+
+    handle_key(&mut app, make_key(KeyCode::Down)).await;
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert_eq!(app.settings_state, SettingsState::RelayPort);
+    for _ in 0..app.relay_port_input.value().len() {
+        handle_key(&mut app, make_key(KeyCode::Backspace)).await;
+    }
+    handle_key(&mut app, make_key(KeyCode::Char('0'))).await;
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert!(app.login_error.is_some());
+    handle_key(&mut app, make_key(KeyCode::Esc)).await;
+    handle_key(&mut app, make_key(KeyCode::Backspace)).await;
+    for c in "9100".chars() {
+        handle_key(&mut app, make_key(KeyCode::Char(c))).await;
+    }
+    handle_key(&mut app, make_key(KeyCode::Enter)).await;
+    assert_eq!(app.config.relay_port, 9100);
+    assert_eq!(app.settings_state, SettingsState::Main);
+}
+
+#[tokio::test]
+async fn test_radio_detection_uses_stream_type_and_category_overrides() {
+    use matrix_iptv_lib::app::SettingsState;
+
+    let mut app = App::with_storage(ConfigStorage::memory(None));
+    app.config.accounts.push(
+        serde_json::from_value(serde_json::json!({
+            "name": "Home",
+            "base_url": "http://example.com",
+            "username": "me",
+            "password": "pw",
+            "epg_url": null,
+            "last_refreshed": null,
+            "total_channels": null,
+            "total_movies": null,
+            "total_series": null,
+            "server_timezone": null
+        }))
+        .unwrap(),
+    );
+    app.all_categories = vec![
+        make_category("1", "UK| RADIOHEAD LIVE"),
+        make_category("2", "UK| TALK"),
+    ];
+    let in_category = |id: &str, stream_type: &str| Stream {
+        name: "Station".to_string(),
+        category_id: Some(id.to_string()),
+        stream_type: stream_type.to_string(),
+        ..Default::default()
+    };
+
+    // "Radiohead" is not the word "radio"; the provider's stream type is trusted
+    assert!(!app.is_radio_stream(&in_category("1", "live")));
+    assert!(app.is_radio_stream(&in_category("2", "radio_streams")));
+    assert!(!app.is_radio_stream(&in_category("2", "live")));
+
+    // Settings › Manage Category Visibility: 'a' cycles auto -> radio -> video -> auto
+    app.current_screen = CurrentScreen::Settings;
+    app.settings_state = SettingsState::CategoryManagement;
+    app.category_mgmt.list_state.select(Some(1));
+    handle_key(&mut app, make_key(KeyCode::Char('a'))).await;
+    assert!(app.is_radio_stream(&in_category("2", "live")));
+    handle_key(&mut app, make_key(KeyCode::Char('a'))).await;
+    assert!(!app.is_radio_stream(&in_category("2", "live")));
+    assert_eq!(
+        app.config.accounts[0].radio_overrides.get("2"),
+        Some(&false)
+    );
+    handle_key(&mut app, make_key(KeyCode::Char('a'))).await;
+    assert!(app.config.accounts[0].radio_overrides.is_empty());
+}
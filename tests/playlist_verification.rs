@@ -1,11 +1,12 @@
 use matrix_iptv_lib::api::Category;
 use matrix_iptv_lib::app::{App, CurrentScreen, Pane};
 use matrix_iptv_lib::flex_id::FlexId;
+use matrix_iptv_lib::storage::ConfigStorage;
 use std::sync::Arc;
 
 #[test]
 fn test_navigation_sync_live_tv() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_categories = vec![
         Arc::new(Category {
             category_id: "1".to_string(),
@@ -53,7 +54,7 @@ fn test_navigation_sync_live_tv() {
 
 #[test]
 fn test_navigation_sync_vod() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_vod_categories = vec![Arc::new(Category {
         category_id: "10".to_string(),
         category_name: "Movie1".to_string(),
@@ -88,7 +89,7 @@ fn test_navigation_sync_vod() {
 
 #[test]
 fn test_navigation_sync_series() {
-    let mut app = App::new();
+    let mut app = App::with_storage(ConfigStorage::memory(None));
     app.all_series_categories = vec![Arc::new(Category {
         category_id: "20".to_string(),
         category_name: "Series1".to_string(),
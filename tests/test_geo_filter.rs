@@ -24,7 +24,7 @@ fn test_merica_filter_all_content_types() {
         let client = XtreamClient::new(
             account.base_url.clone(),
             account.username.clone(),
-            account.password.clone(),
+            account.secret().to_string(),
         );
 
         // =====================================================================
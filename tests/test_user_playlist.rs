@@ -23,7 +23,7 @@ fn test_user_real_playlist_msnbc() {
         let client = XtreamClient::new(
             account.base_url.clone(),
             account.username.clone(),
-            account.password.clone(),
+            account.secret().to_string(),
         );
 
         // 4. Fetch ALL Live Streams